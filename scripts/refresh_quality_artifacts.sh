@@ -14,7 +14,7 @@ PHASE_NAME="${PHASE_NAME:-Phase 8 - Deterministic runbook and crash recovery}"
 BASE_BRANCH="${BASE_BRANCH:-main}"
 UPDATE_DECISIONS="${UPDATE_DECISIONS:-1}"
 RUNBOOK_VERSION="${RUNBOOK_VERSION:-1.0}"
-EXPECTED_DB_SCHEMA_VERSION="${EXPECTED_DB_SCHEMA_VERSION:-0.4.0}"
+EXPECTED_DB_SCHEMA_VERSION="${EXPECTED_DB_SCHEMA_VERSION:-0.5.0}"
 REBUILD_ON_COMPAT_MISMATCH="${REBUILD_ON_COMPAT_MISMATCH:-0}"
 ALLOW_BLOCKED_RESUME="${ALLOW_BLOCKED_RESUME:-0}"
 
@@ -21,6 +21,13 @@ pub enum Commands {
     Query(QueryArgs),
     Status(StatusArgs),
     Validate(ValidateArgs),
+    Export(ExportArgs),
+    Define(DefineArgs),
+    DiffEditions(DiffEditionsArgs),
+    Refs(RefsArgs),
+    Serve(ServeArgs),
+    Mcp(McpArgs),
+    Shell(ShellArgs),
 }
 
 #[derive(Args, Debug, Clone)]
@@ -55,12 +62,24 @@ pub struct IngestArgs {
     #[arg(long, default_value_t = false)]
     pub seed_page_chunks: bool,
 
+    #[arg(long, default_value_t = false)]
+    pub force: bool,
+
+    #[arg(long)]
+    pub jobs: Option<usize>,
+
     #[arg(long = "target-part")]
     pub target_parts: Vec<u32>,
 
+    #[arg(long = "target-year")]
+    pub target_years: Vec<u32>,
+
     #[arg(long)]
     pub max_pages_per_doc: Option<usize>,
 
+    #[arg(long, value_enum, default_value_t = ExtractBackend::Text)]
+    pub extract_backend: ExtractBackend,
+
     #[arg(long, value_enum, default_value_t = OcrMode::Off)]
     pub ocr_mode: OcrMode,
 
@@ -71,6 +90,21 @@ pub struct IngestArgs {
     pub ocr_min_text_chars: usize,
 }
 
+#[derive(Copy, Clone, Debug, Eq, PartialEq, ValueEnum)]
+pub enum ExtractBackend {
+    Text,
+    Layout,
+}
+
+impl ExtractBackend {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Text => "text",
+            Self::Layout => "layout",
+        }
+    }
+}
+
 #[derive(Copy, Clone, Debug, Eq, PartialEq, ValueEnum)]
 pub enum OcrMode {
     Off,
@@ -105,6 +139,12 @@ pub struct EmbedArgs {
     #[arg(long, default_value = "miniLM-L6-v2-local-v1")]
     pub model_id: String,
 
+    #[arg(long)]
+    pub model_dir: Option<PathBuf>,
+
+    #[arg(long, default_value_t = false)]
+    pub reset_embeddings: bool,
+
     #[arg(long, value_enum, default_value_t = EmbedRefreshMode::MissingOrStale)]
     pub refresh_mode: EmbedRefreshMode,
 
@@ -114,10 +154,27 @@ pub struct EmbedArgs {
     #[arg(long = "chunk-type")]
     pub chunk_types: Vec<String>,
 
+    #[arg(long = "node-type")]
+    pub node_types: Vec<String>,
+
+    #[arg(long, default_value_t = false)]
+    pub skip_node_embeddings: bool,
+
+    #[arg(long, value_enum)]
+    pub storage_encoding: Option<StorageEncoding>,
+
     #[arg(long)]
     pub semantic_model_lock_path: Option<PathBuf>,
 }
 
+#[derive(Copy, Clone, Debug, Eq, PartialEq, ValueEnum)]
+pub enum StorageEncoding {
+    F32,
+    F16,
+    Int8,
+    Binary,
+}
+
 #[derive(Copy, Clone, Debug, Eq, PartialEq, ValueEnum)]
 pub enum RetrievalMode {
     Lexical,
@@ -125,11 +182,57 @@ pub enum RetrievalMode {
     Hybrid,
 }
 
+#[derive(Copy, Clone, Debug, Eq, PartialEq, ValueEnum)]
+pub enum QueryGranularity {
+    Chunk,
+    Node,
+}
+
 #[derive(Copy, Clone, Debug, Eq, PartialEq, ValueEnum)]
 pub enum FusionMode {
     Rrf,
 }
 
+#[derive(Copy, Clone, Debug, Eq, PartialEq, ValueEnum)]
+pub enum AsilLevel {
+    A,
+    B,
+    C,
+    D,
+}
+
+impl AsilLevel {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::A => "A",
+            Self::B => "B",
+            Self::C => "C",
+            Self::D => "D",
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq, ValueEnum)]
+pub enum RequirementModality {
+    Shall,
+    ShallNot,
+    Should,
+    ShouldNot,
+    May,
+}
+
+impl RequirementModality {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Shall => "shall",
+            Self::ShallNot => "shall_not",
+            Self::Should => "should",
+            Self::ShouldNot => "should_not",
+            Self::May => "may",
+        }
+    }
+}
+
 #[derive(Args, Debug, Clone)]
 pub struct StatusArgs {
     #[arg(long, default_value = ".cache/iso26262")]
@@ -159,6 +262,9 @@ pub struct QueryArgs {
     #[arg(long, value_enum, default_value_t = FusionMode::Rrf)]
     pub fusion: FusionMode,
 
+    #[arg(long, value_enum, default_value_t = QueryGranularity::Chunk)]
+    pub granularity: QueryGranularity,
+
     #[arg(long, default_value_t = 60)]
     pub rrf_k: u32,
 
@@ -177,18 +283,39 @@ pub struct QueryArgs {
     #[arg(long)]
     pub part: Option<u32>,
 
+    #[arg(long)]
+    pub edition: Option<u32>,
+
     #[arg(long = "type")]
     pub chunk_type: Option<String>,
 
     #[arg(long)]
     pub node_type: Option<String>,
 
+    #[arg(long, value_enum, ignore_case = true)]
+    pub modality: Option<RequirementModality>,
+
+    #[arg(long, value_enum, ignore_case = true)]
+    pub asil: Option<AsilLevel>,
+
+    #[arg(long, default_value_t = false, requires = "asil")]
+    pub exclude_not_recommended: bool,
+
     #[arg(long, default_value_t = false)]
     pub with_ancestors: bool,
 
     #[arg(long, default_value_t = false)]
     pub with_descendants: bool,
 
+    #[arg(long, default_value_t = false)]
+    pub with_references: bool,
+
+    #[arg(long, default_value_t = false)]
+    pub with_referenced_by: bool,
+
+    #[arg(long, default_value_t = false)]
+    pub with_definitions: bool,
+
     #[arg(long, default_value_t = false)]
     pub with_pinpoint: bool,
 
@@ -213,3 +340,156 @@ pub struct ValidateArgs {
     #[arg(long)]
     pub quality_report_path: Option<PathBuf>,
 }
+
+#[derive(Args, Debug, Clone)]
+pub struct DefineArgs {
+    pub term: String,
+
+    #[arg(long, default_value = ".cache/iso26262")]
+    pub cache_root: PathBuf,
+
+    #[arg(long)]
+    pub db_path: Option<PathBuf>,
+
+    #[arg(long, default_value_t = 5)]
+    pub limit: usize,
+
+    #[arg(long, default_value_t = false)]
+    pub json: bool,
+}
+
+#[derive(Args, Debug, Clone)]
+pub struct DiffEditionsArgs {
+    #[arg(long, default_value = ".cache/iso26262")]
+    pub cache_root: PathBuf,
+
+    #[arg(long)]
+    pub db_path: Option<PathBuf>,
+
+    #[arg(long)]
+    pub part: u32,
+
+    #[arg(long)]
+    pub from: u32,
+
+    #[arg(long)]
+    pub to: u32,
+
+    #[arg(long, default_value_t = false)]
+    pub json: bool,
+
+    #[arg(long)]
+    pub output: Option<PathBuf>,
+}
+
+#[derive(Args, Debug, Clone)]
+pub struct ExportArgs {
+    #[command(subcommand)]
+    pub command: ExportCommands,
+}
+
+#[derive(Subcommand, Debug, Clone)]
+pub enum ExportCommands {
+    CodingStandard(ExportCodingStandardArgs),
+    WorkProducts(ExportWorkProductsArgs),
+}
+
+#[derive(Args, Debug, Clone)]
+pub struct ExportCodingStandardArgs {
+    #[arg(long, default_value = ".cache/iso26262")]
+    pub cache_root: PathBuf,
+
+    #[arg(long)]
+    pub db_path: Option<PathBuf>,
+
+    #[arg(long)]
+    pub output_dir: Option<PathBuf>,
+
+    #[arg(long, default_value_t = 6)]
+    pub part: u32,
+
+    #[arg(long)]
+    pub edition: Option<u32>,
+
+    #[arg(long = "table")]
+    pub tables: Vec<String>,
+}
+
+#[derive(Args, Debug, Clone)]
+pub struct ExportWorkProductsArgs {
+    #[arg(long, default_value = ".cache/iso26262")]
+    pub cache_root: PathBuf,
+
+    #[arg(long)]
+    pub db_path: Option<PathBuf>,
+
+    #[arg(long)]
+    pub output_dir: Option<PathBuf>,
+
+    #[arg(long = "part")]
+    pub parts: Vec<u32>,
+}
+
+#[derive(Args, Debug, Clone)]
+pub struct RefsArgs {
+    #[command(subcommand)]
+    pub command: RefsCommands,
+}
+
+#[derive(Subcommand, Debug, Clone)]
+pub enum RefsCommands {
+    External(RefsExternalArgs),
+}
+
+#[derive(Args, Debug, Clone)]
+pub struct RefsExternalArgs {
+    pub standard: String,
+
+    #[arg(long, default_value = ".cache/iso26262")]
+    pub cache_root: PathBuf,
+
+    #[arg(long)]
+    pub db_path: Option<PathBuf>,
+
+    #[arg(long = "part")]
+    pub parts: Vec<u32>,
+
+    #[arg(long, default_value_t = false)]
+    pub json: bool,
+}
+
+#[derive(Args, Debug, Clone)]
+pub struct ServeArgs {
+    #[arg(long, default_value = ".cache/iso26262")]
+    pub cache_root: PathBuf,
+
+    #[arg(long)]
+    pub db_path: Option<PathBuf>,
+
+    #[arg(long, default_value = "127.0.0.1:8426")]
+    pub bind: String,
+
+    #[arg(long)]
+    pub workers: Option<usize>,
+}
+
+#[derive(Args, Debug, Clone)]
+pub struct McpArgs {
+    #[arg(long, default_value = ".cache/iso26262")]
+    pub cache_root: PathBuf,
+
+    #[arg(long)]
+    pub db_path: Option<PathBuf>,
+}
+
+#[derive(Args, Debug, Clone)]
+pub struct ShellArgs {
+    #[arg(long, default_value = ".cache/iso26262")]
+    pub cache_root: PathBuf,
+
+    #[arg(long)]
+    pub db_path: Option<PathBuf>,
+
+    #[arg(long)]
+    pub history_path: Option<PathBuf>,
+}
@@ -0,0 +1,70 @@
+use std::io::{self, Write};
+
+use anyhow::{Context, Result, bail};
+use rusqlite::{Connection, OpenFlags};
+use serde::Serialize;
+use tracing::info;
+
+use crate::cli::DefineArgs;
+use crate::commands::query::{TermDefinition, ensure_terms_available, lookup_term_definitions};
+
+#[derive(Debug, Serialize)]
+struct DefineResponse {
+    term: String,
+    returned: usize,
+    definitions: Vec<TermDefinition>,
+}
+
+pub fn run(args: DefineArgs) -> Result<()> {
+    let term = args.term.trim();
+    if term.is_empty() {
+        bail!("term must not be empty");
+    }
+
+    let db_path = args
+        .db_path
+        .clone()
+        .unwrap_or_else(|| args.cache_root.join("iso26262_index.sqlite"));
+    let connection = Connection::open_with_flags(
+        &db_path,
+        OpenFlags::SQLITE_OPEN_READ_ONLY | OpenFlags::SQLITE_OPEN_NO_MUTEX,
+    )
+    .with_context(|| format!("failed to open database read-only: {}", db_path.display()))?;
+
+    ensure_terms_available(&connection)?;
+    let definitions = lookup_term_definitions(&connection, term, args.limit)?;
+    info!(term = %term, returned = definitions.len(), "term lookup completed");
+
+    let mut output = io::BufWriter::new(io::stdout().lock());
+    if args.json {
+        let response = DefineResponse {
+            term: term.to_string(),
+            returned: definitions.len(),
+            definitions,
+        };
+        serde_json::to_writer_pretty(&mut output, &response)
+            .context("failed to serialize define json output")?;
+        writeln!(output)?;
+    } else if definitions.is_empty() {
+        writeln!(output, "No definition found for: {term}")?;
+    } else {
+        for definition in &definitions {
+            match &definition.abbreviation {
+                Some(abbreviation) => writeln!(
+                    output,
+                    "{} {} ({abbreviation})",
+                    definition.reference, definition.term
+                )?,
+                None => writeln!(output, "{} {}", definition.reference, definition.term)?,
+            }
+            writeln!(output, "\tdefinition: {}", definition.definition)?;
+            for note in &definition.notes {
+                writeln!(output, "\tnote: {note}")?;
+            }
+            writeln!(output, "\tcitation: {}", definition.citation)?;
+        }
+    }
+    output.flush()?;
+
+    Ok(())
+}
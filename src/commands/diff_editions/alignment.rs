@@ -0,0 +1,228 @@
+use std::collections::HashSet;
+
+use serde::Serialize;
+
+const SAME_REF_MIN_SIMILARITY: f64 = 0.5;
+const RENUMBER_HEADING_SIMILARITY: f64 = 0.8;
+const RENUMBER_HEADING_TEXT_SIMILARITY: f64 = 0.3;
+const RENUMBER_TEXT_SIMILARITY: f64 = 0.8;
+
+#[derive(Debug, Clone)]
+pub(super) struct EditionClause {
+    pub(super) reference: String,
+    pub(super) heading: String,
+    pub(super) text: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub(super) struct EditionDiffEntry {
+    pub(super) status: String,
+    pub(super) from_ref: Option<String>,
+    pub(super) to_ref: Option<String>,
+    pub(super) from_heading: Option<String>,
+    pub(super) to_heading: Option<String>,
+    pub(super) heading_similarity: Option<f64>,
+    pub(super) text_similarity: Option<f64>,
+    pub(super) text_changed: bool,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub(super) struct EditionDiffSummary {
+    pub(super) unchanged: usize,
+    pub(super) changed: usize,
+    pub(super) renumbered: usize,
+    pub(super) added: usize,
+    pub(super) removed: usize,
+}
+
+struct ClauseFeatures {
+    heading_tokens: HashSet<String>,
+    text_tokens: HashSet<String>,
+    normalized_text: String,
+}
+
+impl ClauseFeatures {
+    fn new(clause: &EditionClause) -> Self {
+        Self {
+            heading_tokens: tokenize(&clause.heading),
+            text_tokens: tokenize(&clause.text),
+            normalized_text: normalize_text(&clause.text),
+        }
+    }
+}
+
+pub(super) fn align_editions(
+    from: &[EditionClause],
+    to: &[EditionClause],
+) -> Vec<EditionDiffEntry> {
+    let from_features = from.iter().map(ClauseFeatures::new).collect::<Vec<_>>();
+    let to_features = to.iter().map(ClauseFeatures::new).collect::<Vec<_>>();
+    let mut from_match = vec![None::<usize>; from.len()];
+    let mut to_match = vec![None::<usize>; to.len()];
+
+    for (to_index, to_clause) in to.iter().enumerate() {
+        let Some(from_index) = from
+            .iter()
+            .position(|clause| clause.reference == to_clause.reference)
+        else {
+            continue;
+        };
+        if from_match[from_index].is_some() {
+            continue;
+        }
+
+        let heading_similarity = jaccard(
+            &from_features[from_index].heading_tokens,
+            &to_features[to_index].heading_tokens,
+        );
+        let text_similarity = jaccard(
+            &from_features[from_index].text_tokens,
+            &to_features[to_index].text_tokens,
+        );
+        if heading_similarity.max(text_similarity) >= SAME_REF_MIN_SIMILARITY {
+            from_match[from_index] = Some(to_index);
+            to_match[to_index] = Some(from_index);
+        }
+    }
+
+    let mut renumber_pairs = Vec::<(f64, usize, usize)>::new();
+    for (from_index, from_feature) in from_features.iter().enumerate() {
+        if from_match[from_index].is_some() {
+            continue;
+        }
+        for (to_index, to_feature) in to_features.iter().enumerate() {
+            if to_match[to_index].is_some() {
+                continue;
+            }
+
+            let heading_similarity =
+                jaccard(&from_feature.heading_tokens, &to_feature.heading_tokens);
+            let text_similarity = jaccard(&from_feature.text_tokens, &to_feature.text_tokens);
+            let is_candidate = (heading_similarity >= RENUMBER_HEADING_SIMILARITY
+                && text_similarity >= RENUMBER_HEADING_TEXT_SIMILARITY)
+                || text_similarity >= RENUMBER_TEXT_SIMILARITY;
+            if is_candidate {
+                renumber_pairs.push((heading_similarity + text_similarity, from_index, to_index));
+            }
+        }
+    }
+
+    renumber_pairs.sort_by(|left, right| {
+        right
+            .0
+            .total_cmp(&left.0)
+            .then(left.1.cmp(&right.1))
+            .then(left.2.cmp(&right.2))
+    });
+    for (_, from_index, to_index) in renumber_pairs {
+        if from_match[from_index].is_none() && to_match[to_index].is_none() {
+            from_match[from_index] = Some(to_index);
+            to_match[to_index] = Some(from_index);
+        }
+    }
+
+    let mut entries = Vec::<EditionDiffEntry>::with_capacity(from.len().max(to.len()));
+    for (to_index, to_clause) in to.iter().enumerate() {
+        let Some(from_index) = to_match[to_index] else {
+            entries.push(EditionDiffEntry {
+                status: "added".to_string(),
+                from_ref: None,
+                to_ref: Some(to_clause.reference.clone()),
+                from_heading: None,
+                to_heading: Some(to_clause.heading.clone()),
+                heading_similarity: None,
+                text_similarity: None,
+                text_changed: true,
+            });
+            continue;
+        };
+
+        let from_clause = &from[from_index];
+        let text_changed =
+            from_features[from_index].normalized_text != to_features[to_index].normalized_text;
+        let status = if from_clause.reference != to_clause.reference {
+            "renumbered"
+        } else if text_changed {
+            "changed"
+        } else {
+            "unchanged"
+        };
+        entries.push(EditionDiffEntry {
+            status: status.to_string(),
+            from_ref: Some(from_clause.reference.clone()),
+            to_ref: Some(to_clause.reference.clone()),
+            from_heading: Some(from_clause.heading.clone()),
+            to_heading: Some(to_clause.heading.clone()),
+            heading_similarity: Some(round_similarity(jaccard(
+                &from_features[from_index].heading_tokens,
+                &to_features[to_index].heading_tokens,
+            ))),
+            text_similarity: Some(round_similarity(jaccard(
+                &from_features[from_index].text_tokens,
+                &to_features[to_index].text_tokens,
+            ))),
+            text_changed,
+        });
+    }
+
+    for (from_index, from_clause) in from.iter().enumerate() {
+        if from_match[from_index].is_none() {
+            entries.push(EditionDiffEntry {
+                status: "removed".to_string(),
+                from_ref: Some(from_clause.reference.clone()),
+                to_ref: None,
+                from_heading: Some(from_clause.heading.clone()),
+                to_heading: None,
+                heading_similarity: None,
+                text_similarity: None,
+                text_changed: true,
+            });
+        }
+    }
+
+    entries
+}
+
+pub(super) fn summarize_entries(entries: &[EditionDiffEntry]) -> EditionDiffSummary {
+    let mut summary = EditionDiffSummary::default();
+    for entry in entries {
+        match entry.status.as_str() {
+            "unchanged" => summary.unchanged += 1,
+            "changed" => summary.changed += 1,
+            "renumbered" => summary.renumbered += 1,
+            "added" => summary.added += 1,
+            "removed" => summary.removed += 1,
+            _ => {}
+        }
+    }
+    summary
+}
+
+fn tokenize(value: &str) -> HashSet<String> {
+    value
+        .split(|ch: char| !ch.is_alphanumeric())
+        .filter(|token| !token.is_empty())
+        .map(str::to_lowercase)
+        .collect()
+}
+
+fn normalize_text(value: &str) -> String {
+    value
+        .split_whitespace()
+        .collect::<Vec<&str>>()
+        .join(" ")
+        .to_lowercase()
+}
+
+fn jaccard(left: &HashSet<String>, right: &HashSet<String>) -> f64 {
+    if left.is_empty() && right.is_empty() {
+        return 1.0;
+    }
+    let intersection = left.intersection(right).count();
+    let union = left.len() + right.len() - intersection;
+    intersection as f64 / union as f64
+}
+
+fn round_similarity(value: f64) -> f64 {
+    (value * 1000.0).round() / 1000.0
+}
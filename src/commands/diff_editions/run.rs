@@ -0,0 +1,205 @@
+use std::io::{self, Write};
+
+use anyhow::{Context, Result, bail};
+use rusqlite::{Connection, OpenFlags, OptionalExtension};
+use serde::Serialize;
+use tracing::info;
+
+use crate::cli::DiffEditionsArgs;
+use crate::util::write_json_pretty;
+
+use super::alignment::{
+    EditionClause, EditionDiffEntry, EditionDiffSummary, align_editions, summarize_entries,
+};
+
+#[derive(Debug, Serialize)]
+pub(super) struct EditionDiffReport {
+    pub(super) part: u32,
+    pub(super) from_year: u32,
+    pub(super) to_year: u32,
+    pub(super) from_doc_id: String,
+    pub(super) to_doc_id: String,
+    pub(super) summary: EditionDiffSummary,
+    pub(super) entries: Vec<EditionDiffEntry>,
+}
+
+pub fn run(args: DiffEditionsArgs) -> Result<()> {
+    if args.from == args.to {
+        bail!("--from and --to must name different editions");
+    }
+
+    let db_path = args
+        .db_path
+        .clone()
+        .unwrap_or_else(|| args.cache_root.join("iso26262_index.sqlite"));
+    let connection = Connection::open_with_flags(
+        &db_path,
+        OpenFlags::SQLITE_OPEN_READ_ONLY | OpenFlags::SQLITE_OPEN_NO_MUTEX,
+    )
+    .with_context(|| format!("failed to open database read-only: {}", db_path.display()))?;
+
+    let report = build_edition_diff(&connection, args.part, args.from, args.to)?;
+    info!(
+        part = report.part,
+        from_year = report.from_year,
+        to_year = report.to_year,
+        changed = report.summary.changed,
+        renumbered = report.summary.renumbered,
+        added = report.summary.added,
+        removed = report.summary.removed,
+        "edition diff completed"
+    );
+
+    if let Some(output_path) = &args.output {
+        write_json_pretty(output_path, &report)?;
+    }
+
+    let mut output = io::BufWriter::new(io::stdout().lock());
+    if args.json {
+        serde_json::to_writer_pretty(&mut output, &report)
+            .context("failed to serialize edition diff json output")?;
+        writeln!(output)?;
+    } else {
+        write_text_report(&mut output, &report)?;
+    }
+    output.flush()?;
+
+    Ok(())
+}
+
+pub(super) fn build_edition_diff(
+    connection: &Connection,
+    part: u32,
+    from_year: u32,
+    to_year: u32,
+) -> Result<EditionDiffReport> {
+    let from_doc_id = find_edition_doc_id(connection, part, from_year)?;
+    let to_doc_id = find_edition_doc_id(connection, part, to_year)?;
+    let from_clauses = load_edition_clauses(connection, &from_doc_id)?;
+    let to_clauses = load_edition_clauses(connection, &to_doc_id)?;
+
+    let entries = align_editions(&from_clauses, &to_clauses);
+    let summary = summarize_entries(&entries);
+
+    Ok(EditionDiffReport {
+        part,
+        from_year,
+        to_year,
+        from_doc_id,
+        to_doc_id,
+        summary,
+        entries,
+    })
+}
+
+fn find_edition_doc_id(connection: &Connection, part: u32, year: u32) -> Result<String> {
+    let doc_id = connection
+        .query_row(
+            "SELECT doc_id FROM docs WHERE part = ?1 AND year = ?2 LIMIT 1",
+            [part, year],
+            |row| row.get::<_, String>(0),
+        )
+        .optional()?;
+    match doc_id {
+        Some(doc_id) => Ok(doc_id),
+        None => bail!(
+            "ISO 26262-{part}:{year} is not ingested; run ingest with --target-part {part} --target-year {year}"
+        ),
+    }
+}
+
+fn load_edition_clauses(connection: &Connection, doc_id: &str) -> Result<Vec<EditionClause>> {
+    let mut statement = connection.prepare(
+        "
+        SELECT ref, heading, text
+        FROM nodes
+        WHERE doc_id = ?1
+          AND node_type IN ('clause', 'subclause')
+          AND ref IS NOT NULL
+        ORDER BY order_index ASC
+        ",
+    )?;
+    let mut rows = statement.query([doc_id])?;
+    let mut clauses = Vec::<EditionClause>::new();
+
+    while let Some(row) = rows.next()? {
+        let reference: String = row.get(0)?;
+        let heading = row.get::<_, Option<String>>(1)?.unwrap_or_default();
+        let text = row.get::<_, Option<String>>(2)?.unwrap_or_default();
+        let body = strip_heading_line(&text, &heading);
+
+        if let Some(existing) = clauses
+            .iter_mut()
+            .find(|clause| clause.reference == reference)
+        {
+            existing.text.push(' ');
+            existing.text.push_str(&body);
+            continue;
+        }
+
+        clauses.push(EditionClause {
+            heading: heading.trim_start_matches(&reference).trim().to_string(),
+            reference,
+            text: body,
+        });
+    }
+
+    Ok(clauses)
+}
+
+fn strip_heading_line(text: &str, heading: &str) -> String {
+    let mut lines = text.lines().collect::<Vec<&str>>();
+    if lines
+        .first()
+        .is_some_and(|line| line.trim() == heading.trim())
+    {
+        lines.remove(0);
+    }
+
+    lines.join(" ")
+}
+
+fn write_text_report(output: &mut impl Write, report: &EditionDiffReport) -> Result<()> {
+    writeln!(
+        output,
+        "ISO 26262-{}: {} -> {} ({} -> {})",
+        report.part, report.from_year, report.to_year, report.from_doc_id, report.to_doc_id
+    )?;
+    writeln!(
+        output,
+        "unchanged={} changed={} renumbered={} added={} removed={}",
+        report.summary.unchanged,
+        report.summary.changed,
+        report.summary.renumbered,
+        report.summary.added,
+        report.summary.removed
+    )?;
+
+    for entry in &report.entries {
+        match entry.status.as_str() {
+            "unchanged" => {}
+            "added" => writeln!(
+                output,
+                "added\t{} {}",
+                entry.to_ref.as_deref().unwrap_or_default(),
+                entry.to_heading.as_deref().unwrap_or_default()
+            )?,
+            "removed" => writeln!(
+                output,
+                "removed\t{} {}",
+                entry.from_ref.as_deref().unwrap_or_default(),
+                entry.from_heading.as_deref().unwrap_or_default()
+            )?,
+            status => writeln!(
+                output,
+                "{status}\t{} -> {} {} (text similarity {:.3})",
+                entry.from_ref.as_deref().unwrap_or_default(),
+                entry.to_ref.as_deref().unwrap_or_default(),
+                entry.to_heading.as_deref().unwrap_or_default(),
+                entry.text_similarity.unwrap_or_default()
+            )?,
+        }
+    }
+
+    Ok(())
+}
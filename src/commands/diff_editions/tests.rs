@@ -0,0 +1,129 @@
+use rusqlite::Connection;
+
+use crate::commands::ingest::ensure_schema;
+
+use super::alignment::{EditionClause, align_editions, summarize_entries};
+use super::run::build_edition_diff;
+
+fn clause(reference: &str, heading: &str, text: &str) -> EditionClause {
+    EditionClause {
+        reference: reference.to_string(),
+        heading: heading.to_string(),
+        text: text.to_string(),
+    }
+}
+
+#[test]
+fn align_editions_classifies_unchanged_changed_renumbered_added_and_removed() {
+    let from = vec![
+        clause(
+            "6.4.1",
+            "Initiation of software unit design",
+            "The software unit design shall be developed in accordance with Clause 5.",
+        ),
+        clause(
+            "6.4.2",
+            "Notations",
+            "Notations for software unit design shall be applied as listed in Table 5.",
+        ),
+        clause(
+            "6.4.3",
+            "Design principles",
+            "The design principles listed in Table 6 shall be applied to achieve robustness.",
+        ),
+        clause(
+            "6.4.9",
+            "Obsolete guidance",
+            "Hardware interrupts should be handled by a dedicated dispatcher.",
+        ),
+    ];
+    let to = vec![
+        clause(
+            "6.4.1",
+            "Initiation of software unit design",
+            "The software unit design shall be developed in accordance with Clause 5.",
+        ),
+        clause(
+            "6.4.2",
+            "Notations",
+            "Notations for software unit design shall be applied as listed in Table 6.",
+        ),
+        clause(
+            "6.4.4",
+            "Design principles",
+            "The design principles listed in Table 6 shall be applied to achieve robustness.",
+        ),
+        clause(
+            "6.4.5",
+            "Software unit verification",
+            "Verification of each software unit shall demonstrate compliance with the design.",
+        ),
+    ];
+
+    let entries = align_editions(&from, &to);
+    let statuses = entries
+        .iter()
+        .map(|entry| {
+            (
+                entry.status.as_str(),
+                entry.from_ref.as_deref(),
+                entry.to_ref.as_deref(),
+            )
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        statuses,
+        vec![
+            ("unchanged", Some("6.4.1"), Some("6.4.1")),
+            ("changed", Some("6.4.2"), Some("6.4.2")),
+            ("renumbered", Some("6.4.3"), Some("6.4.4")),
+            ("added", None, Some("6.4.5")),
+            ("removed", Some("6.4.9"), None),
+        ]
+    );
+    assert!(!entries[2].text_changed);
+    assert_eq!(entries[2].heading_similarity, Some(1.0));
+
+    let summary = summarize_entries(&entries);
+    assert_eq!(summary.unchanged, 1);
+    assert_eq!(summary.changed, 1);
+    assert_eq!(summary.renumbered, 1);
+    assert_eq!(summary.added, 1);
+    assert_eq!(summary.removed, 1);
+}
+
+#[test]
+fn build_edition_diff_loads_both_editions_and_requires_each_to_be_ingested() {
+    let connection = Connection::open_in_memory().expect("in-memory DB should open");
+    ensure_schema(&connection).expect("schema should be created");
+    connection
+        .execute_batch(
+            "
+            INSERT INTO docs(doc_id, filename, sha256, part, year) VALUES
+              ('ISO26262-6-2011', 'part6-2011.pdf', 'hash-2011', 6, 2011),
+              ('ISO26262-6-2018', 'part6-2018.pdf', 'hash-2018', 6, 2018);
+            INSERT INTO nodes(node_id, doc_id, node_type, ref, heading, order_index, text) VALUES
+              ('a1', 'ISO26262-6-2011', 'subclause', '8.4.1', '8.4.1 Unit design', 1,
+               '8.4.1 Unit design
+The unit design shall be specified.'),
+              ('a2', 'ISO26262-6-2011', 'subclause', '8.4.1', '8.4.1 Unit design', 2,
+               'It shall be verified.'),
+              ('b1', 'ISO26262-6-2018', 'subclause', '8.4.1', '8.4.1 Unit design', 1,
+               '8.4.1 Unit design
+The unit design shall be specified. It shall be verified.'),
+              ('b2', 'ISO26262-6-2018', 'requirement_atom', '8.4.1', NULL, 2,
+               'The unit design shall be specified.');
+            ",
+        )
+        .expect("seed edition nodes");
+
+    let report = build_edition_diff(&connection, 6, 2011, 2018).expect("diff should build");
+    assert_eq!(report.from_doc_id, "ISO26262-6-2011");
+    assert_eq!(report.to_doc_id, "ISO26262-6-2018");
+    assert_eq!(report.entries.len(), 1);
+    assert_eq!(report.entries[0].status, "unchanged");
+    assert_eq!(report.entries[0].to_heading.as_deref(), Some("Unit design"));
+
+    let error = build_edition_diff(&connection, 6, 2011, 2026).expect_err("missing edition");
+    assert!(error.to_string().contains("--target-year 2026"));
+}
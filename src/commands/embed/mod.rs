@@ -4,3 +4,6 @@ mod store;
 mod types;
 
 pub(crate) use run::run;
+
+#[cfg(test)]
+mod tests;
@@ -1,8 +1,8 @@
 use std::collections::HashSet;
 
-use crate::semantic::chunk_payload_for_embedding;
+use crate::semantic::{chunk_payload_for_embedding, node_payload_for_embedding};
 
-use super::types::EmbedChunkRow;
+use super::types::{EmbedChunkRow, EmbedNodeRow};
 
 pub(super) fn resolve_chunk_type_filter(raw_values: &[String]) -> HashSet<String> {
     raw_values
@@ -41,3 +41,7 @@ pub(super) fn build_chunk_payload(row: &EmbedChunkRow) -> Option<String> {
         row.table_md.as_deref(),
     )
 }
+
+pub(super) fn build_node_payload(row: &EmbedNodeRow) -> Option<String> {
+    node_payload_for_embedding(&row.node_type, &row.reference, row.text.as_deref())
+}
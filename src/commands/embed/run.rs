@@ -2,7 +2,7 @@ use std::collections::HashSet;
 use std::path::{Path, PathBuf};
 use std::time::Instant;
 
-use anyhow::Result;
+use anyhow::{bail, Result};
 use chrono::Utc;
 use rusqlite::Connection;
 use sha2::{Digest, Sha256};
@@ -10,18 +10,22 @@ use tracing::info;
 
 use crate::cli::{EmbedArgs, EmbedRefreshMode};
 use crate::commands::ingest::ensure_embedding_schema;
+use crate::semantic::ann::{ann_index_path, build_ivf_index, load_model_vectors, write_ann_index};
 use crate::semantic::{
-    embed_text_local, embedding_text_hash, encode_embedding_blob, resolve_model_config,
-    SemanticModelConfig,
+    embedding_text_hash, encode_embedding_blob, load_embedding_backend,
+    load_model_storage_encoding, load_registered_model_config, resolve_model_config,
+    resolve_parent_chunk_id, EmbeddingEncoding, SemanticModelConfig, NODE_EMBEDDING_TYPES,
 };
 use crate::util::{ensure_directory, now_utc_string, utc_compact_string, write_json_pretty};
 
 use super::payload::{
-    build_chunk_payload, is_eligible_chunk, is_supported_chunk_type, resolve_chunk_type_filter,
+    build_chunk_payload, build_node_payload, is_eligible_chunk, is_supported_chunk_type,
+    resolve_chunk_type_filter,
 };
 use super::store::{
-    ensure_model_entry, load_chunk_rows, load_existing_embedding, open_embed_connection,
-    upsert_chunk_embedding,
+    clear_model_embeddings, ensure_model_entry, load_chunk_rows, load_existing_embedding,
+    load_existing_node_embedding, load_node_rows, open_embed_connection, upsert_chunk_embedding,
+    upsert_node_embedding,
 };
 use super::types::{
     EmbedChunkRow, EmbeddingRunManifest, SemanticModelConfigLock, EMBEDDING_DB_SCHEMA_VERSION,
@@ -30,8 +34,17 @@ use super::types::{
 
 pub(crate) fn run(args: EmbedArgs) -> Result<()> {
     let batch_size = args.batch_size.max(1);
-    let model = resolve_model_config(&args.model_id);
     let chunk_type_filter = resolve_chunk_type_filter(&args.chunk_types);
+    let node_type_filter = resolve_chunk_type_filter(&args.node_types);
+    if let Some(unsupported) = node_type_filter
+        .iter()
+        .find(|node_type| !NODE_EMBEDDING_TYPES.contains(&node_type.as_str()))
+    {
+        bail!(
+            "unsupported --node-type '{unsupported}'; expected one of: {}",
+            NODE_EMBEDDING_TYPES.join(", ")
+        );
+    }
 
     let db_path = args
         .db_path
@@ -44,9 +57,52 @@ pub(crate) fn run(args: EmbedArgs) -> Result<()> {
         .clone()
         .unwrap_or_else(|| PathBuf::from(SEMANTIC_MODEL_CONFIG_LOCK_PATH));
 
+    let mut warnings = Vec::<String>::new();
     let mut connection = open_embed_connection(&db_path)?;
     ensure_embedding_schema(&connection)?;
-    ensure_model_entry(&connection, &model)?;
+    let registered_model = load_registered_model_config(&connection, &args.model_id)?;
+    let mut model = match (&args.model_dir, &registered_model) {
+        (None, Some(registered)) => registered.clone(),
+        (model_dir, _) => resolve_model_config(&args.model_id, model_dir.as_deref())?,
+    };
+    let mut embedder = load_embedding_backend(&model)?;
+    model.dimensions = embedder.dimensions();
+    if let Some(registered) = &registered_model
+        && !registered.same_embedding_space(&model)
+    {
+        if !args.reset_embeddings {
+            bail!(
+                "embedding backend for model '{}' changed from {} to {}; pass --reset-embeddings to clear its existing embeddings",
+                model.model_id,
+                registered.backend,
+                model.backend
+            );
+        }
+        let cleared = clear_model_embeddings(&connection, &model.model_id)?;
+        warnings.push(format!(
+            "embedding backend for model '{}' changed from {} to {}; cleared {} embeddings",
+            model.model_id, registered.backend, model.backend, cleared
+        ));
+    }
+    let registered_encoding = load_model_storage_encoding(&connection, &model.model_id)?;
+    let storage_encoding = args
+        .storage_encoding
+        .map(EmbeddingEncoding::from)
+        .or(registered_encoding)
+        .unwrap_or_default();
+    if let Some(registered) = registered_encoding
+        && registered != storage_encoding
+    {
+        let cleared = clear_model_embeddings(&connection, &model.model_id)?;
+        warnings.push(format!(
+            "storage encoding for model '{}' changed from {} to {}; cleared {} embeddings",
+            model.model_id,
+            registered.as_str(),
+            storage_encoding.as_str(),
+            cleared
+        ));
+    }
+    ensure_model_entry(&connection, &model, storage_encoding)?;
     write_semantic_model_config_lockfile(&model, &semantic_model_lock_path)?;
 
     let chunk_rows = load_chunk_rows(&connection)?;
@@ -59,7 +115,6 @@ pub(crate) fn run(args: EmbedArgs) -> Result<()> {
     let mut stale_rows_before = 0usize;
     let mut updated_chunks = 0usize;
     let mut pending_updates = Vec::<(String, String, Vec<u8>)>::new();
-    let mut warnings = Vec::<String>::new();
 
     for row in &chunk_rows {
         let payload = build_chunk_payload(row);
@@ -100,8 +155,8 @@ pub(crate) fn run(args: EmbedArgs) -> Result<()> {
             continue;
         }
 
-        let embedding = embed_text_local(&payload, model.dimensions);
-        let embedding_blob = encode_embedding_blob(&embedding);
+        let embedding = embedder.embed(&payload)?;
+        let embedding_blob = encode_embedding_blob(&embedding, storage_encoding);
         pending_updates.push((row.chunk_id.clone(), text_hash, embedding_blob));
 
         if pending_updates.len() >= batch_size {
@@ -133,6 +188,74 @@ pub(crate) fn run(args: EmbedArgs) -> Result<()> {
         warnings.push("no eligible chunks matched embed filters".to_string());
     }
 
+    let mut eligible_nodes = 0usize;
+    let mut updated_nodes = 0usize;
+    let mut skipped_orphan_nodes = 0usize;
+    let mut stale_node_rows_before = 0usize;
+    if !args.skip_node_embeddings {
+        let mut pending_node_updates = Vec::<(String, String, String, Vec<u8>)>::new();
+        for row in load_node_rows(&connection, &node_type_filter)? {
+            let Some(payload) = build_node_payload(&row) else {
+                continue;
+            };
+            let Some(chunk_id) = resolve_parent_chunk_id(&connection, &row.node_id)? else {
+                skipped_orphan_nodes += 1;
+                continue;
+            };
+            eligible_nodes += 1;
+
+            let text_hash = embedding_text_hash(&payload);
+            let existing =
+                load_existing_node_embedding(&connection, &row.node_id, &model.model_id)?;
+            let stale = existing
+                .as_ref()
+                .map(|value| {
+                    value.text_hash != text_hash || value.embedding_dim != model.dimensions
+                })
+                .unwrap_or(true);
+            if stale {
+                stale_node_rows_before += 1;
+            }
+            if !stale && args.refresh_mode == EmbedRefreshMode::MissingOrStale {
+                continue;
+            }
+
+            let embedding = embedder.embed(&payload)?;
+            pending_node_updates.push((
+                row.node_id,
+                chunk_id,
+                text_hash,
+                encode_embedding_blob(&embedding, storage_encoding),
+            ));
+            if pending_node_updates.len() >= batch_size {
+                updated_nodes += flush_node_embed_batch(
+                    &mut connection,
+                    &model.model_id,
+                    model.dimensions,
+                    &mut pending_node_updates,
+                )?;
+                info!(
+                    model_id = %model.model_id,
+                    updated_nodes,
+                    eligible_nodes,
+                    "node embed batch committed"
+                );
+            }
+        }
+
+        updated_nodes += flush_node_embed_batch(
+            &mut connection,
+            &model.model_id,
+            model.dimensions,
+            &mut pending_node_updates,
+        )?;
+        if skipped_orphan_nodes > 0 {
+            warnings.push(format!(
+                "{skipped_orphan_nodes} nodes have no parent chunk and were not embedded"
+            ));
+        }
+    }
+
     let stale_rows_after = count_stale_rows(
         &connection,
         &chunk_rows,
@@ -141,6 +264,25 @@ pub(crate) fn run(args: EmbedArgs) -> Result<()> {
         model.dimensions,
     )?;
     let embedded_chunks = eligible_chunks.saturating_sub(stale_rows_after);
+
+    let vectors = load_model_vectors(&connection, &model.model_id, model.dimensions)?;
+    let ann_index = if vectors.is_empty() {
+        None
+    } else {
+        let index = build_ivf_index(&model.model_id, model.dimensions, &vectors);
+        let record = write_ann_index(
+            &connection,
+            &index,
+            &ann_index_path(&db_path, &model.model_id),
+        )?;
+        info!(
+            path = %record.index_path,
+            vector_count = record.vector_count,
+            list_count = record.list_count,
+            "ann index rebuilt"
+        );
+        Some(record)
+    };
     let duration_ms = started.elapsed().as_millis();
 
     let manifest = EmbeddingRunManifest {
@@ -152,6 +294,9 @@ pub(crate) fn run(args: EmbedArgs) -> Result<()> {
         embedding_dim: model.dimensions,
         normalization: model.normalization.clone(),
         backend: model.backend.clone(),
+        storage_encoding: storage_encoding.as_str().to_string(),
+        model_sha256: model.model_sha256.clone(),
+        tokenizer_sha256: model.tokenizer_sha256.clone(),
         db_schema_version: EMBEDDING_DB_SCHEMA_VERSION.to_string(),
         refresh_mode: match args.refresh_mode {
             EmbedRefreshMode::Full => "full",
@@ -175,6 +320,25 @@ pub(crate) fn run(args: EmbedArgs) -> Result<()> {
         skipped_empty_chunks,
         stale_rows_before,
         stale_rows_after,
+        node_type_filter: if args.skip_node_embeddings {
+            Vec::new()
+        } else {
+            NODE_EMBEDDING_TYPES
+                .iter()
+                .filter(|node_type| {
+                    node_type_filter.is_empty() || node_type_filter.contains(**node_type)
+                })
+                .map(|node_type| node_type.to_string())
+                .collect()
+        },
+        eligible_nodes,
+        updated_nodes,
+        skipped_orphan_nodes,
+        stale_node_rows_before,
+        ann_index_path: ann_index.as_ref().map(|record| record.index_path.clone()),
+        ann_index_checksum: ann_index.as_ref().map(|record| record.checksum.clone()),
+        ann_index_vectors: ann_index.as_ref().map_or(0, |record| record.vector_count),
+        ann_index_lists: ann_index.as_ref().map_or(0, |record| record.list_count),
         batch_size,
         duration_ms,
         status: "completed".to_string(),
@@ -190,9 +354,11 @@ pub(crate) fn run(args: EmbedArgs) -> Result<()> {
     info!(
         path = %manifest_path.display(),
         model_id = %model.model_id,
+        storage_encoding = storage_encoding.as_str(),
         eligible_chunks,
         updated_chunks,
         stale_rows_after,
+        updated_nodes,
         "embedding refresh completed"
     );
 
@@ -205,8 +371,14 @@ fn write_semantic_model_config_lockfile(
 ) -> Result<()> {
     let created_at = now_utc_string();
     let checksum_input = format!(
-        "{}|{}|{}|{}|{}",
-        model.model_id, model.model_name, model.dimensions, model.normalization, model.backend
+        "{}|{}|{}|{}|{}|{}|{}",
+        model.model_id,
+        model.model_name,
+        model.dimensions,
+        model.normalization,
+        model.backend,
+        model.model_sha256.as_deref().unwrap_or_default(),
+        model.tokenizer_sha256.as_deref().unwrap_or_default()
     );
     let mut hasher = Sha256::new();
     hasher.update(checksum_input.as_bytes());
@@ -219,6 +391,8 @@ fn write_semantic_model_config_lockfile(
         embedding_dim: model.dimensions,
         normalization: model.normalization.clone(),
         runtime_backend: model.backend.clone(),
+        model_sha256: model.model_sha256.clone(),
+        tokenizer_sha256: model.tokenizer_sha256.clone(),
         created_at,
         checksum,
     };
@@ -260,6 +434,35 @@ fn flush_embed_batch(
     Ok(updated)
 }
 
+fn flush_node_embed_batch(
+    connection: &mut Connection,
+    model_id: &str,
+    dimensions: usize,
+    pending_updates: &mut Vec<(String, String, String, Vec<u8>)>,
+) -> Result<usize> {
+    if pending_updates.is_empty() {
+        return Ok(0);
+    }
+
+    let tx = connection.transaction()?;
+    let mut updated = 0usize;
+    for (node_id, chunk_id, text_hash, embedding_blob) in pending_updates.drain(..) {
+        upsert_node_embedding(
+            &tx,
+            &node_id,
+            model_id,
+            &chunk_id,
+            &embedding_blob,
+            dimensions,
+            &text_hash,
+        )?;
+        updated += 1;
+    }
+    tx.commit()?;
+
+    Ok(updated)
+}
+
 fn count_stale_rows(
     connection: &Connection,
     chunk_rows: &[EmbedChunkRow],
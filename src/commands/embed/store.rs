@@ -1,12 +1,13 @@
+use std::collections::HashSet;
 use std::path::PathBuf;
 
 use anyhow::{Context, Result};
-use rusqlite::{params, Connection, OpenFlags};
+use rusqlite::{params, Connection, OpenFlags, OptionalExtension};
 
-use crate::semantic::SemanticModelConfig;
+use crate::semantic::{EmbeddingEncoding, SemanticModelConfig, NODE_EMBEDDING_TYPES};
 use crate::util::now_utc_string;
 
-use super::types::{EmbedChunkRow, ExistingEmbeddingRow};
+use super::types::{EmbedChunkRow, EmbedNodeRow, ExistingEmbeddingRow};
 
 pub(super) fn open_embed_connection(db_path: &PathBuf) -> Result<Connection> {
     let connection = Connection::open_with_flags(
@@ -30,30 +31,41 @@ pub(super) fn open_embed_connection(db_path: &PathBuf) -> Result<Connection> {
     Ok(connection)
 }
 
+pub(super) fn clear_model_embeddings(connection: &Connection, model_id: &str) -> Result<usize> {
+    let chunks = connection.execute(
+        "DELETE FROM chunk_embeddings WHERE model_id = ?1",
+        [model_id],
+    )?;
+    let nodes = connection.execute(
+        "DELETE FROM node_embeddings WHERE model_id = ?1",
+        [model_id],
+    )?;
+    Ok(chunks + nodes)
+}
+
 pub(super) fn ensure_model_entry(
     connection: &Connection,
     model: &SemanticModelConfig,
+    storage_encoding: EmbeddingEncoding,
 ) -> Result<()> {
     let created_at = now_utc_string();
-    let config_json = serde_json::json!({
-        "model_id": model.model_id,
-        "model_name": model.model_name,
-        "dimensions": model.dimensions,
-        "normalization": model.normalization,
-        "backend": model.backend,
-    })
-    .to_string();
+    let config_json =
+        serde_json::to_string(model).context("failed to serialize embedding model config")?;
 
     connection.execute(
         "
-        INSERT INTO embedding_models(model_id, backend, model_name, dimensions, normalize, created_at, config_json)
-        VALUES(?1, ?2, ?3, ?4, 1, ?5, ?6)
+        INSERT INTO embedding_models(
+          model_id, backend, model_name, dimensions, normalize, created_at, config_json,
+          storage_encoding
+        )
+        VALUES(?1, ?2, ?3, ?4, 1, ?5, ?6, ?7)
         ON CONFLICT(model_id) DO UPDATE SET
           backend=excluded.backend,
           model_name=excluded.model_name,
           dimensions=excluded.dimensions,
           normalize=excluded.normalize,
-          config_json=excluded.config_json
+          config_json=excluded.config_json,
+          storage_encoding=excluded.storage_encoding
         ",
         params![
             model.model_id,
@@ -62,6 +74,7 @@ pub(super) fn ensure_model_entry(
             model.dimensions as i64,
             created_at,
             config_json,
+            storage_encoding.as_str(),
         ],
     )?;
 
@@ -100,6 +113,42 @@ pub(super) fn load_chunk_rows(connection: &Connection) -> Result<Vec<EmbedChunkR
     Ok(out)
 }
 
+pub(super) fn load_node_rows(
+    connection: &Connection,
+    node_type_filter: &HashSet<String>,
+) -> Result<Vec<EmbedNodeRow>> {
+    let mut statement = connection.prepare(
+        "
+        SELECT
+          node_id,
+          lower(node_type),
+          COALESCE(ref, ''),
+          text
+        FROM nodes
+        WHERE lower(node_type) IN (SELECT value FROM json_each(?1))
+        ORDER BY node_id ASC
+        ",
+    )?;
+
+    let node_types = NODE_EMBEDDING_TYPES
+        .iter()
+        .filter(|node_type| node_type_filter.is_empty() || node_type_filter.contains(**node_type))
+        .collect::<Vec<&&str>>();
+    let mut rows = statement.query([serde_json::to_string(&node_types)?])?;
+    let mut out = Vec::<EmbedNodeRow>::new();
+
+    while let Some(row) = rows.next()? {
+        out.push(EmbedNodeRow {
+            node_id: row.get(0)?,
+            node_type: row.get(1)?,
+            reference: row.get(2)?,
+            text: row.get(3)?,
+        });
+    }
+
+    Ok(out)
+}
+
 pub(super) fn load_existing_embedding(
     connection: &Connection,
     chunk_id: &str,
@@ -156,3 +205,63 @@ pub(super) fn upsert_chunk_embedding(
 
     Ok(())
 }
+
+pub(super) fn load_existing_node_embedding(
+    connection: &Connection,
+    node_id: &str,
+    model_id: &str,
+) -> Result<Option<ExistingEmbeddingRow>> {
+    let row = connection
+        .query_row(
+            "
+            SELECT text_hash, embedding_dim
+            FROM node_embeddings
+            WHERE node_id = ?1 AND model_id = ?2
+            LIMIT 1
+            ",
+            params![node_id, model_id],
+            |row| {
+                Ok(ExistingEmbeddingRow {
+                    text_hash: row.get(0)?,
+                    embedding_dim: row.get::<_, i64>(1)? as usize,
+                })
+            },
+        )
+        .optional()?;
+
+    Ok(row)
+}
+
+pub(super) fn upsert_node_embedding(
+    connection: &Connection,
+    node_id: &str,
+    model_id: &str,
+    chunk_id: &str,
+    embedding_blob: &[u8],
+    embedding_dim: usize,
+    text_hash: &str,
+) -> Result<()> {
+    connection.execute(
+        "
+        INSERT INTO node_embeddings(node_id, model_id, chunk_id, embedding, embedding_dim, text_hash, generated_at)
+        VALUES(?1, ?2, ?3, ?4, ?5, ?6, ?7)
+        ON CONFLICT(node_id, model_id) DO UPDATE SET
+          chunk_id=excluded.chunk_id,
+          embedding=excluded.embedding,
+          embedding_dim=excluded.embedding_dim,
+          text_hash=excluded.text_hash,
+          generated_at=excluded.generated_at
+        ",
+        params![
+            node_id,
+            model_id,
+            chunk_id,
+            embedding_blob,
+            embedding_dim as i64,
+            text_hash,
+            now_utc_string(),
+        ],
+    )?;
+
+    Ok(())
+}
@@ -0,0 +1,83 @@
+use std::path::Path;
+
+use rusqlite::Connection;
+
+use crate::cli::{EmbedArgs, EmbedRefreshMode};
+
+use super::run;
+
+fn embed_args(cache_root: &Path) -> EmbedArgs {
+    EmbedArgs {
+        cache_root: cache_root.to_path_buf(),
+        db_path: Some(cache_root.join("index.sqlite")),
+        model_id: "hash-test".to_string(),
+        model_dir: None,
+        reset_embeddings: false,
+        refresh_mode: EmbedRefreshMode::MissingOrStale,
+        batch_size: 8,
+        chunk_types: Vec::new(),
+        node_types: Vec::new(),
+        skip_node_embeddings: true,
+        storage_encoding: None,
+        semantic_model_lock_path: Some(cache_root.join("model.lock.json")),
+    }
+}
+
+#[test]
+fn embed_without_model_dir_reuses_the_registered_model_config() {
+    let cache_root = std::env::temp_dir().join(format!("iso26262_embed_{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&cache_root);
+    std::fs::create_dir_all(&cache_root).expect("cache root should be created");
+    let args = embed_args(&cache_root);
+    let db_path = args.db_path.clone().expect("fixture sets a DB path");
+
+    let connection = Connection::open(&db_path).expect("fixture DB should open");
+    crate::commands::ingest::ensure_schema(&connection).expect("schema should initialize");
+    connection
+        .execute_batch(
+            "
+            INSERT INTO docs(doc_id, filename, sha256, part, year, title)
+            VALUES ('ISO26262-6-2018', 'part6.pdf', 'sha', 6, 2018, 'Part 6');
+            INSERT INTO chunks(
+              chunk_id, doc_id, type, ref, heading, chunk_seq, page_pdf_start, page_pdf_end,
+              text, source_hash
+            ) VALUES (
+              'ISO26262-6-2018:8.4.1', 'ISO26262-6-2018', 'clause', '8.4.1',
+              '8.4.1 Software unit verification', 0, 24, 24,
+              'The software unit design shall be verified.', 'hash'
+            );
+            ",
+        )
+        .expect("fixture rows should insert");
+
+    run(args.clone()).expect("first embed should succeed");
+    connection
+        .execute(
+            "UPDATE embedding_models SET config_json = json_set(config_json, '$.model_sha256', 'registered')",
+            [],
+        )
+        .expect("registered config should update");
+    let generated_at = |connection: &Connection| {
+        connection
+            .query_row(
+                "SELECT generated_at FROM chunk_embeddings WHERE model_id = 'hash-test'",
+                [],
+                |row| row.get::<_, String>(0),
+            )
+            .expect("embedding should be kept")
+    };
+    let before = generated_at(&connection);
+
+    run(args).expect("re-embedding without --model-dir should reuse the registered config");
+    assert_eq!(generated_at(&connection), before);
+    let model_sha256 = connection
+        .query_row(
+            "SELECT json_extract(config_json, '$.model_sha256') FROM embedding_models",
+            [],
+            |row| row.get::<_, Option<String>>(0),
+        )
+        .expect("model should stay registered");
+    assert_eq!(model_sha256.as_deref(), Some("registered"));
+
+    let _ = std::fs::remove_dir_all(&cache_root);
+}
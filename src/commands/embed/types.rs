@@ -1,6 +1,9 @@
 use serde::Serialize;
 
-pub(super) const EMBEDDING_DB_SCHEMA_VERSION: &str = "0.4.0";
+use crate::cli::StorageEncoding;
+use crate::semantic::EmbeddingEncoding;
+
+pub(super) const EMBEDDING_DB_SCHEMA_VERSION: &str = "0.5.0";
 pub(super) const SEMANTIC_MODEL_CONFIG_LOCK_PATH: &str =
     "manifests/semantic_model_config.lock.json";
 
@@ -14,6 +17,14 @@ pub(super) struct EmbedChunkRow {
     pub(super) table_md: Option<String>,
 }
 
+#[derive(Debug, Clone)]
+pub(super) struct EmbedNodeRow {
+    pub(super) node_id: String,
+    pub(super) node_type: String,
+    pub(super) reference: String,
+    pub(super) text: Option<String>,
+}
+
 #[derive(Debug, Clone)]
 pub(super) struct ExistingEmbeddingRow {
     pub(super) text_hash: String,
@@ -30,6 +41,9 @@ pub(super) struct EmbeddingRunManifest {
     pub(super) embedding_dim: usize,
     pub(super) normalization: String,
     pub(super) backend: String,
+    pub(super) storage_encoding: String,
+    pub(super) model_sha256: Option<String>,
+    pub(super) tokenizer_sha256: Option<String>,
     pub(super) db_schema_version: String,
     pub(super) refresh_mode: String,
     pub(super) chunk_type_filter: Vec<String>,
@@ -39,6 +53,15 @@ pub(super) struct EmbeddingRunManifest {
     pub(super) skipped_empty_chunks: usize,
     pub(super) stale_rows_before: usize,
     pub(super) stale_rows_after: usize,
+    pub(super) node_type_filter: Vec<String>,
+    pub(super) eligible_nodes: usize,
+    pub(super) updated_nodes: usize,
+    pub(super) skipped_orphan_nodes: usize,
+    pub(super) stale_node_rows_before: usize,
+    pub(super) ann_index_path: Option<String>,
+    pub(super) ann_index_checksum: Option<String>,
+    pub(super) ann_index_vectors: usize,
+    pub(super) ann_index_lists: usize,
     pub(super) batch_size: usize,
     pub(super) duration_ms: u128,
     pub(super) status: String,
@@ -53,6 +76,19 @@ pub(super) struct SemanticModelConfigLock {
     pub(super) embedding_dim: usize,
     pub(super) normalization: String,
     pub(super) runtime_backend: String,
+    pub(super) model_sha256: Option<String>,
+    pub(super) tokenizer_sha256: Option<String>,
     pub(super) created_at: String,
     pub(super) checksum: String,
 }
+
+impl From<StorageEncoding> for EmbeddingEncoding {
+    fn from(value: StorageEncoding) -> Self {
+        match value {
+            StorageEncoding::F32 => EmbeddingEncoding::F32,
+            StorageEncoding::F16 => EmbeddingEncoding::F16,
+            StorageEncoding::Int8 => EmbeddingEncoding::Int8,
+            StorageEncoding::Binary => EmbeddingEncoding::Binary,
+        }
+    }
+}
@@ -0,0 +1,309 @@
+use std::fs;
+use std::path::Path;
+
+use anyhow::{Context, Result, bail};
+use rusqlite::{Connection, OpenFlags, OptionalExtension, params};
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use tracing::info;
+
+use crate::cli::ExportCodingStandardArgs;
+use crate::commands::query::{CitationSource, render_anchor_citation};
+use crate::util::{ensure_directory, write_json_pretty};
+
+use super::render::{render_coding_standard_markdown, render_yaml_document};
+
+pub(super) const CODING_STANDARD_CATALOG_VERSION: &str = "1.0.0";
+
+#[derive(Debug, Clone, Serialize)]
+pub(super) struct CodingStandardCatalog {
+    pub(super) catalog_version: String,
+    pub(super) catalog_sha256: String,
+    pub(super) standard: CatalogSourceDocument,
+    pub(super) rule_count: usize,
+    pub(super) rules: Vec<CodingStandardRule>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub(super) struct CatalogSourceDocument {
+    pub(super) doc_id: String,
+    pub(super) part: u32,
+    pub(super) year: u32,
+    pub(super) sha256: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub(super) struct CodingStandardRule {
+    pub(super) rule_id: String,
+    pub(super) category: String,
+    pub(super) table_ref: String,
+    pub(super) table_title: String,
+    pub(super) marker: String,
+    pub(super) method: String,
+    pub(super) footnotes: Vec<String>,
+    pub(super) citation: String,
+    pub(super) obligations: Vec<AsilObligation>,
+    pub(super) page_pdf_start: Option<i64>,
+    pub(super) page_pdf_end: Option<i64>,
+    pub(super) source_hash: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub(super) struct AsilObligation {
+    pub(super) asil: String,
+    pub(super) rating: String,
+    pub(super) recommendation_level: String,
+}
+
+pub(super) fn export_coding_standard(args: ExportCodingStandardArgs) -> Result<()> {
+    let db_path = args
+        .db_path
+        .clone()
+        .unwrap_or_else(|| args.cache_root.join("iso26262_index.sqlite"));
+    let output_dir = args
+        .output_dir
+        .clone()
+        .unwrap_or_else(|| args.cache_root.join("exports").join("coding_standard"));
+
+    let connection = Connection::open_with_flags(
+        &db_path,
+        OpenFlags::SQLITE_OPEN_READ_ONLY | OpenFlags::SQLITE_OPEN_NO_MUTEX,
+    )
+    .with_context(|| format!("failed to open database read-only: {}", db_path.display()))?;
+
+    let catalog =
+        build_coding_standard_catalog(&connection, args.part, args.edition, &args.tables)?;
+    if catalog.rules.is_empty() {
+        bail!(
+            "no ASIL-rated guideline or principle tables found for ISO 26262-{}; re-run ingest or pass --table",
+            args.part
+        );
+    }
+
+    write_coding_standard_catalog(&output_dir, &catalog)?;
+
+    info!(
+        doc_id = %catalog.standard.doc_id,
+        rule_count = catalog.rule_count,
+        catalog_sha256 = %catalog.catalog_sha256,
+        output_dir = %output_dir.display(),
+        "coding standard catalog exported"
+    );
+
+    Ok(())
+}
+
+pub(super) fn build_coding_standard_catalog(
+    connection: &Connection,
+    part: u32,
+    edition: Option<u32>,
+    table_filter: &[String],
+) -> Result<CodingStandardCatalog> {
+    let standard = resolve_source_document(connection, part, edition)?;
+    let rules = load_coding_standard_rules(connection, &standard, table_filter)?;
+
+    let rules_json =
+        serde_json::to_vec(&rules).context("failed to serialize coding standard rules")?;
+    let mut hasher = Sha256::new();
+    hasher.update(standard.sha256.as_bytes());
+    hasher.update(&rules_json);
+
+    Ok(CodingStandardCatalog {
+        catalog_version: CODING_STANDARD_CATALOG_VERSION.to_string(),
+        catalog_sha256: format!("{:x}", hasher.finalize()),
+        standard,
+        rule_count: rules.len(),
+        rules,
+    })
+}
+
+fn resolve_source_document(
+    connection: &Connection,
+    part: u32,
+    edition: Option<u32>,
+) -> Result<CatalogSourceDocument> {
+    let document = connection
+        .query_row(
+            "
+            SELECT doc_id, part, year, sha256
+            FROM docs
+            WHERE part = ?1
+              AND (?2 IS NULL OR year = ?2)
+            ORDER BY year DESC, doc_id ASC
+            LIMIT 1
+            ",
+            params![part, edition],
+            |row| {
+                Ok(CatalogSourceDocument {
+                    doc_id: row.get(0)?,
+                    part: row.get(1)?,
+                    year: row.get(2)?,
+                    sha256: row.get(3)?,
+                })
+            },
+        )
+        .optional()
+        .context("failed to resolve source document for coding standard export")?;
+
+    match (document, edition) {
+        (Some(document), _) => Ok(document),
+        (None, Some(year)) => {
+            bail!("no ingested document found for ISO 26262-{part}:{year}; run ingest first")
+        }
+        (None, None) => bail!("no ingested document found for ISO 26262-{part}; run ingest first"),
+    }
+}
+
+fn load_coding_standard_rules(
+    connection: &Connection,
+    standard: &CatalogSourceDocument,
+    table_filter: &[String],
+) -> Result<Vec<CodingStandardRule>> {
+    let mut statement = connection.prepare(
+        "
+        SELECT
+          a.table_ref,
+          t.heading,
+          a.row_node_id,
+          a.marker,
+          a.method_text,
+          a.footnotes,
+          a.asil,
+          a.rating,
+          a.recommendation_level,
+          a.source_hash,
+          r.page_pdf_start,
+          r.page_pdf_end
+        FROM asil_recommendations a
+        JOIN nodes t ON t.node_id = a.table_node_id
+        LEFT JOIN nodes r ON r.node_id = a.row_node_id
+        WHERE a.doc_id = ?1
+        ORDER BY t.order_index ASC, r.row_idx ASC, a.row_node_id ASC, a.asil ASC
+        ",
+    )?;
+
+    let mut rows = statement.query(params![standard.doc_id])?;
+    let mut rules = Vec::<CodingStandardRule>::new();
+    let mut active_row_node_id = None::<String>;
+
+    while let Some(row) = rows.next()? {
+        let table_ref: String = row.get(0)?;
+        let table_heading: Option<String> = row.get(1)?;
+        let row_node_id: String = row.get(2)?;
+        let table_heading = table_heading.unwrap_or_default();
+
+        let Some(category) = select_rule_category(&table_ref, &table_heading, table_filter) else {
+            continue;
+        };
+
+        let obligation = AsilObligation {
+            asil: row.get(6)?,
+            rating: row.get(7)?,
+            recommendation_level: row.get(8)?,
+        };
+
+        if active_row_node_id.as_deref() == Some(row_node_id.as_str())
+            && let Some(rule) = rules.last_mut()
+        {
+            rule.obligations.push(obligation);
+            continue;
+        }
+
+        let marker: String = row.get(3)?;
+        let footnotes_json: String = row.get(5)?;
+        let footnotes = serde_json::from_str::<Vec<String>>(&footnotes_json)
+            .with_context(|| format!("invalid footnotes payload for {row_node_id}"))?;
+        let page_pdf_start: Option<i64> = row.get(10)?;
+        let page_pdf_end: Option<i64> = row.get(11)?;
+        let citation = render_anchor_citation(&CitationSource {
+            part: standard.part,
+            year: standard.year,
+            reference: &table_ref,
+            anchor_type: Some("marker"),
+            anchor_label_norm: Some(&marker),
+            page_pdf_start,
+            page_pdf_end,
+        });
+
+        rules.push(CodingStandardRule {
+            rule_id: coding_standard_rule_id(standard.part, &table_ref, &marker),
+            category: category.to_string(),
+            table_title: table_title_from_heading(&table_ref, &table_heading),
+            table_ref,
+            marker,
+            method: row.get(4)?,
+            footnotes,
+            citation,
+            obligations: vec![obligation],
+            page_pdf_start,
+            page_pdf_end,
+            source_hash: row.get::<_, Option<String>>(9)?.unwrap_or_default(),
+        });
+        active_row_node_id = Some(row_node_id);
+    }
+
+    Ok(rules)
+}
+
+pub(super) fn coding_standard_category(table_heading: &str) -> Option<&'static str> {
+    let heading = table_heading.to_ascii_lowercase();
+    if heading.contains("guideline") {
+        Some("modelling_coding_guideline")
+    } else if heading.contains("principle") {
+        Some("design_principle")
+    } else {
+        None
+    }
+}
+
+fn select_rule_category(
+    table_ref: &str,
+    table_heading: &str,
+    table_filter: &[String],
+) -> Option<&'static str> {
+    if table_filter.is_empty() {
+        return coding_standard_category(table_heading);
+    }
+
+    table_filter
+        .iter()
+        .any(|value| value.trim().eq_ignore_ascii_case(table_ref))
+        .then(|| coding_standard_category(table_heading).unwrap_or("asil_table"))
+}
+
+pub(super) fn coding_standard_rule_id(part: u32, table_ref: &str, marker: &str) -> String {
+    let table_number = table_ref
+        .trim()
+        .strip_prefix("Table")
+        .unwrap_or(table_ref)
+        .trim();
+    format!("ISO26262-{part}-T{table_number}-{marker}")
+}
+
+pub(super) fn table_title_from_heading(table_ref: &str, table_heading: &str) -> String {
+    table_heading
+        .trim()
+        .strip_prefix(table_ref)
+        .unwrap_or(table_heading)
+        .trim_start_matches(|ch: char| ch.is_whitespace() || matches!(ch, '-' | ':' | '–' | '—'))
+        .trim()
+        .to_string()
+}
+
+fn write_coding_standard_catalog(output_dir: &Path, catalog: &CodingStandardCatalog) -> Result<()> {
+    ensure_directory(output_dir)?;
+
+    write_json_pretty(&output_dir.join("coding_standard.json"), catalog)?;
+
+    let value =
+        serde_json::to_value(catalog).context("failed to convert coding standard catalog")?;
+    let yaml_path = output_dir.join("coding_standard.yaml");
+    fs::write(&yaml_path, render_yaml_document(&value))
+        .with_context(|| format!("failed to write {}", yaml_path.display()))?;
+
+    let markdown_path = output_dir.join("coding_standard.md");
+    fs::write(&markdown_path, render_coding_standard_markdown(catalog))
+        .with_context(|| format!("failed to write {}", markdown_path.display()))?;
+
+    Ok(())
+}
@@ -0,0 +1,8 @@
+mod coding_standard;
+mod render;
+mod run;
+#[cfg(test)]
+mod tests;
+mod work_products;
+
+pub(crate) use run::run;
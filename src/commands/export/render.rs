@@ -0,0 +1,227 @@
+use serde_json::{Map, Value};
+
+use super::coding_standard::{CodingStandardCatalog, CodingStandardRule};
+use super::work_products::WorkProductMatrix;
+
+const ASIL_COLUMNS: [&str; 4] = ["A", "B", "C", "D"];
+
+pub(super) fn render_yaml_document(value: &Value) -> String {
+    let mut output = String::from("---\n");
+    match value {
+        Value::Object(map) if !map.is_empty() => write_yaml_mapping(&mut output, map, 0),
+        Value::Array(items) if !items.is_empty() => write_yaml_sequence(&mut output, items, 0),
+        _ => {
+            output.push_str(&yaml_scalar(value));
+            output.push('\n');
+        }
+    }
+    output
+}
+
+fn write_yaml_mapping(output: &mut String, map: &Map<String, Value>, indent: usize) {
+    let pad = " ".repeat(indent);
+    for (key, value) in map {
+        output.push_str(&pad);
+        output.push_str(&yaml_key(key));
+        output.push(':');
+        match value {
+            Value::Object(nested) if !nested.is_empty() => {
+                output.push('\n');
+                write_yaml_mapping(output, nested, indent + 2);
+            }
+            Value::Array(items) if !items.is_empty() => {
+                output.push('\n');
+                write_yaml_sequence(output, items, indent + 2);
+            }
+            _ => {
+                output.push(' ');
+                output.push_str(&yaml_scalar(value));
+                output.push('\n');
+            }
+        }
+    }
+}
+
+fn write_yaml_sequence(output: &mut String, items: &[Value], indent: usize) {
+    let pad = " ".repeat(indent);
+    for item in items {
+        match item {
+            Value::Object(nested) if !nested.is_empty() => {
+                let mut nested_output = String::new();
+                write_yaml_mapping(&mut nested_output, nested, indent + 2);
+                output.push_str(&pad);
+                output.push_str("- ");
+                output.push_str(&nested_output[indent + 2..]);
+            }
+            Value::Array(nested) if !nested.is_empty() => {
+                output.push_str(&pad);
+                output.push_str("-\n");
+                write_yaml_sequence(output, nested, indent + 2);
+            }
+            _ => {
+                output.push_str(&pad);
+                output.push_str("- ");
+                output.push_str(&yaml_scalar(item));
+                output.push('\n');
+            }
+        }
+    }
+}
+
+fn yaml_key(key: &str) -> String {
+    let is_plain = !key.is_empty()
+        && key
+            .chars()
+            .all(|ch| ch.is_ascii_alphanumeric() || matches!(ch, '_' | '-'));
+    if is_plain {
+        key.to_string()
+    } else {
+        Value::String(key.to_string()).to_string()
+    }
+}
+
+fn yaml_scalar(value: &Value) -> String {
+    match value {
+        Value::Null => "null".to_string(),
+        Value::Object(_) => "{}".to_string(),
+        Value::Array(_) => "[]".to_string(),
+        _ => value.to_string(),
+    }
+}
+
+pub(super) fn render_coding_standard_markdown(catalog: &CodingStandardCatalog) -> String {
+    let standard = &catalog.standard;
+    let mut output = format!(
+        "# ISO 26262-{}:{} coding standard catalog\n\n",
+        standard.part, standard.year
+    );
+    output.push_str(&format!("- Catalog version: {}\n", catalog.catalog_version));
+    output.push_str(&format!(
+        "- Catalog SHA-256: `{}`\n",
+        catalog.catalog_sha256
+    ));
+    output.push_str(&format!(
+        "- Source document: {} (SHA-256 `{}`)\n",
+        standard.doc_id, standard.sha256
+    ));
+    output.push_str(&format!("- Rules: {}\n", catalog.rule_count));
+
+    let mut active_table = None::<&str>;
+    for rule in &catalog.rules {
+        if active_table != Some(rule.table_ref.as_str()) {
+            output.push_str(&format!("\n## {}", rule.table_ref));
+            if !rule.table_title.is_empty() {
+                output.push_str(&format!(" — {}", rule.table_title));
+            }
+            output.push_str("\n\n");
+            output.push_str(&format!("Category: `{}`\n\n", rule.category));
+            output
+                .push_str("| Rule ID | Method | ASIL A | ASIL B | ASIL C | ASIL D | Citation |\n");
+            output.push_str("|---|---|---|---|---|---|---|\n");
+            active_table = Some(rule.table_ref.as_str());
+        }
+
+        output.push_str(&render_markdown_rule_row(rule));
+    }
+
+    output
+}
+
+fn render_markdown_rule_row(rule: &CodingStandardRule) -> String {
+    let mut method = rule.method.clone();
+    if !rule.footnotes.is_empty() {
+        method.push_str(&format!(" (footnotes: {})", rule.footnotes.join(", ")));
+    }
+
+    let ratings = ASIL_COLUMNS
+        .iter()
+        .map(|asil| {
+            rule.obligations
+                .iter()
+                .find(|obligation| obligation.asil == *asil)
+                .map(|obligation| escape_markdown_cell(&obligation.rating))
+                .unwrap_or_default()
+        })
+        .collect::<Vec<String>>();
+
+    format!(
+        "| {} | {} | {} | {} |\n",
+        escape_markdown_cell(&rule.rule_id),
+        escape_markdown_cell(&method),
+        ratings.join(" | "),
+        escape_markdown_cell(&rule.citation)
+    )
+}
+
+pub(super) fn escape_markdown_cell(value: &str) -> String {
+    value
+        .split_whitespace()
+        .collect::<Vec<&str>>()
+        .join(" ")
+        .replace('|', "\\|")
+}
+
+pub(super) fn render_work_product_csv(matrix: &WorkProductMatrix) -> String {
+    let mut output = String::from(
+        "work_product_id,part,ref,work_product,source_part,source_ref,source_heading,resolution_status,citation\n",
+    );
+
+    for work_product in &matrix.work_products {
+        let prefix = [
+            work_product.work_product_id.clone(),
+            work_product.part.to_string(),
+            work_product.reference.clone(),
+            work_product.name.clone(),
+        ];
+        if work_product.sources.is_empty() {
+            let row = prefix
+                .iter()
+                .cloned()
+                .chain([
+                    String::new(),
+                    String::new(),
+                    String::new(),
+                    String::new(),
+                    work_product.citation.clone(),
+                ])
+                .collect::<Vec<String>>();
+            output.push_str(&render_csv_row(&row));
+            continue;
+        }
+
+        for source in &work_product.sources {
+            let row = prefix
+                .iter()
+                .cloned()
+                .chain([
+                    source.source_part.to_string(),
+                    source.source_ref.clone(),
+                    source.source_heading.clone().unwrap_or_default(),
+                    source.resolution_status.clone(),
+                    work_product.citation.clone(),
+                ])
+                .collect::<Vec<String>>();
+            output.push_str(&render_csv_row(&row));
+        }
+    }
+
+    output
+}
+
+fn render_csv_row(cells: &[String]) -> String {
+    let mut row = cells
+        .iter()
+        .map(|cell| escape_csv_cell(cell))
+        .collect::<Vec<String>>()
+        .join(",");
+    row.push('\n');
+    row
+}
+
+pub(super) fn escape_csv_cell(value: &str) -> String {
+    if value.contains(',') || value.contains('"') || value.contains('\n') {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
@@ -0,0 +1,13 @@
+use anyhow::Result;
+
+use crate::cli::{ExportArgs, ExportCommands};
+
+use super::coding_standard::export_coding_standard;
+use super::work_products::export_work_products;
+
+pub fn run(args: ExportArgs) -> Result<()> {
+    match args.command {
+        ExportCommands::CodingStandard(args) => export_coding_standard(args),
+        ExportCommands::WorkProducts(args) => export_work_products(args),
+    }
+}
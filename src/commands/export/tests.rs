@@ -0,0 +1,199 @@
+use rusqlite::Connection;
+use serde_json::json;
+
+use crate::commands::ingest::ensure_schema;
+
+use super::coding_standard::{
+    build_coding_standard_catalog, coding_standard_rule_id, table_title_from_heading,
+};
+use super::render::{escape_markdown_cell, render_work_product_csv, render_yaml_document};
+use super::work_products::build_work_product_matrix;
+
+fn seed_coding_standard_db() -> Connection {
+    let connection = Connection::open_in_memory().expect("in-memory DB should open");
+    ensure_schema(&connection).expect("schema should be created");
+    connection
+        .execute_batch(
+            "
+            INSERT INTO docs(doc_id, filename, sha256, part, year)
+            VALUES ('ISO26262-6-2018', 'part6.pdf', 'pdf-sha', 6, 2018);
+            INSERT INTO nodes(
+              node_id, doc_id, node_type, heading, order_index, page_pdf_start, page_pdf_end, row_idx
+            ) VALUES
+              ('t1', 'ISO26262-6-2018', 'table',
+               'Table 1 — Topics to be covered by modelling and coding guidelines', 1, 20, 20, NULL),
+              ('t1:row:002', 'ISO26262-6-2018', 'table_row', NULL, 2, 20, 20, 2),
+              ('t7', 'ISO26262-6-2018', 'table',
+               'Table 7 — Methods for software unit verification', 3, 30, 30, NULL),
+              ('t7:row:002', 'ISO26262-6-2018', 'table_row', NULL, 4, 30, 30, 2);
+            INSERT INTO asil_recommendations VALUES
+              ('ISO26262-6-2018', 't1', 'Table 1', 't1:row:002', '1a', 'Enforcement of low complexity', 'A', '++', 'highly_recommended', '[\"a\"]', 'hash-1'),
+              ('ISO26262-6-2018', 't1', 'Table 1', 't1:row:002', '1a', 'Enforcement of low complexity', 'B', '++', 'highly_recommended', '[\"a\"]', 'hash-1'),
+              ('ISO26262-6-2018', 't1', 'Table 1', 't1:row:002', '1a', 'Enforcement of low complexity', 'C', '+', 'recommended', '[\"a\"]', 'hash-1'),
+              ('ISO26262-6-2018', 't1', 'Table 1', 't1:row:002', '1a', 'Enforcement of low complexity', 'D', 'o', 'no_recommendation', '[\"a\"]', 'hash-1'),
+              ('ISO26262-6-2018', 't7', 'Table 7', 't7:row:002', '1a', 'Walk-through', 'A', '++', 'highly_recommended', '[]', 'hash-1');
+            ",
+        )
+        .expect("seed rows should insert");
+    connection
+}
+
+#[test]
+fn build_coding_standard_catalog_groups_asil_obligations_per_rule() {
+    let connection = seed_coding_standard_db();
+
+    let catalog =
+        build_coding_standard_catalog(&connection, 6, None, &[]).expect("catalog should build");
+
+    assert_eq!(catalog.standard.doc_id, "ISO26262-6-2018");
+    assert_eq!(catalog.rule_count, 1);
+
+    let rule = &catalog.rules[0];
+    assert_eq!(rule.rule_id, "ISO26262-6-T1-1a");
+    assert_eq!(rule.category, "modelling_coding_guideline");
+    assert_eq!(
+        rule.table_title,
+        "Topics to be covered by modelling and coding guidelines"
+    );
+    assert_eq!(rule.citation, "ISO 26262-6:2018, Table 1(1a), PDF pages 20");
+    assert_eq!(rule.footnotes, vec!["a".to_string()]);
+    assert_eq!(rule.source_hash, "hash-1");
+    let ratings = rule
+        .obligations
+        .iter()
+        .map(|obligation| (obligation.asil.as_str(), obligation.rating.as_str()))
+        .collect::<Vec<(&str, &str)>>();
+    assert_eq!(
+        ratings,
+        vec![("A", "++"), ("B", "++"), ("C", "+"), ("D", "o")]
+    );
+
+    let again =
+        build_coding_standard_catalog(&connection, 6, None, &[]).expect("catalog should rebuild");
+    assert_eq!(catalog.catalog_sha256, again.catalog_sha256);
+}
+
+#[test]
+fn build_coding_standard_catalog_honors_explicit_table_filter() {
+    let connection = seed_coding_standard_db();
+
+    let catalog = build_coding_standard_catalog(&connection, 6, None, &["table 7".to_string()])
+        .expect("catalog should build");
+
+    assert_eq!(catalog.rule_count, 1);
+    assert_eq!(catalog.rules[0].rule_id, "ISO26262-6-T7-1a");
+    assert_eq!(catalog.rules[0].category, "asil_table");
+}
+
+#[test]
+fn build_coding_standard_catalog_selects_the_requested_edition() {
+    let connection = seed_coding_standard_db();
+    connection
+        .execute_batch(
+            "
+            INSERT INTO docs(doc_id, filename, sha256, part, year)
+            VALUES ('ISO26262-6-2011', 'part6-2011.pdf', 'pdf-sha-2011', 6, 2011);
+            INSERT INTO nodes(node_id, doc_id, node_type, heading, order_index, row_idx) VALUES
+              ('old-t1', 'ISO26262-6-2011', 'table',
+               'Table 1 — Topics to be covered by modelling and coding guidelines', 1, NULL),
+              ('old-t1:row:002', 'ISO26262-6-2011', 'table_row', NULL, 2, 2);
+            INSERT INTO asil_recommendations VALUES
+              ('ISO26262-6-2011', 'old-t1', 'Table 1', 'old-t1:row:002', '1a', 'Enforcement of low complexity', 'A', '+', 'recommended', '[]', 'hash-2011');
+            ",
+        )
+        .expect("2011 edition rows should insert");
+
+    let latest =
+        build_coding_standard_catalog(&connection, 6, None, &[]).expect("catalog should build");
+    assert_eq!(latest.standard.doc_id, "ISO26262-6-2018");
+
+    let catalog = build_coding_standard_catalog(&connection, 6, Some(2011), &[])
+        .expect("2011 catalog should build");
+    assert_eq!(catalog.standard.doc_id, "ISO26262-6-2011");
+    assert_eq!(catalog.rule_count, 1);
+    assert_eq!(catalog.rules[0].source_hash, "hash-2011");
+    assert_eq!(catalog.rules[0].obligations[0].rating, "+");
+
+    let error = build_coding_standard_catalog(&connection, 6, Some(2026), &[])
+        .expect_err("missing edition should fail");
+    assert!(error.to_string().contains("ISO 26262-6:2026"));
+}
+
+#[test]
+fn render_yaml_document_nests_mappings_and_sequences() {
+    let value = json!({
+        "rules": [
+            {"rule_id": "R-1", "footnotes": [], "obligations": [{"asil": "A"}]}
+        ],
+        "version": "1.0.0"
+    });
+
+    assert_eq!(
+        render_yaml_document(&value),
+        "---\nrules:\n  - footnotes: []\n    obligations:\n      - asil: \"A\"\n    rule_id: \"R-1\"\nversion: \"1.0.0\"\n"
+    );
+}
+
+#[test]
+fn coding_standard_helpers_normalize_identifiers_and_cells() {
+    assert_eq!(
+        coding_standard_rule_id(6, "Table 6", "1b"),
+        "ISO26262-6-T6-1b"
+    );
+    assert_eq!(
+        table_title_from_heading(
+            "Table 3",
+            "Table 3 - Principles for software architectural design"
+        ),
+        "Principles for software architectural design"
+    );
+    assert_eq!(escape_markdown_cell("a | b\nc"), "a \\| b c");
+}
+
+#[test]
+fn build_work_product_matrix_links_sources_and_renders_csv() {
+    let connection = Connection::open_in_memory().expect("in-memory DB should open");
+    ensure_schema(&connection).expect("schema should be created");
+    connection
+        .execute_batch(
+            "
+            INSERT INTO docs(doc_id, filename, sha256, part, year) VALUES
+              ('ISO26262-6-2018', 'part6.pdf', 'hash-6', 6, 2018),
+              ('ISO26262-9-2018', 'part9.pdf', 'hash-9', 9, 2018);
+            INSERT INTO nodes(node_id, doc_id, node_type, ref, heading)
+            VALUES (
+              'n-842', 'ISO26262-6-2018', 'subclause', '8.4.2',
+              '8.4.2 Software unit design, notation'
+            );
+            INSERT INTO work_products(
+              work_product_id, doc_id, ref, name, name_norm, source_text,
+              page_pdf_start, page_pdf_end
+            ) VALUES
+              ('ISO26262-6-2018:wp:8_5_1', 'ISO26262-6-2018', '8.5.1',
+               'Software unit design specification', 'software unit design specification',
+               'resulting from requirements of 8.4.2', 40, 40),
+              ('ISO26262-9-2018:wp:5_5_1', 'ISO26262-9-2018', '5.5.1',
+               'Tailoring rationale', 'tailoring rationale', '', 12, 12);
+            INSERT INTO work_product_sources VALUES
+              ('ISO26262-6-2018:wp:8_5_1', 1, 6, 'ISO26262-6-2018', '8.4.2', 'n-842'),
+              ('ISO26262-6-2018:wp:8_5_1', 2, 8, NULL, '6', NULL);
+            ",
+        )
+        .expect("seed rows should insert");
+
+    let matrix = build_work_product_matrix(&connection, &[6]).expect("matrix should build");
+
+    assert_eq!(matrix.work_product_count, 1);
+    assert_eq!(matrix.link_count, 2);
+    assert_eq!(matrix.unresolved_link_count, 1);
+    assert_eq!(
+        matrix.work_products[0].citation,
+        "ISO 26262-6:2018, 8.5.1, PDF pages 40"
+    );
+    assert_eq!(
+        render_work_product_csv(&matrix),
+        "work_product_id,part,ref,work_product,source_part,source_ref,source_heading,resolution_status,citation\n\
+         ISO26262-6-2018:wp:8_5_1,6,8.5.1,Software unit design specification,6,8.4.2,\"8.4.2 Software unit design, notation\",resolved,\"ISO 26262-6:2018, 8.5.1, PDF pages 40\"\n\
+         ISO26262-6-2018:wp:8_5_1,6,8.5.1,Software unit design specification,8,6,,missing_document,\"ISO 26262-6:2018, 8.5.1, PDF pages 40\"\n"
+    );
+}
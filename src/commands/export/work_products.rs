@@ -0,0 +1,240 @@
+use std::fs;
+use std::path::Path;
+
+use anyhow::{Context, Result, bail};
+use rusqlite::{Connection, OpenFlags, OptionalExtension};
+use serde::Serialize;
+use tracing::info;
+
+use crate::cli::ExportWorkProductsArgs;
+use crate::commands::query::{CitationSource, render_anchor_citation};
+use crate::util::{ensure_directory, write_json_pretty};
+
+use super::render::render_work_product_csv;
+
+pub(super) const WORK_PRODUCT_MATRIX_VERSION: &str = "1.0.0";
+
+#[derive(Debug, Clone, Serialize)]
+pub(super) struct WorkProductMatrix {
+    pub(super) matrix_version: String,
+    pub(super) part_filter: Vec<u32>,
+    pub(super) work_product_count: usize,
+    pub(super) link_count: usize,
+    pub(super) unresolved_link_count: usize,
+    pub(super) work_products: Vec<WorkProductEntry>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub(super) struct WorkProductEntry {
+    pub(super) work_product_id: String,
+    pub(super) doc_id: String,
+    pub(super) part: u32,
+    pub(super) year: u32,
+    pub(super) reference: String,
+    pub(super) name: String,
+    pub(super) source_text: String,
+    pub(super) citation: String,
+    pub(super) sources: Vec<WorkProductSourceLink>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub(super) struct WorkProductSourceLink {
+    pub(super) source_part: u32,
+    pub(super) source_ref: String,
+    pub(super) source_doc_id: Option<String>,
+    pub(super) source_node_id: Option<String>,
+    pub(super) source_heading: Option<String>,
+    pub(super) resolution_status: String,
+}
+
+pub(super) fn export_work_products(args: ExportWorkProductsArgs) -> Result<()> {
+    let db_path = args
+        .db_path
+        .clone()
+        .unwrap_or_else(|| args.cache_root.join("iso26262_index.sqlite"));
+    let output_dir = args
+        .output_dir
+        .clone()
+        .unwrap_or_else(|| args.cache_root.join("exports").join("work_products"));
+
+    let connection = Connection::open_with_flags(
+        &db_path,
+        OpenFlags::SQLITE_OPEN_READ_ONLY | OpenFlags::SQLITE_OPEN_NO_MUTEX,
+    )
+    .with_context(|| format!("failed to open database read-only: {}", db_path.display()))?;
+
+    ensure_work_products_available(&connection)?;
+    let matrix = build_work_product_matrix(&connection, &args.parts)?;
+    if matrix.work_products.is_empty() {
+        bail!("no work products found for the selected parts; re-run ingest first");
+    }
+
+    write_work_product_matrix(&output_dir, &matrix)?;
+
+    info!(
+        work_product_count = matrix.work_product_count,
+        link_count = matrix.link_count,
+        unresolved_link_count = matrix.unresolved_link_count,
+        output_dir = %output_dir.display(),
+        "work product traceability matrix exported"
+    );
+
+    Ok(())
+}
+
+fn ensure_work_products_available(connection: &Connection) -> Result<()> {
+    let table_exists = connection
+        .query_row(
+            "
+            SELECT name
+            FROM sqlite_master
+            WHERE type = 'table' AND name = 'work_products'
+            LIMIT 1
+            ",
+            [],
+            |row| row.get::<_, String>(0),
+        )
+        .optional()?
+        .is_some();
+    if !table_exists {
+        bail!("work_products table is missing; re-run ingest to extract work products");
+    }
+    Ok(())
+}
+
+pub(super) fn build_work_product_matrix(
+    connection: &Connection,
+    parts: &[u32],
+) -> Result<WorkProductMatrix> {
+    let mut part_filter = parts.to_vec();
+    part_filter.sort_unstable();
+    part_filter.dedup();
+
+    let mut statement = connection.prepare(
+        "
+        SELECT
+          w.work_product_id,
+          w.doc_id,
+          d.part,
+          d.year,
+          w.ref,
+          w.name,
+          w.source_text,
+          w.page_pdf_start,
+          w.page_pdf_end
+        FROM work_products w
+        JOIN docs d ON d.doc_id = w.doc_id
+        ORDER BY d.part ASC, d.year DESC, w.rowid ASC
+        ",
+    )?;
+    let mut rows = statement.query([])?;
+    let mut work_products = Vec::<WorkProductEntry>::new();
+
+    while let Some(row) = rows.next()? {
+        let part: u32 = row.get(2)?;
+        if !part_filter.is_empty() && !part_filter.contains(&part) {
+            continue;
+        }
+
+        let work_product_id: String = row.get(0)?;
+        let year: u32 = row.get(3)?;
+        let reference: String = row.get(4)?;
+        let citation = render_anchor_citation(&CitationSource {
+            part,
+            year,
+            reference: &reference,
+            anchor_type: None,
+            anchor_label_norm: None,
+            page_pdf_start: row.get(7)?,
+            page_pdf_end: row.get(8)?,
+        });
+        let sources = load_work_product_sources(connection, &work_product_id)?;
+
+        work_products.push(WorkProductEntry {
+            work_product_id,
+            doc_id: row.get(1)?,
+            part,
+            year,
+            reference,
+            name: row.get(5)?,
+            source_text: row.get(6)?,
+            citation,
+            sources,
+        });
+    }
+
+    let link_count = work_products
+        .iter()
+        .map(|work_product| work_product.sources.len())
+        .sum();
+    let unresolved_link_count = work_products
+        .iter()
+        .flat_map(|work_product| work_product.sources.iter())
+        .filter(|source| source.source_node_id.is_none())
+        .count();
+
+    Ok(WorkProductMatrix {
+        matrix_version: WORK_PRODUCT_MATRIX_VERSION.to_string(),
+        part_filter,
+        work_product_count: work_products.len(),
+        link_count,
+        unresolved_link_count,
+        work_products,
+    })
+}
+
+fn load_work_product_sources(
+    connection: &Connection,
+    work_product_id: &str,
+) -> Result<Vec<WorkProductSourceLink>> {
+    let mut statement = connection.prepare_cached(
+        "
+        SELECT
+          s.source_part,
+          s.source_ref,
+          s.source_doc_id,
+          s.source_node_id,
+          n.heading
+        FROM work_product_sources s
+        LEFT JOIN nodes n ON n.node_id = s.source_node_id
+        WHERE s.work_product_id = ?1
+        ORDER BY s.source_seq ASC
+        ",
+    )?;
+    let rows = statement.query_map([work_product_id], |row| {
+        let source_doc_id: Option<String> = row.get(2)?;
+        let source_node_id: Option<String> = row.get(3)?;
+        let resolution_status = match (&source_doc_id, &source_node_id) {
+            (_, Some(_)) => "resolved",
+            (None, None) => "missing_document",
+            (Some(_), None) => "unresolved",
+        };
+
+        Ok(WorkProductSourceLink {
+            source_part: row.get(0)?,
+            source_ref: row.get(1)?,
+            source_doc_id,
+            source_node_id,
+            source_heading: row.get(4)?,
+            resolution_status: resolution_status.to_string(),
+        })
+    })?;
+
+    let mut sources = Vec::new();
+    for row in rows {
+        sources.push(row?);
+    }
+    Ok(sources)
+}
+
+fn write_work_product_matrix(output_dir: &Path, matrix: &WorkProductMatrix) -> Result<()> {
+    ensure_directory(output_dir)?;
+
+    write_json_pretty(&output_dir.join("work_products.json"), matrix)?;
+
+    let csv_path = output_dir.join("work_products.csv");
+    fs::write(&csv_path, render_work_product_csv(matrix))
+        .with_context(|| format!("failed to write {}", csv_path.display()))?;
+
+    Ok(())
+}
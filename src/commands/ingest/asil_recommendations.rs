@@ -0,0 +1,154 @@
+use super::*;
+
+pub const ASIL_LEVELS: [&str; 4] = ["A", "B", "C", "D"];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AsilRecommendationDraft {
+    pub row_idx: usize,
+    pub marker: String,
+    pub method_text: String,
+    pub asil: String,
+    pub rating: String,
+    pub recommendation_level: String,
+    pub footnotes: Vec<String>,
+}
+
+#[derive(Debug, Default)]
+pub struct AsilRecommendationExtraction {
+    pub recommendations: Vec<AsilRecommendationDraft>,
+    pub incomplete_rows_count: usize,
+}
+
+pub fn extract_asil_recommendations(
+    rows: &[Vec<String>],
+    body_lines: &[&str],
+) -> AsilRecommendationExtraction {
+    let mut extraction = AsilRecommendationExtraction::default();
+    if !is_asil_recommendation_table(rows, body_lines) {
+        return extraction;
+    }
+
+    let header_row_count = infer_table_header_rows(rows);
+    for (row_idx, row) in rows.iter().enumerate().skip(header_row_count) {
+        let Some(marker) = row.first().map(|value| normalize_marker_label(value)) else {
+            continue;
+        };
+        if parse_table_marker_token(&marker).is_none() {
+            continue;
+        }
+
+        let description = row.get(1).map(|value| value.as_str()).unwrap_or_default();
+        let (method_text, footnotes) = split_method_footnote_markers(description);
+        if method_text.is_empty() {
+            extraction.incomplete_rows_count += 1;
+            continue;
+        }
+
+        let ratings = row
+            .iter()
+            .skip(2)
+            .flat_map(|cell| cell.split_whitespace())
+            .map(|token| token.trim_matches(['(', ')', '.', ':', ';', ',']))
+            .filter(|token| is_table_rating_token(token))
+            .collect::<Vec<&str>>();
+        if ratings.len() != ASIL_LEVELS.len() {
+            extraction.incomplete_rows_count += 1;
+            continue;
+        }
+
+        for (asil, rating) in ASIL_LEVELS.iter().zip(ratings.iter()) {
+            extraction.recommendations.push(AsilRecommendationDraft {
+                row_idx: row_idx + 1,
+                marker: marker.clone(),
+                method_text: method_text.clone(),
+                asil: (*asil).to_string(),
+                rating: (*rating).to_string(),
+                recommendation_level: classify_recommendation_level(rating).to_string(),
+                footnotes: footnotes.clone(),
+            });
+        }
+    }
+
+    extraction
+}
+
+pub fn is_asil_recommendation_table(rows: &[Vec<String>], body_lines: &[&str]) -> bool {
+    if looks_like_asil_matrix(body_lines) {
+        return true;
+    }
+
+    let header_row_count = infer_table_header_rows(rows);
+    rows.iter().take(header_row_count).any(|row| {
+        row.iter()
+            .any(|cell| cell.to_ascii_uppercase().contains("ASIL"))
+    })
+}
+
+pub fn classify_recommendation_level(rating: &str) -> &'static str {
+    match rating {
+        "++" => "highly_recommended",
+        "+" => "recommended",
+        "o" => "no_recommendation",
+        "-" | "--" => "not_recommended",
+        _ => "conditional",
+    }
+}
+
+pub fn split_method_footnote_markers(description: &str) -> (String, Vec<String>) {
+    let mut tokens = description.split_whitespace().collect::<Vec<&str>>();
+    let mut footnotes = Vec::<String>::new();
+
+    while tokens.len() > 1 {
+        let Some(last) = tokens.last() else {
+            break;
+        };
+        let candidate = last.trim_matches([',', ')', '(']);
+        if !is_footnote_marker_line(candidate) {
+            break;
+        }
+
+        footnotes.push(candidate.to_string());
+        tokens.pop();
+    }
+
+    footnotes.reverse();
+    (tokens.join(" "), footnotes)
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn insert_asil_recommendations(
+    asil_statement: &mut rusqlite::Statement<'_>,
+    doc_id: &str,
+    table_node_id: &str,
+    table_reference: &str,
+    parsed_table: &ParsedTableRows,
+    body_lines: &[&str],
+    source_hash: &str,
+    stats: &mut ChunkInsertStats,
+) -> Result<()> {
+    let extraction = extract_asil_recommendations(&parsed_table.rows, body_lines);
+    stats.asil_matrix_rows_incomplete_count += extraction.incomplete_rows_count;
+
+    for recommendation in &extraction.recommendations {
+        let row_node_id = table_row_node_id(table_node_id, recommendation.row_idx);
+        let footnotes_json = serde_json::to_string(&recommendation.footnotes)
+            .context("failed to serialize ASIL recommendation footnotes")?;
+
+        asil_statement.execute(params![
+            doc_id,
+            table_node_id,
+            table_reference,
+            row_node_id,
+            &recommendation.marker,
+            &recommendation.method_text,
+            &recommendation.asil,
+            &recommendation.rating,
+            &recommendation.recommendation_level,
+            footnotes_json,
+            source_hash
+        ])?;
+        stats.asil_recommendations_inserted += 1;
+    }
+
+    Ok(())
+}
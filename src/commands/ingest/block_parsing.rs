@@ -113,6 +113,7 @@ pub fn insert_paragraph_nodes(
             None,
             None,
             None,
+            None,
         )?;
 
         *node_order_index += 1;
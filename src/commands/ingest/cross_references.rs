@@ -0,0 +1,283 @@
+use super::*;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CrossReferenceDraft {
+    pub raw_text: String,
+    pub reference_kind: &'static str,
+    pub target_part: Option<u32>,
+    pub target_year: Option<u32>,
+    pub target_ref: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CrossReferenceDiagnostic {
+    pub reference_id: String,
+    pub doc_id: String,
+    pub source_node_id: String,
+    pub source_ref: Option<String>,
+    pub raw_text: String,
+    pub reference_kind: String,
+    pub target_doc_id: Option<String>,
+    pub target_part: Option<u32>,
+    pub target_ref: String,
+    pub resolution_status: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CrossReferenceDiagnosticsManifest {
+    pub manifest_version: u32,
+    pub run_id: String,
+    pub generated_at: String,
+    pub references_total: usize,
+    pub resolved_count: usize,
+    pub unresolved_count: usize,
+    pub entries: Vec<CrossReferenceDiagnostic>,
+}
+
+pub fn extract_cross_references(
+    text: &str,
+    heading: &str,
+    own_reference: &str,
+    regexes: &IngestRegexes,
+) -> Vec<CrossReferenceDraft> {
+    let body = body_without_heading(text, heading);
+    let mut drafts = Vec::<CrossReferenceDraft>::new();
+    let mut external_spans = Vec::<(usize, usize)>::new();
+
+    for captures in regexes.cross_reference_external_regex.captures_iter(&body) {
+        let Some(full_match) = captures.get(0) else {
+            continue;
+        };
+        external_spans.push((full_match.start(), full_match.end()));
+
+        let target_part = captures
+            .name("part")
+            .and_then(|value| value.as_str().parse::<u32>().ok());
+        let target_year = captures
+            .name("year")
+            .and_then(|value| value.as_str().parse::<u32>().ok());
+        let (reference_kind, target_ref) = match captures.name("target") {
+            Some(target) => {
+                let target_ref = normalize_cross_reference_target(target.as_str());
+                (cross_reference_kind(&target_ref), target_ref)
+            }
+            None => ("part", String::new()),
+        };
+
+        push_cross_reference(
+            &mut drafts,
+            CrossReferenceDraft {
+                raw_text: condense_cross_reference_text(full_match.as_str()),
+                reference_kind,
+                target_part,
+                target_year,
+                target_ref,
+            },
+        );
+    }
+
+    for captures in regexes.cross_reference_local_regex.captures_iter(&body) {
+        let Some(full_match) = captures.get(0) else {
+            continue;
+        };
+        let overlaps_external = external_spans
+            .iter()
+            .any(|(start, end)| full_match.start() < *end && *start < full_match.end());
+        if overlaps_external {
+            continue;
+        }
+
+        let Some(target) = captures
+            .name("target")
+            .or_else(|| captures.name("clause"))
+            .or_else(|| captures.name("top"))
+        else {
+            continue;
+        };
+        let target_ref = normalize_cross_reference_target(target.as_str());
+        if target_ref.eq_ignore_ascii_case(own_reference) {
+            continue;
+        }
+
+        push_cross_reference(
+            &mut drafts,
+            CrossReferenceDraft {
+                raw_text: condense_cross_reference_text(full_match.as_str()),
+                reference_kind: cross_reference_kind(&target_ref),
+                target_part: None,
+                target_year: None,
+                target_ref,
+            },
+        );
+    }
+
+    drafts
+}
+
+fn push_cross_reference(drafts: &mut Vec<CrossReferenceDraft>, draft: CrossReferenceDraft) {
+    let duplicate = drafts.iter().any(|existing| {
+        existing.target_part == draft.target_part
+            && existing.target_year == draft.target_year
+            && existing.target_ref.eq_ignore_ascii_case(&draft.target_ref)
+    });
+    if !duplicate {
+        drafts.push(draft);
+    }
+}
+
+pub fn normalize_cross_reference_target(value: &str) -> String {
+    let condensed = condense_cross_reference_text(value);
+    let lowered = condensed.to_ascii_lowercase();
+    for prefix in ["subclause ", "clause "] {
+        if lowered.starts_with(prefix) {
+            return condensed[prefix.len()..].trim().to_string();
+        }
+    }
+    condensed
+}
+
+fn condense_cross_reference_text(value: &str) -> String {
+    value.split_whitespace().collect::<Vec<&str>>().join(" ")
+}
+
+fn cross_reference_kind(target_ref: &str) -> &'static str {
+    if target_ref.starts_with("Table ") {
+        "table"
+    } else if target_ref.starts_with("Annex ") {
+        "annex"
+    } else {
+        "clause"
+    }
+}
+
+pub fn insert_cross_references(
+    reference_statement: &mut rusqlite::Statement<'_>,
+    doc_id: &str,
+    source_node_id: &str,
+    drafts: &[CrossReferenceDraft],
+    stats: &mut ChunkInsertStats,
+) -> Result<()> {
+    for (index, draft) in drafts.iter().enumerate() {
+        let reference_id = format!("{}:xref:{:03}", source_node_id, index + 1);
+        let target_doc_id = draft.target_part.is_none().then_some(doc_id);
+
+        reference_statement.execute(params![
+            reference_id,
+            doc_id,
+            source_node_id,
+            target_doc_id,
+            draft.target_part,
+            draft.target_year,
+            draft.target_ref,
+            draft.reference_kind,
+            draft.raw_text,
+        ])?;
+        stats.node_references_inserted += 1;
+    }
+
+    Ok(())
+}
+
+pub fn resolve_cross_references(
+    connection: &Connection,
+    stats: &mut ChunkInsertStats,
+) -> Result<()> {
+    connection
+        .execute_batch(
+            "
+            UPDATE node_references
+            SET target_doc_id = (
+              SELECT d.doc_id
+              FROM docs d
+              JOIN docs s ON s.doc_id = node_references.doc_id
+              WHERE d.part = node_references.target_part
+                AND (node_references.target_year IS NULL OR d.year = node_references.target_year)
+              ORDER BY d.year = s.year DESC, d.year DESC
+              LIMIT 1
+            )
+            WHERE target_part IS NOT NULL;
+
+            UPDATE node_references
+            SET target_node_id = CASE
+              WHEN reference_kind = 'part' THEN (
+                SELECT n.node_id
+                FROM nodes n
+                WHERE n.doc_id = node_references.target_doc_id
+                  AND n.node_type = 'document'
+                LIMIT 1
+              )
+              ELSE (
+                SELECT n.node_id
+                FROM nodes n
+                WHERE n.doc_id = node_references.target_doc_id
+                  AND lower(n.ref) = lower(node_references.target_ref)
+                  AND n.node_type IN ('section_heading', 'clause', 'subclause', 'table', 'annex')
+                ORDER BY n.order_index ASC
+                LIMIT 1
+              )
+            END;
+
+            UPDATE node_references
+            SET resolution_status = CASE
+              WHEN target_node_id IS NOT NULL THEN 'resolved'
+              WHEN target_doc_id IS NULL THEN 'missing_document'
+              ELSE 'unresolved'
+            END;
+            ",
+        )
+        .context("failed to resolve node cross-references")?;
+
+    stats.node_references_resolved = count_rows(
+        connection,
+        "SELECT COUNT(*) FROM node_references WHERE resolution_status = 'resolved'",
+    )? as usize;
+    stats.cross_reference_diagnostics = collect_unresolved_cross_references(connection)?;
+    stats.node_references_unresolved = stats.cross_reference_diagnostics.len();
+
+    Ok(())
+}
+
+pub fn collect_unresolved_cross_references(
+    connection: &Connection,
+) -> Result<Vec<CrossReferenceDiagnostic>> {
+    let mut statement = connection.prepare(
+        "
+        SELECT
+          r.reference_id,
+          r.doc_id,
+          r.source_node_id,
+          n.ref,
+          r.raw_text,
+          r.reference_kind,
+          r.target_doc_id,
+          r.target_part,
+          r.target_ref,
+          r.resolution_status
+        FROM node_references r
+        LEFT JOIN nodes n ON n.node_id = r.source_node_id
+        WHERE r.resolution_status <> 'resolved'
+        ORDER BY r.doc_id ASC, r.reference_id ASC
+        ",
+    )?;
+
+    let rows = statement.query_map([], |row| {
+        Ok(CrossReferenceDiagnostic {
+            reference_id: row.get(0)?,
+            doc_id: row.get(1)?,
+            source_node_id: row.get(2)?,
+            source_ref: row.get(3)?,
+            raw_text: row.get(4)?,
+            reference_kind: row.get(5)?,
+            target_doc_id: row.get(6)?,
+            target_part: row.get(7)?,
+            target_ref: row.get(8)?,
+            resolution_status: row.get(9)?,
+        })
+    })?;
+
+    let mut diagnostics = Vec::new();
+    for row in rows {
+        diagnostics.push(row?);
+    }
+    Ok(diagnostics)
+}
@@ -0,0 +1,20 @@
+use super::*;
+
+pub fn open_fixture_database(db_path: Option<&Path>, rows_sql: &str) -> Connection {
+    let connection = match db_path {
+        Some(db_path) => {
+            let _ = fs::remove_file(db_path);
+            Connection::open(db_path)
+        }
+        None => Connection::open_in_memory(),
+    }
+    .expect("fixture DB should open");
+    ensure_schema(&connection).expect("schema should initialize");
+    connection
+        .execute_batch(rows_sql)
+        .expect("fixture rows should insert");
+    connection
+        .execute_batch("INSERT INTO chunks_fts(chunks_fts) VALUES('rebuild');")
+        .expect("fixture FTS index should rebuild");
+    connection
+}
@@ -55,6 +55,10 @@ pub fn ensure_schema(connection: &Connection) -> Result<()> {
           is_header INTEGER,
           row_span INTEGER,
           col_span INTEGER,
+          modality TEXT,
+          requirement_actor TEXT,
+          requirement_conditions TEXT,
+          requirement_references TEXT,
           FOREIGN KEY(doc_id) REFERENCES docs(doc_id),
           FOREIGN KEY(parent_node_id) REFERENCES nodes(node_id)
         );
@@ -85,6 +89,157 @@ pub fn ensure_schema(connection: &Connection) -> Result<()> {
           citation_anchor_id TEXT,
           FOREIGN KEY(doc_id) REFERENCES docs(doc_id)
         );
+
+        CREATE TABLE IF NOT EXISTS asil_recommendations (
+          doc_id TEXT NOT NULL,
+          table_node_id TEXT NOT NULL,
+          table_ref TEXT NOT NULL,
+          row_node_id TEXT NOT NULL,
+          marker TEXT NOT NULL,
+          method_text TEXT NOT NULL,
+          asil TEXT NOT NULL,
+          rating TEXT NOT NULL,
+          recommendation_level TEXT NOT NULL,
+          footnotes TEXT NOT NULL DEFAULT '[]',
+          source_hash TEXT,
+          PRIMARY KEY (row_node_id, asil),
+          FOREIGN KEY(doc_id) REFERENCES docs(doc_id),
+          FOREIGN KEY(table_node_id) REFERENCES nodes(node_id),
+          FOREIGN KEY(row_node_id) REFERENCES nodes(node_id)
+        );
+
+        CREATE TABLE IF NOT EXISTS table_footnote_links (
+          cell_node_id TEXT NOT NULL,
+          footnote_node_id TEXT NOT NULL,
+          doc_id TEXT NOT NULL,
+          table_node_id TEXT NOT NULL,
+          marker TEXT NOT NULL,
+          PRIMARY KEY (cell_node_id, footnote_node_id),
+          FOREIGN KEY(doc_id) REFERENCES docs(doc_id),
+          FOREIGN KEY(cell_node_id) REFERENCES nodes(node_id),
+          FOREIGN KEY(footnote_node_id) REFERENCES nodes(node_id)
+        );
+
+        CREATE TABLE IF NOT EXISTS node_references (
+          reference_id TEXT PRIMARY KEY,
+          doc_id TEXT NOT NULL,
+          source_node_id TEXT NOT NULL,
+          target_node_id TEXT,
+          target_doc_id TEXT,
+          target_part INTEGER,
+          target_year INTEGER,
+          target_ref TEXT NOT NULL,
+          reference_kind TEXT NOT NULL,
+          raw_text TEXT NOT NULL,
+          resolution_status TEXT NOT NULL DEFAULT 'pending',
+          FOREIGN KEY(doc_id) REFERENCES docs(doc_id),
+          FOREIGN KEY(source_node_id) REFERENCES nodes(node_id)
+        );
+
+        CREATE TABLE IF NOT EXISTS terms (
+          term_id TEXT PRIMARY KEY,
+          doc_id TEXT NOT NULL,
+          ref TEXT NOT NULL,
+          term TEXT NOT NULL,
+          term_norm TEXT NOT NULL,
+          abbreviation TEXT,
+          definition TEXT NOT NULL,
+          notes TEXT NOT NULL DEFAULT '[]',
+          page_pdf_start INTEGER,
+          page_pdf_end INTEGER,
+          source_hash TEXT,
+          FOREIGN KEY(doc_id) REFERENCES docs(doc_id),
+          FOREIGN KEY(term_id) REFERENCES nodes(node_id)
+        );
+
+        CREATE TABLE IF NOT EXISTS work_products (
+          work_product_id TEXT PRIMARY KEY,
+          doc_id TEXT NOT NULL,
+          node_id TEXT,
+          ref TEXT NOT NULL,
+          name TEXT NOT NULL,
+          name_norm TEXT NOT NULL,
+          source_text TEXT NOT NULL,
+          page_pdf_start INTEGER,
+          page_pdf_end INTEGER,
+          source_hash TEXT,
+          FOREIGN KEY(doc_id) REFERENCES docs(doc_id),
+          FOREIGN KEY(node_id) REFERENCES nodes(node_id)
+        );
+
+        CREATE TABLE IF NOT EXISTS work_product_sources (
+          work_product_id TEXT NOT NULL,
+          source_seq INTEGER NOT NULL,
+          source_part INTEGER NOT NULL,
+          source_doc_id TEXT,
+          source_ref TEXT NOT NULL,
+          source_node_id TEXT,
+          PRIMARY KEY (work_product_id, source_seq),
+          FOREIGN KEY(work_product_id) REFERENCES work_products(work_product_id),
+          FOREIGN KEY(source_node_id) REFERENCES nodes(node_id)
+        );
+
+        CREATE TABLE IF NOT EXISTS node_regions (
+          node_id TEXT NOT NULL,
+          region_seq INTEGER NOT NULL,
+          doc_id TEXT NOT NULL,
+          page_pdf INTEGER NOT NULL,
+          x_min REAL NOT NULL,
+          y_min REAL NOT NULL,
+          x_max REAL NOT NULL,
+          y_max REAL NOT NULL,
+          page_width REAL NOT NULL,
+          page_height REAL NOT NULL,
+          PRIMARY KEY (node_id, region_seq),
+          FOREIGN KEY(doc_id) REFERENCES docs(doc_id),
+          FOREIGN KEY(node_id) REFERENCES nodes(node_id)
+        );
+
+        CREATE TABLE IF NOT EXISTS external_references (
+          external_reference_id TEXT PRIMARY KEY,
+          doc_id TEXT NOT NULL,
+          source_kind TEXT NOT NULL,
+          list_label TEXT,
+          standard_id TEXT NOT NULL,
+          year INTEGER,
+          title TEXT,
+          raw_text TEXT NOT NULL,
+          page_pdf_start INTEGER,
+          page_pdf_end INTEGER,
+          source_hash TEXT,
+          FOREIGN KEY(doc_id) REFERENCES docs(doc_id)
+        );
+
+        CREATE TABLE IF NOT EXISTS external_reference_citations (
+          external_reference_id TEXT NOT NULL,
+          node_id TEXT NOT NULL,
+          doc_id TEXT NOT NULL,
+          ref TEXT NOT NULL,
+          PRIMARY KEY (external_reference_id, node_id),
+          FOREIGN KEY(external_reference_id) REFERENCES external_references(external_reference_id),
+          FOREIGN KEY(node_id) REFERENCES nodes(node_id),
+          FOREIGN KEY(doc_id) REFERENCES docs(doc_id)
+        );
+
+        CREATE TABLE IF NOT EXISTS ingest_ledger (
+          doc_id TEXT PRIMARY KEY,
+          source_sha256 TEXT NOT NULL,
+          parser_version TEXT NOT NULL,
+          db_schema_version TEXT NOT NULL,
+          tool_versions TEXT NOT NULL,
+          options TEXT NOT NULL,
+          ingested_at TEXT NOT NULL,
+          FOREIGN KEY(doc_id) REFERENCES docs(doc_id)
+        );
+
+        CREATE TABLE IF NOT EXISTS term_occurrences (
+          chunk_id TEXT NOT NULL,
+          term_id TEXT NOT NULL,
+          occurrence_count INTEGER NOT NULL,
+          PRIMARY KEY (chunk_id, term_id),
+          FOREIGN KEY(chunk_id) REFERENCES chunks(chunk_id),
+          FOREIGN KEY(term_id) REFERENCES terms(term_id)
+        );
         ",
     )?;
 
@@ -103,6 +258,10 @@ pub fn ensure_schema(connection: &Connection) -> Result<()> {
     ensure_column_exists(connection, "nodes", "is_header INTEGER")?;
     ensure_column_exists(connection, "nodes", "row_span INTEGER")?;
     ensure_column_exists(connection, "nodes", "col_span INTEGER")?;
+    ensure_column_exists(connection, "nodes", "modality TEXT")?;
+    ensure_column_exists(connection, "nodes", "requirement_actor TEXT")?;
+    ensure_column_exists(connection, "nodes", "requirement_conditions TEXT")?;
+    ensure_column_exists(connection, "nodes", "requirement_references TEXT")?;
     ensure_column_exists(connection, "chunks", "origin_node_id TEXT")?;
     ensure_column_exists(connection, "chunks", "leaf_node_type TEXT")?;
     ensure_column_exists(connection, "chunks", "ancestor_path TEXT")?;
@@ -132,6 +291,20 @@ pub fn ensure_schema(connection: &Connection) -> Result<()> {
         CREATE INDEX IF NOT EXISTS idx_chunks_origin_node ON chunks(origin_node_id);
         CREATE INDEX IF NOT EXISTS idx_chunks_doc_citation_anchor ON chunks(doc_id, citation_anchor_id);
         CREATE INDEX IF NOT EXISTS idx_chunks_doc_ref_anchor_label ON chunks(doc_id, ref, anchor_label_norm);
+        CREATE INDEX IF NOT EXISTS idx_asil_recommendations_asil_level ON asil_recommendations(asil, recommendation_level);
+        CREATE INDEX IF NOT EXISTS idx_asil_recommendations_doc_table ON asil_recommendations(doc_id, table_ref);
+        CREATE INDEX IF NOT EXISTS idx_node_references_source ON node_references(source_node_id);
+        CREATE INDEX IF NOT EXISTS idx_node_references_target ON node_references(target_node_id);
+        CREATE INDEX IF NOT EXISTS idx_node_references_target_ref ON node_references(target_doc_id, target_ref);
+        CREATE INDEX IF NOT EXISTS idx_nodes_modality ON nodes(modality);
+        CREATE INDEX IF NOT EXISTS idx_work_products_doc ON work_products(doc_id, ref);
+        CREATE INDEX IF NOT EXISTS idx_work_product_sources_node ON work_product_sources(source_node_id);
+        CREATE INDEX IF NOT EXISTS idx_terms_term_norm ON terms(term_norm);
+        CREATE INDEX IF NOT EXISTS idx_terms_abbreviation ON terms(abbreviation);
+        CREATE INDEX IF NOT EXISTS idx_term_occurrences_term ON term_occurrences(term_id);
+        CREATE INDEX IF NOT EXISTS idx_node_regions_doc_page ON node_regions(doc_id, page_pdf);
+        CREATE INDEX IF NOT EXISTS idx_external_references_standard ON external_references(standard_id);
+        CREATE INDEX IF NOT EXISTS idx_external_reference_citations_node ON external_reference_citations(node_id);
         ",
     )?;
 
@@ -162,7 +335,8 @@ pub fn ensure_embedding_schema(connection: &Connection) -> Result<()> {
           dimensions INTEGER NOT NULL,
           normalize INTEGER NOT NULL,
           created_at TEXT NOT NULL,
-          config_json TEXT NOT NULL
+          config_json TEXT NOT NULL,
+          storage_encoding TEXT NOT NULL DEFAULT 'f32'
         );
 
         CREATE TABLE IF NOT EXISTS chunk_embeddings (
@@ -177,11 +351,44 @@ pub fn ensure_embedding_schema(connection: &Connection) -> Result<()> {
           FOREIGN KEY (model_id) REFERENCES embedding_models(model_id) ON DELETE CASCADE
         );
 
+        CREATE TABLE IF NOT EXISTS node_embeddings (
+          node_id TEXT NOT NULL,
+          model_id TEXT NOT NULL,
+          chunk_id TEXT NOT NULL,
+          embedding BLOB NOT NULL,
+          embedding_dim INTEGER NOT NULL,
+          text_hash TEXT NOT NULL,
+          generated_at TEXT NOT NULL,
+          PRIMARY KEY (node_id, model_id),
+          FOREIGN KEY (node_id) REFERENCES nodes(node_id) ON DELETE CASCADE,
+          FOREIGN KEY (chunk_id) REFERENCES chunks(chunk_id) ON DELETE CASCADE,
+          FOREIGN KEY (model_id) REFERENCES embedding_models(model_id) ON DELETE CASCADE
+        );
+
+        CREATE TABLE IF NOT EXISTS ann_indexes (
+          model_id TEXT PRIMARY KEY,
+          index_format TEXT NOT NULL,
+          index_path TEXT NOT NULL,
+          checksum TEXT NOT NULL,
+          vector_count INTEGER NOT NULL,
+          list_count INTEGER NOT NULL,
+          embeddings_generated_at TEXT,
+          built_at TEXT NOT NULL,
+          FOREIGN KEY (model_id) REFERENCES embedding_models(model_id) ON DELETE CASCADE
+        );
+
         CREATE INDEX IF NOT EXISTS idx_chunk_embeddings_model ON chunk_embeddings(model_id);
         CREATE INDEX IF NOT EXISTS idx_chunk_embeddings_chunk ON chunk_embeddings(chunk_id);
         CREATE INDEX IF NOT EXISTS idx_chunk_embeddings_model_hash ON chunk_embeddings(model_id, text_hash);
+        CREATE INDEX IF NOT EXISTS idx_node_embeddings_model ON node_embeddings(model_id);
+        CREATE INDEX IF NOT EXISTS idx_node_embeddings_chunk ON node_embeddings(chunk_id);
         ",
     )?;
+    ensure_column_exists(
+        connection,
+        "embedding_models",
+        "storage_encoding TEXT NOT NULL DEFAULT 'f32'",
+    )?;
 
     Ok(())
 }
@@ -251,3 +458,38 @@ pub fn upsert_docs(connection: &mut Connection, inventory: &PdfInventoryManifest
     tx.commit()?;
     Ok(inventory.pdfs.len())
 }
+
+pub fn clear_document_rows(connection: &Connection, doc_id: &str) -> Result<()> {
+    connection.execute("DELETE FROM ingest_ledger WHERE doc_id = ?1", [doc_id])?;
+    connection.execute(
+        "DELETE FROM asil_recommendations WHERE doc_id = ?1",
+        [doc_id],
+    )?;
+    connection.execute(
+        "DELETE FROM table_footnote_links WHERE doc_id = ?1",
+        [doc_id],
+    )?;
+    connection.execute("DELETE FROM node_references WHERE doc_id = ?1", [doc_id])?;
+    connection.execute(
+        "DELETE FROM term_occurrences WHERE chunk_id IN (SELECT chunk_id FROM chunks WHERE doc_id = ?1)",
+        [doc_id],
+    )?;
+    connection.execute("DELETE FROM terms WHERE doc_id = ?1", [doc_id])?;
+    connection.execute(
+        "DELETE FROM work_product_sources WHERE work_product_id IN (SELECT work_product_id FROM work_products WHERE doc_id = ?1)",
+        [doc_id],
+    )?;
+    connection.execute("DELETE FROM work_products WHERE doc_id = ?1", [doc_id])?;
+    connection.execute(
+        "DELETE FROM external_reference_citations WHERE doc_id = ?1",
+        [doc_id],
+    )?;
+    connection.execute(
+        "DELETE FROM external_references WHERE doc_id = ?1",
+        [doc_id],
+    )?;
+    connection.execute("DELETE FROM node_regions WHERE doc_id = ?1", [doc_id])?;
+    connection.execute("DELETE FROM chunks WHERE doc_id = ?1", [doc_id])?;
+    connection.execute("DELETE FROM nodes WHERE doc_id = ?1", [doc_id])?;
+    Ok(())
+}
@@ -0,0 +1,286 @@
+use super::*;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ExternalReferenceKind {
+    Normative,
+    Bibliography,
+}
+
+impl ExternalReferenceKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ExternalReferenceKind::Normative => "normative",
+            ExternalReferenceKind::Bibliography => "bibliography",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExternalReferenceDraft {
+    pub kind: ExternalReferenceKind,
+    pub list_label: Option<String>,
+    pub standard_id: String,
+    pub year: Option<i64>,
+    pub title: Option<String>,
+    pub raw_text: String,
+    pub page_start: i64,
+    pub page_end: i64,
+}
+
+struct ReferenceSection {
+    kind: ExternalReferenceKind,
+    lines: Vec<String>,
+    page_start: i64,
+    page_end: i64,
+}
+
+pub fn extract_external_references(
+    pages: &[String],
+    parser: &StructuredChunkParser,
+    regexes: &IngestRegexes,
+) -> Vec<ExternalReferenceDraft> {
+    let mut references = Vec::<ExternalReferenceDraft>::new();
+    let mut current: Option<ReferenceSection> = None;
+
+    for (page_index, page_text) in pages.iter().enumerate() {
+        let page_number = (page_index + 1) as i64;
+        for raw_line in page_text.lines() {
+            let line = normalize_line(raw_line);
+            if line.is_empty() || parser.toc_line.is_match(line) {
+                continue;
+            }
+
+            if let Some(kind) = parser.detect_reference_section(line) {
+                if let Some(section) = current.take() {
+                    references.extend(parse_reference_section(section, regexes));
+                }
+                current = Some(ReferenceSection {
+                    kind,
+                    lines: Vec::new(),
+                    page_start: page_number,
+                    page_end: page_number,
+                });
+                continue;
+            }
+
+            let Some(section) = current.as_mut() else {
+                continue;
+            };
+            if parser.detect_heading(line).is_some()
+                || regexes.reference_section_end_regex.is_match(line)
+            {
+                if let Some(section) = current.take() {
+                    references.extend(parse_reference_section(section, regexes));
+                }
+                continue;
+            }
+
+            section.page_end = page_number;
+            section.lines.push(line.to_string());
+        }
+    }
+
+    if let Some(section) = current.take() {
+        references.extend(parse_reference_section(section, regexes));
+    }
+
+    references
+}
+
+fn parse_reference_section(
+    section: ReferenceSection,
+    regexes: &IngestRegexes,
+) -> Vec<ExternalReferenceDraft> {
+    let list_items = if section.kind == ExternalReferenceKind::Bibliography {
+        parse_list_items(
+            &section.lines.join("\n"),
+            "",
+            &regexes.list_item_regex,
+            &regexes.note_item_regex,
+        )
+        .0
+    } else {
+        Vec::new()
+    };
+
+    let entries = if list_items.is_empty() {
+        group_reference_lines(&section.lines, &regexes.external_standard_regex)
+    } else {
+        list_items
+            .into_iter()
+            .map(|item| (Some(item.marker_norm), item.text))
+            .collect()
+    };
+
+    entries
+        .into_iter()
+        .filter_map(|(list_label, raw_text)| {
+            let captures = regexes.external_standard_regex.captures(&raw_text)?;
+            let standard_id = normalize_standard_id(captures.name("id")?.as_str());
+            let year = captures
+                .name("year")
+                .and_then(|value| value.as_str().parse::<i64>().ok());
+            let title = captures
+                .name("title")
+                .map(|value| {
+                    value
+                        .as_str()
+                        .trim()
+                        .trim_end_matches('.')
+                        .trim()
+                        .to_string()
+                })
+                .filter(|value| !value.is_empty());
+
+            Some(ExternalReferenceDraft {
+                kind: section.kind,
+                list_label,
+                standard_id,
+                year,
+                title,
+                raw_text,
+                page_start: section.page_start,
+                page_end: section.page_end,
+            })
+        })
+        .collect()
+}
+
+fn group_reference_lines(
+    lines: &[String],
+    standard_regex: &Regex,
+) -> Vec<(Option<String>, String)> {
+    let mut entries = Vec::<(Option<String>, String)>::new();
+    let mut in_entry = false;
+
+    for line in lines {
+        if standard_regex.is_match(line) {
+            entries.push((None, line.to_string()));
+            in_entry = true;
+            continue;
+        }
+        if line.ends_with(':') {
+            in_entry = false;
+            continue;
+        }
+        if in_entry && let Some((_, text)) = entries.last_mut() {
+            text.push(' ');
+            text.push_str(line);
+        }
+    }
+
+    entries
+}
+
+pub fn normalize_standard_id(value: &str) -> String {
+    value
+        .split_whitespace()
+        .collect::<Vec<&str>>()
+        .join(" ")
+        .to_ascii_uppercase()
+}
+
+pub fn text_mentions_standard(text: &str, standard_id: &str) -> bool {
+    let haystack = normalize_standard_id(text);
+    if haystack_mentions(&haystack, standard_id, true) {
+        return true;
+    }
+
+    // A clause citing the whole family ("IEC 61508") also points at each listed part.
+    standard_family_root(standard_id).is_some_and(|root| haystack_mentions(&haystack, root, false))
+}
+
+pub fn standard_family_root(standard_id: &str) -> Option<&str> {
+    let (root, part) = standard_id.rsplit_once('-')?;
+    let is_part_number = !part.is_empty() && part.chars().all(|ch| ch.is_ascii_digit());
+    (is_part_number && root.contains(' ')).then_some(root)
+}
+
+fn haystack_mentions(haystack: &str, needle: &str, allow_part_suffix: bool) -> bool {
+    let mut offset = 0usize;
+    while let Some(position) = haystack[offset..].find(needle) {
+        let start = offset + position;
+        let end = start + needle.len();
+        let boundary_before = haystack[..start]
+            .chars()
+            .next_back()
+            .is_none_or(|ch| !ch.is_ascii_alphanumeric() && ch != '/');
+        let boundary_after = haystack[end..]
+            .chars()
+            .next()
+            .is_none_or(|ch| !ch.is_ascii_alphanumeric() && (allow_part_suffix || ch != '-'));
+        if boundary_before && boundary_after {
+            return true;
+        }
+        offset = end;
+    }
+
+    false
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn insert_external_references_for_pdf(
+    external_reference_statement: &mut rusqlite::Statement<'_>,
+    citation_statement: &mut rusqlite::Statement<'_>,
+    doc_id: &str,
+    source_hash: &str,
+    references: &[ExternalReferenceDraft],
+    structured_chunks: &[StructuredChunkDraft],
+    state: &PdfNodeState,
+    stats: &mut ChunkInsertStats,
+) -> Result<()> {
+    let mut seen = HashSet::<(ExternalReferenceKind, String)>::new();
+    let mut sequence = 0usize;
+
+    for reference in references {
+        if !seen.insert((reference.kind, reference.standard_id.clone())) {
+            continue;
+        }
+        sequence += 1;
+        let external_reference_id = format!(
+            "{}:extref:{}:{:03}",
+            doc_id,
+            reference.kind.as_str(),
+            sequence
+        );
+
+        external_reference_statement.execute(params![
+            external_reference_id,
+            doc_id,
+            reference.kind.as_str(),
+            reference.list_label,
+            reference.standard_id,
+            reference.year,
+            reference.title,
+            reference.raw_text,
+            reference.page_start,
+            reference.page_end,
+            source_hash,
+        ])?;
+        stats.external_references_inserted += 1;
+
+        let mut cited_refs = HashSet::<&str>::new();
+        for chunk in structured_chunks {
+            if chunk.chunk_type != ChunkType::Clause
+                || cited_refs.contains(chunk.reference.as_str())
+                || !text_mentions_standard(&chunk.text, &reference.standard_id)
+            {
+                continue;
+            }
+            let Some(node_id) = state.clause_ref_to_node_id.get(&chunk.reference) else {
+                continue;
+            };
+
+            citation_statement.execute(params![
+                external_reference_id,
+                node_id,
+                doc_id,
+                chunk.reference,
+            ])?;
+            cited_refs.insert(chunk.reference.as_str());
+            stats.external_reference_citations_inserted += 1;
+        }
+    }
+
+    Ok(())
+}
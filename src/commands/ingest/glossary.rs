@@ -0,0 +1,432 @@
+use super::*;
+
+pub const GLOSSARY_PART: u32 = 1;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TermDraft {
+    pub reference: String,
+    pub term: String,
+    pub abbreviation: Option<String>,
+    pub definition: String,
+    pub notes: Vec<String>,
+    pub page_start: i64,
+    pub page_end: i64,
+}
+
+#[derive(Debug)]
+struct ActiveTerm {
+    reference: String,
+    term: Option<String>,
+    abbreviation: Option<String>,
+    definition_lines: Vec<String>,
+    notes: Vec<String>,
+    in_notes: bool,
+    page_start: i64,
+    page_end: i64,
+}
+
+impl ActiveTerm {
+    fn finalize(self) -> Option<TermDraft> {
+        let term = self.term?;
+        let definition = self.definition_lines.join(" ");
+        if definition.is_empty() {
+            return None;
+        }
+
+        Some(TermDraft {
+            reference: self.reference,
+            term,
+            abbreviation: self.abbreviation,
+            definition,
+            notes: self.notes,
+            page_start: self.page_start,
+            page_end: self.page_end,
+        })
+    }
+
+    fn push_line(&mut self, line: &str, regexes: &IngestRegexes) {
+        if self.term.is_none() {
+            self.term = Some(line.to_string());
+            return;
+        }
+
+        if regexes.glossary_note_regex.is_match(line) {
+            self.notes.push(line.to_string());
+            self.in_notes = true;
+            return;
+        }
+
+        if self.in_notes {
+            if let Some(note) = self.notes.last_mut() {
+                note.push(' ');
+                note.push_str(line);
+            }
+            return;
+        }
+
+        if self.abbreviation.is_none()
+            && self.definition_lines.is_empty()
+            && regexes.glossary_abbreviation_regex.is_match(line)
+        {
+            self.abbreviation = Some(line.to_string());
+            return;
+        }
+
+        self.definition_lines.push(line.to_string());
+    }
+}
+
+pub fn parse_glossary_terms(
+    pages: &[String],
+    parser: &StructuredChunkParser,
+    regexes: &IngestRegexes,
+) -> Vec<TermDraft> {
+    let mut terms = Vec::<TermDraft>::new();
+    let mut terms_clause: Option<u32> = None;
+    let mut current: Option<ActiveTerm> = None;
+
+    for (page_index, page_text) in pages.iter().enumerate() {
+        let page_number = (page_index + 1) as i64;
+        for raw_line in page_text.lines() {
+            let line = normalize_line(raw_line);
+            if line.is_empty() || parser.toc_line.is_match(line) || is_page_furniture(line) {
+                continue;
+            }
+
+            if let Some(captures) = regexes.glossary_clause_start_regex.captures(line) {
+                terms_clause = captures["clause"].parse().ok();
+                continue;
+            }
+            let Some(clause) = terms_clause else {
+                continue;
+            };
+
+            let next_clause = regexes
+                .glossary_clause_end_regex
+                .captures(line)
+                .and_then(|captures| captures["clause"].parse::<u32>().ok())
+                .is_some_and(|number| number > clause);
+            if next_clause
+                || parser.detect_reference_section(line).is_some()
+                || parser.annex_heading.is_match(line)
+            {
+                terms_clause = None;
+                if let Some(active) = current.take() {
+                    terms.extend(active.finalize());
+                }
+                continue;
+            }
+
+            if let Some(captures) = regexes
+                .glossary_entry_regex
+                .captures(line)
+                .filter(|captures| captures["clause"].parse() == Ok(clause))
+            {
+                if let Some(active) = current.take() {
+                    terms.extend(active.finalize());
+                }
+
+                let reference = captures
+                    .name("reference")
+                    .map(|value| value.as_str().to_string())
+                    .unwrap_or_default();
+                let term = captures
+                    .name("term")
+                    .map(|value| value.as_str().trim().to_string())
+                    .filter(|value| !value.is_empty());
+                current = Some(ActiveTerm {
+                    reference,
+                    term,
+                    abbreviation: None,
+                    definition_lines: Vec::new(),
+                    notes: Vec::new(),
+                    in_notes: false,
+                    page_start: page_number,
+                    page_end: page_number,
+                });
+                continue;
+            }
+
+            if let Some(active) = current.as_mut() {
+                active.page_end = page_number;
+                active.push_line(line, regexes);
+            }
+        }
+    }
+
+    if let Some(active) = current.take() {
+        terms.extend(active.finalize());
+    }
+
+    terms
+}
+
+/// Page furniture that survives edge stripping on short glossary pages.
+fn is_page_furniture(line: &str) -> bool {
+    line.chars().all(|ch| ch.is_ascii_digit())
+        || line.starts_with('©')
+        || (line.starts_with("ISO 26262-") && line.ends_with("(E)"))
+}
+
+pub fn normalize_term(value: &str) -> String {
+    value
+        .split_whitespace()
+        .collect::<Vec<&str>>()
+        .join(" ")
+        .to_lowercase()
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn insert_glossary_terms_for_pdf(
+    node_statement: &mut rusqlite::Statement<'_>,
+    term_statement: &mut rusqlite::Statement<'_>,
+    doc_id: &str,
+    source_hash: &str,
+    terms: &[TermDraft],
+    state: &mut PdfNodeState,
+    stats: &mut ChunkInsertStats,
+) -> Result<()> {
+    for term in terms {
+        let parent_node_id = state
+            .clause_ref_to_node_id
+            .get(&term.reference)
+            .cloned()
+            .or_else(|| find_parent_clause_node_id(&term.reference, &state.clause_ref_to_node_id))
+            .unwrap_or_else(|| state.document_node_id.clone());
+        let node_id = format!(
+            "{}:node:{}:{}:001",
+            doc_id,
+            NodeType::Term.as_str(),
+            sanitize_ref_for_id(&term.reference)
+        );
+        let heading = format!("{} {}", term.reference, term.term);
+        let ancestor_path = build_ancestor_path(
+            Some(&parent_node_id),
+            &state.node_paths,
+            NodeType::Term,
+            &term.reference,
+            &heading,
+        );
+        let text = render_term_text(term);
+        let anchor_id = build_citation_anchor_id(
+            doc_id,
+            &term.reference,
+            "clause",
+            Some(&term.reference),
+            Some(1),
+        );
+
+        insert_node(
+            node_statement,
+            &node_id,
+            Some(&parent_node_id),
+            doc_id,
+            NodeType::Term,
+            Some(&term.reference),
+            Some(&term.reference),
+            Some(&heading),
+            state.node_order_index,
+            Some(term.page_start),
+            Some(term.page_end),
+            Some(&text),
+            source_hash,
+            &ancestor_path,
+            Some("clause"),
+            Some(&term.reference),
+            Some(&term.reference),
+            Some(1),
+            Some(&anchor_id),
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+        )?;
+        state.node_paths.insert(node_id.clone(), ancestor_path);
+        state.node_order_index += 1;
+        stats.nodes_total += 1;
+        increment_node_type_stat(stats, NodeType::Term);
+
+        let notes_json =
+            serde_json::to_string(&term.notes).context("failed to serialize term notes")?;
+        term_statement.execute(params![
+            node_id,
+            doc_id,
+            term.reference,
+            term.term,
+            normalize_term(&term.term),
+            term.abbreviation,
+            term.definition,
+            notes_json,
+            term.page_start,
+            term.page_end,
+            source_hash,
+        ])?;
+    }
+
+    Ok(())
+}
+
+fn render_term_text(term: &TermDraft) -> String {
+    let mut lines = vec![term.term.clone()];
+    if let Some(abbreviation) = &term.abbreviation {
+        lines.push(abbreviation.clone());
+    }
+    lines.push(term.definition.clone());
+    lines.extend(term.notes.iter().cloned());
+    lines.join("\n")
+}
+
+/// Links multi-word glossary terms and abbreviations into the ingested chunks.
+pub fn link_term_occurrences(
+    connection: &Connection,
+    ingested_doc_ids: &[String],
+    stats: &mut ChunkInsertStats,
+) -> Result<()> {
+    let mut term_statement = connection.prepare(
+        "
+        SELECT t.term_id, t.doc_id, d.year, t.term_norm, t.abbreviation
+        FROM terms t
+        JOIN docs d ON d.doc_id = t.doc_id
+        ORDER BY d.year DESC, length(t.term_norm) DESC, t.term_id ASC
+        ",
+    )?;
+    let mut term_rows = term_statement.query([])?;
+    let mut term_ids_by_text = HashMap::<String, Vec<(u32, String)>>::new();
+    let mut abbreviation_ids = HashMap::<String, Vec<(u32, String)>>::new();
+    let mut glossary_doc_ids = HashSet::<String>::new();
+    while let Some(row) = term_rows.next()? {
+        let term_id: String = row.get(0)?;
+        glossary_doc_ids.insert(row.get(1)?);
+        let year: u32 = row.get(2)?;
+        let term_norm: String = row.get(3)?;
+        if let Some(abbreviation) = row.get::<_, Option<String>>(4)? {
+            abbreviation_ids
+                .entry(abbreviation)
+                .or_default()
+                .push((year, term_id.clone()));
+        }
+        if term_norm.split_whitespace().count() >= 2 {
+            term_ids_by_text
+                .entry(term_norm)
+                .or_default()
+                .push((year, term_id));
+        }
+    }
+
+    let target_doc_ids = if ingested_doc_ids
+        .iter()
+        .any(|doc_id| glossary_doc_ids.contains(doc_id))
+    {
+        connection
+            .prepare("SELECT doc_id FROM docs ORDER BY doc_id")?
+            .query_map([], |row| row.get::<_, String>(0))?
+            .collect::<rusqlite::Result<Vec<String>>>()?
+    } else {
+        ingested_doc_ids.to_vec()
+    };
+    for doc_id in &target_doc_ids {
+        connection.execute(
+            "DELETE FROM term_occurrences WHERE chunk_id IN (SELECT chunk_id FROM chunks WHERE doc_id = ?1)",
+            [doc_id],
+        )?;
+    }
+
+    let term_regex = build_term_regex(term_ids_by_text.keys(), true)?;
+    let abbreviation_regex = build_term_regex(abbreviation_ids.keys(), false)?;
+    if term_regex.is_none() && abbreviation_regex.is_none() {
+        return Ok(());
+    }
+
+    let mut chunk_statement = connection.prepare(
+        "
+        SELECT c.chunk_id, d.year, COALESCE(c.text, '')
+        FROM chunks c
+        JOIN docs d ON d.doc_id = c.doc_id
+        WHERE c.doc_id = ?1
+        ",
+    )?;
+    let mut occurrence_statement = connection.prepare(
+        "
+        INSERT INTO term_occurrences(chunk_id, term_id, occurrence_count)
+        VALUES(?1, ?2, ?3)
+        ",
+    )?;
+
+    for doc_id in target_doc_ids
+        .iter()
+        .filter(|doc_id| !glossary_doc_ids.contains(*doc_id))
+    {
+        let mut chunk_rows = chunk_statement.query([doc_id])?;
+        while let Some(row) = chunk_rows.next()? {
+            let chunk_id: String = row.get(0)?;
+            let year: u32 = row.get(1)?;
+            let text: String = row.get(2)?;
+            let mut counts = HashMap::<&str, i64>::new();
+            for found in term_regex.iter().flat_map(|regex| regex.find_iter(&text)) {
+                if let Some(term_id) = term_ids_by_text
+                    .get(&normalize_term(found.as_str()))
+                    .and_then(|candidates| select_edition_term(candidates, year))
+                {
+                    *counts.entry(term_id).or_insert(0) += 1;
+                }
+            }
+            for found in abbreviation_regex
+                .iter()
+                .flat_map(|regex| regex.find_iter(&text))
+            {
+                if let Some(term_id) = abbreviation_ids
+                    .get(found.as_str())
+                    .and_then(|candidates| select_edition_term(candidates, year))
+                {
+                    *counts.entry(term_id).or_insert(0) += 1;
+                }
+            }
+
+            let mut counts = counts.into_iter().collect::<Vec<(&str, i64)>>();
+            counts.sort_unstable();
+            for (term_id, occurrence_count) in counts {
+                occurrence_statement.execute(params![chunk_id, term_id, occurrence_count])?;
+                stats.term_occurrences_linked += 1;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn select_edition_term(candidates: &[(u32, String)], year: u32) -> Option<&str> {
+    candidates
+        .iter()
+        .find(|(term_year, _)| *term_year == year)
+        .or_else(|| candidates.first())
+        .map(|(_, term_id)| term_id.as_str())
+}
+
+fn build_term_regex<'a>(
+    values: impl Iterator<Item = &'a String>,
+    case_insensitive: bool,
+) -> Result<Option<Regex>> {
+    let mut values = values
+        .filter(|value| !value.trim().is_empty())
+        .collect::<Vec<&String>>();
+    if values.is_empty() {
+        return Ok(None);
+    }
+
+    values.sort_by(|left, right| right.len().cmp(&left.len()).then(left.cmp(right)));
+    let alternation = values
+        .iter()
+        .map(|value| regex::escape(value).replace(' ', r"\s+"))
+        .collect::<Vec<String>>()
+        .join("|");
+    let flags = if case_insensitive { "(?i)" } else { "" };
+    let regex = Regex::new(&format!(r"{flags}\b(?:{alternation})\b"))
+        .context("failed to compile glossary term regex")?;
+    Ok(Some(regex))
+}
@@ -0,0 +1,102 @@
+use super::*;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IngestFingerprint {
+    pub parser_version: String,
+    pub db_schema_version: String,
+    pub tool_versions: String,
+    pub options: String,
+}
+
+pub fn build_ingest_fingerprint(
+    args: &IngestArgs,
+    tool_versions: &ToolVersions,
+) -> Result<IngestFingerprint> {
+    let extraction_tools = serde_json::json!({
+        "pdftotext": tool_versions.pdftotext,
+        "pdftohtml": tool_versions.pdftohtml,
+        "pdftoppm": tool_versions.pdftoppm,
+        "tesseract": tool_versions.tesseract,
+    });
+    let mut options = serde_json::json!({
+        "max_pages_per_doc": args.max_pages_per_doc,
+        "seed_page_chunks": args.seed_page_chunks,
+        "extract_backend": args.extract_backend.as_str(),
+        "ocr_mode": args.ocr_mode.as_str(),
+    });
+    if args.ocr_mode != OcrMode::Off {
+        options["ocr_lang"] = serde_json::json!(args.ocr_lang);
+        options["ocr_min_text_chars"] = serde_json::json!(args.ocr_min_text_chars);
+    }
+
+    Ok(IngestFingerprint {
+        parser_version: PARSER_VERSION.to_string(),
+        db_schema_version: DB_SCHEMA_VERSION.to_string(),
+        tool_versions: serde_json::to_string(&extraction_tools)
+            .context("failed to serialize ingest tool versions")?,
+        options: serde_json::to_string(&options).context("failed to serialize ingest options")?,
+    })
+}
+
+pub fn ingest_ledger_is_current(
+    connection: &Connection,
+    doc_id: &str,
+    source_sha256: &str,
+    fingerprint: &IngestFingerprint,
+) -> Result<bool> {
+    let matches = connection.query_row(
+        "
+        SELECT COUNT(*)
+        FROM ingest_ledger
+        WHERE doc_id = ?1
+          AND source_sha256 = ?2
+          AND parser_version = ?3
+          AND db_schema_version = ?4
+          AND tool_versions = ?5
+          AND options = ?6
+        ",
+        params![
+            doc_id,
+            source_sha256,
+            fingerprint.parser_version,
+            fingerprint.db_schema_version,
+            fingerprint.tool_versions,
+            fingerprint.options,
+        ],
+        |row| row.get::<_, i64>(0),
+    )?;
+    Ok(matches > 0)
+}
+
+pub fn record_ingest_ledger_entry(
+    connection: &Connection,
+    doc_id: &str,
+    source_sha256: &str,
+    fingerprint: &IngestFingerprint,
+) -> Result<()> {
+    connection.execute(
+        "
+        INSERT INTO ingest_ledger(
+          doc_id, source_sha256, parser_version, db_schema_version, tool_versions, options, ingested_at
+        )
+        VALUES(?1, ?2, ?3, ?4, ?5, ?6, ?7)
+        ON CONFLICT(doc_id) DO UPDATE SET
+          source_sha256=excluded.source_sha256,
+          parser_version=excluded.parser_version,
+          db_schema_version=excluded.db_schema_version,
+          tool_versions=excluded.tool_versions,
+          options=excluded.options,
+          ingested_at=excluded.ingested_at
+        ",
+        params![
+            doc_id,
+            source_sha256,
+            fingerprint.parser_version,
+            fingerprint.db_schema_version,
+            fingerprint.tool_versions,
+            fingerprint.options,
+            now_utc_string(),
+        ],
+    )?;
+    Ok(())
+}
@@ -0,0 +1,94 @@
+use super::*;
+
+pub struct IngestRegexes {
+    pub list_item_regex: Regex,
+    pub note_item_regex: Regex,
+    pub table_cell_split_regex: Regex,
+    pub requirement_split_regex: Regex,
+    pub requirement_keyword_regex: Regex,
+    pub requirement_modality_regex: Regex,
+    pub requirement_condition_regex: Regex,
+    pub requirement_work_product_regex: Regex,
+    pub work_product_clause_regex: Regex,
+    pub work_product_source_regex: Regex,
+    pub work_product_source_ref_regex: Regex,
+    pub cross_reference_external_regex: Regex,
+    pub cross_reference_local_regex: Regex,
+    pub glossary_clause_start_regex: Regex,
+    pub glossary_clause_end_regex: Regex,
+    pub glossary_entry_regex: Regex,
+    pub glossary_note_regex: Regex,
+    pub glossary_abbreviation_regex: Regex,
+    pub external_standard_regex: Regex,
+    pub reference_section_end_regex: Regex,
+}
+
+impl IngestRegexes {
+    pub fn build() -> Result<Self> {
+        Ok(Self {
+            list_item_regex: Regex::new(
+                r"^(?P<marker>(?:\[\d+\]|(?:\d+[A-Za-z]?|[A-Za-z])(?:[\.)])?|[-*•—–]))(?:\s+(?P<body>.+))?$",
+            )
+            .context("failed to compile list item regex")?,
+            note_item_regex: Regex::new(r"^(?i)(?P<marker>NOTE(?:\s+\d+)?)(?:\s+(?P<body>.+))?$")
+                .context("failed to compile note item regex")?,
+            table_cell_split_regex: Regex::new(r"\t+|\s{2,}")
+                .context("failed to compile table cell split regex")?,
+            requirement_split_regex: Regex::new(r"[.;]\s+")
+                .context("failed to compile requirement split regex")?,
+            // Includes permissions ("may"); changing what matches requires a PARSER_VERSION bump.
+            requirement_keyword_regex: Regex::new(r"(?i)\bshall(?:\s+not)?\b|\bshould\b|\bmay\b")
+                .context("failed to compile requirement keyword regex")?,
+            requirement_modality_regex: Regex::new(
+                r"(?i)\b(?P<modal>shall|should|may)(?P<negation>\s+not)?\b",
+            )
+            .context("failed to compile requirement modality regex")?,
+            requirement_condition_regex: Regex::new(
+                r"\b[Ff]or\s+ASILs?\s+[A-D]\b(?:(?:\s*,\s*|\s+and\s+|\s+or\s+|\s+to\s+)(?:ASIL\s+)?[A-D]\b)*|(?i:\b(?:if|when|where|unless|in\s+case(?:\s+of)?)\b[^,;]*)",
+            )
+            .context("failed to compile requirement condition regex")?,
+            requirement_work_product_regex: Regex::new(
+                r"(?i)\b(?P<work_product>(?:(?:safety|software|hardware|system|technical|verification|validation|integration|test|unit|architectural|design|requirements?|configuration|change|impact|confirmation|assessment|development|interface|production|operation|item|functional|tool|qualification)[\s-]+){1,4}(?:specification|plan|report|manual|case|analysis))\b",
+            )
+            .context("failed to compile requirement work product regex")?,
+            work_product_clause_regex: Regex::new(
+                r"(?i)^(?P<reference>[1-9]\d?(?:\.\d{1,2}){0,4})\s+work\s+products?\s*$",
+            )
+            .context("failed to compile work product clause regex")?,
+            work_product_source_regex: Regex::new(r"(?i)\bresulting\s+from\b")
+                .context("failed to compile work product source regex")?,
+            work_product_source_ref_regex: Regex::new(
+                r"(?:ISO\s*26262-(?P<part>[1-9]\d?)(?::\d{4})?\s*,\s*(?:[Cc]lause\s+)?)?\b(?P<start>[1-9]\d?(?:\.\d{1,2}){0,5})\b(?:\s*(?:to|-|–)\s*(?P<end>[1-9]\d?(?:\.\d{1,2}){1,5})\b)?",
+            )
+            .context("failed to compile work product source reference regex")?,
+            cross_reference_external_regex: Regex::new(
+                r"ISO\s*26262-(?P<part>[1-9]\d?)(?::(?P<year>\d{4}))?(?:\s*,\s*(?P<target>(?:(?:[Cc]lause|subclause)\s+)?[1-9]\d?(?:\.\d{1,2}){0,5}\b|Table\s+\d+|Annex\s+[A-Z]\b))?",
+            )
+            .context("failed to compile external cross-reference regex")?,
+            cross_reference_local_regex: Regex::new(
+                r"\b(?P<target>Table\s+\d+|Annex\s+[A-Z])\b|\b(?:see|in|to|with|of|and|or|per|[Cc]lause|subclause)\s+(?P<clause>[1-9]\d?(?:\.\d{1,2}){1,5})\b|\b[Cc]lause\s+(?P<top>[1-9]\d?)\b",
+            )
+            .context("failed to compile local cross-reference regex")?,
+            glossary_clause_start_regex: Regex::new(
+                r"(?i)^(?P<clause>[1-9])\s+terms\s+and\s+definitions\b",
+            )
+                .context("failed to compile glossary clause start regex")?,
+            glossary_clause_end_regex: Regex::new(r"^(?P<clause>[1-9]\d?)\s+[A-Z][a-z]")
+                .context("failed to compile glossary clause end regex")?,
+            glossary_entry_regex: Regex::new(
+                r"^(?P<reference>(?P<clause>[1-9])(?:\.\d{1,3}){1,2})(?:\s+(?P<term>.+))?$",
+            )
+                .context("failed to compile glossary entry regex")?,
+            glossary_note_regex: Regex::new(r"^(?:Note\s+\d+\s+to\s+entry:|NOTE\b|EXAMPLE\b|\[SOURCE:)")
+                .context("failed to compile glossary note regex")?,
+            glossary_abbreviation_regex: Regex::new(r"^[A-Z][A-Za-z0-9/&\-]{0,10}[A-Z0-9]$")
+                .context("failed to compile glossary abbreviation regex")?,
+            external_standard_regex: Regex::new(
+                r"^(?P<id>(?:ISO|IEC|SAE|IEEE|MISRA|DIN|EN|RTCA|ANSI|UL|AEC|IATF|ASAM)(?:/(?:IEC|IEEE|SAE|ASTM|TS|TR|PAS))*(?:\s+(?:TS|TR|PAS))?\s+[A-Z0-9][\w.\-]*)(?::(?P<year>\d{4}))?(?:\s*\([^)]*\))?\s*[,.]?\s*(?P<title>.*)$",
+            )
+            .context("failed to compile external standard regex")?,
+            reference_section_end_regex: Regex::new(r"^(?:[1-9]|1\d)\s+[A-Z][a-z]")
+                .context("failed to compile reference section end regex")?,
+        })
+    }
+}
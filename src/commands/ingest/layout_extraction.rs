@@ -0,0 +1,264 @@
+use super::*;
+
+const LAYOUT_EDGE_BAND_RATIO: f64 = 0.07;
+const LAYOUT_INDENT_STEP_PT: f64 = 12.0;
+const LAYOUT_MAX_INDENT_LEVEL: usize = 5;
+const LAYOUT_COLUMN_GAP_RATIO: f64 = 1.8;
+const LAYOUT_ROW_TOLERANCE_RATIO: f64 = 0.5;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct LayoutWord {
+    pub x_min: f64,
+    pub y_min: f64,
+    pub x_max: f64,
+    pub y_max: f64,
+    pub text: String,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct LayoutPage {
+    pub text: String,
+    pub header_lines: Vec<String>,
+    pub footer_lines: Vec<String>,
+    pub geometry: PageGeometry,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PageGeometry {
+    pub width: f64,
+    pub height: f64,
+    pub words: Vec<PageWord>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PageWord {
+    pub row: usize,
+    pub token: String,
+    pub x_min: f64,
+    pub y_min: f64,
+    pub x_max: f64,
+    pub y_max: f64,
+}
+
+struct LayoutRow {
+    words: Vec<LayoutWord>,
+    y_min: f64,
+    y_max: f64,
+}
+
+impl LayoutRow {
+    fn center(&self) -> f64 {
+        (self.y_min + self.y_max) / 2.0
+    }
+
+    fn height(&self) -> f64 {
+        (self.y_max - self.y_min).max(1.0)
+    }
+
+    fn x_min(&self) -> f64 {
+        self.words
+            .iter()
+            .map(|word| word.x_min)
+            .fold(f64::INFINITY, f64::min)
+    }
+}
+
+pub fn extract_pages_with_pdftotext_layout(
+    pdf_path: &Path,
+    max_pages_per_doc: Option<usize>,
+) -> Result<Vec<LayoutPage>> {
+    let mut command = Command::new("pdftotext");
+    command
+        .arg("-bbox-layout")
+        .arg("-enc")
+        .arg("UTF-8")
+        .arg("-f")
+        .arg("1");
+    if let Some(max_pages) = max_pages_per_doc {
+        command.arg("-l").arg(max_pages.to_string());
+    }
+    command.arg(pdf_path).arg("-");
+
+    let output = command.output().with_context(|| {
+        format!(
+            "failed to execute pdftotext -bbox-layout for {}",
+            pdf_path.display()
+        )
+    })?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        bail!(
+            "pdftotext -bbox-layout returned non-zero exit status for {}: {}",
+            pdf_path.display(),
+            stderr.trim()
+        );
+    }
+
+    parse_bbox_layout_pages(&String::from_utf8_lossy(&output.stdout))
+}
+
+pub fn parse_bbox_layout_pages(document: &str) -> Result<Vec<LayoutPage>> {
+    let page_regex =
+        Regex::new(r#"(?s)<page\s+width="([0-9.]+)"\s+height="([0-9.]+)">(.*?)</page>"#)
+            .context("failed to compile layout page regex")?;
+    let word_regex = Regex::new(
+        r#"(?s)<word\s+xMin="([0-9.\-]+)"\s+yMin="([0-9.\-]+)"\s+xMax="([0-9.\-]+)"\s+yMax="([0-9.\-]+)">(.*?)</word>"#,
+    )
+    .context("failed to compile layout word regex")?;
+
+    let mut pages = Vec::<LayoutPage>::new();
+    for page in page_regex.captures_iter(document) {
+        let width = page[1].parse::<f64>().unwrap_or_default();
+        let height = page[2].parse::<f64>().unwrap_or_default();
+        let words = word_regex
+            .captures_iter(&page[3])
+            .filter_map(|word| {
+                let text = unescape_layout_text(word[5].trim());
+                if text.is_empty() {
+                    return None;
+                }
+                Some(LayoutWord {
+                    x_min: word[1].parse().ok()?,
+                    y_min: word[2].parse().ok()?,
+                    x_max: word[3].parse().ok()?,
+                    y_max: word[4].parse().ok()?,
+                    text,
+                })
+            })
+            .collect::<Vec<LayoutWord>>();
+
+        pages.push(render_layout_page(width, height, words));
+    }
+
+    while pages.last().is_some_and(|page| {
+        page.text.trim().is_empty() && page.header_lines.is_empty() && page.footer_lines.is_empty()
+    }) {
+        pages.pop();
+    }
+
+    Ok(pages)
+}
+
+pub fn render_layout_page(page_width: f64, page_height: f64, words: Vec<LayoutWord>) -> LayoutPage {
+    let rows = group_layout_rows(words);
+    let header_limit = page_height * LAYOUT_EDGE_BAND_RATIO;
+    let footer_limit = page_height * (1.0 - LAYOUT_EDGE_BAND_RATIO);
+
+    let mut page = LayoutPage {
+        geometry: PageGeometry {
+            width: page_width,
+            height: page_height,
+            words: Vec::new(),
+        },
+        ..LayoutPage::default()
+    };
+    let mut body_rows = Vec::<&LayoutRow>::new();
+    for row in &rows {
+        if page_height > 0.0 && row.y_max <= header_limit {
+            page.header_lines.push(render_layout_row(row, 0));
+        } else if page_height > 0.0 && row.y_min >= footer_limit {
+            page.footer_lines.push(render_layout_row(row, 0));
+        } else {
+            body_rows.push(row);
+        }
+    }
+
+    let left_margin = body_rows
+        .iter()
+        .map(|row| row.x_min())
+        .fold(f64::INFINITY, f64::min);
+    page.text = body_rows
+        .iter()
+        .map(|row| {
+            let indent_level = ((row.x_min() - left_margin) / LAYOUT_INDENT_STEP_PT)
+                .round()
+                .max(0.0) as usize;
+            render_layout_row(row, indent_level.min(LAYOUT_MAX_INDENT_LEVEL))
+        })
+        .collect::<Vec<String>>()
+        .join("\n");
+
+    for (row_index, row) in body_rows.iter().enumerate() {
+        for word in &row.words {
+            for token in tokenize_region_text(&word.text) {
+                page.geometry.words.push(PageWord {
+                    row: row_index,
+                    token,
+                    x_min: word.x_min,
+                    y_min: word.y_min,
+                    x_max: word.x_max,
+                    y_max: word.y_max,
+                });
+            }
+        }
+    }
+
+    page
+}
+
+fn group_layout_rows(mut words: Vec<LayoutWord>) -> Vec<LayoutRow> {
+    words.sort_by(|left, right| {
+        let left_center = (left.y_min + left.y_max) / 2.0;
+        let right_center = (right.y_min + right.y_max) / 2.0;
+        left_center
+            .total_cmp(&right_center)
+            .then(left.x_min.total_cmp(&right.x_min))
+    });
+
+    let mut rows = Vec::<LayoutRow>::new();
+    for word in words {
+        let center = (word.y_min + word.y_max) / 2.0;
+        if let Some(row) = rows.last_mut()
+            && (center - row.center()).abs() <= row.height() * LAYOUT_ROW_TOLERANCE_RATIO
+        {
+            row.y_min = row.y_min.min(word.y_min);
+            row.y_max = row.y_max.max(word.y_max);
+            row.words.push(word);
+            continue;
+        }
+
+        rows.push(LayoutRow {
+            y_min: word.y_min,
+            y_max: word.y_max,
+            words: vec![word],
+        });
+    }
+
+    for row in &mut rows {
+        row.words
+            .sort_by(|left, right| left.x_min.total_cmp(&right.x_min));
+    }
+
+    rows
+}
+
+fn render_layout_row(row: &LayoutRow, indent_level: usize) -> String {
+    let column_gap = row.height() * LAYOUT_COLUMN_GAP_RATIO;
+    let mut line = "  ".repeat(indent_level);
+    let mut previous_x_max = None::<f64>;
+
+    for word in &row.words {
+        if let Some(x_max) = previous_x_max {
+            if word.x_min - x_max >= column_gap {
+                line.push('\t');
+            } else {
+                line.push(' ');
+            }
+        }
+        line.push_str(&word.text);
+        previous_x_max = Some(word.x_max);
+    }
+
+    line
+}
+
+fn unescape_layout_text(value: &str) -> String {
+    value
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&")
+}
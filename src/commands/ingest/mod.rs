@@ -10,47 +10,80 @@ use rusqlite::{Connection, params};
 use serde::Serialize;
 use tracing::{info, warn};
 
-use crate::cli::{IngestArgs, OcrMode};
+use crate::cli::{ExtractBackend, IngestArgs, OcrMode};
 use crate::commands::inventory;
 use crate::model::{
-    IngestCounts, IngestPaths, IngestRunManifest, PdfEntry, PdfInventoryManifest, ToolVersions,
+    IngestCounts, IngestPaths, IngestRunManifest, PdfEntry, PdfInventoryManifest,
+    ProcessedEdition, ToolVersions,
 };
 use crate::util::{ensure_directory, now_utc_string, utc_compact_string, write_json_pretty};
 
-const DB_SCHEMA_VERSION: &str = "0.4.0";
+const DB_SCHEMA_VERSION: &str = "0.5.0";
+const PARSER_VERSION: &str = "1.8.0";
 
+mod asil_recommendations;
+mod cross_references;
+#[cfg(test)]
+mod db_fixture;
 mod db_setup;
+mod external_references;
+mod glossary;
+mod ingest_ledger;
+mod ingest_regexes;
+mod layout_extraction;
 mod reference_outline;
 mod structured_insertions;
+mod node_regions;
 mod node_table_insert;
 mod ocr_manifest;
 mod page_processing;
 mod block_parsing;
 mod pipeline;
+mod requirement_classification;
 mod pipeline_page_chunks;
 mod pipeline_section_nodes;
 mod pipeline_structured_chunks;
 mod run;
+mod scheduling;
+mod structured_chunk_state;
 mod table_parsing;
 mod table_parsing_quality;
+mod work_products;
+mod worker_pool;
 #[cfg(test)]
 mod tests;
 mod structured_types;
 
-pub use run::run;
-pub use db_setup::ensure_embedding_schema;
+pub use run::{execute_ingest, run};
+#[cfg(test)]
+pub(crate) use db_fixture::open_fixture_database;
+pub use db_setup::{ensure_embedding_schema, ensure_schema};
+pub(crate) use external_references::standard_family_root;
 
+use asil_recommendations::*;
+use cross_references::*;
+use external_references::*;
 use db_setup::*;
+use glossary::*;
+use ingest_ledger::*;
+use ingest_regexes::*;
+use layout_extraction::*;
 use reference_outline::*;
 use structured_insertions::*;
+use node_regions::*;
 use node_table_insert::*;
 use ocr_manifest::*;
 use page_processing::*;
 use block_parsing::*;
 use pipeline::*;
+use requirement_classification::*;
 use pipeline_page_chunks::*;
 use pipeline_section_nodes::*;
 use pipeline_structured_chunks::*;
+use scheduling::*;
+use structured_chunk_state::*;
 use table_parsing::*;
 use table_parsing_quality::*;
+use work_products::*;
+use worker_pool::*;
 use structured_types::*;
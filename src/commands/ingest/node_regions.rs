@@ -0,0 +1,203 @@
+use super::*;
+
+const REGION_ANCHOR_TOKENS: usize = 8;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct NodeRegionDraft {
+    pub page_pdf: i64,
+    pub x_min: f64,
+    pub y_min: f64,
+    pub x_max: f64,
+    pub y_max: f64,
+    pub page_width: f64,
+    pub page_height: f64,
+}
+
+struct RegionWord<'a> {
+    page_pdf: i64,
+    geometry: &'a PageGeometry,
+    word: &'a PageWord,
+}
+
+pub fn tokenize_region_text(text: &str) -> Vec<String> {
+    text.split(|ch: char| !ch.is_alphanumeric())
+        .filter(|token| !token.is_empty())
+        .map(str::to_lowercase)
+        .collect()
+}
+
+pub fn insert_node_regions_for_pdf(
+    connection: &Connection,
+    region_statement: &mut rusqlite::Statement<'_>,
+    doc_id: &str,
+    page_geometry: &[Option<PageGeometry>],
+    stats: &mut ChunkInsertStats,
+) -> Result<()> {
+    let mut stream = Vec::<RegionWord<'_>>::new();
+    let mut page_offsets = Vec::<(usize, usize)>::with_capacity(page_geometry.len());
+    for (page_index, geometry) in page_geometry.iter().enumerate() {
+        let start = stream.len();
+        if let Some(geometry) = geometry {
+            stream.extend(geometry.words.iter().map(|word| RegionWord {
+                page_pdf: page_index as i64 + 1,
+                geometry,
+                word,
+            }));
+        }
+        page_offsets.push((start, stream.len()));
+    }
+    if stream.is_empty() {
+        return Ok(());
+    }
+    let tokens = stream
+        .iter()
+        .map(|entry| entry.word.token.as_str())
+        .collect::<Vec<&str>>();
+
+    let mut node_statement = connection.prepare(
+        "
+        SELECT node_id, parent_node_id, page_pdf_start, page_pdf_end, text
+        FROM nodes
+        WHERE doc_id = ?1
+          AND node_type NOT IN ('document', 'page')
+          AND text IS NOT NULL
+        ORDER BY order_index ASC
+        ",
+    )?;
+    let mut rows = node_statement.query([doc_id])?;
+    let mut child_cursors = HashMap::<String, usize>::new();
+
+    while let Some(row) = rows.next()? {
+        let node_id: String = row.get(0)?;
+        let parent_node_id: Option<String> = row.get(1)?;
+        let page_start: Option<i64> = row.get(2)?;
+        let page_end: Option<i64> = row.get(3)?;
+        let text: String = row.get(4)?;
+
+        let (range_start, range_end) =
+            region_search_range(&page_offsets, page_start, page_end, tokens.len());
+        let cursor = parent_node_id
+            .as_deref()
+            .and_then(|parent| child_cursors.get(parent))
+            .copied()
+            .unwrap_or(range_start)
+            .max(range_start);
+
+        let node_tokens = tokenize_region_text(&text);
+        let Some((first, last)) = locate_region_span(&tokens, &node_tokens, cursor, range_end)
+        else {
+            continue;
+        };
+
+        if let Some(parent) = parent_node_id {
+            child_cursors.insert(parent, last + 1);
+        }
+        child_cursors.insert(node_id.clone(), first);
+
+        for (region_seq, region) in regions_for_span(&stream[first..=last]).iter().enumerate() {
+            region_statement.execute(params![
+                node_id,
+                region_seq as i64 + 1,
+                doc_id,
+                region.page_pdf,
+                region.x_min,
+                region.y_min,
+                region.x_max,
+                region.y_max,
+                region.page_width,
+                region.page_height,
+            ])?;
+            stats.node_regions_inserted += 1;
+        }
+    }
+
+    Ok(())
+}
+
+fn region_search_range(
+    page_offsets: &[(usize, usize)],
+    page_start: Option<i64>,
+    page_end: Option<i64>,
+    token_count: usize,
+) -> (usize, usize) {
+    let offset_for = |page: Option<i64>| {
+        page.filter(|value| *value > 0)
+            .and_then(|value| page_offsets.get(value as usize - 1))
+            .copied()
+    };
+
+    let start = offset_for(page_start).map(|(start, _)| start).unwrap_or(0);
+    let end = offset_for(page_end.or(page_start))
+        .map(|(_, end)| end)
+        .unwrap_or(token_count);
+    (start, end.max(start))
+}
+
+pub fn locate_region_span(
+    tokens: &[&str],
+    node_tokens: &[String],
+    from: usize,
+    to: usize,
+) -> Option<(usize, usize)> {
+    if node_tokens.is_empty() || to > tokens.len() || from >= to {
+        return None;
+    }
+
+    let head = &node_tokens[..node_tokens.len().min(REGION_ANCHOR_TOKENS)];
+    let first = (from..=to.checked_sub(head.len())?).find(|index| {
+        tokens[*index..*index + head.len()]
+            .iter()
+            .zip(head)
+            .all(|(left, right)| *left == right)
+    })?;
+    if node_tokens.len() <= head.len() {
+        return Some((first, first + head.len() - 1));
+    }
+
+    let tail = &node_tokens[node_tokens.len() - node_tokens.len().min(REGION_ANCHOR_TOKENS)..];
+    let expected_last = first + node_tokens.len() - 1;
+    let search_end = to.min(first + node_tokens.len() * 2 + REGION_ANCHOR_TOKENS * 2);
+    let tail_start = (first + head.len()..=search_end.saturating_sub(tail.len())).find(|index| {
+        tokens[*index..*index + tail.len()]
+            .iter()
+            .zip(tail)
+            .all(|(left, right)| *left == right)
+    });
+
+    let last = match tail_start {
+        Some(index) => index + tail.len() - 1,
+        None => expected_last.min(to - 1),
+    };
+    Some((first, last))
+}
+
+fn regions_for_span(words: &[RegionWord<'_>]) -> Vec<NodeRegionDraft> {
+    let mut regions = Vec::<NodeRegionDraft>::new();
+    let mut current_row = None::<(i64, usize)>;
+
+    for entry in words {
+        let row_key = (entry.page_pdf, entry.word.row);
+        if current_row == Some(row_key)
+            && let Some(region) = regions.last_mut()
+        {
+            region.x_min = region.x_min.min(entry.word.x_min);
+            region.y_min = region.y_min.min(entry.word.y_min);
+            region.x_max = region.x_max.max(entry.word.x_max);
+            region.y_max = region.y_max.max(entry.word.y_max);
+            continue;
+        }
+
+        current_row = Some(row_key);
+        regions.push(NodeRegionDraft {
+            page_pdf: entry.page_pdf,
+            x_min: entry.word.x_min,
+            y_min: entry.word.y_min,
+            x_max: entry.word.x_max,
+            y_max: entry.word.y_max,
+            page_width: entry.geometry.width,
+            page_height: entry.geometry.height,
+        });
+    }
+
+    regions
+}
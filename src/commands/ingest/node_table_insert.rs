@@ -10,11 +10,14 @@ pub fn increment_node_type_stat(stats: &mut ChunkInsertStats, node_type: NodeTyp
         NodeType::Table => stats.table_nodes_inserted += 1,
         NodeType::TableRow => stats.table_row_nodes_inserted += 1,
         NodeType::TableCell => stats.table_cell_nodes_inserted += 1,
+        NodeType::TableFootnote => stats.table_footnote_nodes_inserted += 1,
+        NodeType::Figure => stats.figure_nodes_inserted += 1,
         NodeType::List => stats.list_nodes_inserted += 1,
         NodeType::ListItem => stats.list_item_nodes_inserted += 1,
         NodeType::Note => stats.note_nodes_inserted += 1,
         NodeType::NoteItem => stats.note_item_nodes_inserted += 1,
         NodeType::RequirementAtom => stats.requirement_atom_nodes_inserted += 1,
+        NodeType::Term => stats.term_nodes_inserted += 1,
         NodeType::Document | NodeType::Page => {}
     }
 }
@@ -30,6 +33,7 @@ pub fn chunk_origin_node_type(chunk_type: ChunkType, reference: &str) -> NodeTyp
             }
         }
         ChunkType::Table => NodeType::Table,
+        ChunkType::Figure => NodeType::Figure,
         ChunkType::Annex => NodeType::Annex,
     }
 }
@@ -114,7 +118,16 @@ pub fn insert_node(
     is_header: Option<i64>,
     row_span: Option<i64>,
     col_span: Option<i64>,
+    requirement: Option<&RequirementAtomDraft>,
 ) -> Result<()> {
+    let requirement_conditions = requirement
+        .map(|draft| serde_json::to_string(&draft.conditions))
+        .transpose()
+        .context("failed to serialize requirement conditions")?;
+    let requirement_references = requirement
+        .map(|draft| serde_json::to_string(&draft.references))
+        .transpose()
+        .context("failed to serialize requirement references")?;
     statement.execute(params![
         node_id,
         parent_node_id,
@@ -142,11 +155,19 @@ pub fn insert_node(
         col_idx,
         is_header,
         row_span,
-        col_span
+        col_span,
+        requirement.and_then(|draft| draft.modality),
+        requirement.and_then(|draft| draft.actor.as_deref()),
+        requirement_conditions,
+        requirement_references
     ])?;
     Ok(())
 }
 
+pub fn table_row_node_id(table_node_id: &str, row_number: usize) -> String {
+    format!("{}:row:{:03}", table_node_id, row_number)
+}
+
 #[allow(clippy::too_many_arguments)]
 pub fn insert_table_child_nodes(
     node_statement: &mut rusqlite::Statement<'_>,
@@ -164,7 +185,7 @@ pub fn insert_table_child_nodes(
     let header_row_count = infer_table_header_rows(&parsed_table.rows);
 
     for (row_idx, row_cells) in parsed_table.rows.iter().enumerate() {
-        let row_node_id = format!("{}:row:{:03}", table_node_id, row_idx + 1);
+        let row_node_id = table_row_node_id(table_node_id, row_idx + 1);
         let row_ref = format!("{} row {}", table_reference, row_idx + 1);
         let row_heading = format!("{} row {}", table_reference, row_idx + 1);
         let row_text = row_cells.join(" | ");
@@ -209,6 +230,7 @@ pub fn insert_table_child_nodes(
             Some(row_is_header),
             Some(1),
             None,
+            None,
         )?;
 
         *node_order_index += 1;
@@ -264,6 +286,7 @@ pub fn insert_table_child_nodes(
                 Some(row_is_header),
                 Some(1),
                 Some(1),
+                None,
             )?;
 
             *node_order_index += 1;
@@ -272,5 +295,97 @@ pub fn insert_table_child_nodes(
         }
     }
 
+    for (footnote_idx, footnote) in parsed_table.footnotes.iter().enumerate() {
+        let footnote_node_id = table_footnote_node_id(table_node_id, &footnote.marker);
+        let footnote_ref = table_footnote_ref(table_reference, &footnote.marker);
+        let footnote_path = format!(
+            "{} > table_footnote:{}",
+            table_ancestor_path, footnote.marker
+        );
+        let footnote_order = (footnote_idx + 1) as i64;
+        let footnote_anchor_id = build_citation_anchor_id(
+            doc_id,
+            table_reference,
+            "table_footnote",
+            Some(&footnote.marker),
+            Some(footnote_order),
+        );
+
+        insert_node(
+            node_statement,
+            &footnote_node_id,
+            Some(table_node_id),
+            doc_id,
+            NodeType::TableFootnote,
+            Some(&footnote_ref),
+            Some(&footnote_ref),
+            Some(&footnote_ref),
+            *node_order_index,
+            Some(page_start),
+            Some(page_end),
+            Some(&footnote.text),
+            source_hash,
+            &footnote_path,
+            Some("table_footnote"),
+            Some(&footnote.marker),
+            Some(&footnote.marker),
+            Some(footnote_order),
+            Some(&footnote_anchor_id),
+            None,
+            None,
+            Some(footnote_order),
+            Some(table_node_id),
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+        )?;
+
+        *node_order_index += 1;
+        stats.nodes_total += 1;
+        increment_node_type_stat(stats, NodeType::TableFootnote);
+    }
+
+    Ok(())
+}
+
+pub fn insert_table_footnote_links(
+    footnote_link_statement: &mut rusqlite::Statement<'_>,
+    doc_id: &str,
+    table_node_id: &str,
+    parsed_table: &ParsedTableRows,
+    stats: &mut ChunkInsertStats,
+) -> Result<()> {
+    for footnote in &parsed_table.footnotes {
+        let footnote_node_id = table_footnote_node_id(table_node_id, &footnote.marker);
+        for (row_idx, col_idx) in &footnote.linked_cells {
+            let cell_node_id = format!(
+                "{}:cell:{:03}:{:03}",
+                table_node_id,
+                row_idx + 1,
+                col_idx + 1
+            );
+
+            footnote_link_statement.execute(params![
+                cell_node_id,
+                footnote_node_id,
+                doc_id,
+                table_node_id,
+                footnote.marker,
+            ])?;
+            stats.table_footnote_links_inserted += 1;
+        }
+    }
+
     Ok(())
 }
+
+pub fn table_footnote_node_id(table_node_id: &str, marker: &str) -> String {
+    format!("{}:footnote:{}", table_node_id, marker)
+}
+
+pub fn table_footnote_ref(table_reference: &str, marker: &str) -> String {
+    format!("{} footnote {}", table_reference, marker)
+}
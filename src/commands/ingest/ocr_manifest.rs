@@ -234,14 +234,29 @@ pub fn render_ingest_command(args: &IngestArgs) -> String {
     if args.seed_page_chunks {
         command.push("--seed-page-chunks".to_string());
     }
+    if args.force {
+        command.push("--force".to_string());
+    }
+    if let Some(jobs) = args.jobs {
+        command.push("--jobs".to_string());
+        command.push(jobs.to_string());
+    }
     for part in &args.target_parts {
         command.push("--target-part".to_string());
         command.push(part.to_string());
     }
+    for year in &args.target_years {
+        command.push("--target-year".to_string());
+        command.push(year.to_string());
+    }
     if let Some(max_pages) = args.max_pages_per_doc {
         command.push("--max-pages-per-doc".to_string());
         command.push(max_pages.to_string());
     }
+    if args.extract_backend != ExtractBackend::Text {
+        command.push("--extract-backend".to_string());
+        command.push(args.extract_backend.as_str().to_string());
+    }
     if args.ocr_mode != OcrMode::Off {
         command.push("--ocr-mode".to_string());
         command.push(args.ocr_mode.as_str().to_string());
@@ -1,16 +1,63 @@
 use super::*;
 
+#[allow(clippy::too_many_arguments)]
 pub fn extract_pages_with_backend(
     pdf_path: &Path,
     doc_id: &str,
     max_pages_per_doc: Option<usize>,
+    extract_backend: ExtractBackend,
     ocr_mode: OcrMode,
     ocr_lang: &str,
     ocr_min_text_chars: usize,
+    ocr_jobs: usize,
 ) -> Result<ExtractedPages> {
-    let pages = extract_pages_with_pdftotext(pdf_path, max_pages_per_doc)?;
+    let (pages, page_printed_labels, page_geometry, header_lines_removed, footer_lines_removed) =
+        match extract_backend {
+            ExtractBackend::Text => {
+                let pages = extract_pages_with_pdftotext(pdf_path, max_pages_per_doc)?;
+                let labels = vec![None; pages.len()];
+                (pages, labels, Vec::new(), 0, 0)
+            }
+            ExtractBackend::Layout => {
+                let layout_pages =
+                    extract_pages_with_pdftotext_layout(pdf_path, max_pages_per_doc)?;
+                let labels = layout_pages
+                    .iter()
+                    .map(|page| {
+                        let edge_text = page
+                            .header_lines
+                            .iter()
+                            .chain(page.footer_lines.iter())
+                            .cloned()
+                            .collect::<Vec<String>>()
+                            .join("\n");
+                        detect_printed_page_label(&edge_text)
+                    })
+                    .collect::<Vec<Option<String>>>();
+                let header_lines = layout_pages
+                    .iter()
+                    .map(|page| page.header_lines.len())
+                    .sum::<usize>();
+                let footer_lines = layout_pages
+                    .iter()
+                    .map(|page| page.footer_lines.len())
+                    .sum::<usize>();
+                let (pages, geometry) = layout_pages
+                    .into_iter()
+                    .map(|page| (page.text, Some(page.geometry)))
+                    .unzip::<_, _, Vec<String>, Vec<Option<PageGeometry>>>();
+                (pages, labels, geometry, header_lines, footer_lines)
+            }
+        };
+    let backend = match extract_backend {
+        ExtractBackend::Text => "text_layer",
+        ExtractBackend::Layout => "layout",
+    };
     let mut extraction = ExtractedPages {
-        page_printed_labels: vec![None; pages.len()],
+        page_printed_labels,
+        page_geometry,
+        header_lines_removed,
+        footer_lines_removed,
         text_layer_page_count: pages.len(),
         empty_page_count: pages
             .iter()
@@ -24,11 +71,11 @@ pub fn extract_pages_with_backend(
                 PageExtractionProvenance {
                     doc_id: doc_id.to_string(),
                     page_pdf: (index + 1) as i64,
-                    backend: "text_layer".to_string(),
+                    backend: backend.to_string(),
                     reason: if chars == 0 {
-                        "text_layer_empty".to_string()
+                        format!("{backend}_empty")
                     } else {
-                        "text_layer_default".to_string()
+                        format!("{backend}_default")
                     },
                     text_char_count: chars,
                     ocr_char_count: None,
@@ -70,7 +117,10 @@ pub fn extract_pages_with_backend(
         return Ok(extraction);
     }
 
-    for page_number in candidate_pages {
+    let ocr_results = map_in_order(&candidate_pages, ocr_jobs, |page_number| {
+        extract_page_with_ocr(pdf_path, *page_number, ocr_lang)
+    })?;
+    for (page_number, ocr_result) in candidate_pages.into_iter().zip(ocr_results) {
         let page_index = page_number.saturating_sub(1);
         let current_text = extraction
             .pages
@@ -78,7 +128,7 @@ pub fn extract_pages_with_backend(
             .cloned()
             .unwrap_or_default();
 
-        match extract_page_with_ocr(pdf_path, page_number, ocr_lang) {
+        match ocr_result {
             Ok(ocr_text) => {
                 let ocr_char_count = non_whitespace_char_count(&ocr_text);
                 if ocr_char_count == 0 && matches!(ocr_mode, OcrMode::Auto) {
@@ -97,6 +147,9 @@ pub fn extract_pages_with_backend(
                 if let Some(page) = extraction.pages.get_mut(page_index) {
                     *page = ocr_text;
                 }
+                if let Some(geometry) = extraction.page_geometry.get_mut(page_index) {
+                    *geometry = None;
+                }
                 extraction.ocr_page_count += 1;
                 extraction.text_layer_page_count =
                     extraction.text_layer_page_count.saturating_sub(1);
@@ -205,7 +258,11 @@ pub fn refresh_printed_page_labels(extraction: &mut ExtractedPages) {
     extraction.page_printed_labels = extraction
         .pages
         .iter()
-        .map(|page| detect_printed_page_label(page))
+        .enumerate()
+        .map(|(index, page)| {
+            detect_printed_page_label(page)
+                .or_else(|| extraction.page_printed_labels.get(index).cloned().flatten())
+        })
         .collect();
 
     for (index, label) in extraction.page_printed_labels.iter().enumerate() {
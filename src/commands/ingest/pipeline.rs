@@ -1,32 +1,5 @@
 use super::*;
 
-pub struct IngestRegexes {
-    pub list_item_regex: Regex,
-    pub note_item_regex: Regex,
-    pub table_cell_split_regex: Regex,
-    pub requirement_split_regex: Regex,
-    pub requirement_keyword_regex: Regex,
-}
-
-impl IngestRegexes {
-    pub fn build() -> Result<Self> {
-        Ok(Self {
-            list_item_regex: Regex::new(
-                r"^(?P<marker>(?:(?:\d+[A-Za-z]?|[A-Za-z])(?:[\.)])?|[-*•—–]))(?:\s+(?P<body>.+))?$",
-            )
-            .context("failed to compile list item regex")?,
-            note_item_regex: Regex::new(r"^(?i)(?P<marker>NOTE(?:\s+\d+)?)(?:\s+(?P<body>.+))?$")
-                .context("failed to compile note item regex")?,
-            table_cell_split_regex: Regex::new(r"\t+|\s{2,}")
-                .context("failed to compile table cell split regex")?,
-            requirement_split_regex: Regex::new(r"[.;]\s+")
-                .context("failed to compile requirement split regex")?,
-            requirement_keyword_regex: Regex::new(r"(?i)\bshall(?:\s+not)?\b|\bshould\b")
-                .context("failed to compile requirement keyword regex")?,
-        })
-    }
-}
-
 #[derive(Debug)]
 pub struct PdfNodeState {
     pub document_node_id: String,
@@ -53,22 +26,22 @@ impl PdfNodeState {
     }
 }
 
-pub fn insert_chunks(
+#[allow(clippy::too_many_arguments)]
+pub fn insert_prepared_chunks(
     connection: &mut Connection,
-    cache_root: &Path,
     pdfs: &[PdfEntry],
-    parser: &StructuredChunkParser,
-    max_pages_per_doc: Option<usize>,
+    regexes: &IngestRegexes,
     seed_page_chunks: bool,
     target_parts: &[u32],
-    ocr_mode: OcrMode,
-    ocr_lang: &str,
-    ocr_min_text_chars: usize,
+    target_years: &[u32],
+    fingerprint: &IngestFingerprint,
+    force: bool,
+    jobs: usize,
+    prepare: impl Fn(&PdfEntry, usize) -> Result<PreparedDocument> + Sync,
 ) -> Result<ChunkInsertStats> {
-    let target_set: HashSet<u32> = target_parts.iter().copied().collect();
     let tx = connection.transaction()?;
     let mut stats = ChunkInsertStats::default();
-    let regexes = IngestRegexes::build()?;
+    let mut ingested_doc_ids = Vec::<String>::new();
 
     {
         let mut chunk_statement = tx.prepare(
@@ -114,9 +87,10 @@ pub fn insert_chunks(
               order_index, page_pdf_start, page_pdf_end, text, source_hash, ancestor_path,
               anchor_type, anchor_label_raw, anchor_label_norm, anchor_order, citation_anchor_id,
               list_depth, list_marker_style, item_index,
-              table_node_id, row_idx, col_idx, is_header, row_span, col_span
+              table_node_id, row_idx, col_idx, is_header, row_span, col_span,
+              modality, requirement_actor, requirement_conditions, requirement_references
             )
-            VALUES(?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18, ?19, ?20, ?21, ?22, ?23, ?24, ?25, ?26, ?27)
+            VALUES(?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18, ?19, ?20, ?21, ?22, ?23, ?24, ?25, ?26, ?27, ?28, ?29, ?30, ?31)
             ON CONFLICT(node_id) DO UPDATE SET
               parent_node_id=excluded.parent_node_id,
               doc_id=excluded.doc_id,
@@ -143,104 +117,282 @@ pub fn insert_chunks(
               col_idx=excluded.col_idx,
               is_header=excluded.is_header,
               row_span=excluded.row_span,
-              col_span=excluded.col_span
+              col_span=excluded.col_span,
+              modality=excluded.modality,
+              requirement_actor=excluded.requirement_actor,
+              requirement_conditions=excluded.requirement_conditions,
+              requirement_references=excluded.requirement_references
+            ",
+        )?;
+
+        let mut asil_statement = tx.prepare(
+            "
+            INSERT INTO asil_recommendations(
+              doc_id, table_node_id, table_ref, row_node_id, marker, method_text,
+              asil, rating, recommendation_level, footnotes, source_hash
+            )
+            VALUES(?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)
+            ON CONFLICT(row_node_id, asil) DO UPDATE SET
+              doc_id=excluded.doc_id,
+              table_node_id=excluded.table_node_id,
+              table_ref=excluded.table_ref,
+              marker=excluded.marker,
+              method_text=excluded.method_text,
+              rating=excluded.rating,
+              recommendation_level=excluded.recommendation_level,
+              footnotes=excluded.footnotes,
+              source_hash=excluded.source_hash
+            ",
+        )?;
+
+        let mut footnote_link_statement = tx.prepare(
+            "
+            INSERT INTO table_footnote_links(
+              cell_node_id, footnote_node_id, doc_id, table_node_id, marker
+            )
+            VALUES(?1, ?2, ?3, ?4, ?5)
+            ON CONFLICT(cell_node_id, footnote_node_id) DO UPDATE SET
+              doc_id=excluded.doc_id,
+              table_node_id=excluded.table_node_id,
+              marker=excluded.marker
+            ",
+        )?;
+
+        let mut reference_statement = tx.prepare(
+            "
+            INSERT INTO node_references(
+              reference_id, doc_id, source_node_id, target_doc_id, target_part,
+              target_year, target_ref, reference_kind, raw_text
+            )
+            VALUES(?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)
+            ON CONFLICT(reference_id) DO UPDATE SET
+              doc_id=excluded.doc_id,
+              source_node_id=excluded.source_node_id,
+              target_node_id=NULL,
+              target_doc_id=excluded.target_doc_id,
+              target_part=excluded.target_part,
+              target_year=excluded.target_year,
+              target_ref=excluded.target_ref,
+              reference_kind=excluded.reference_kind,
+              raw_text=excluded.raw_text,
+              resolution_status='pending'
+            ",
+        )?;
+
+        let mut term_statement = tx.prepare(
+            "
+            INSERT INTO terms(
+              term_id, doc_id, ref, term, term_norm, abbreviation, definition, notes,
+              page_pdf_start, page_pdf_end, source_hash
+            )
+            VALUES(?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)
+            ON CONFLICT(term_id) DO UPDATE SET
+              doc_id=excluded.doc_id,
+              ref=excluded.ref,
+              term=excluded.term,
+              term_norm=excluded.term_norm,
+              abbreviation=excluded.abbreviation,
+              definition=excluded.definition,
+              notes=excluded.notes,
+              page_pdf_start=excluded.page_pdf_start,
+              page_pdf_end=excluded.page_pdf_end,
+              source_hash=excluded.source_hash
+            ",
+        )?;
+
+        let mut work_product_statement = tx.prepare(
+            "
+            INSERT INTO work_products(
+              work_product_id, doc_id, node_id, ref, name, name_norm, source_text,
+              page_pdf_start, page_pdf_end, source_hash
+            )
+            VALUES(?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)
+            ",
+        )?;
+        let mut work_product_source_statement = tx.prepare(
+            "
+            INSERT INTO work_product_sources(
+              work_product_id, source_seq, source_part, source_doc_id, source_ref
+            )
+            VALUES(?1, ?2, ?3, ?4, ?5)
+            ",
+        )?;
+
+        let mut external_reference_statement = tx.prepare(
+            "
+            INSERT INTO external_references(
+              external_reference_id, doc_id, source_kind, list_label, standard_id, year, title,
+              raw_text, page_pdf_start, page_pdf_end, source_hash
+            )
+            VALUES(?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)
+            ",
+        )?;
+        let mut external_reference_citation_statement = tx.prepare(
+            "
+            INSERT OR IGNORE INTO external_reference_citations(
+              external_reference_id, node_id, doc_id, ref
+            )
+            VALUES(?1, ?2, ?3, ?4)
+            ",
+        )?;
+
+        let mut region_statement = tx.prepare(
+            "
+            INSERT INTO node_regions(
+              node_id, region_seq, doc_id, page_pdf, x_min, y_min, x_max, y_max,
+              page_width, page_height
+            )
+            VALUES(?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)
             ",
         )?;
 
-        for pdf in pdfs {
-            if !target_set.is_empty() && !target_set.contains(&pdf.part) {
-                continue;
-            }
-
-            track_processed_part(&mut stats, pdf.part);
-
-            let doc_id = doc_id_for(pdf);
-            tx.execute("DELETE FROM chunks WHERE doc_id = ?1", [&doc_id])?;
-            tx.execute("DELETE FROM nodes WHERE doc_id = ?1", [&doc_id])?;
-
-            let pdf_path = cache_root.join(&pdf.filename);
-            if !pdf_path.exists() {
-                stats
-                    .warnings
-                    .push(format!("missing source PDF: {}", pdf_path.display()));
-                continue;
-            }
-
-            let Some(page_extraction) = extract_pages_for_pdf(
-                &pdf_path,
-                &doc_id,
-                max_pages_per_doc,
-                ocr_mode,
-                ocr_lang,
-                ocr_min_text_chars,
-                &mut stats,
-            )?
-            else {
-                continue;
-            };
-
-            accumulate_page_extraction_stats(&mut stats, &page_extraction);
-            let page_printed_labels = page_extraction.page_printed_labels.clone();
-            let pages = page_extraction.pages;
-
-            let section_headings = extract_section_headings_for_pdf(&pdf_path, &mut stats);
-
-            let mut state = initialize_document_node_state(
-                &mut node_statement,
-                &doc_id,
-                pdf.part,
-                &pdf.sha256,
-                pages.len(),
-                &mut stats,
-            )?;
-
-            insert_section_heading_nodes(
-                &mut node_statement,
-                &doc_id,
-                &pdf.sha256,
-                &section_headings,
-                &mut state,
-                &mut stats,
-            )?;
-
-            let structured_chunks = split_long_structured_chunks(parser.parse_pages(&pages));
-            insert_structured_chunks_for_pdf(
-                &mut chunk_statement,
-                &mut node_statement,
-                &doc_id,
-                &pdf.sha256,
-                &structured_chunks,
-                &page_printed_labels,
-                &regexes,
-                &mut state,
-                &mut stats,
-            )?;
-
-            if seed_page_chunks {
-                seed_page_chunks_for_pdf(
+        let pending_pdfs = select_pending_pdfs(
+            &tx,
+            pdfs,
+            target_parts,
+            target_years,
+            fingerprint,
+            force,
+            &mut stats,
+        )?;
+        ingested_doc_ids.extend(pending_pdfs.iter().map(|pdf| doc_id_for(pdf)));
+        let (workers, ocr_jobs) = split_worker_jobs(jobs, pending_pdfs.len());
+        for_each_in_order(
+            &pending_pdfs,
+            workers,
+            |pdf| prepare(pdf, ocr_jobs),
+            |index, prepared| {
+                let pdf = pending_pdfs[index];
+                let prepared = prepared?;
+
+                track_processed_part(&mut stats, pdf.part, pdf.year);
+
+                let doc_id = doc_id_for(pdf);
+                clear_document_rows(&tx, &doc_id)?;
+
+                let Some(page_extraction) = prepared.page_extraction else {
+                    stats.warnings.extend(prepared.warnings);
+                    return Ok(());
+                };
+
+                accumulate_page_extraction_stats(&mut stats, &page_extraction);
+                stats.warnings.extend(prepared.warnings);
+                let page_printed_labels = page_extraction.page_printed_labels;
+                let page_geometry = page_extraction.page_geometry;
+                let pages = page_extraction.pages;
+
+                let mut state = initialize_document_node_state(
+                    &mut node_statement,
+                    &doc_id,
+                    pdf.part,
+                    &pdf.sha256,
+                    pages.len(),
+                    &mut stats,
+                )?;
+
+                insert_section_heading_nodes(
+                    &mut node_statement,
+                    &doc_id,
+                    &pdf.sha256,
+                    &prepared.section_headings,
+                    &mut state,
+                    &mut stats,
+                )?;
+
+                insert_structured_chunks_for_pdf(
                     &mut chunk_statement,
                     &mut node_statement,
+                    &mut asil_statement,
+                    &mut reference_statement,
+                    &mut footnote_link_statement,
                     &doc_id,
                     &pdf.sha256,
-                    &pages,
+                    &prepared.structured_chunks,
                     &page_printed_labels,
+                    regexes,
                     &mut state,
                     &mut stats,
                 )?;
-            }
-        }
+
+                insert_external_references_for_pdf(
+                    &mut external_reference_statement,
+                    &mut external_reference_citation_statement,
+                    &doc_id,
+                    &pdf.sha256,
+                    &prepared.external_references,
+                    &prepared.structured_chunks,
+                    &state,
+                    &mut stats,
+                )?;
+
+                insert_work_products_for_pdf(
+                    &mut work_product_statement,
+                    &mut work_product_source_statement,
+                    &doc_id,
+                    pdf.part,
+                    &pdf.sha256,
+                    &prepared.work_products,
+                    &state,
+                    &mut stats,
+                )?;
+
+                if !prepared.terms.is_empty() {
+                    insert_glossary_terms_for_pdf(
+                        &mut node_statement,
+                        &mut term_statement,
+                        &doc_id,
+                        &pdf.sha256,
+                        &prepared.terms,
+                        &mut state,
+                        &mut stats,
+                    )?;
+                }
+
+                if seed_page_chunks {
+                    seed_page_chunks_for_pdf(
+                        &mut chunk_statement,
+                        &mut node_statement,
+                        &doc_id,
+                        &pdf.sha256,
+                        &pages,
+                        &page_printed_labels,
+                        &mut state,
+                        &mut stats,
+                    )?;
+                }
+
+                insert_node_regions_for_pdf(
+                    &tx,
+                    &mut region_statement,
+                    &doc_id,
+                    &page_geometry,
+                    &mut stats,
+                )?;
+
+                record_ingest_ledger_entry(&tx, &doc_id, &pdf.sha256, fingerprint)?;
+                Ok(())
+            },
+        )?;
     }
 
+    resolve_cross_references(&tx, &mut stats)?;
+    link_term_occurrences(&tx, &ingested_doc_ids, &mut stats)?;
+    resolve_work_product_sources(&tx, &mut stats)?;
     tx.commit()?;
     Ok(stats)
 }
 
-pub fn track_processed_part(stats: &mut ChunkInsertStats, part: u32) {
+pub fn track_processed_part(stats: &mut ChunkInsertStats, part: u32, year: u32) {
     stats.processed_pdf_count += 1;
     if !stats.processed_parts.contains(&part) {
         stats.processed_parts.push(part);
         stats.processed_parts.sort_unstable();
     }
+    let edition = ProcessedEdition { part, year };
+    if !stats.processed_editions.contains(&edition) {
+        stats.processed_editions.push(edition);
+        stats.processed_editions.sort_unstable();
+    }
 }
 
 pub fn accumulate_page_extraction_stats(stats: &mut ChunkInsertStats, extraction: &ExtractedPages) {
@@ -257,57 +409,6 @@ pub fn accumulate_page_extraction_stats(stats: &mut ChunkInsertStats, extraction
     stats.warnings.extend(extraction.warnings.clone());
 }
 
-#[allow(clippy::too_many_arguments)]
-pub fn extract_pages_for_pdf(
-    pdf_path: &Path,
-    doc_id: &str,
-    max_pages_per_doc: Option<usize>,
-    ocr_mode: OcrMode,
-    ocr_lang: &str,
-    ocr_min_text_chars: usize,
-    stats: &mut ChunkInsertStats,
-) -> Result<Option<ExtractedPages>> {
-    match extract_pages_with_backend(
-        pdf_path,
-        doc_id,
-        max_pages_per_doc,
-        ocr_mode,
-        ocr_lang,
-        ocr_min_text_chars,
-    ) {
-        Ok(extraction) => Ok(Some(extraction)),
-        Err(err) => {
-            if matches!(ocr_mode, OcrMode::Force) {
-                return Err(err)
-                    .with_context(|| format!("failed to extract text for {}", pdf_path.display()));
-            }
-
-            let warning = format!("failed to extract text for {}: {err}", pdf_path.display());
-            warn!(warning = %warning, "pdf extraction warning");
-            stats.warnings.push(warning);
-            Ok(None)
-        }
-    }
-}
-
-pub fn extract_section_headings_for_pdf(
-    pdf_path: &Path,
-    stats: &mut ChunkInsertStats,
-) -> Vec<SectionHeadingDraft> {
-    match extract_section_headings_with_pdftohtml(pdf_path) {
-        Ok(headings) => headings,
-        Err(err) => {
-            let warning = format!(
-                "failed to extract outline headings for {}: {err}",
-                pdf_path.display()
-            );
-            warn!(warning = %warning, "pdf outline extraction warning");
-            stats.warnings.push(warning);
-            Vec::new()
-        }
-    }
-}
-
 pub fn initialize_document_node_state(
     node_statement: &mut rusqlite::Statement<'_>,
     doc_id: &str,
@@ -348,6 +449,7 @@ pub fn initialize_document_node_state(
         None,
         None,
         None,
+        None,
     )?;
 
     stats.nodes_total += 1;
@@ -60,6 +60,7 @@ pub fn seed_page_chunks_for_pdf(
             None,
             None,
             None,
+            None,
         )?;
         state.node_order_index += 1;
         stats.nodes_total += 1;
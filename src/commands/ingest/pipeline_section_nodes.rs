@@ -61,6 +61,7 @@ pub fn insert_section_heading_nodes(
             None,
             None,
             None,
+            None,
         )?;
 
         state
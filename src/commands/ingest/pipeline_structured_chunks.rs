@@ -4,6 +4,9 @@ use super::*;
 pub fn insert_structured_chunks_for_pdf(
     chunk_statement: &mut rusqlite::Statement<'_>,
     node_statement: &mut rusqlite::Statement<'_>,
+    asil_statement: &mut rusqlite::Statement<'_>,
+    reference_statement: &mut rusqlite::Statement<'_>,
+    footnote_link_statement: &mut rusqlite::Statement<'_>,
     doc_id: &str,
     source_hash: &str,
     structured_chunks: &[StructuredChunkDraft],
@@ -19,7 +22,7 @@ pub fn insert_structured_chunks_for_pdf(
     for chunk in structured_chunks {
         let origin_node_type = chunk_origin_node_type(chunk.chunk_type, &chunk.reference);
         let parent_node_id = match chunk.chunk_type {
-            ChunkType::Table => state
+            ChunkType::Table | ChunkType::Figure => state
                 .last_clause_node_id
                 .clone()
                 .unwrap_or_else(|| state.document_node_id.clone()),
@@ -62,9 +65,11 @@ pub fn insert_structured_chunks_for_pdf(
             *next
         };
         let node_anchor_type = match origin_node_type {
-            NodeType::Clause | NodeType::Subclause | NodeType::Annex | NodeType::Table => {
-                Some("clause")
-            }
+            NodeType::Clause
+            | NodeType::Subclause
+            | NodeType::Annex
+            | NodeType::Table
+            | NodeType::Figure => Some("clause"),
             _ => None,
         };
         let node_anchor_order = node_anchor_type.map(|_| structured_seq);
@@ -107,6 +112,7 @@ pub fn insert_structured_chunks_for_pdf(
             None,
             None,
             None,
+            None,
         )?;
 
         state
@@ -116,6 +122,16 @@ pub fn insert_structured_chunks_for_pdf(
         stats.nodes_total += 1;
         increment_node_type_stat(stats, origin_node_type);
 
+        let cross_references =
+            extract_cross_references(&chunk.text, &chunk.heading, &chunk.reference, regexes);
+        insert_cross_references(
+            reference_statement,
+            doc_id,
+            &origin_node_id,
+            &cross_references,
+            stats,
+        )?;
+
         if matches!(origin_node_type, NodeType::Clause | NodeType::Subclause) {
             state
                 .clause_ref_to_node_id
@@ -138,6 +154,11 @@ pub fn insert_structured_chunks_for_pdf(
             stats.table_raw_fallback_count += 1;
         }
 
+        if chunk.chunk_type == ChunkType::Table {
+            stats.table_continuations_merged_count += chunk.merged_continuations;
+            stats.table_repeated_header_rows_dropped_count += chunk.repeated_header_rows_dropped;
+        }
+
         if let Some(parsed) = parsed_table_rows.as_ref() {
             stats.table_sparse_rows_count += parsed.quality.sparse_rows_count;
             stats.table_overloaded_rows_count += parsed.quality.overloaded_rows_count;
@@ -201,6 +222,7 @@ pub fn insert_structured_chunks_for_pdf(
         match chunk.chunk_type {
             ChunkType::Clause => stats.clause_chunks_inserted += 1,
             ChunkType::Table => stats.table_chunks_inserted += 1,
+            ChunkType::Figure => stats.figure_chunks_inserted += 1,
             ChunkType::Annex => stats.annex_chunks_inserted += 1,
         }
 
@@ -218,6 +240,25 @@ pub fn insert_structured_chunks_for_pdf(
                 &mut state.node_order_index,
                 stats,
             )?;
+
+            insert_table_footnote_links(
+                footnote_link_statement,
+                doc_id,
+                &origin_node_id,
+                &parsed,
+                stats,
+            )?;
+
+            insert_asil_recommendations(
+                asil_statement,
+                doc_id,
+                &origin_node_id,
+                &chunk.reference,
+                &parsed,
+                &extract_body_lines(&chunk.text, &chunk.heading),
+                source_hash,
+                stats,
+            )?;
         }
 
         if matches!(
@@ -300,7 +341,10 @@ pub fn insert_structured_chunks_for_pdf(
                 &chunk.heading,
                 &regexes.requirement_split_regex,
                 &regexes.requirement_keyword_regex,
-            );
+            )
+            .iter()
+            .map(|atom| classify_requirement_atom(atom, &chunk.reference, regexes))
+            .collect::<Vec<RequirementAtomDraft>>();
             if !requirement_atoms.is_empty() {
                 insert_requirement_atom_nodes(
                     node_statement,
@@ -3,7 +3,7 @@ use super::*;
 pub fn derive_ref_path(reference: &str, chunk_type: ChunkType) -> String {
     match chunk_type {
         ChunkType::Clause => reference.split('.').collect::<Vec<&str>>().join(" > "),
-        ChunkType::Table | ChunkType::Annex => reference.to_string(),
+        ChunkType::Table | ChunkType::Figure | ChunkType::Annex => reference.to_string(),
     }
 }
 
@@ -0,0 +1,120 @@
+use super::*;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct RequirementReference {
+    pub kind: String,
+    pub target: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RequirementAtomDraft {
+    pub text: String,
+    pub modality: Option<&'static str>,
+    pub actor: Option<String>,
+    pub conditions: Vec<String>,
+    pub references: Vec<RequirementReference>,
+}
+
+const ACTOR_LEADING_WORDS: [&str; 6] = ["the ", "a ", "an ", "each ", "every ", "all "];
+
+pub fn classify_requirement_atom(
+    text: &str,
+    own_reference: &str,
+    regexes: &IngestRegexes,
+) -> RequirementAtomDraft {
+    // "... may ... shall ..." is a shall requirement: keep the strongest modal, first on ties.
+    let mut strongest: Option<(usize, &'static str, usize)> = None;
+    for captures in regexes.requirement_modality_regex.captures_iter(text) {
+        let negated = captures.name("negation").is_some();
+        let (strength, modality) = match (captures["modal"].to_lowercase().as_str(), negated) {
+            ("shall", true) => (3, "shall_not"),
+            ("shall", false) => (3, "shall"),
+            ("should", true) => (2, "should_not"),
+            ("should", false) => (2, "should"),
+            _ => (1, "may"),
+        };
+        if strongest.is_none_or(|(current, _, _)| strength > current) {
+            strongest = Some((strength, modality, captures.get(0).map_or(0, |m| m.start())));
+        }
+    }
+    let modality = strongest.map(|(_, modality, _)| modality);
+    let actor = strongest.and_then(|(_, _, start)| extract_requirement_actor(&text[..start]));
+
+    let mut conditions = Vec::<String>::new();
+    for found in regexes.requirement_condition_regex.find_iter(text) {
+        let condition = condense_requirement_text(found.as_str());
+        if !condition.is_empty() && !conditions.contains(&condition) {
+            conditions.push(condition);
+        }
+    }
+
+    RequirementAtomDraft {
+        text: text.to_string(),
+        modality,
+        actor,
+        conditions,
+        references: extract_requirement_references(text, own_reference, regexes),
+    }
+}
+
+fn extract_requirement_actor(prefix: &str) -> Option<String> {
+    let clause = prefix.rsplit(',').next().unwrap_or(prefix);
+    let mut actor = condense_requirement_text(clause);
+    loop {
+        let lowered = actor.to_lowercase();
+        let Some(word) = ACTOR_LEADING_WORDS
+            .iter()
+            .find(|word| lowered.starts_with(**word))
+        else {
+            break;
+        };
+        actor = actor[word.len()..].trim_start().to_string();
+    }
+
+    (!actor.is_empty()).then_some(actor)
+}
+
+fn extract_requirement_references(
+    text: &str,
+    own_reference: &str,
+    regexes: &IngestRegexes,
+) -> Vec<RequirementReference> {
+    let mut references = extract_cross_references(text, "", own_reference, regexes)
+        .into_iter()
+        .map(|draft| RequirementReference {
+            kind: draft.reference_kind.to_string(),
+            target: match draft.target_part {
+                Some(part) if draft.target_ref.is_empty() => format!("ISO 26262-{part}"),
+                Some(part) => format!("ISO 26262-{part} {}", draft.target_ref),
+                None => draft.target_ref,
+            },
+        })
+        .collect::<Vec<RequirementReference>>();
+
+    for captures in regexes.requirement_work_product_regex.captures_iter(text) {
+        let Some(work_product) = captures.name("work_product") else {
+            continue;
+        };
+        let target = condense_requirement_text(work_product.as_str()).to_lowercase();
+        let duplicate = references
+            .iter()
+            .any(|reference| reference.kind == "work_product" && reference.target == target);
+        if !duplicate {
+            references.push(RequirementReference {
+                kind: "work_product".to_string(),
+                target,
+            });
+        }
+    }
+
+    references
+}
+
+fn condense_requirement_text(value: &str) -> String {
+    value
+        .split_whitespace()
+        .collect::<Vec<&str>>()
+        .join(" ")
+        .trim_matches(|ch: char| ch == ',' || ch == ':')
+        .to_string()
+}
@@ -1,6 +1,10 @@
 use super::*;
 
 pub fn run(args: IngestArgs) -> Result<()> {
+    execute_ingest(&args).map(|_| ())
+}
+
+pub fn execute_ingest(args: &IngestArgs) -> Result<IngestRunManifest> {
     let started_ts = Utc::now();
     let started_at = now_utc_string();
     let run_id = format!("run-{}", utc_compact_string(started_ts));
@@ -23,6 +27,10 @@ pub fn run(args: IngestArgs) -> Result<()> {
         "ingest_page_provenance_{}.json",
         utc_compact_string(started_ts)
     ));
+    let cross_reference_diagnostics_path = manifest_dir.join(format!(
+        "cross_reference_diagnostics_{}.json",
+        utc_compact_string(started_ts)
+    ));
     let db_path = args
         .db_path
         .clone()
@@ -37,6 +45,7 @@ pub fn run(args: IngestArgs) -> Result<()> {
     )?;
 
     let tool_versions = collect_tool_versions()?;
+    let fingerprint = build_ingest_fingerprint(args, &tool_versions)?;
 
     let mut connection = Connection::open(&db_path)
         .with_context(|| format!("failed to open {}", db_path.display()))?;
@@ -54,9 +63,14 @@ pub fn run(args: IngestArgs) -> Result<()> {
         args.max_pages_per_doc,
         args.seed_page_chunks,
         &args.target_parts,
+        &args.target_years,
+        args.extract_backend,
         args.ocr_mode,
         &args.ocr_lang,
         args.ocr_min_text_chars,
+        &fingerprint,
+        args.force,
+        resolve_ingest_jobs(args.jobs),
     )?;
 
     sync_fts_index(&connection)?;
@@ -73,6 +87,18 @@ pub fn run(args: IngestArgs) -> Result<()> {
     };
     write_json_pretty(&page_provenance_path, &page_provenance_manifest)?;
 
+    let cross_reference_manifest = CrossReferenceDiagnosticsManifest {
+        manifest_version: 1,
+        run_id: run_id.clone(),
+        generated_at: updated_at.clone(),
+        references_total: chunk_stats.node_references_resolved
+            + chunk_stats.node_references_unresolved,
+        resolved_count: chunk_stats.node_references_resolved,
+        unresolved_count: chunk_stats.node_references_unresolved,
+        entries: chunk_stats.cross_reference_diagnostics.clone(),
+    };
+    write_json_pretty(&cross_reference_diagnostics_path, &cross_reference_manifest)?;
+
     let manifest = IngestRunManifest {
         manifest_version: 1,
         run_id: run_id.clone(),
@@ -84,7 +110,7 @@ pub fn run(args: IngestArgs) -> Result<()> {
         current_step: "R05-COMPLETE".to_string(),
         failed_step: None,
         failure_reason: None,
-        command: render_ingest_command(&args),
+        command: render_ingest_command(args),
         tool_versions,
         paths: IngestPaths {
             cache_root: cache_root.display().to_string(),
@@ -92,11 +118,16 @@ pub fn run(args: IngestArgs) -> Result<()> {
             inventory_manifest_path: inventory_manifest_path.display().to_string(),
             db_path: db_path.display().to_string(),
             page_provenance_path: page_provenance_path.display().to_string(),
+            cross_reference_diagnostics_path: cross_reference_diagnostics_path
+                .display()
+                .to_string(),
         },
         processed_parts: chunk_stats.processed_parts.clone(),
+        processed_editions: chunk_stats.processed_editions.clone(),
         counts: IngestCounts {
             pdf_count: inventory.pdf_count,
             processed_pdf_count: chunk_stats.processed_pdf_count,
+            skipped_unchanged_pdf_count: chunk_stats.skipped_unchanged_pdf_count,
             text_layer_page_count: chunk_stats.text_layer_page_count,
             ocr_fallback_page_count: chunk_stats.ocr_fallback_page_count,
             empty_page_count: chunk_stats.empty_page_count,
@@ -110,6 +141,7 @@ pub fn run(args: IngestArgs) -> Result<()> {
             structured_chunks_inserted: chunk_stats.structured_chunks_inserted,
             clause_chunks_inserted: chunk_stats.clause_chunks_inserted,
             table_chunks_inserted: chunk_stats.table_chunks_inserted,
+            figure_chunks_inserted: chunk_stats.figure_chunks_inserted,
             annex_chunks_inserted: chunk_stats.annex_chunks_inserted,
             page_chunks_inserted: chunk_stats.page_chunks_inserted,
             clause_nodes_inserted: chunk_stats.clause_nodes_inserted,
@@ -118,6 +150,9 @@ pub fn run(args: IngestArgs) -> Result<()> {
             table_nodes_inserted: chunk_stats.table_nodes_inserted,
             table_row_nodes_inserted: chunk_stats.table_row_nodes_inserted,
             table_cell_nodes_inserted: chunk_stats.table_cell_nodes_inserted,
+            table_footnote_nodes_inserted: chunk_stats.table_footnote_nodes_inserted,
+            table_footnote_links_inserted: chunk_stats.table_footnote_links_inserted,
+            figure_nodes_inserted: chunk_stats.figure_nodes_inserted,
             list_nodes_inserted: chunk_stats.list_nodes_inserted,
             list_item_nodes_inserted: chunk_stats.list_item_nodes_inserted,
             note_nodes_inserted: chunk_stats.note_nodes_inserted,
@@ -133,6 +168,23 @@ pub fn run(args: IngestArgs) -> Result<()> {
             table_rows_with_descriptions_count: chunk_stats.table_rows_with_descriptions_count,
             table_marker_expected_count: chunk_stats.table_marker_expected_count,
             table_marker_observed_count: chunk_stats.table_marker_observed_count,
+            table_continuations_merged_count: chunk_stats.table_continuations_merged_count,
+            table_repeated_header_rows_dropped_count: chunk_stats
+                .table_repeated_header_rows_dropped_count,
+            asil_recommendations_inserted: chunk_stats.asil_recommendations_inserted,
+            asil_matrix_rows_incomplete_count: chunk_stats.asil_matrix_rows_incomplete_count,
+            node_references_inserted: chunk_stats.node_references_inserted,
+            node_references_resolved: chunk_stats.node_references_resolved,
+            node_references_unresolved: chunk_stats.node_references_unresolved,
+            term_nodes_inserted: chunk_stats.term_nodes_inserted,
+            term_occurrences_linked: chunk_stats.term_occurrences_linked,
+            work_products_inserted: chunk_stats.work_products_inserted,
+            work_product_sources_inserted: chunk_stats.work_product_sources_inserted,
+            work_product_sources_unresolved: chunk_stats.work_product_sources_unresolved,
+            node_regions_inserted: chunk_stats.node_regions_inserted,
+            external_references_inserted: chunk_stats.external_references_inserted,
+            external_reference_citations_inserted: chunk_stats
+                .external_reference_citations_inserted,
             ocr_page_count: chunk_stats.ocr_page_count,
         },
         source_hashes: inventory.pdfs,
@@ -149,7 +201,7 @@ pub fn run(args: IngestArgs) -> Result<()> {
     info!(path = %ingest_manifest_path.display(), "wrote ingest run manifest");
     info!(docs = docs_total, chunks = chunks_total, "ingest completed");
 
-    Ok(())
+    Ok(manifest)
 }
 
 pub fn load_or_refresh_inventory(
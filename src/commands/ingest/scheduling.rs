@@ -0,0 +1,219 @@
+use super::*;
+
+#[allow(clippy::too_many_arguments)]
+pub fn insert_chunks(
+    connection: &mut Connection,
+    cache_root: &Path,
+    pdfs: &[PdfEntry],
+    parser: &StructuredChunkParser,
+    max_pages_per_doc: Option<usize>,
+    seed_page_chunks: bool,
+    target_parts: &[u32],
+    target_years: &[u32],
+    extract_backend: ExtractBackend,
+    ocr_mode: OcrMode,
+    ocr_lang: &str,
+    ocr_min_text_chars: usize,
+    fingerprint: &IngestFingerprint,
+    force: bool,
+    jobs: usize,
+) -> Result<ChunkInsertStats> {
+    let regexes = IngestRegexes::build()?;
+
+    insert_prepared_chunks(
+        connection,
+        pdfs,
+        &regexes,
+        seed_page_chunks,
+        target_parts,
+        target_years,
+        fingerprint,
+        force,
+        jobs,
+        |pdf, ocr_jobs| {
+            prepare_document(
+                &cache_root.join(&pdf.filename),
+                &doc_id_for(pdf),
+                pdf.part,
+                parser,
+                &regexes,
+                max_pages_per_doc,
+                extract_backend,
+                ocr_mode,
+                ocr_lang,
+                ocr_min_text_chars,
+                ocr_jobs,
+            )
+        },
+    )
+}
+
+pub fn select_pending_pdfs<'a>(
+    connection: &Connection,
+    pdfs: &'a [PdfEntry],
+    target_parts: &[u32],
+    target_years: &[u32],
+    fingerprint: &IngestFingerprint,
+    force: bool,
+    stats: &mut ChunkInsertStats,
+) -> Result<Vec<&'a PdfEntry>> {
+    let target_set: HashSet<u32> = target_parts.iter().copied().collect();
+    let target_year_set: HashSet<u32> = target_years.iter().copied().collect();
+    let mut pending_pdfs = Vec::<&PdfEntry>::new();
+
+    for pdf in pdfs {
+        if !target_set.is_empty() && !target_set.contains(&pdf.part) {
+            continue;
+        }
+        if !target_year_set.is_empty() && !target_year_set.contains(&pdf.year) {
+            continue;
+        }
+
+        let doc_id = doc_id_for(pdf);
+        if !force && ingest_ledger_is_current(connection, &doc_id, &pdf.sha256, fingerprint)? {
+            info!(doc_id = %doc_id, "skipping unchanged document");
+            stats.skipped_unchanged_pdf_count += 1;
+            continue;
+        }
+        pending_pdfs.push(pdf);
+    }
+
+    Ok(pending_pdfs)
+}
+
+pub fn split_worker_jobs(jobs: usize, pending_count: usize) -> (usize, usize) {
+    let workers = jobs.clamp(1, pending_count.max(1));
+    let ocr_jobs = (jobs / workers).max(1);
+    (workers, ocr_jobs)
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn prepare_document(
+    pdf_path: &Path,
+    doc_id: &str,
+    part: u32,
+    parser: &StructuredChunkParser,
+    regexes: &IngestRegexes,
+    max_pages_per_doc: Option<usize>,
+    extract_backend: ExtractBackend,
+    ocr_mode: OcrMode,
+    ocr_lang: &str,
+    ocr_min_text_chars: usize,
+    ocr_jobs: usize,
+) -> Result<PreparedDocument> {
+    let mut warnings = Vec::<String>::new();
+    if !pdf_path.exists() {
+        warnings.push(format!("missing source PDF: {}", pdf_path.display()));
+        return Ok(PreparedDocument {
+            warnings,
+            ..PreparedDocument::default()
+        });
+    }
+
+    let Some(page_extraction) = extract_pages_for_pdf(
+        pdf_path,
+        doc_id,
+        max_pages_per_doc,
+        extract_backend,
+        ocr_mode,
+        ocr_lang,
+        ocr_min_text_chars,
+        ocr_jobs,
+        &mut warnings,
+    )?
+    else {
+        return Ok(PreparedDocument {
+            warnings,
+            ..PreparedDocument::default()
+        });
+    };
+    let section_headings = extract_section_headings_for_pdf(pdf_path, &mut warnings);
+
+    let mut prepared =
+        prepare_document_from_pages(part, page_extraction, section_headings, parser, regexes);
+    prepared.warnings = warnings;
+    Ok(prepared)
+}
+
+pub fn prepare_document_from_pages(
+    part: u32,
+    page_extraction: ExtractedPages,
+    section_headings: Vec<SectionHeadingDraft>,
+    parser: &StructuredChunkParser,
+    regexes: &IngestRegexes,
+) -> PreparedDocument {
+    let structured_chunks =
+        split_long_structured_chunks(parser.parse_pages(&page_extraction.pages));
+    let work_products = extract_work_products(&structured_chunks, regexes);
+    let terms = if part == GLOSSARY_PART {
+        parse_glossary_terms(&page_extraction.pages, parser, regexes)
+    } else {
+        Vec::new()
+    };
+    let external_references = extract_external_references(&page_extraction.pages, parser, regexes);
+
+    PreparedDocument {
+        page_extraction: Some(page_extraction),
+        section_headings,
+        structured_chunks,
+        work_products,
+        terms,
+        external_references,
+        warnings: Vec::new(),
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn extract_pages_for_pdf(
+    pdf_path: &Path,
+    doc_id: &str,
+    max_pages_per_doc: Option<usize>,
+    extract_backend: ExtractBackend,
+    ocr_mode: OcrMode,
+    ocr_lang: &str,
+    ocr_min_text_chars: usize,
+    ocr_jobs: usize,
+    warnings: &mut Vec<String>,
+) -> Result<Option<ExtractedPages>> {
+    match extract_pages_with_backend(
+        pdf_path,
+        doc_id,
+        max_pages_per_doc,
+        extract_backend,
+        ocr_mode,
+        ocr_lang,
+        ocr_min_text_chars,
+        ocr_jobs,
+    ) {
+        Ok(extraction) => Ok(Some(extraction)),
+        Err(err) => {
+            if matches!(ocr_mode, OcrMode::Force) {
+                return Err(err)
+                    .with_context(|| format!("failed to extract text for {}", pdf_path.display()));
+            }
+
+            let warning = format!("failed to extract text for {}: {err}", pdf_path.display());
+            warn!(warning = %warning, "pdf extraction warning");
+            warnings.push(warning);
+            Ok(None)
+        }
+    }
+}
+
+pub fn extract_section_headings_for_pdf(
+    pdf_path: &Path,
+    warnings: &mut Vec<String>,
+) -> Vec<SectionHeadingDraft> {
+    match extract_section_headings_with_pdftohtml(pdf_path) {
+        Ok(headings) => headings,
+        Err(err) => {
+            let warning = format!(
+                "failed to extract outline headings for {}: {err}",
+                pdf_path.display()
+            );
+            warn!(warning = %warning, "pdf outline extraction warning");
+            warnings.push(warning);
+            Vec::new()
+        }
+    }
+}
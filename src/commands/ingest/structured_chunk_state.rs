@@ -0,0 +1,151 @@
+use super::*;
+
+const FIGURE_LABEL_LIMIT: usize = 40;
+const FIGURE_LABEL_MAX_WORDS: usize = 8;
+
+#[derive(Debug)]
+pub struct ActiveChunk {
+    pub chunk_type: ChunkType,
+    pub reference: String,
+    pub heading: String,
+    pub page_start: i64,
+    pub page_end: i64,
+    pub body_lines: Vec<String>,
+    pub page_body_start: usize,
+    pub figures: Vec<StructuredChunkDraft>,
+    pub header_rows: Vec<String>,
+    pub header_skip_index: Option<usize>,
+    pub merged_continuations: usize,
+    pub repeated_header_rows_dropped: usize,
+}
+
+impl ActiveChunk {
+    pub fn new(
+        chunk_type: ChunkType,
+        reference: String,
+        heading: String,
+        page_number: i64,
+    ) -> Self {
+        Self {
+            chunk_type,
+            reference,
+            heading,
+            page_start: page_number,
+            page_end: page_number,
+            body_lines: Vec::new(),
+            page_body_start: 0,
+            figures: Vec::new(),
+            header_rows: Vec::new(),
+            header_skip_index: None,
+            merged_continuations: 0,
+            repeated_header_rows_dropped: 0,
+        }
+    }
+
+    pub fn finalize(self, chunks: &mut Vec<StructuredChunkDraft>) {
+        let body = self.body_lines.join("\n").trim().to_string();
+        let text = if body.is_empty() {
+            self.heading.clone()
+        } else {
+            format!("{}\n\n{}", self.heading, body)
+        };
+        let ref_path = derive_ref_path(&self.reference, self.chunk_type);
+
+        chunks.push(StructuredChunkDraft {
+            chunk_type: self.chunk_type,
+            reference: self.reference,
+            ref_path,
+            heading: self.heading,
+            text,
+            page_start: self.page_start,
+            page_end: self.page_end,
+            merged_continuations: self.merged_continuations,
+            repeated_header_rows_dropped: self.repeated_header_rows_dropped,
+        });
+        chunks.extend(self.figures);
+    }
+
+    pub fn take_figure_labels(&mut self, page_number: i64) -> Vec<String> {
+        if !matches!(self.chunk_type, ChunkType::Clause | ChunkType::Annex)
+            || self.page_end != page_number
+        {
+            return Vec::new();
+        }
+
+        let floor = self
+            .page_body_start
+            .max(self.body_lines.len().saturating_sub(FIGURE_LABEL_LIMIT));
+        let mut start = self.body_lines.len();
+        while start > floor && looks_like_figure_label(&self.body_lines[start - 1]) {
+            start -= 1;
+        }
+        // Labels sit between the last finished paragraph (or the page top) and the caption;
+        // a run after a wrapped line or a list introduction is still clause text.
+        let after_paragraph =
+            start == self.page_body_start || self.body_lines[start - 1].trim_end().ends_with('.');
+        if !after_paragraph {
+            return Vec::new();
+        }
+        self.body_lines.split_off(start)
+    }
+
+    pub fn merge_table_continuation(&mut self, page_number: i64, cell_split_regex: &Regex) {
+        if self.merged_continuations == 0 {
+            let rows = self
+                .body_lines
+                .iter()
+                .map(|line| split_table_cells(line, cell_split_regex))
+                .collect::<Vec<Vec<String>>>();
+            self.header_rows = self
+                .body_lines
+                .iter()
+                .take(infer_table_header_rows(&rows))
+                .map(|row| condense_line_whitespace(row))
+                .collect();
+        }
+        self.merged_continuations += 1;
+        self.header_skip_index = Some(0);
+        self.page_end = page_number;
+    }
+
+    pub fn push_body_line(&mut self, line: &str, page_number: i64) {
+        if let Some(index) = self.header_skip_index {
+            if self
+                .header_rows
+                .get(index)
+                .is_some_and(|row| *row == condense_line_whitespace(line))
+            {
+                self.header_skip_index = Some(index + 1);
+                self.repeated_header_rows_dropped += 1;
+                return;
+            }
+            self.header_skip_index = None;
+        }
+        if self.page_end != page_number {
+            self.page_body_start = self.body_lines.len();
+        }
+        self.page_end = page_number;
+        self.body_lines.push(line.to_string());
+    }
+}
+
+fn looks_like_figure_label(line: &str) -> bool {
+    let trimmed = line.trim();
+    !trimmed.is_empty()
+        && trimmed.split_whitespace().count() <= FIGURE_LABEL_MAX_WORDS
+        && !trimmed.ends_with(['.', ';', ':', ','])
+        && !starts_with_list_marker(trimmed)
+}
+
+fn starts_with_list_marker(line: &str) -> bool {
+    let Some(first) = line.split_whitespace().next() else {
+        return false;
+    };
+    matches!(first, "-" | "—" | "–" | "•")
+        || first.strip_suffix([')', '.']).is_some_and(|marker| {
+            (1..=2).contains(&marker.len())
+                && marker
+                    .chars()
+                    .all(|ch| ch.is_ascii_lowercase() || ch.is_ascii_digit())
+        })
+}
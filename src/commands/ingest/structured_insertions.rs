@@ -48,6 +48,7 @@ pub fn insert_list_nodes(
         None,
         None,
         None,
+        None,
     )?;
 
     *node_order_index += 1;
@@ -129,6 +130,7 @@ pub fn insert_list_nodes(
             None,
             None,
             None,
+            None,
         )?;
 
         last_item_node_id_by_depth.insert(effective_depth, list_item_node_id.clone());
@@ -189,6 +191,7 @@ pub fn insert_note_nodes(
         None,
         None,
         None,
+        None,
     )?;
 
     *node_order_index += 1;
@@ -238,6 +241,7 @@ pub fn insert_note_nodes(
             None,
             None,
             None,
+            None,
         )?;
 
         *node_order_index += 1;
@@ -272,7 +276,7 @@ pub fn insert_requirement_atom_nodes(
     parent_node_id: &str,
     parent_path: &str,
     reference: &str,
-    atoms: &[String],
+    atoms: &[RequirementAtomDraft],
     page_start: i64,
     page_end: i64,
     source_hash: &str,
@@ -297,7 +301,7 @@ pub fn insert_requirement_atom_nodes(
             *node_order_index,
             Some(page_start),
             Some(page_end),
-            Some(atom),
+            Some(&atom.text),
             source_hash,
             &atom_path,
             None,
@@ -314,6 +318,7 @@ pub fn insert_requirement_atom_nodes(
             None,
             None,
             None,
+            Some(atom),
         )?;
 
         *node_order_index += 1;
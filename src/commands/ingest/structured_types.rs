@@ -3,7 +3,9 @@ use super::*;
 #[derive(Debug, Default)]
 pub struct ChunkInsertStats {
     pub processed_pdf_count: usize,
+    pub skipped_unchanged_pdf_count: usize,
     pub processed_parts: Vec<u32>,
+    pub processed_editions: Vec<ProcessedEdition>,
     pub ocr_page_count: usize,
     pub text_layer_page_count: usize,
     pub ocr_fallback_page_count: usize,
@@ -14,6 +16,7 @@ pub struct ChunkInsertStats {
     pub structured_chunks_inserted: usize,
     pub clause_chunks_inserted: usize,
     pub table_chunks_inserted: usize,
+    pub figure_chunks_inserted: usize,
     pub annex_chunks_inserted: usize,
     pub page_chunks_inserted: usize,
     pub nodes_total: i64,
@@ -23,6 +26,9 @@ pub struct ChunkInsertStats {
     pub table_nodes_inserted: usize,
     pub table_row_nodes_inserted: usize,
     pub table_cell_nodes_inserted: usize,
+    pub table_footnote_nodes_inserted: usize,
+    pub table_footnote_links_inserted: usize,
+    pub figure_nodes_inserted: usize,
     pub list_nodes_inserted: usize,
     pub list_item_nodes_inserted: usize,
     pub note_nodes_inserted: usize,
@@ -38,14 +44,42 @@ pub struct ChunkInsertStats {
     pub table_rows_with_descriptions_count: usize,
     pub table_marker_expected_count: usize,
     pub table_marker_observed_count: usize,
+    pub table_continuations_merged_count: usize,
+    pub table_repeated_header_rows_dropped_count: usize,
+    pub asil_recommendations_inserted: usize,
+    pub asil_matrix_rows_incomplete_count: usize,
+    pub node_references_inserted: usize,
+    pub node_references_resolved: usize,
+    pub node_references_unresolved: usize,
+    pub cross_reference_diagnostics: Vec<CrossReferenceDiagnostic>,
+    pub term_nodes_inserted: usize,
+    pub term_occurrences_linked: usize,
+    pub work_products_inserted: usize,
+    pub work_product_sources_inserted: usize,
+    pub work_product_sources_unresolved: usize,
+    pub external_references_inserted: usize,
+    pub external_reference_citations_inserted: usize,
+    pub node_regions_inserted: usize,
     pub page_provenance: Vec<PageExtractionProvenance>,
     pub warnings: Vec<String>,
 }
 
+#[derive(Debug, Default)]
+pub struct PreparedDocument {
+    pub page_extraction: Option<ExtractedPages>,
+    pub section_headings: Vec<SectionHeadingDraft>,
+    pub structured_chunks: Vec<StructuredChunkDraft>,
+    pub work_products: Vec<WorkProductDraft>,
+    pub terms: Vec<TermDraft>,
+    pub external_references: Vec<ExternalReferenceDraft>,
+    pub warnings: Vec<String>,
+}
+
 #[derive(Debug, Default)]
 pub struct ExtractedPages {
     pub pages: Vec<String>,
     pub page_printed_labels: Vec<Option<String>>,
+    pub page_geometry: Vec<Option<PageGeometry>>,
     pub ocr_page_count: usize,
     pub text_layer_page_count: usize,
     pub ocr_fallback_page_count: usize,
@@ -86,12 +120,15 @@ pub struct StructuredChunkDraft {
     pub text: String,
     pub page_start: i64,
     pub page_end: i64,
+    pub merged_continuations: usize,
+    pub repeated_header_rows_dropped: usize,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ChunkType {
     Clause,
     Table,
+    Figure,
     Annex,
 }
 
@@ -100,6 +137,7 @@ impl ChunkType {
         match self {
             ChunkType::Clause => "clause",
             ChunkType::Table => "table",
+            ChunkType::Figure => "figure",
             ChunkType::Annex => "annex",
         }
     }
@@ -116,11 +154,14 @@ pub enum NodeType {
     Table,
     TableRow,
     TableCell,
+    TableFootnote,
+    Figure,
     List,
     ListItem,
     Note,
     NoteItem,
     RequirementAtom,
+    Term,
     Page,
 }
 
@@ -136,11 +177,14 @@ impl NodeType {
             NodeType::Table => "table",
             NodeType::TableRow => "table_row",
             NodeType::TableCell => "table_cell",
+            NodeType::TableFootnote => "table_footnote",
+            NodeType::Figure => "figure",
             NodeType::List => "list",
             NodeType::ListItem => "list_item",
             NodeType::Note => "note",
             NodeType::NoteItem => "note_item",
             NodeType::RequirementAtom => "requirement_atom",
+            NodeType::Term => "term",
             NodeType::Page => "page",
         }
     }
@@ -153,6 +197,14 @@ pub struct ParsedTableRows {
     pub csv: Option<String>,
     pub used_fallback: bool,
     pub quality: TableQualityCounters,
+    pub footnotes: Vec<TableFootnoteDraft>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TableFootnoteDraft {
+    pub marker: String,
+    pub text: String,
+    pub linked_cells: Vec<(usize, usize)>,
 }
 
 #[derive(Debug, Default)]
@@ -192,8 +244,11 @@ pub struct SectionHeadingDraft {
 pub struct StructuredChunkParser {
     pub clause_heading: Regex,
     pub table_heading: Regex,
+    pub figure_heading: Regex,
     pub annex_heading: Regex,
     pub toc_line: Regex,
+    pub reference_section: Regex,
+    pub table_cell_split: Regex,
 }
 
 impl StructuredChunkParser {
@@ -203,44 +258,22 @@ impl StructuredChunkParser {
                 .context("failed to compile clause heading regex")?,
             table_heading: Regex::new(r"^\s*(Table\s+\d+)\s*[-:–—]?\s*(.*)$")
                 .context("failed to compile table heading regex")?,
+            figure_heading: Regex::new(r"^\s*(Figure\s+\d+)\s*[-:–—]\s*(.+)$")
+                .context("failed to compile figure heading regex")?,
             annex_heading: Regex::new(r"^\s*(Annex\s+[A-Z])(?:\s*\([^)]*\))?\s*[-:–—]?\s*(.*)$")
                 .context("failed to compile annex heading regex")?,
             toc_line: Regex::new(r"\.{3,}\s*\d+\s*$")
                 .context("failed to compile table-of-contents line regex")?,
+            reference_section: Regex::new(
+                r"(?i)^(?:(?P<normative>2\s+normative\s+references)|(?P<bibliography>bibliography))\s*$",
+            )
+            .context("failed to compile reference section regex")?,
+            table_cell_split: Regex::new(r"\t+|\s{2,}")
+                .context("failed to compile table cell split regex")?,
         })
     }
 
     pub fn parse_pages(&self, pages: &[String]) -> Vec<StructuredChunkDraft> {
-        #[derive(Debug)]
-        struct ActiveChunk {
-            chunk_type: ChunkType,
-            reference: String,
-            heading: String,
-            page_start: i64,
-            page_end: i64,
-            body_lines: Vec<String>,
-        }
-
-        fn finalize(active: ActiveChunk) -> StructuredChunkDraft {
-            let body = active.body_lines.join("\n").trim().to_string();
-            let text = if body.is_empty() {
-                active.heading.clone()
-            } else {
-                format!("{}\n\n{}", active.heading, body)
-            };
-            let ref_path = derive_ref_path(&active.reference, active.chunk_type);
-
-            StructuredChunkDraft {
-                chunk_type: active.chunk_type,
-                reference: active.reference,
-                ref_path,
-                heading: active.heading,
-                text,
-                page_start: active.page_start,
-                page_end: active.page_end,
-            }
-        }
-
         let mut chunks = Vec::new();
         let mut current: Option<ActiveChunk> = None;
 
@@ -252,36 +285,89 @@ impl StructuredChunkParser {
                     continue;
                 }
 
+                if self.detect_reference_section(line) == Some(ExternalReferenceKind::Bibliography)
+                {
+                    if let Some(active) = current.take() {
+                        active.finalize(&mut chunks);
+                    }
+                    continue;
+                }
+
                 if let Some((chunk_type, reference, heading)) = self.detect_heading(line) {
+                    if chunk_type == ChunkType::Figure {
+                        let labels = current
+                            .as_mut()
+                            .map(|active| active.take_figure_labels(page_number))
+                            .unwrap_or_default();
+                        let text = if labels.is_empty() {
+                            heading.clone()
+                        } else {
+                            format!("{}\n\n{}", heading, labels.join("\n"))
+                        };
+                        let figure = StructuredChunkDraft {
+                            chunk_type,
+                            ref_path: derive_ref_path(&reference, chunk_type),
+                            reference,
+                            heading,
+                            text,
+                            page_start: page_number,
+                            page_end: page_number,
+                            merged_continuations: 0,
+                            repeated_header_rows_dropped: 0,
+                        };
+                        match current.as_mut() {
+                            Some(active) => active.figures.push(figure),
+                            None => chunks.push(figure),
+                        }
+                        continue;
+                    }
+
+                    if let Some(active) = current.as_mut()
+                        && chunk_type == ChunkType::Table
+                        && active.chunk_type == ChunkType::Table
+                        && active.reference == reference
+                        && (active.page_end != page_number
+                            || heading.to_ascii_lowercase().contains("(continued)"))
+                    {
+                        active.merge_table_continuation(page_number, &self.table_cell_split);
+                        continue;
+                    }
+
                     if let Some(active) = current.take() {
-                        chunks.push(finalize(active));
+                        active.finalize(&mut chunks);
                     }
 
-                    current = Some(ActiveChunk {
+                    current = Some(ActiveChunk::new(
                         chunk_type,
                         reference,
                         heading,
-                        page_start: page_number,
-                        page_end: page_number,
-                        body_lines: Vec::new(),
-                    });
+                        page_number,
+                    ));
                     continue;
                 }
 
                 if let Some(active) = current.as_mut() {
-                    active.page_end = page_number;
-                    active.body_lines.push(line.to_string());
+                    active.push_body_line(line, page_number);
                 }
             }
         }
 
         if let Some(active) = current.take() {
-            chunks.push(finalize(active));
+            active.finalize(&mut chunks);
         }
 
         chunks
     }
 
+    pub fn detect_reference_section(&self, line: &str) -> Option<ExternalReferenceKind> {
+        let captures = self.reference_section.captures(line)?;
+        if captures.name("normative").is_some() {
+            Some(ExternalReferenceKind::Normative)
+        } else {
+            Some(ExternalReferenceKind::Bibliography)
+        }
+    }
+
     pub fn detect_heading(&self, line: &str) -> Option<(ChunkType, String, String)> {
         if self.toc_line.is_match(line) {
             return None;
@@ -289,9 +375,16 @@ impl StructuredChunkParser {
 
         if let Some(captures) = self.table_heading.captures(line) {
             let reference = captures.get(1).map(|m| m.as_str().trim().to_string())?;
+            let reference = condense_line_whitespace(&reference);
             return Some((ChunkType::Table, reference, line.to_string()));
         }
 
+        if let Some(captures) = self.figure_heading.captures(line) {
+            let reference = captures.get(1).map(|m| m.as_str().trim().to_string())?;
+            let reference = condense_line_whitespace(&reference);
+            return Some((ChunkType::Figure, reference, line.to_string()));
+        }
+
         if let Some(captures) = self.annex_heading.captures(line) {
             let reference = captures.get(1).map(|m| m.as_str().trim().to_string())?;
             return Some((ChunkType::Annex, reference, line.to_string()));
@@ -311,6 +404,10 @@ impl StructuredChunkParser {
     }
 }
 
+pub fn condense_line_whitespace(line: &str) -> String {
+    line.split_whitespace().collect::<Vec<&str>>().join(" ")
+}
+
 pub fn split_long_structured_chunks(
     chunks: Vec<StructuredChunkDraft>,
 ) -> Vec<StructuredChunkDraft> {
@@ -343,6 +440,8 @@ pub fn split_long_structured_chunks(
                 text,
                 page_start: chunk.page_start,
                 page_end: chunk.page_end,
+                merged_continuations: chunk.merged_continuations,
+                repeated_header_rows_dropped: chunk.repeated_header_rows_dropped,
             });
         }
     }
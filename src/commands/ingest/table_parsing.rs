@@ -1,7 +1,7 @@
 use super::*;
 
 pub fn parse_table_rows(text: &str, heading: &str, cell_split_regex: &Regex) -> ParsedTableRows {
-    let body_lines = extract_body_lines(text, heading);
+    let (body_lines, mut footnotes) = split_table_footnotes(&extract_body_lines_preserve_blanks(text, heading));
     let mut rows = Vec::<Vec<String>>::new();
 
     for line in &body_lines {
@@ -45,15 +45,20 @@ pub fn parse_table_rows(text: &str, heading: &str, cell_split_regex: &Regex) ->
         }
     }
 
+    link_table_footnotes(&rows, &mut footnotes);
+
     let markdown = if rows.is_empty() {
         None
     } else {
-        Some(table_to_markdown(&rows))
+        Some(append_footnotes_to_markdown(
+            table_to_markdown(&rows),
+            &footnotes,
+        ))
     };
     let csv = if rows.is_empty() {
         None
     } else {
-        Some(table_to_csv(&rows))
+        Some(append_footnotes_to_csv(table_to_csv(&rows), &footnotes))
     };
     let quality = analyze_table_rows(&rows);
 
@@ -63,7 +68,165 @@ pub fn parse_table_rows(text: &str, heading: &str, cell_split_regex: &Regex) ->
         csv,
         used_fallback: !structured,
         quality,
+        footnotes,
+    }
+}
+
+pub fn split_table_footnotes<'a>(
+    body_lines: &[&'a str],
+) -> (Vec<&'a str>, Vec<TableFootnoteDraft>) {
+    let start = body_lines.iter().enumerate().find_map(|(index, line)| {
+        let (marker, _) = parse_footnote_definition(line)?;
+        let referenced = body_lines[..index]
+            .iter()
+            .any(|previous| line_references_footnote(previous, &marker));
+        (marker == "a" && referenced).then_some(index)
+    });
+    let non_blank = |lines: &[&'a str]| {
+        lines
+            .iter()
+            .map(|line| line.trim())
+            .filter(|line| !line.is_empty())
+            .collect::<Vec<&'a str>>()
+    };
+    let Some(start) = start else {
+        return (non_blank(body_lines), Vec::new());
+    };
+
+    let mut kept = non_blank(&body_lines[..start]);
+    let mut footnotes = Vec::<TableFootnoteDraft>::new();
+    let mut in_footnote = false;
+    for line in body_lines[start..].iter().map(|line| line.trim()) {
+        if line.is_empty() {
+            in_footnote = false;
+            continue;
+        }
+
+        if let Some((marker, text)) = parse_footnote_definition(line)
+            && !footnotes.iter().any(|footnote| footnote.marker == marker)
+        {
+            footnotes.push(TableFootnoteDraft {
+                marker,
+                text,
+                linked_cells: Vec::new(),
+            });
+            in_footnote = true;
+            continue;
+        }
+
+        if line.starts_with("NOTE") || is_table_row_line(line) {
+            in_footnote = false;
+        }
+        match footnotes.last_mut() {
+            Some(footnote) if in_footnote => {
+                footnote.text.push(' ');
+                footnote.text.push_str(line);
+            }
+            _ => kept.push(line),
+        }
+    }
+
+    (kept, footnotes)
+}
+
+/// Rows continued after the footnotes start with a method marker such as `1c` or carry ratings.
+pub fn is_table_row_line(line: &str) -> bool {
+    let mut tokens = line.split_whitespace();
+    tokens
+        .next()
+        .is_some_and(|first| parse_table_marker_token(first).is_some())
+        || tokens.filter(|token| is_table_rating_token(token)).count() >= 2
+}
+
+pub fn line_references_footnote(line: &str, marker: &str) -> bool {
+    line.split_whitespace().skip(1).any(|token| {
+        let token = token.trim_matches(['(', ')', '.', ':', ';', ',']);
+        token == marker
+            || token
+                .strip_suffix(marker)
+                .is_some_and(is_table_rating_token)
+    })
+}
+
+pub fn parse_footnote_definition(line: &str) -> Option<(String, String)> {
+    let trimmed = line.trim();
+    let mut chars = trimmed.chars();
+    let marker = chars.next().filter(|ch| ch.is_ascii_lowercase())?;
+    if !chars.next().is_some_and(char::is_whitespace) {
+        return None;
+    }
+
+    let text = chars.as_str().trim();
+    let starts_with_capital = text.chars().next().is_some_and(char::is_uppercase);
+    if !starts_with_capital || text.split_whitespace().count() < 2 {
+        return None;
+    }
+
+    Some((marker.to_string(), text.to_string()))
+}
+
+pub fn link_table_footnotes(rows: &[Vec<String>], footnotes: &mut [TableFootnoteDraft]) {
+    for (row_idx, row) in rows.iter().enumerate() {
+        for (col_idx, cell) in row.iter().enumerate() {
+            for marker in cell_footnote_markers(cell) {
+                if let Some(footnote) = footnotes
+                    .iter_mut()
+                    .find(|footnote| footnote.marker == marker)
+                {
+                    footnote.linked_cells.push((row_idx, col_idx));
+                }
+            }
+        }
+    }
+}
+
+pub fn cell_footnote_markers(cell: &str) -> Vec<String> {
+    let trimmed = cell.trim();
+    if is_footnote_marker_line(trimmed) {
+        return vec![
+            trimmed
+                .trim_matches(['(', ')', '.', ':', ';', ','])
+                .to_string(),
+        ];
+    }
+
+    let (_, mut markers) = split_method_footnote_markers(trimmed);
+    if markers.is_empty()
+        && let Some(last) = trimmed.split_whitespace().last()
+        && let Some((rating, marker)) = last
+            .char_indices()
+            .last()
+            .map(|(index, ch)| (&last[..index], ch))
+        && marker.is_ascii_lowercase()
+        && is_table_rating_token(rating)
+    {
+        markers.push(marker.to_string());
+    }
+    markers
+}
+
+pub fn append_footnotes_to_markdown(markdown: String, footnotes: &[TableFootnoteDraft]) -> String {
+    if footnotes.is_empty() {
+        return markdown;
+    }
+
+    let definitions = footnotes
+        .iter()
+        .map(|footnote| format!("[^{}]: {}", footnote.marker, footnote.text))
+        .collect::<Vec<String>>();
+    format!("{}\n\n{}", markdown, definitions.join("\n"))
+}
+
+pub fn append_footnotes_to_csv(csv: String, footnotes: &[TableFootnoteDraft]) -> String {
+    let mut lines = vec![csv];
+    for footnote in footnotes {
+        lines.push(format!(
+            "{},{}",
+            escape_csv_cell(&format!("footnote {}", footnote.marker)),
+            escape_csv_cell(&footnote.text)
+        ));
     }
+    lines.join("\n")
 }
 
 pub fn normalize_table_rows_for_alignment(rows: &mut Vec<Vec<String>>) {
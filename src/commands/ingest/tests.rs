@@ -174,6 +174,95 @@ fn infer_table_header_rows_detects_first_non_marker_row() {
     assert_eq!(infer_table_header_rows(&rows), 1);
 }
 
+#[test]
+fn extract_asil_recommendations_maps_ratings_to_asil_levels() {
+    let rows = vec![
+        vec![
+            "Methods".to_string(),
+            "".to_string(),
+            "ASIL A B C D".to_string(),
+        ],
+        vec![
+            "1a".to_string(),
+            "Enforcement of low complexity a b".to_string(),
+            "++ ++ ++ ++".to_string(),
+        ],
+        vec![
+            "1b".to_string(),
+            "Use of language subsets".to_string(),
+            "+".to_string(),
+            "+ o -".to_string(),
+        ],
+    ];
+
+    let extraction = extract_asil_recommendations(&rows, &[]);
+
+    assert_eq!(extraction.incomplete_rows_count, 0);
+    assert_eq!(extraction.recommendations.len(), 8);
+
+    let first = &extraction.recommendations[0];
+    assert_eq!(first.marker, "1a");
+    assert_eq!(first.method_text, "Enforcement of low complexity");
+    assert_eq!(first.footnotes, vec!["a".to_string(), "b".to_string()]);
+    assert_eq!(first.asil, "A");
+    assert_eq!(first.recommendation_level, "highly_recommended");
+
+    let subset_levels = extraction
+        .recommendations
+        .iter()
+        .filter(|recommendation| recommendation.marker == "1b")
+        .map(|recommendation| {
+            (
+                recommendation.asil.as_str(),
+                recommendation.recommendation_level.as_str(),
+            )
+        })
+        .collect::<Vec<(&str, &str)>>();
+    assert_eq!(
+        subset_levels,
+        vec![
+            ("A", "recommended"),
+            ("B", "recommended"),
+            ("C", "no_recommendation"),
+            ("D", "not_recommended"),
+        ]
+    );
+}
+
+#[test]
+fn extract_asil_recommendations_counts_rows_missing_ratings() {
+    let rows = vec![
+        vec!["Topics".to_string(), "ASIL".to_string()],
+        vec![
+            "1a".to_string(),
+            "Hierarchical structure".to_string(),
+            "++ ++".to_string(),
+        ],
+    ];
+
+    let extraction = extract_asil_recommendations(&rows, &[]);
+
+    assert!(extraction.recommendations.is_empty());
+    assert_eq!(extraction.incomplete_rows_count, 1);
+}
+
+#[test]
+fn extract_asil_recommendations_counts_rows_with_extra_ratings() {
+    let rows = vec![
+        vec!["Topics".to_string(), "ASIL".to_string()],
+        vec![
+            "1a".to_string(),
+            "Hierarchical structure".to_string(),
+            "++ ++ + + o".to_string(),
+        ],
+    ];
+
+    let extraction = extract_asil_recommendations(&rows, &[]);
+
+    assert!(extraction.recommendations.is_empty());
+    assert_eq!(extraction.incomplete_rows_count, 1);
+}
+
 #[test]
 fn parse_list_items_excludes_note_markers() {
     let list_item_regex = Regex::new(
@@ -309,8 +398,12 @@ fn render_ingest_command_includes_ocr_flags_when_enabled() {
         db_path: None,
         refresh_inventory: false,
         seed_page_chunks: false,
+        force: false,
+        jobs: None,
         target_parts: vec![6],
+        target_years: vec![2011],
         max_pages_per_doc: Some(5),
+        extract_backend: ExtractBackend::Text,
         ocr_mode: OcrMode::Auto,
         ocr_lang: "eng".to_string(),
         ocr_min_text_chars: 200,
@@ -320,6 +413,7 @@ fn render_ingest_command_includes_ocr_flags_when_enabled() {
     assert!(command.contains("--ocr-mode auto"));
     assert!(command.contains("--ocr-lang eng"));
     assert!(command.contains("--ocr-min-text-chars 200"));
+    assert!(command.contains("--target-part 6 --target-year 2011"));
 }
 
 #[test]
@@ -431,6 +525,8 @@ fn split_long_structured_chunks_preserves_reference_and_heading() {
         text: format!("5.2 Software safety\n\n{body}"),
         page_start: 10,
         page_end: 12,
+        merged_continuations: 0,
+        repeated_header_rows_dropped: 0,
     };
 
     let expanded = split_long_structured_chunks(vec![input]);
@@ -443,3 +539,1010 @@ fn split_long_structured_chunks_preserves_reference_and_heading() {
         .iter()
         .all(|chunk| chunk.page_start == 10 && chunk.page_end == 12));
 }
+
+#[test]
+fn extract_cross_references_classifies_local_and_external_targets() {
+    let regexes = IngestRegexes::build().expect("regexes should compile");
+    let text = "7.4.2 Design\nThe method shall be applied according to Table 3 and 7.4.2, see 7.4.3.\nConfidence in tools is described in ISO 26262-8:2018, Clause 11 and Annex B.";
+
+    let references = extract_cross_references(text, "7.4.2 Design", "7.4.2", &regexes);
+    let targets = references
+        .iter()
+        .map(|reference| {
+            (
+                reference.reference_kind,
+                reference.target_part,
+                reference.target_ref.as_str(),
+            )
+        })
+        .collect::<Vec<(&str, Option<u32>, &str)>>();
+
+    assert_eq!(
+        targets,
+        vec![
+            ("clause", Some(8), "11"),
+            ("table", None, "Table 3"),
+            ("clause", None, "7.4.3"),
+            ("annex", None, "Annex B"),
+        ]
+    );
+    assert_eq!(references[0].target_year, Some(2018));
+    assert_eq!(references[0].raw_text, "ISO 26262-8:2018, Clause 11");
+}
+
+#[test]
+fn resolve_cross_references_marks_missing_targets_for_diagnostics() {
+    let connection = Connection::open_in_memory().expect("in-memory DB should open");
+    ensure_schema(&connection).expect("schema should initialize");
+    connection
+        .execute_batch(
+            "
+            INSERT INTO docs(doc_id, filename, sha256, part, year) VALUES
+              ('ISO26262-6-2018', 'part6.pdf', 'sha', 6, 2018);
+            INSERT INTO nodes(node_id, doc_id, node_type, ref, order_index) VALUES
+              ('n-clause', 'ISO26262-6-2018', 'clause', '7.4.3', 1),
+              ('n-table', 'ISO26262-6-2018', 'table', 'Table 3', 2);
+            ",
+        )
+        .expect("seed rows should insert");
+
+    let mut stats = ChunkInsertStats::default();
+    {
+        let mut statement = connection
+            .prepare(
+                "
+                INSERT INTO node_references(
+                  reference_id, doc_id, source_node_id, target_doc_id, target_part,
+                  target_year, target_ref, reference_kind, raw_text
+                )
+                VALUES(?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)
+                ",
+            )
+            .expect("statement should prepare");
+        let drafts = vec![
+            CrossReferenceDraft {
+                raw_text: "Table 3".to_string(),
+                reference_kind: "table",
+                target_part: None,
+                target_year: None,
+                target_ref: "Table 3".to_string(),
+            },
+            CrossReferenceDraft {
+                raw_text: "see 9.9.9".to_string(),
+                reference_kind: "clause",
+                target_part: None,
+                target_year: None,
+                target_ref: "9.9.9".to_string(),
+            },
+            CrossReferenceDraft {
+                raw_text: "ISO 26262-8:2018, Clause 11".to_string(),
+                reference_kind: "clause",
+                target_part: Some(8),
+                target_year: Some(2018),
+                target_ref: "11".to_string(),
+            },
+        ];
+        insert_cross_references(
+            &mut statement,
+            "ISO26262-6-2018",
+            "n-clause",
+            &drafts,
+            &mut stats,
+        )
+        .expect("references should insert");
+    }
+
+    resolve_cross_references(&connection, &mut stats).expect("resolution should succeed");
+
+    assert_eq!(stats.node_references_inserted, 3);
+    assert_eq!(stats.node_references_resolved, 1);
+    assert_eq!(stats.node_references_unresolved, 2);
+    let statuses = stats
+        .cross_reference_diagnostics
+        .iter()
+        .map(|diagnostic| (diagnostic.target_ref.as_str(), diagnostic.resolution_status.as_str()))
+        .collect::<Vec<(&str, &str)>>();
+    assert_eq!(
+        statuses,
+        vec![("9.9.9", "unresolved"), ("11", "missing_document")]
+    );
+}
+
+#[test]
+fn parse_glossary_terms_extracts_term_abbreviation_definition_and_notes() {
+    let parser = StructuredChunkParser::new().expect("parser should compile");
+    let regexes = IngestRegexes::build().expect("regexes should compile");
+    let pages = vec![
+        "3 Terms and definitions ........ 1\nForeword".to_string(),
+        "3 Terms and definitions\n3.1\nallocation\nassignment of a requirement to an architectural element\nNote 1 to entry: Allocation can also apply\nto hardware.\n3.6 automotive safety integrity level\nASIL\none of four levels to specify the item's necessary\nrequirements".to_string(),
+        "3.7 availability\ncapability of a product to be in a state to perform\n4 Abbreviated terms\nASIL automotive safety integrity level".to_string(),
+    ];
+
+    let terms = parse_glossary_terms(&pages, &parser, &regexes);
+
+    assert_eq!(terms.len(), 3);
+    assert_eq!(terms[0].reference, "3.1");
+    assert_eq!(terms[0].term, "allocation");
+    assert_eq!(terms[0].abbreviation, None);
+    assert_eq!(
+        terms[0].notes,
+        vec!["Note 1 to entry: Allocation can also apply to hardware.".to_string()]
+    );
+    assert_eq!(terms[1].term, "automotive safety integrity level");
+    assert_eq!(terms[1].abbreviation.as_deref(), Some("ASIL"));
+    assert_eq!(
+        terms[1].definition,
+        "one of four levels to specify the item's necessary requirements"
+    );
+    assert_eq!((terms[1].page_start, terms[1].page_end), (2, 2));
+    assert_eq!(terms[2].definition, "capability of a product to be in a state to perform");
+    assert_eq!((terms[2].page_start, terms[2].page_end), (3, 3));
+}
+
+#[test]
+fn parse_glossary_terms_skips_page_furniture_and_stops_at_annex_or_bibliography() {
+    let parser = StructuredChunkParser::new().expect("parser should compile");
+    let regexes = IngestRegexes::build().expect("regexes should compile");
+    let pages = vec![
+        "2 Terms and definitions\n2.1\nallocation\nassignment of a requirement\n12\n© ISO 2011 – All rights reserved\n2.1.1 hardware allocation\nassignment of a requirement to hardware".to_string(),
+        "ISO 26262-1:2011(E)\n2.142 walk-through\nsystematic examination of a work product\nAnnex A (informative) Overview\nA.1 General\nThis annex describes the parts.".to_string(),
+        "3 Terms and definitions\n3.9 baseline\nversion of work products\nBibliography\n[1] ISO 9001 Quality management systems".to_string(),
+    ];
+
+    let terms = parse_glossary_terms(&pages, &parser, &regexes);
+
+    let summary = terms
+        .iter()
+        .map(|term| (term.reference.as_str(), term.definition.as_str()))
+        .collect::<Vec<(&str, &str)>>();
+    assert_eq!(
+        summary,
+        vec![
+            ("2.1", "assignment of a requirement"),
+            ("2.1.1", "assignment of a requirement to hardware"),
+            ("2.142", "systematic examination of a work product"),
+            ("3.9", "version of work products"),
+        ]
+    );
+}
+
+#[test]
+fn link_term_occurrences_counts_terms_and_abbreviations_outside_glossary() {
+    let connection = Connection::open_in_memory().expect("in-memory DB should open");
+    ensure_schema(&connection).expect("schema should initialize");
+    connection
+        .execute_batch(
+            "
+            INSERT INTO docs(doc_id, filename, sha256, part, year) VALUES
+              ('ISO26262-1-2018', 'part1.pdf', 'sha1', 1, 2018),
+              ('ISO26262-6-2018', 'part6.pdf', 'sha6', 6, 2018);
+            INSERT INTO docs(doc_id, filename, sha256, part, year) VALUES
+              ('ISO26262-9-2018', 'part9.pdf', 'sha9', 9, 2018);
+            INSERT INTO nodes(node_id, doc_id, node_type, ref, order_index) VALUES
+              ('term-asil', 'ISO26262-1-2018', 'term', '3.6', 1),
+              ('term-element', 'ISO26262-1-2018', 'term', '3.41', 2);
+            INSERT INTO terms(term_id, doc_id, ref, term, term_norm, abbreviation, definition)
+              VALUES('term-asil', 'ISO26262-1-2018', '3.6', 'automotive safety integrity level',
+                     'automotive safety integrity level', 'ASIL', 'one of four levels'),
+                    ('term-element', 'ISO26262-1-2018', '3.41', 'element', 'element', NULL,
+                     'system or part of a system');
+            INSERT INTO chunks(chunk_id, doc_id, type, text) VALUES
+              ('c-glossary', 'ISO26262-1-2018', 'clause', 'ASIL automotive safety integrity level'),
+              ('c-part6', 'ISO26262-6-2018', 'clause',
+               'Depending on the ASIL, the Automotive Safety Integrity Level applies to each element. BASIL is unrelated.'),
+              ('c-none', 'ISO26262-6-2018', 'clause', 'No glossary terms here.'),
+              ('c-part9', 'ISO26262-9-2018', 'clause', 'The ASIL of the element.');
+            INSERT INTO term_occurrences(chunk_id, term_id, occurrence_count)
+              VALUES('c-part9', 'term-asil', 7);
+            ",
+        )
+        .expect("seed rows should insert");
+
+    let mut stats = ChunkInsertStats::default();
+    link_term_occurrences(&connection, &["ISO26262-6-2018".to_string()], &mut stats)
+        .expect("linking should succeed");
+
+    assert_eq!(stats.term_occurrences_linked, 1);
+    let part9_count = connection
+        .query_row(
+            "SELECT occurrence_count FROM term_occurrences WHERE chunk_id = 'c-part9'",
+            [],
+            |row| row.get::<_, i64>(0),
+        )
+        .expect("occurrences outside the ingested documents should be kept");
+    assert_eq!(part9_count, 7);
+    let (chunk_id, occurrence_count) = connection
+        .query_row(
+            "SELECT chunk_id, occurrence_count FROM term_occurrences WHERE chunk_id != 'c-part9'",
+            [],
+            |row| Ok((row.get::<_, String>(0)?, row.get::<_, i64>(1)?)),
+        )
+        .expect("occurrence row should exist");
+    assert_eq!(chunk_id, "c-part6");
+    assert_eq!(occurrence_count, 2);
+
+    let mut stats = ChunkInsertStats::default();
+    link_term_occurrences(&connection, &["ISO26262-1-2018".to_string()], &mut stats)
+        .expect("relinking after a glossary change should succeed");
+    assert_eq!(stats.term_occurrences_linked, 2);
+}
+
+#[test]
+fn classify_requirement_atom_extracts_modality_actor_conditions_and_references() {
+    let regexes = IngestRegexes::build().expect("regexes should compile");
+
+    let atom = classify_requirement_atom(
+        "For ASIL C and D, if the software unit is safety-related, the software unit design shall not use dynamic objects as given in Table 6 and the software verification plan",
+        "8.4.5",
+        &regexes,
+    );
+    assert_eq!(atom.modality, Some("shall_not"));
+    assert_eq!(atom.actor.as_deref(), Some("software unit design"));
+    assert_eq!(
+        atom.conditions,
+        vec![
+            "For ASIL C and D".to_string(),
+            "if the software unit is safety-related".to_string()
+        ]
+    );
+    assert_eq!(
+        atom.references,
+        vec![
+            RequirementReference {
+                kind: "table".to_string(),
+                target: "Table 6".to_string(),
+            },
+            RequirementReference {
+                kind: "work_product".to_string(),
+                target: "software verification plan".to_string(),
+            },
+        ]
+    );
+
+    let atom = classify_requirement_atom(
+        "Each safety requirement should be verified according to ISO 26262-8:2018, Clause 9",
+        "6.4.1",
+        &regexes,
+    );
+    assert_eq!(atom.modality, Some("should"));
+    assert_eq!(atom.actor.as_deref(), Some("safety requirement"));
+    assert!(atom.conditions.is_empty());
+    assert_eq!(atom.references[0].target, "ISO 26262-8 9");
+
+    let atom = classify_requirement_atom("Tool qualification may be omitted", "11.4", &regexes);
+    assert_eq!(atom.modality, Some("may"));
+    assert_eq!(atom.actor.as_deref(), Some("Tool qualification"));
+
+    let atom = classify_requirement_atom(
+        "Where tools may be reused, the tool classification shall be documented",
+        "11.4.5",
+        &regexes,
+    );
+    assert_eq!(atom.modality, Some("shall"));
+    assert_eq!(atom.actor.as_deref(), Some("tool classification"));
+}
+
+#[test]
+fn extract_work_products_reads_entries_under_work_product_clause() {
+    let regexes = IngestRegexes::build().expect("regexes should compile");
+    let clause = |reference: &str, heading: &str, text: &str| StructuredChunkDraft {
+        chunk_type: ChunkType::Clause,
+        reference: reference.to_string(),
+        ref_path: reference.to_string(),
+        heading: heading.to_string(),
+        text: text.to_string(),
+        page_start: 40,
+        page_end: 40,
+        merged_continuations: 0,
+        repeated_header_rows_dropped: 0,
+    };
+    let chunks = vec![
+        clause("8.4.5", "8.4.5 Design principles", "8.4.5 Design principles\nThe design shall be verified."),
+        clause("8.5", "8.5 Work products", "8.5 Work products"),
+        clause(
+            "8.5.1",
+            "8.5.1 Software unit design specification resulting from requirements of 8.4.2 to",
+            "8.5.1 Software unit design specification resulting from requirements of 8.4.2 to\n8.4.4 and ISO 26262-8:2018, Clause 6.",
+        ),
+        clause("8.5.2", "8.5.2 Software unit implementation", "8.5.2 Software unit implementation"),
+        clause("9.1", "9.1 Objectives", "9.1 Objectives\n9.1.1 Unrelated"),
+    ];
+
+    let work_products = extract_work_products(&chunks, &regexes);
+
+    assert_eq!(work_products.len(), 2);
+    assert_eq!(work_products[0].reference, "8.5.1");
+    assert_eq!(work_products[0].name, "Software unit design specification");
+    assert_eq!(
+        work_products[0].source_text,
+        "resulting from requirements of 8.4.2 to 8.4.4 and ISO 26262-8:2018, Clause 6"
+    );
+    let sources = work_products[0]
+        .sources
+        .iter()
+        .map(|source| (source.source_part, source.source_ref.as_str()))
+        .collect::<Vec<(Option<u32>, &str)>>();
+    assert_eq!(
+        sources,
+        vec![(None, "8.4.2"), (None, "8.4.3"), (None, "8.4.4"), (Some(8), "6")]
+    );
+    assert_eq!(work_products[1].name, "Software unit implementation");
+    assert!(work_products[1].sources.is_empty());
+}
+
+#[test]
+fn ingest_ledger_detects_changed_source_hash_and_options() {
+    let connection = Connection::open_in_memory().expect("in-memory DB should open");
+    ensure_schema(&connection).expect("schema should initialize");
+    connection
+        .execute(
+            "INSERT INTO docs(doc_id, filename, sha256, part, year) VALUES('ISO26262-6-2018', 'ISO-26262-6-2018.pdf', 'abc', 6, 2018)",
+            [],
+        )
+        .expect("doc row should insert");
+
+    let mut args = IngestArgs {
+        cache_root: PathBuf::from(".cache/iso26262"),
+        inventory_manifest_path: None,
+        ingest_manifest_path: None,
+        db_path: None,
+        refresh_inventory: false,
+        seed_page_chunks: false,
+        force: false,
+        jobs: None,
+        target_parts: Vec::new(),
+        target_years: Vec::new(),
+        max_pages_per_doc: None,
+        extract_backend: ExtractBackend::Text,
+        ocr_mode: OcrMode::Off,
+        ocr_lang: "eng".to_string(),
+        ocr_min_text_chars: 120,
+    };
+    let tool_versions = ToolVersions {
+        rustc: "rustc 1.0.0".to_string(),
+        cargo: "cargo 1.0.0".to_string(),
+        pdftotext: "pdftotext version 24.02.0".to_string(),
+        pdftohtml: "pdftohtml version 24.02.0".to_string(),
+        pdftoppm: None,
+        tesseract: None,
+    };
+    let fingerprint =
+        build_ingest_fingerprint(&args, &tool_versions).expect("fingerprint should build");
+
+    assert!(!ingest_ledger_is_current(&connection, "ISO26262-6-2018", "abc", &fingerprint)
+        .expect("ledger lookup should succeed"));
+    record_ingest_ledger_entry(&connection, "ISO26262-6-2018", "abc", &fingerprint)
+        .expect("ledger entry should record");
+    assert!(ingest_ledger_is_current(&connection, "ISO26262-6-2018", "abc", &fingerprint)
+        .expect("ledger lookup should succeed"));
+    assert!(!ingest_ledger_is_current(&connection, "ISO26262-6-2018", "def", &fingerprint)
+        .expect("ledger lookup should succeed"));
+
+    let rebuilt_tools = ToolVersions {
+        rustc: "rustc 2.0.0".to_string(),
+        ..tool_versions.clone()
+    };
+    let rebuilt =
+        build_ingest_fingerprint(&args, &rebuilt_tools).expect("fingerprint should build");
+    assert_eq!(rebuilt, fingerprint);
+
+    args.ocr_mode = OcrMode::Auto;
+    let ocr_fingerprint =
+        build_ingest_fingerprint(&args, &tool_versions).expect("fingerprint should build");
+    assert!(!ingest_ledger_is_current(&connection, "ISO26262-6-2018", "abc", &ocr_fingerprint)
+        .expect("ledger lookup should succeed"));
+}
+
+#[test]
+fn ingest_ledger_reingests_documents_recorded_under_an_older_schema_version() {
+    let mut connection = Connection::open_in_memory().expect("in-memory DB should open");
+    ensure_schema(&connection).expect("schema should initialize");
+    let pdfs = ingest_fixture_pdfs();
+    for pdf in &pdfs {
+        connection
+            .execute(
+                "INSERT INTO docs(doc_id, filename, sha256, part, year) VALUES(?1, ?2, ?3, ?4, ?5)",
+                params![doc_id_for(pdf), pdf.filename, pdf.sha256, pdf.part, pdf.year],
+            )
+            .expect("doc row should insert");
+    }
+
+    let parser = StructuredChunkParser::new().expect("parser should build");
+    let regexes = IngestRegexes::build().expect("regexes should build");
+    let fingerprint = IngestFingerprint {
+        parser_version: PARSER_VERSION.to_string(),
+        db_schema_version: DB_SCHEMA_VERSION.to_string(),
+        tool_versions: "{}".to_string(),
+        options: "{}".to_string(),
+    };
+    let previous_schema = IngestFingerprint {
+        db_schema_version: "0.4.0".to_string(),
+        ..fingerprint.clone()
+    };
+    for pdf in &pdfs {
+        record_ingest_ledger_entry(&connection, &doc_id_for(pdf), &pdf.sha256, &previous_schema)
+            .expect("ledger entry should record");
+    }
+
+    let stats = insert_prepared_chunks(
+        &mut connection,
+        &pdfs,
+        &regexes,
+        false,
+        &[],
+        &[],
+        &fingerprint,
+        false,
+        1,
+        |pdf, _| Ok(ingest_fixture_document(pdf, &parser, &regexes)),
+    )
+    .expect("ingest should succeed");
+
+    assert_eq!(stats.skipped_unchanged_pdf_count, 0);
+    assert_eq!(stats.processed_pdf_count, pdfs.len());
+    for pdf in &pdfs {
+        assert!(
+            ingest_ledger_is_current(&connection, &doc_id_for(pdf), &pdf.sha256, &fingerprint)
+                .expect("ledger lookup should succeed")
+        );
+    }
+}
+
+fn ingest_fixture_pdfs() -> Vec<PdfEntry> {
+    [2, 6, 8]
+        .into_iter()
+        .map(|part| PdfEntry {
+            filename: format!("ISO-26262-{part}-2018.pdf"),
+            part,
+            year: 2018,
+            sha256: format!("sha-{part}"),
+        })
+        .collect()
+}
+
+fn ingest_fixture_document(
+    pdf: &PdfEntry,
+    parser: &StructuredChunkParser,
+    regexes: &IngestRegexes,
+) -> PreparedDocument {
+    if pdf.part == 2 {
+        std::thread::sleep(std::time::Duration::from_millis(30));
+    }
+
+    let part = pdf.part;
+    let pages = vec![
+        format!(
+            "ISO 26262-{part}:2018\n7 Specification of requirements\n7.1 Objectives\nThe objective is to specify requirements for Part {part}.\n7.4 Requirements and recommendations\n7.4.1 The safety requirements shall be specified in accordance with 7.4.2.\n7.4.2 If a hazard is identified, the item shall be analysed:\na) by inspection;\nb) by walk-through."
+        ),
+        format!(
+            "ISO 26262-{part}:2018\n7.4.3 The verification report should reference ISO 26262-8:2018, Clause 9.\nNOTE The report may be combined.\n7.5 Work products\n7.5.1 Safety plan for Part {part} resulting from requirements of 7.4.1 to 7.4.3."
+        ),
+    ];
+    let extraction = ExtractedPages {
+        page_printed_labels: vec![None; pages.len()],
+        text_layer_page_count: pages.len(),
+        pages,
+        ..ExtractedPages::default()
+    };
+
+    prepare_document_from_pages(part, extraction, Vec::new(), parser, regexes)
+}
+
+fn dump_table_rows(connection: &Connection, sql: &str) -> Vec<Vec<rusqlite::types::Value>> {
+    let mut statement = connection.prepare(sql).expect("dump statement should prepare");
+    let column_count = statement.column_count();
+    statement
+        .query_map([], |row| {
+            (0..column_count)
+                .map(|index| row.get::<_, rusqlite::types::Value>(index))
+                .collect::<rusqlite::Result<Vec<_>>>()
+        })
+        .expect("dump query should run")
+        .collect::<rusqlite::Result<Vec<_>>>()
+        .expect("dump rows should load")
+}
+
+fn run_fixture_ingest(
+    jobs: usize,
+) -> (
+    ChunkInsertStats,
+    Vec<Vec<rusqlite::types::Value>>,
+    Vec<Vec<rusqlite::types::Value>>,
+) {
+    let mut connection = Connection::open_in_memory().expect("in-memory DB should open");
+    ensure_schema(&connection).expect("schema should initialize");
+    let pdfs = ingest_fixture_pdfs();
+    for pdf in &pdfs {
+        connection
+            .execute(
+                "INSERT INTO docs(doc_id, filename, sha256, part, year) VALUES(?1, ?2, ?3, ?4, ?5)",
+                params![doc_id_for(pdf), pdf.filename, pdf.sha256, pdf.part, pdf.year],
+            )
+            .expect("doc row should insert");
+    }
+
+    let parser = StructuredChunkParser::new().expect("parser should build");
+    let regexes = IngestRegexes::build().expect("regexes should build");
+    let fingerprint = IngestFingerprint {
+        parser_version: PARSER_VERSION.to_string(),
+        db_schema_version: DB_SCHEMA_VERSION.to_string(),
+        tool_versions: "{}".to_string(),
+        options: "{}".to_string(),
+    };
+    let stats = insert_prepared_chunks(
+        &mut connection,
+        &pdfs,
+        &regexes,
+        false,
+        &[],
+        &[],
+        &fingerprint,
+        false,
+        jobs,
+        |pdf, _| Ok(ingest_fixture_document(pdf, &parser, &regexes)),
+    )
+    .expect("fixture ingest should succeed");
+
+    let rerun_stats = insert_prepared_chunks(
+        &mut connection,
+        &pdfs,
+        &regexes,
+        false,
+        &[],
+        &[],
+        &fingerprint,
+        false,
+        jobs,
+        |_, _| panic!("unchanged documents must not be re-extracted"),
+    )
+    .expect("incremental rerun should succeed");
+    assert_eq!(rerun_stats.skipped_unchanged_pdf_count, pdfs.len());
+    assert_eq!(rerun_stats.processed_pdf_count, 0);
+
+    let chunks = dump_table_rows(&connection, "SELECT * FROM chunks ORDER BY chunk_id");
+    let nodes = dump_table_rows(&connection, "SELECT * FROM nodes ORDER BY node_id");
+    (stats, chunks, nodes)
+}
+
+#[test]
+fn parallel_ingest_matches_serial_chunks_and_nodes() {
+    let (serial_stats, serial_chunks, serial_nodes) = run_fixture_ingest(1);
+    let (parallel_stats, parallel_chunks, parallel_nodes) = run_fixture_ingest(4);
+
+    assert_eq!(serial_stats.processed_pdf_count, 3);
+    assert_eq!(parallel_stats.processed_pdf_count, 3);
+    assert_eq!(serial_stats.processed_parts, parallel_stats.processed_parts);
+    assert_eq!(
+        serial_stats.processed_editions,
+        parallel_stats.processed_editions
+    );
+    assert!(!serial_chunks.is_empty());
+    assert!(serial_nodes.len() > serial_chunks.len());
+    assert_eq!(serial_chunks, parallel_chunks);
+    assert_eq!(serial_nodes, parallel_nodes);
+}
+
+#[test]
+fn parse_bbox_layout_pages_separates_bands_columns_and_indentation() {
+    let document = r#"<!DOCTYPE html>
+<html><body><doc>
+<page width="595.0" height="842.0">
+  <flow><block><line>
+    <word xMin="56.0" yMin="30.0" xMax="90.0" yMax="40.0">ISO</word>
+    <word xMin="93.0" yMin="30.0" xMax="160.0" yMax="40.0">26262-6:2018(E)</word>
+  </line></block></flow>
+  <flow><block><line>
+    <word xMin="56.0" yMin="100.0" xMax="80.0" yMax="110.0">7.4.2</word>
+    <word xMin="84.0" yMin="100.0" xMax="130.0" yMax="110.0">Design</word>
+  </line></block></flow>
+  <flow><block><line>
+    <word xMin="80.0" yMin="120.0" xMax="86.0" yMax="130.0">a)</word>
+    <word xMin="90.0" yMin="120.0" xMax="140.0" yMax="130.0">hierarchical</word>
+    <word xMin="143.0" yMin="120.0" xMax="180.0" yMax="130.0">&amp; modular;</word>
+  </line></block></flow>
+  <flow><block><line>
+    <word xMin="56.0" yMin="140.0" xMax="62.0" yMax="150.0">1a</word>
+    <word xMin="66.0" yMin="140.0" xMax="120.0" yMax="150.0">Restricted</word>
+  </line></block>
+  <block><line>
+    <word xMin="300.0" yMin="140.5" xMax="308.0" yMax="150.5">++</word>
+    <word xMin="400.0" yMin="140.0" xMax="406.0" yMax="150.0">+</word>
+  </line></block></flow>
+  <flow><block><line>
+    <word xMin="290.0" yMin="800.0" xMax="300.0" yMax="810.0">15</word>
+  </line></block></flow>
+</page>
+<page width="595.0" height="842.0">
+</page>
+</doc></body></html>"#;
+
+    let pages = parse_bbox_layout_pages(document).expect("layout should parse");
+
+    assert_eq!(pages.len(), 1);
+    assert_eq!(pages[0].header_lines, vec!["ISO 26262-6:2018(E)".to_string()]);
+    assert_eq!(pages[0].footer_lines, vec!["15".to_string()]);
+    assert_eq!(
+        pages[0].text,
+        "7.4.2 Design\n    a) hierarchical & modular;\n1a Restricted\t++\t+"
+    );
+    assert_eq!(infer_list_depth("    a) hierarchical & modular;"), 3);
+
+    let regexes = IngestRegexes::build().expect("regexes should build");
+    assert_eq!(
+        split_table_cells("1a Restricted\t++\t+", &regexes.table_cell_split_regex),
+        vec!["1a Restricted", "++", "+"]
+    );
+}
+
+#[test]
+fn insert_node_regions_maps_node_text_to_layout_rectangles() {
+    let connection = Connection::open_in_memory().expect("in-memory DB should open");
+    ensure_schema(&connection).expect("schema should initialize");
+    connection
+        .execute(
+            "INSERT INTO docs(doc_id, filename, sha256, part, year) VALUES('ISO26262-6-2018', 'ISO-26262-6-2018.pdf', 'abc', 6, 2018)",
+            [],
+        )
+        .expect("doc row should insert");
+    for (node_id, parent_node_id, node_type, order_index, page, text) in [
+        ("clause", None, "clause", 1, 1, "7.4.2 Design rules"),
+        (
+            "para",
+            Some("clause"),
+            "paragraph",
+            2,
+            1,
+            "The software architectural design shall be verified.",
+        ),
+        ("list", Some("clause"), "list_item", 3, 2, "a) hierarchical structure;"),
+    ] {
+        connection
+            .execute(
+                "INSERT INTO nodes(node_id, parent_node_id, doc_id, node_type, order_index, page_pdf_start, page_pdf_end, text) VALUES(?1, ?2, 'ISO26262-6-2018', ?3, ?4, ?5, ?5, ?6)",
+                params![node_id, parent_node_id, node_type, order_index, page, text],
+            )
+            .expect("node row should insert");
+    }
+
+    let word = |x_min: f64, y_min: f64, text: &str| LayoutWord {
+        x_min,
+        y_min,
+        x_max: x_min + 40.0,
+        y_max: y_min + 10.0,
+        text: text.to_string(),
+    };
+    let first_page = render_layout_page(
+        595.0,
+        842.0,
+        vec![
+            word(72.0, 100.0, "7.4.2"),
+            word(120.0, 100.0, "Design"),
+            word(170.0, 100.0, "rules"),
+            word(72.0, 120.0, "The"),
+            word(120.0, 120.0, "software"),
+            word(170.0, 120.0, "architectural"),
+            word(72.0, 135.0, "design"),
+            word(120.0, 135.0, "shall"),
+            word(170.0, 135.0, "be"),
+            word(220.0, 135.0, "verified."),
+        ],
+    );
+    let second_page = render_layout_page(
+        595.0,
+        842.0,
+        vec![
+            word(84.0, 200.0, "a)"),
+            word(130.0, 200.0, "hierarchical"),
+            word(180.0, 200.0, "structure;"),
+        ],
+    );
+    let page_geometry = vec![Some(first_page.geometry), Some(second_page.geometry)];
+
+    let mut stats = ChunkInsertStats::default();
+    {
+        let mut region_statement = connection
+            .prepare(
+                "INSERT INTO node_regions(node_id, region_seq, doc_id, page_pdf, x_min, y_min, x_max, y_max, page_width, page_height) VALUES(?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
+            )
+            .expect("region statement should prepare");
+        insert_node_regions_for_pdf(
+            &connection,
+            &mut region_statement,
+            "ISO26262-6-2018",
+            &page_geometry,
+            &mut stats,
+        )
+        .expect("regions should insert");
+    }
+
+    let mut statement = connection
+        .prepare(
+            "SELECT node_id, region_seq, page_pdf, x_min, y_min, x_max, y_max FROM node_regions ORDER BY node_id, region_seq",
+        )
+        .expect("region query should prepare");
+    let regions = statement
+        .query_map([], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, i64>(1)?,
+                row.get::<_, i64>(2)?,
+                row.get::<_, f64>(3)?,
+                row.get::<_, f64>(4)?,
+                row.get::<_, f64>(5)?,
+                row.get::<_, f64>(6)?,
+            ))
+        })
+        .expect("region query should run")
+        .collect::<Result<Vec<_>, _>>()
+        .expect("region rows should decode");
+
+    assert_eq!(stats.node_regions_inserted, 4);
+    assert_eq!(
+        regions,
+        vec![
+            ("clause".to_string(), 1, 1, 72.0, 100.0, 210.0, 110.0),
+            ("list".to_string(), 1, 2, 84.0, 200.0, 220.0, 210.0),
+            ("para".to_string(), 1, 1, 72.0, 120.0, 210.0, 130.0),
+            ("para".to_string(), 2, 1, 72.0, 135.0, 260.0, 145.0),
+        ]
+    );
+
+    let tokens = ["a", "b", "c", "a", "b", "d"];
+    let node_tokens = tokenize_region_text("A b D");
+    assert_eq!(locate_region_span(&tokens, &node_tokens, 0, 6), Some((3, 5)));
+    assert_eq!(locate_region_span(&tokens, &node_tokens, 4, 6), None);
+}
+
+#[test]
+fn parse_pages_merges_continued_table_and_drops_repeated_header_rows() {
+    let parser = StructuredChunkParser::new().expect("parser should build");
+    let pages = vec![
+        "7.4.3 Design principles\nTable 3 — Principles for architectural design\nMethods  A  B  C  D\n1a Hierarchical structure  ++ ++ ++ ++".to_string(),
+        "Table 3 (continued)\nMethods   A   B   C   D\n1b Restricted size  ++ ++ ++ ++\n7.4.4 Next clause".to_string(),
+    ];
+
+    let chunks = parser.parse_pages(&pages);
+
+    let tables = chunks
+        .iter()
+        .filter(|chunk| chunk.chunk_type == ChunkType::Table)
+        .collect::<Vec<&StructuredChunkDraft>>();
+    assert_eq!(tables.len(), 1);
+    assert_eq!(tables[0].reference, "Table 3");
+    assert_eq!(tables[0].heading, "Table 3 — Principles for architectural design");
+    assert_eq!((tables[0].page_start, tables[0].page_end), (1, 2));
+    assert_eq!(tables[0].merged_continuations, 1);
+    assert_eq!(tables[0].repeated_header_rows_dropped, 1);
+    assert_eq!(
+        tables[0].text,
+        "Table 3 — Principles for architectural design\n\nMethods  A  B  C  D\n1a Hierarchical structure  ++ ++ ++ ++\n1b Restricted size  ++ ++ ++ ++"
+    );
+    assert_eq!(chunks.last().map(|chunk| chunk.reference.as_str()), Some("7.4.4"));
+}
+
+#[test]
+fn parse_pages_keeps_repeated_table_heading_on_same_page_without_continued_marker() {
+    let parser = StructuredChunkParser::new().expect("parser should build");
+    let pages = vec![
+        "Table 3 — Principles for architectural design\n1a Hierarchical structure  ++ ++ ++ ++\nTable 3 — Principles for architectural design\n1b Restricted size  ++ ++ ++ ++".to_string(),
+    ];
+
+    let chunks = parser.parse_pages(&pages);
+
+    let tables = chunks
+        .iter()
+        .filter(|chunk| chunk.chunk_type == ChunkType::Table)
+        .collect::<Vec<&StructuredChunkDraft>>();
+    assert_eq!(tables.len(), 2);
+    assert!(tables.iter().all(|table| table.merged_continuations == 0));
+    assert!(tables[1].text.ends_with("1b Restricted size  ++ ++ ++ ++"));
+}
+
+#[test]
+fn parse_table_rows_extracts_footnotes_and_links_marked_cells() {
+    let regexes = IngestRegexes::build().expect("regexes should build");
+    let heading = "Table 3 — Principles for architectural design";
+    let text = format!(
+        "{heading}\n1a  Restricted size a  ++  ++  ++  ++\n1b  Strong typing  +  ++b  ++  ++\na  Applies to newly developed components.\nb  Where supported by the\nprogramming language."
+    );
+
+    let parsed = parse_table_rows(&text, heading, &regexes.table_cell_split_regex);
+
+    assert_eq!(parsed.rows.len(), 2);
+    assert_eq!(
+        parsed.footnotes,
+        vec![
+            TableFootnoteDraft {
+                marker: "a".to_string(),
+                text: "Applies to newly developed components.".to_string(),
+                linked_cells: vec![(0, 1)],
+            },
+            TableFootnoteDraft {
+                marker: "b".to_string(),
+                text: "Where supported by the programming language.".to_string(),
+                linked_cells: vec![(1, 3)],
+            },
+        ]
+    );
+    assert!(parsed.markdown.as_deref().is_some_and(|markdown| markdown
+        .ends_with("[^a]: Applies to newly developed components.\n[^b]: Where supported by the programming language.")));
+    assert!(parsed.csv.as_deref().is_some_and(|csv| csv
+        .ends_with("footnote a,Applies to newly developed components.\nfootnote b,Where supported by the programming language.")));
+
+    let plain = parse_table_rows(
+        "Table 4 — Methods\n1a  Walk-through  ++  +  o  o\na  Informal review",
+        "Table 4 — Methods",
+        &regexes.table_cell_split_regex,
+    );
+    assert!(plain.footnotes.is_empty());
+}
+
+#[test]
+fn parse_table_rows_keeps_continuation_rows_after_footnotes() {
+    let regexes = IngestRegexes::build().expect("regexes should build");
+    let heading = "Table 3 — Principles for architectural design";
+    let text = format!(
+        "{heading}\n1a  Restricted size a  ++  ++  ++  ++\na  Applies to newly developed components.\n1b  Strong typing b  +  ++  ++  ++\nb  Where supported by the\nprogramming language.\n\nUse of language subsets\n1c  Defensive implementation  o  +  ++  ++"
+    );
+
+    let parsed = parse_table_rows(&text, heading, &regexes.table_cell_split_regex);
+
+    assert_eq!(
+        parsed
+            .footnotes
+            .iter()
+            .map(|footnote| footnote.text.as_str())
+            .collect::<Vec<&str>>(),
+        vec![
+            "Applies to newly developed components.",
+            "Where supported by the programming language.",
+        ]
+    );
+    assert_eq!(
+        parsed
+            .rows
+            .iter()
+            .map(|row| row[0].as_str())
+            .collect::<Vec<&str>>(),
+        vec!["1a", "1b", "1c"]
+    );
+    assert_eq!(parsed.rows[1][1], "Strong typing b Use of language subsets");
+}
+
+#[test]
+fn parse_pages_extracts_figure_caption_and_labels_after_parent_clause() {
+    let parser = StructuredChunkParser::new().expect("parser should build");
+    let pages = vec![
+        "5.2 Reference phase model\nThe reference phase model is shown below.".to_string(),
+        "Specification of software safety requirements\nSoftware architectural design\nVerification of software\nFigure 2 — Reference phase model for the software development\nEach phase is described in the following clauses.\n5.3 Next clause".to_string(),
+    ];
+
+    let chunks = parser.parse_pages(&pages);
+
+    let summary = chunks
+        .iter()
+        .map(|chunk| (chunk.chunk_type, chunk.reference.as_str()))
+        .collect::<Vec<(ChunkType, &str)>>();
+    assert_eq!(
+        summary,
+        vec![
+            (ChunkType::Clause, "5.2"),
+            (ChunkType::Figure, "Figure 2"),
+            (ChunkType::Clause, "5.3"),
+        ]
+    );
+    assert_eq!(
+        chunks[0].text,
+        "5.2 Reference phase model\n\nThe reference phase model is shown below.\nEach phase is described in the following clauses."
+    );
+    assert_eq!(
+        chunks[1].heading,
+        "Figure 2 — Reference phase model for the software development"
+    );
+    assert_eq!(
+        chunks[1].text,
+        "Figure 2 — Reference phase model for the software development\n\nSpecification of software safety requirements\nSoftware architectural design\nVerification of software"
+    );
+    assert_eq!((chunks[1].page_start, chunks[1].page_end), (2, 2));
+    assert_eq!(parser.detect_heading("Figure 3 shows the flow"), None);
+}
+
+#[test]
+fn parse_pages_keeps_trailing_short_list_in_clause_before_figure() {
+    let parser = StructuredChunkParser::new().expect("parser should build");
+    let pages = vec![
+        "7.4.2 Design principles\nThe software architectural design shall consider the\nfollowing properties\na) modularity\nb) encapsulation\nFigure 4 — Software architecture\n7.4.3 Next clause".to_string(),
+        "7.4.4 Interfaces\nThe interfaces shall be described:\nsender\nreceiver\nFigure 5 — Interface overview".to_string(),
+    ];
+
+    let chunks = parser.parse_pages(&pages);
+
+    assert_eq!(
+        chunks[0].text,
+        "7.4.2 Design principles\n\nThe software architectural design shall consider the\nfollowing properties\na) modularity\nb) encapsulation"
+    );
+    assert_eq!(chunks[1].text, "Figure 4 — Software architecture");
+    let interfaces = chunks
+        .iter()
+        .find(|chunk| chunk.reference == "7.4.4")
+        .expect("clause 7.4.4 should be parsed");
+    assert!(interfaces.text.ends_with("described:\nsender\nreceiver"));
+}
+
+#[test]
+fn extract_external_references_parses_normative_and_bibliography_entries() {
+    let parser = StructuredChunkParser::new().expect("parser should build");
+    let regexes = IngestRegexes::build().expect("regexes should compile");
+    let pages = vec![
+        "2 Normative references\nThe following documents are referred to in the text in such a way that some or all of their content constitutes requirements of this document.\nISO 26262-1, Road vehicles — Functional safety — Part 1: Vocabulary\nISO/SAE 21434:2021, Road vehicles — Cybersecurity\nengineering\n3 Terms and definitions".to_string(),
+        "7.4.1 The software shall comply with IEC 61508-3 where applicable.\nBibliography\n[1] IEC 61508-3:2010, Functional safety of E/E/PE safety-related systems — Part 3: Software requirements\n[2] MISRA C:2012, Guidelines for the use of the C language in critical systems".to_string(),
+    ];
+
+    let references = extract_external_references(&pages, &parser, &regexes);
+
+    let summary = references
+        .iter()
+        .map(|reference| {
+            (
+                reference.kind,
+                reference.list_label.as_deref(),
+                reference.standard_id.as_str(),
+                reference.year,
+                reference.title.as_deref(),
+            )
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        summary,
+        vec![
+            (
+                ExternalReferenceKind::Normative,
+                None,
+                "ISO 26262-1",
+                None,
+                Some("Road vehicles — Functional safety — Part 1: Vocabulary"),
+            ),
+            (
+                ExternalReferenceKind::Normative,
+                None,
+                "ISO/SAE 21434",
+                Some(2021),
+                Some("Road vehicles — Cybersecurity engineering"),
+            ),
+            (
+                ExternalReferenceKind::Bibliography,
+                Some("[1]"),
+                "IEC 61508-3",
+                Some(2010),
+                Some(
+                    "Functional safety of E/E/PE safety-related systems — Part 3: Software requirements"
+                ),
+            ),
+            (
+                ExternalReferenceKind::Bibliography,
+                Some("[2]"),
+                "MISRA C",
+                Some(2012),
+                Some("Guidelines for the use of the C language in critical systems"),
+            ),
+        ]
+    );
+    assert_eq!(references[2].page_start, 2);
+
+    let chunks = parser.parse_pages(&pages);
+    let clause = chunks
+        .iter()
+        .find(|chunk| chunk.reference == "7.4.1")
+        .expect("clause 7.4.1 should be parsed");
+    assert!(!clause.text.contains("MISRA"));
+    assert!(text_mentions_standard(&clause.text, "IEC 61508-3"));
+    assert!(!text_mentions_standard("see IEC 61508-30", "IEC 61508-3"));
+    assert!(!text_mentions_standard("ISO/IEC 61508-3", "IEC 61508-3"));
+    assert!(text_mentions_standard(
+        "applying the IEC 61508 series",
+        "IEC 61508-3"
+    ));
+    assert!(!text_mentions_standard("see IEC 61508-1", "IEC 61508-3"));
+    assert_eq!(standard_family_root("IEC 61508-3"), Some("IEC 61508"));
+    assert_eq!(standard_family_root("MISRA C"), None);
+}
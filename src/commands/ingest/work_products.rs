@@ -0,0 +1,261 @@
+use super::*;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WorkProductDraft {
+    pub reference: String,
+    pub name: String,
+    pub source_text: String,
+    pub sources: Vec<WorkProductSourceDraft>,
+    pub page_start: i64,
+    pub page_end: i64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WorkProductSourceDraft {
+    pub source_part: Option<u32>,
+    pub source_ref: String,
+}
+
+pub fn extract_work_products(
+    chunks: &[StructuredChunkDraft],
+    regexes: &IngestRegexes,
+) -> Vec<WorkProductDraft> {
+    let mut work_products = Vec::<WorkProductDraft>::new();
+    let mut work_product_clause = None::<String>;
+
+    for chunk in chunks {
+        if chunk.chunk_type != ChunkType::Clause {
+            continue;
+        }
+
+        if let Some(captures) = regexes.work_product_clause_regex.captures(&chunk.heading) {
+            work_product_clause = captures
+                .name("reference")
+                .map(|value| value.as_str().to_string());
+            continue;
+        }
+
+        let Some(clause_ref) = work_product_clause.as_deref() else {
+            continue;
+        };
+        let is_entry = chunk
+            .reference
+            .strip_prefix(clause_ref)
+            .and_then(|rest| rest.strip_prefix('.'))
+            .is_some_and(|rest| !rest.is_empty() && rest.chars().all(|ch| ch.is_ascii_digit()));
+        if !is_entry {
+            if !chunk.reference.starts_with(&format!("{clause_ref}.")) {
+                work_product_clause = None;
+            }
+            continue;
+        }
+        if work_products
+            .iter()
+            .any(|existing| existing.reference == chunk.reference)
+        {
+            continue;
+        }
+
+        let entry_text = format!(
+            "{} {}",
+            chunk.heading.trim_start_matches(&chunk.reference).trim(),
+            body_without_heading(&chunk.text, &chunk.heading)
+        );
+        if let Some(work_product) = parse_work_product_entry(&chunk.reference, &entry_text, regexes)
+        {
+            work_products.push(WorkProductDraft {
+                page_start: chunk.page_start,
+                page_end: chunk.page_end,
+                ..work_product
+            });
+        }
+    }
+
+    work_products
+}
+
+fn parse_work_product_entry(
+    reference: &str,
+    entry_text: &str,
+    regexes: &IngestRegexes,
+) -> Option<WorkProductDraft> {
+    let condensed = entry_text
+        .split_whitespace()
+        .collect::<Vec<&str>>()
+        .join(" ");
+    let (name, source_text) = match regexes.work_product_source_regex.find(&condensed) {
+        Some(found) => (
+            condensed[..found.start()].trim(),
+            condensed[found.start()..].trim(),
+        ),
+        None => (
+            condensed.split(". ").next().unwrap_or(&condensed).trim(),
+            "",
+        ),
+    };
+    let name = name.trim_end_matches(['.', ',', ';']).trim();
+    if name.is_empty() {
+        return None;
+    }
+    let source_text = source_text.trim_end_matches('.').to_string();
+
+    Some(WorkProductDraft {
+        reference: reference.to_string(),
+        name: name.to_string(),
+        sources: parse_work_product_sources(&source_text, regexes),
+        source_text,
+        page_start: 0,
+        page_end: 0,
+    })
+}
+
+pub fn parse_work_product_sources(
+    source_text: &str,
+    regexes: &IngestRegexes,
+) -> Vec<WorkProductSourceDraft> {
+    let mut sources = Vec::<WorkProductSourceDraft>::new();
+
+    for captures in regexes
+        .work_product_source_ref_regex
+        .captures_iter(source_text)
+    {
+        let Some(start) = captures.name("start").map(|value| value.as_str()) else {
+            continue;
+        };
+        let source_part = captures
+            .name("part")
+            .and_then(|value| value.as_str().parse::<u32>().ok());
+        if source_part.is_none() && !start.contains('.') {
+            continue;
+        }
+        let refs = match captures.name("end") {
+            Some(end) => expand_clause_range(start, end.as_str()),
+            None => vec![start.to_string()],
+        };
+
+        for source_ref in refs {
+            let duplicate = sources.iter().any(|existing| {
+                existing.source_part == source_part && existing.source_ref == source_ref
+            });
+            if !duplicate {
+                sources.push(WorkProductSourceDraft {
+                    source_part,
+                    source_ref,
+                });
+            }
+        }
+    }
+
+    sources
+}
+
+fn expand_clause_range(start: &str, end: &str) -> Vec<String> {
+    let expanded = start.rsplit_once('.').zip(end.rsplit_once('.')).and_then(
+        |((start_prefix, start_last), (end_prefix, end_last))| {
+            if start_prefix != end_prefix {
+                return None;
+            }
+            let first = start_last.parse::<u32>().ok()?;
+            let last = end_last.parse::<u32>().ok()?;
+            (first <= last && last - first < 64).then(|| {
+                (first..=last)
+                    .map(|index| format!("{start_prefix}.{index}"))
+                    .collect::<Vec<String>>()
+            })
+        },
+    );
+
+    expanded.unwrap_or_else(|| vec![start.to_string(), end.to_string()])
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn insert_work_products_for_pdf(
+    work_product_statement: &mut rusqlite::Statement<'_>,
+    work_product_source_statement: &mut rusqlite::Statement<'_>,
+    doc_id: &str,
+    part: u32,
+    source_hash: &str,
+    work_products: &[WorkProductDraft],
+    state: &PdfNodeState,
+    stats: &mut ChunkInsertStats,
+) -> Result<()> {
+    for work_product in work_products {
+        let node_id = state
+            .clause_ref_to_node_id
+            .get(&work_product.reference)
+            .cloned();
+        let work_product_id = format!(
+            "{}:wp:{}",
+            doc_id,
+            sanitize_ref_for_id(&work_product.reference)
+        );
+
+        work_product_statement.execute(params![
+            work_product_id,
+            doc_id,
+            node_id,
+            work_product.reference,
+            work_product.name,
+            work_product.name.to_lowercase(),
+            work_product.source_text,
+            work_product.page_start,
+            work_product.page_end,
+            source_hash,
+        ])?;
+        stats.work_products_inserted += 1;
+
+        for (index, source) in work_product.sources.iter().enumerate() {
+            work_product_source_statement.execute(params![
+                work_product_id,
+                index as i64 + 1,
+                source.source_part.unwrap_or(part),
+                source.source_part.is_none().then_some(doc_id),
+                source.source_ref,
+            ])?;
+            stats.work_product_sources_inserted += 1;
+        }
+    }
+
+    Ok(())
+}
+
+pub fn resolve_work_product_sources(
+    connection: &Connection,
+    stats: &mut ChunkInsertStats,
+) -> Result<()> {
+    connection
+        .execute_batch(
+            "
+            UPDATE work_product_sources
+            SET source_doc_id = (
+              SELECT d.doc_id
+              FROM docs d
+              JOIN work_products w ON w.work_product_id = work_product_sources.work_product_id
+              JOIN docs s ON s.doc_id = w.doc_id
+              WHERE d.part = work_product_sources.source_part
+              ORDER BY d.year = s.year DESC, d.year DESC
+              LIMIT 1
+            )
+            WHERE source_doc_id IS NULL;
+
+            UPDATE work_product_sources
+            SET source_node_id = (
+              SELECT n.node_id
+              FROM nodes n
+              WHERE n.doc_id = work_product_sources.source_doc_id
+                AND n.ref = work_product_sources.source_ref
+                AND n.node_type IN ('section_heading', 'clause', 'subclause')
+              ORDER BY n.order_index ASC
+              LIMIT 1
+            );
+            ",
+        )
+        .context("failed to resolve work product sources")?;
+
+    stats.work_product_sources_unresolved = count_rows(
+        connection,
+        "SELECT COUNT(*) FROM work_product_sources WHERE source_node_id IS NULL",
+    )? as usize;
+
+    Ok(())
+}
@@ -0,0 +1,89 @@
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::mpsc;
+use std::thread;
+
+use super::*;
+
+pub fn resolve_ingest_jobs(requested: Option<usize>) -> usize {
+    requested
+        .filter(|jobs| *jobs > 0)
+        .or_else(|| {
+            thread::available_parallelism()
+                .ok()
+                .map(|value| value.get())
+        })
+        .unwrap_or(1)
+}
+
+pub fn for_each_in_order<T, R>(
+    items: &[T],
+    jobs: usize,
+    work: impl Fn(&T) -> R + Sync,
+    mut consume: impl FnMut(usize, R) -> Result<()>,
+) -> Result<()>
+where
+    T: Sync,
+    R: Send,
+{
+    let workers = jobs.min(items.len());
+    if workers <= 1 {
+        for (index, item) in items.iter().enumerate() {
+            consume(index, work(item))?;
+        }
+        return Ok(());
+    }
+
+    let next_index = AtomicUsize::new(0);
+    let cancelled = AtomicBool::new(false);
+
+    thread::scope(|scope| {
+        let (sender, receiver) = mpsc::channel::<(usize, R)>();
+        for _ in 0..workers {
+            let sender = sender.clone();
+            let work = &work;
+            let next_index = &next_index;
+            let cancelled = &cancelled;
+            scope.spawn(move || {
+                while !cancelled.load(Ordering::Relaxed) {
+                    let index = next_index.fetch_add(1, Ordering::Relaxed);
+                    let Some(item) = items.get(index) else {
+                        break;
+                    };
+                    if sender.send((index, work(item))).is_err() {
+                        break;
+                    }
+                }
+            });
+        }
+        drop(sender);
+
+        let mut pending = BTreeMap::<usize, R>::new();
+        let mut next_to_consume = 0usize;
+        for (index, result) in receiver {
+            pending.insert(index, result);
+            while let Some(result) = pending.remove(&next_to_consume) {
+                if let Err(error) = consume(next_to_consume, result) {
+                    cancelled.store(true, Ordering::Relaxed);
+                    return Err(error);
+                }
+                next_to_consume += 1;
+            }
+        }
+
+        Ok(())
+    })
+}
+
+pub fn map_in_order<T, R>(items: &[T], jobs: usize, work: impl Fn(&T) -> R + Sync) -> Result<Vec<R>>
+where
+    T: Sync,
+    R: Send,
+{
+    let mut results = Vec::with_capacity(items.len());
+    for_each_in_order(items, jobs, work, |_, result| {
+        results.push(result);
+        Ok(())
+    })?;
+    Ok(results)
+}
@@ -62,7 +62,24 @@ pub fn build_manifest(cache_root: &Path) -> Result<PdfInventoryManifest> {
         });
     }
 
-    pdfs.sort_by(|a, b| a.part.cmp(&b.part).then(a.filename.cmp(&b.filename)));
+    pdfs.sort_by(|a, b| {
+        a.part
+            .cmp(&b.part)
+            .then(a.year.cmp(&b.year))
+            .then(a.filename.cmp(&b.filename))
+    });
+    if let Some(pair) = pdfs
+        .windows(2)
+        .find(|pair| pair[0].part == pair[1].part && pair[0].year == pair[1].year)
+    {
+        bail!(
+            "multiple PDFs found for ISO 26262-{}:{} ({} and {}); keep one file per part and edition",
+            pair[0].part,
+            pair[0].year,
+            pair[0].filename,
+            pair[1].filename
+        );
+    }
 
     Ok(PdfInventoryManifest {
         manifest_version: 1,
@@ -0,0 +1,6 @@
+mod run;
+#[cfg(test)]
+mod tests;
+mod tools;
+
+pub(crate) use run::run;
@@ -0,0 +1,184 @@
+use std::io::{self, BufRead, Write};
+use std::time::Instant;
+
+use anyhow::Result;
+use rusqlite::Connection;
+use serde_json::{Map, Value, json};
+use tracing::{info, warn};
+
+use crate::cli::McpArgs;
+use crate::commands::query::open_query_connection;
+
+use super::tools::{call_tool, tool_definitions};
+
+const LATEST_PROTOCOL_VERSION: &str = "2025-06-18";
+const SUPPORTED_PROTOCOL_VERSIONS: [&str; 3] = ["2024-11-05", "2025-03-26", "2025-06-18"];
+
+const PARSE_ERROR: i64 = -32700;
+const INVALID_REQUEST: i64 = -32600;
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+
+pub fn run(args: McpArgs) -> Result<()> {
+    let db_path = args
+        .db_path
+        .clone()
+        .unwrap_or_else(|| args.cache_root.join("iso26262_index.sqlite"));
+    let connection = open_query_connection(&db_path)?;
+    info!(db_path = %db_path.display(), "mcp server ready on stdio");
+
+    let stdin = io::stdin();
+    let mut stdout = io::stdout().lock();
+    serve_stdio(&connection, stdin.lock(), &mut stdout)
+}
+
+pub(super) fn serve_stdio<R: BufRead, W: Write>(
+    connection: &Connection,
+    reader: R,
+    writer: &mut W,
+) -> Result<()> {
+    for line in reader.lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        if let Some(response) = handle_message(connection, &line) {
+            serde_json::to_writer(&mut *writer, &response)?;
+            writer.write_all(b"\n")?;
+            writer.flush()?;
+        }
+    }
+
+    Ok(())
+}
+
+pub(super) fn handle_message(connection: &Connection, line: &str) -> Option<Value> {
+    let message = match serde_json::from_str::<Value>(line) {
+        Ok(message) => message,
+        Err(error) => {
+            return Some(error_response(
+                Value::Null,
+                PARSE_ERROR,
+                &format!("invalid JSON: {error}"),
+            ));
+        }
+    };
+    let Some(object) = message.as_object() else {
+        return Some(error_response(
+            Value::Null,
+            INVALID_REQUEST,
+            "message must be a JSON-RPC object",
+        ));
+    };
+
+    let id = object.get("id").cloned();
+    let Some(method) = object.get("method").and_then(Value::as_str) else {
+        // Responses to server-initiated requests are not expected; anything else is malformed.
+        if object.contains_key("result") || object.contains_key("error") {
+            return None;
+        }
+        return Some(error_response(
+            id.unwrap_or(Value::Null),
+            INVALID_REQUEST,
+            "missing method",
+        ));
+    };
+    // Notifications carry no id and never receive a response.
+    let id = id?;
+    let params = object.get("params").cloned().unwrap_or(Value::Null);
+
+    let started = Instant::now();
+    let outcome = match method {
+        "initialize" => Ok(initialize_result(&params)),
+        "ping" => Ok(json!({})),
+        "tools/list" => Ok(json!({ "tools": tool_definitions() })),
+        "tools/call" => tools_call_result(connection, &params),
+        _ => Err((
+            METHOD_NOT_FOUND,
+            format!("method '{method}' is not supported"),
+        )),
+    };
+    info!(
+        method,
+        ok = outcome.is_ok(),
+        duration_ms = started.elapsed().as_secs_f64() * 1000.0,
+        "mcp request handled"
+    );
+
+    Some(match outcome {
+        Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+        Err((code, message)) => error_response(id, code, &message),
+    })
+}
+
+fn initialize_result(params: &Value) -> Value {
+    let requested = params
+        .get("protocolVersion")
+        .and_then(Value::as_str)
+        .unwrap_or(LATEST_PROTOCOL_VERSION);
+    let protocol_version = if SUPPORTED_PROTOCOL_VERSIONS.contains(&requested) {
+        requested
+    } else {
+        LATEST_PROTOCOL_VERSION
+    };
+
+    json!({
+        "protocolVersion": protocol_version,
+        "capabilities": { "tools": { "listChanged": false } },
+        "serverInfo": {
+            "name": env!("CARGO_PKG_NAME"),
+            "version": env!("CARGO_PKG_VERSION"),
+        },
+        "instructions": "Search and cite ISO 26262. Every result carries a rendered citation and citation_anchor_id; quote them when answering.",
+    })
+}
+
+fn tools_call_result(
+    connection: &Connection,
+    params: &Value,
+) -> std::result::Result<Value, (i64, String)> {
+    let Some(name) = params.get("name").and_then(Value::as_str) else {
+        return Err((
+            INVALID_PARAMS,
+            "tools/call requires a tool name".to_string(),
+        ));
+    };
+    let arguments = match params.get("arguments") {
+        None | Some(Value::Null) => Map::new(),
+        Some(Value::Object(arguments)) => arguments.clone(),
+        Some(_) => {
+            return Err((
+                INVALID_PARAMS,
+                "tool arguments must be a JSON object".to_string(),
+            ));
+        }
+    };
+
+    let Some(outcome) = call_tool(connection, name, arguments) else {
+        return Err((INVALID_PARAMS, format!("unknown tool '{name}'")));
+    };
+
+    Ok(match outcome {
+        Ok(output) => json!({
+            "content": [{ "type": "text", "text": output.text }],
+            "structuredContent": output.structured,
+            "isError": false,
+        }),
+        Err(error) => {
+            warn!(tool = name, error = %error, "mcp tool call failed");
+            json!({
+                "content": [{ "type": "text", "text": format!("{error:#}") }],
+                "isError": true,
+            })
+        }
+    })
+}
+
+fn error_response(id: Value, code: i64, message: &str) -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "id": id,
+        "error": { "code": code, "message": message },
+    })
+}
@@ -0,0 +1,216 @@
+use rusqlite::Connection;
+use serde_json::{Value, json};
+
+use super::run::{handle_message, serve_stdio};
+
+fn fixture_connection() -> Connection {
+    crate::commands::ingest::open_fixture_database(
+        None,
+        "
+        INSERT INTO docs(doc_id, filename, sha256, part, year, title)
+        VALUES ('ISO26262-6-2018', 'part6.pdf', 'sha', 6, 2018, 'Part 6');
+        INSERT INTO nodes(
+          node_id, parent_node_id, doc_id, node_type, ref, heading, order_index,
+          page_pdf_start, page_pdf_end, text, anchor_type, anchor_label_norm,
+          citation_anchor_id
+        ) VALUES
+          ('n-8.4.1', NULL, 'ISO26262-6-2018', 'clause', '8.4.1',
+           '8.4.1 Software unit verification', 1, 24, 24, NULL, NULL, NULL, 'anchor-8.4.1'),
+          ('n-8.4.1-a', 'n-8.4.1', 'ISO26262-6-2018', 'list_item', '8.4.1', NULL, 2, 24, 24,
+           'walk-through of the software unit design', 'marker', 'a', 'anchor-8.4.1-a'),
+          ('n-table-3', NULL, 'ISO26262-6-2018', 'table', 'Table 3',
+           'Table 3 — Principles for architectural design', 3, 25, 25, NULL, NULL, NULL,
+           'anchor-table-3'),
+          ('n-table-3-row-1', 'n-table-3', 'ISO26262-6-2018', 'table_row', 'Table 3', NULL, 4,
+           25, 25, '1a Hierarchical structure ++ ++ ++ ++', NULL, NULL, 'anchor-table-3-row-1');
+        INSERT INTO chunks(
+          chunk_id, doc_id, type, ref, heading, chunk_seq, page_pdf_start, page_pdf_end,
+          text, source_hash, origin_node_id, leaf_node_type, citation_anchor_id
+        ) VALUES
+          ('ISO26262-6-2018:8.4.1', 'ISO26262-6-2018', 'clause', '8.4.1',
+           '8.4.1 Software unit verification', 0, 24, 24,
+           'The software unit design shall be verified by walk-through of the software unit design.',
+           'hash-clause', 'n-8.4.1', 'clause', 'anchor-8.4.1'),
+          ('ISO26262-6-2018:table-3', 'ISO26262-6-2018', 'table', 'Table 3',
+           'Table 3 — Principles for architectural design', 1, 25, 25,
+           'Methods ASIL 1a Hierarchical structure ++ ++ ++ ++', 'hash-table', 'n-table-3',
+           'table', 'anchor-table-3');
+        ",
+    )
+}
+
+fn request(connection: &Connection, id: u64, method: &str, params: Value) -> Value {
+    let message = json!({ "jsonrpc": "2.0", "id": id, "method": method, "params": params });
+    let response = handle_message(connection, &message.to_string())
+        .expect("requests with an id should be answered");
+    assert_eq!(response["id"], id);
+    response
+}
+
+fn call(connection: &Connection, name: &str, arguments: Value) -> Value {
+    request(
+        connection,
+        7,
+        "tools/call",
+        json!({ "name": name, "arguments": arguments }),
+    )["result"]
+        .clone()
+}
+
+#[test]
+fn mcp_handshake_lists_tools_and_reports_protocol_errors() {
+    let connection = fixture_connection();
+
+    let initialized = request(
+        &connection,
+        1,
+        "initialize",
+        json!({ "protocolVersion": "2025-03-26", "capabilities": {}, "clientInfo": { "name": "editor" } }),
+    );
+    assert_eq!(initialized["result"]["protocolVersion"], "2025-03-26");
+    assert_eq!(
+        initialized["result"]["capabilities"]["tools"]["listChanged"],
+        false
+    );
+    assert!(
+        handle_message(
+            &connection,
+            r#"{"jsonrpc":"2.0","method":"notifications/initialized"}"#
+        )
+        .is_none()
+    );
+
+    let tools = request(&connection, 2, "tools/list", json!({}));
+    let names = tools["result"]["tools"]
+        .as_array()
+        .expect("tools should be listed")
+        .iter()
+        .map(|tool| tool["name"].as_str().unwrap_or_default().to_string())
+        .collect::<Vec<String>>();
+    assert_eq!(
+        names,
+        vec!["search_iso26262", "get_clause", "get_table", "cite"]
+    );
+
+    assert_eq!(
+        request(&connection, 3, "resources/list", json!({}))["error"]["code"],
+        -32601
+    );
+    assert_eq!(
+        request(
+            &connection,
+            4,
+            "tools/call",
+            json!({ "name": "delete_everything" })
+        )["error"]["code"],
+        -32602
+    );
+    assert_eq!(
+        handle_message(&connection, "{not json").expect("parse errors are answered")["error"]["code"],
+        -32700
+    );
+}
+
+#[test]
+fn mcp_tools_return_citations_and_anchor_ids() {
+    let connection = fixture_connection();
+
+    let search = call(
+        &connection,
+        "search_iso26262",
+        json!({ "query": "software unit design", "part": 6, "limit": 3 }),
+    );
+    assert_eq!(search["isError"], false);
+    assert_eq!(
+        search["structuredContent"]["results"][0]["citation_anchor_id"],
+        "anchor-8.4.1"
+    );
+    let text = search["content"][0]["text"]
+        .as_str()
+        .expect("text content should be present");
+    assert!(
+        text.contains("ISO 26262-6:2018, 8.4.1, PDF pages 24 [citation_anchor_id: anchor-8.4.1]")
+    );
+
+    let clause = call(
+        &connection,
+        "get_clause",
+        json!({ "part": 6, "ref": "8.4.1" }),
+    );
+    assert_eq!(clause["isError"], false);
+    let result = &clause["structuredContent"]["results"][0];
+    assert_eq!(result["chunk_id"], "ISO26262-6-2018:8.4.1");
+    assert!(
+        result["text"]
+            .as_str()
+            .expect("full text should be attached")
+            .ends_with("walk-through of the software unit design.")
+    );
+    assert_eq!(
+        result["descendants"][0]["citation"],
+        "ISO 26262-6:2018, 8.4.1(a), PDF pages 24"
+    );
+    assert_eq!(
+        result["descendants"][0]["citation_anchor_id"],
+        "anchor-8.4.1-a"
+    );
+
+    let table = call(&connection, "get_table", json!({ "part": 6, "table": 3 }));
+    assert_eq!(table["isError"], false);
+    assert_eq!(table["structuredContent"]["returned"], 1);
+    assert_eq!(
+        table["structuredContent"]["results"][0]["descendants"][0]["citation_anchor_id"],
+        "anchor-table-3-row-1"
+    );
+
+    let cited = call(&connection, "cite", json!({ "node_id": "n-8.4.1-a" }));
+    assert_eq!(cited["isError"], false);
+    assert_eq!(
+        cited["structuredContent"]["citations"][0]["citation"],
+        "ISO 26262-6:2018, 8.4.1(a), PDF pages 24"
+    );
+    assert_eq!(
+        cited["structuredContent"]["citations"][0]["citation_anchor_id"],
+        "anchor-8.4.1-a"
+    );
+
+    let missing = call(
+        &connection,
+        "get_clause",
+        json!({ "part": 6, "ref": "9.9.9" }),
+    );
+    assert_eq!(missing["isError"], true);
+    let ambiguous = call(
+        &connection,
+        "cite",
+        json!({ "chunk_id": "a", "node_id": "b" }),
+    );
+    assert_eq!(ambiguous["isError"], true);
+}
+
+#[test]
+fn mcp_stdio_loop_answers_requests_line_by_line() {
+    let connection = fixture_connection();
+    let input = [
+        r#"{"jsonrpc":"2.0","id":1,"method":"initialize","params":{"protocolVersion":"1999-01-01"}}"#,
+        r#"{"jsonrpc":"2.0","method":"notifications/initialized"}"#,
+        "",
+        r#"{"jsonrpc":"2.0","id":2,"method":"ping"}"#,
+    ]
+    .join("\n");
+
+    let mut output = Vec::<u8>::new();
+    serve_stdio(&connection, input.as_bytes(), &mut output).expect("stdio loop should finish");
+    let responses = String::from_utf8(output)
+        .expect("output should be UTF-8")
+        .lines()
+        .map(|line| serde_json::from_str::<Value>(line).expect("each line should be JSON"))
+        .collect::<Vec<Value>>();
+
+    assert_eq!(responses.len(), 2);
+    assert_eq!(responses[0]["result"]["protocolVersion"], "2025-06-18");
+    assert_eq!(
+        responses[1],
+        json!({ "jsonrpc": "2.0", "id": 2, "result": {} })
+    );
+}
@@ -0,0 +1,349 @@
+use anyhow::{Context, Result, bail};
+use rusqlite::{Connection, OptionalExtension};
+use serde_json::{Map, Value, json};
+
+use crate::commands::query::{
+    LookupKind, execute_lookup, execute_query, query_args_from_params, query_params_from_json,
+};
+
+const EXACT_LOOKUP_LIMIT: &str = "25";
+
+pub(super) struct ToolOutput {
+    pub(super) text: String,
+    pub(super) structured: Value,
+}
+
+pub(super) fn tool_definitions() -> Vec<Value> {
+    vec![
+        json!({
+            "name": "search_iso26262",
+            "title": "Search ISO 26262",
+            "description": "Search ISO 26262 clauses, tables and notes with lexical, semantic or hybrid retrieval. Each hit carries a rendered citation and citation_anchor_id.",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "query": { "type": "string", "description": "Free text or an exact reference such as '8.4.5' or 'Table 3'." },
+                    "limit": { "type": "integer", "minimum": 1, "default": 10 },
+                    "part": { "type": "integer", "minimum": 1, "maximum": 12 },
+                    "edition": { "type": "integer", "description": "Publication year, e.g. 2018." },
+                    "type": { "type": "string", "description": "Chunk type filter such as 'clause' or 'table'." },
+                    "retrieval_mode": { "type": "string", "enum": ["lexical", "semantic", "hybrid"], "default": "lexical" },
+                    "asil": { "type": "string", "enum": ["A", "B", "C", "D"] },
+                    "with_pinpoint": { "type": "boolean", "default": false },
+                },
+                "required": ["query"],
+            },
+        }),
+        json!({
+            "name": "get_clause",
+            "title": "Get ISO 26262 clause",
+            "description": "Fetch a clause by part and reference with its full text, ancestors and descendant hierarchy. Every node carries a citation and citation_anchor_id.",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "part": { "type": "integer", "minimum": 1, "maximum": 12 },
+                    "ref": { "type": "string", "description": "Clause reference such as '8.4.5'." },
+                    "edition": { "type": "integer", "description": "Publication year, e.g. 2018." },
+                },
+                "required": ["part", "ref"],
+            },
+        }),
+        json!({
+            "name": "get_table",
+            "title": "Get ISO 26262 table",
+            "description": "Fetch a table by part and number with its full text and rows. Every row carries a citation and citation_anchor_id.",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "part": { "type": "integer", "minimum": 1, "maximum": 12 },
+                    "table": { "type": ["string", "integer"], "description": "Table number, e.g. 3 or 'Table 3'." },
+                    "edition": { "type": "integer", "description": "Publication year, e.g. 2018." },
+                },
+                "required": ["part", "table"],
+            },
+        }),
+        json!({
+            "name": "cite",
+            "title": "Cite ISO 26262 passage",
+            "description": "Render the citation for a chunk_id, node_id or citation_anchor_id returned by another tool.",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "chunk_id": { "type": "string" },
+                    "node_id": { "type": "string" },
+                    "citation_anchor_id": { "type": "string" },
+                },
+            },
+        }),
+    ]
+}
+
+pub(super) fn call_tool(
+    connection: &Connection,
+    name: &str,
+    arguments: Map<String, Value>,
+) -> Option<Result<ToolOutput>> {
+    let outcome = match name {
+        "search_iso26262" => search_iso26262(connection, arguments),
+        "get_clause" => get_clause(connection, arguments),
+        "get_table" => get_table(connection, arguments),
+        "cite" => cite(connection, arguments),
+        _ => return None,
+    };
+    Some(outcome)
+}
+
+fn search_iso26262(connection: &Connection, arguments: Map<String, Value>) -> Result<ToolOutput> {
+    let args = query_args_from_params(None, &query_params_from_json(arguments)?)?;
+    if args.query.trim().is_empty() {
+        bail!("'query' must not be empty");
+    }
+
+    let response = serde_json::to_value(execute_query(connection, &args)?)?;
+    Ok(ToolOutput {
+        text: render_results_text(&response),
+        structured: response,
+    })
+}
+
+fn get_clause(connection: &Connection, arguments: Map<String, Value>) -> Result<ToolOutput> {
+    reject_unknown_arguments(&arguments, &["part", "ref", "edition"])?;
+    let part = required_u32(&arguments, "part")?;
+    let reference = required_string(&arguments, "ref")?;
+
+    let response = fetch_exact_reference(
+        connection,
+        &reference,
+        part,
+        optional_u32(&arguments, "edition")?,
+        None,
+    )?
+    .with_context(|| format!("clause {reference} was not found in ISO 26262-{part}"))?;
+    Ok(ToolOutput {
+        text: render_results_text(&response),
+        structured: response,
+    })
+}
+
+fn get_table(connection: &Connection, arguments: Map<String, Value>) -> Result<ToolOutput> {
+    reject_unknown_arguments(&arguments, &["part", "table", "edition"])?;
+    let part = required_u32(&arguments, "part")?;
+    let table = match arguments.get("table") {
+        Some(Value::Number(value)) => value.to_string(),
+        Some(Value::String(value)) => value.trim().to_string(),
+        _ => bail!("'table' must be a table number such as 3 or 'Table 3'"),
+    };
+    let number = match table.get(..5) {
+        Some(prefix) if prefix.eq_ignore_ascii_case("table") => table[5..].trim(),
+        _ => table.as_str(),
+    };
+    if number.is_empty() || !number.chars().all(|character| character.is_ascii_digit()) {
+        bail!("'table' must be a table number such as 3 or 'Table 3', got '{table}'");
+    }
+    let reference = format!("Table {number}");
+
+    let response = fetch_exact_reference(
+        connection,
+        &reference,
+        part,
+        optional_u32(&arguments, "edition")?,
+        Some("table"),
+    )?
+    .with_context(|| format!("{reference} was not found in ISO 26262-{part}"))?;
+    Ok(ToolOutput {
+        text: render_results_text(&response),
+        structured: response,
+    })
+}
+
+fn cite(connection: &Connection, arguments: Map<String, Value>) -> Result<ToolOutput> {
+    reject_unknown_arguments(&arguments, &["chunk_id", "node_id", "citation_anchor_id"])?;
+    let provided = [
+        ("chunk_id", LookupKind::Chunk),
+        ("node_id", LookupKind::Node),
+        ("citation_anchor_id", LookupKind::Citation),
+    ]
+    .into_iter()
+    .filter(|(key, _)| arguments.contains_key(*key))
+    .collect::<Vec<_>>();
+    let [(key, kind)] = provided.as_slice() else {
+        bail!("provide exactly one of 'chunk_id', 'node_id' or 'citation_anchor_id'");
+    };
+    let id = required_string(&arguments, key)?;
+
+    let args = query_args_from_params(Some(&id), &[])?;
+    let response = serde_json::to_value(execute_lookup(connection, *kind, &args)?)?;
+    let citations = results_of(&response)
+        .iter()
+        .map(|result| {
+            json!({
+                "chunk_id": result["chunk_id"],
+                "origin_node_id": result["origin_node_id"],
+                "reference": result["reference"],
+                "heading": result["heading"],
+                "page_pdf_start": result["page_pdf_start"],
+                "page_pdf_end": result["page_pdf_end"],
+                "citation": result["citation"],
+                "citation_anchor_id": result["citation_anchor_id"],
+            })
+        })
+        .collect::<Vec<Value>>();
+    if citations.is_empty() {
+        bail!("no passage matches {key} '{id}'");
+    }
+
+    let text = citations
+        .iter()
+        .map(citation_line)
+        .collect::<Vec<String>>()
+        .join("\n");
+    Ok(ToolOutput {
+        text,
+        structured: json!({ "query": id, "citations": citations }),
+    })
+}
+
+fn fetch_exact_reference(
+    connection: &Connection,
+    reference: &str,
+    part: u32,
+    edition: Option<u32>,
+    chunk_type: Option<&str>,
+) -> Result<Option<Value>> {
+    let mut params = vec![
+        ("query".to_string(), reference.to_string()),
+        ("part".to_string(), part.to_string()),
+        ("limit".to_string(), EXACT_LOOKUP_LIMIT.to_string()),
+        ("with_ancestors".to_string(), "true".to_string()),
+        ("with_descendants".to_string(), "true".to_string()),
+    ];
+    if let Some(edition) = edition {
+        params.push(("edition".to_string(), edition.to_string()));
+    }
+    if let Some(chunk_type) = chunk_type {
+        params.push(("type".to_string(), chunk_type.to_string()));
+    }
+
+    let args = query_args_from_params(None, &params)?;
+    let mut response = serde_json::to_value(execute_query(connection, &args)?)?;
+    let Some(results) = response["results"].as_array_mut() else {
+        return Ok(None);
+    };
+    results.retain(|result| {
+        result["reference"]
+            .as_str()
+            .is_some_and(|value| value.eq_ignore_ascii_case(reference))
+    });
+    if results.is_empty() {
+        return Ok(None);
+    }
+
+    for (index, result) in results.iter_mut().enumerate() {
+        result["rank"] = json!(index + 1);
+        if let Some(chunk_id) = result["chunk_id"].as_str() {
+            result["text"] = json!(load_chunk_text(connection, chunk_id)?);
+        }
+    }
+    let returned = results.len();
+    response["returned"] = json!(returned);
+    Ok(Some(response))
+}
+
+fn load_chunk_text(connection: &Connection, chunk_id: &str) -> Result<Option<String>> {
+    Ok(connection
+        .query_row(
+            "SELECT text FROM chunks WHERE chunk_id = ?1",
+            [chunk_id],
+            |row| row.get::<_, Option<String>>(0),
+        )
+        .optional()?
+        .flatten())
+}
+
+fn render_results_text(response: &Value) -> String {
+    let results = results_of(response);
+    if results.is_empty() {
+        return format!(
+            "No ISO 26262 passages matched '{}'.",
+            response["query"].as_str().unwrap_or_default()
+        );
+    }
+
+    let mut lines = Vec::new();
+    for result in results {
+        lines.push(format!(
+            "{}. {}",
+            result["rank"].as_u64().unwrap_or_default(),
+            citation_line(result)
+        ));
+        if let Some(heading) = result["heading"].as_str().filter(|value| !value.is_empty()) {
+            lines.push(format!("   {heading}"));
+        }
+        let body = result["text"]
+            .as_str()
+            .or_else(|| result["snippet"].as_str())
+            .unwrap_or_default()
+            .trim();
+        if !body.is_empty() {
+            lines.push(format!("   {body}"));
+        }
+        for descendant in result["descendants"].as_array().into_iter().flatten() {
+            let label = descendant["heading"]
+                .as_str()
+                .or_else(|| descendant["text_preview"].as_str())
+                .unwrap_or_default();
+            lines.push(format!("   - {} {label}", citation_line(descendant)));
+        }
+    }
+    lines.join("\n")
+}
+
+fn citation_line(value: &Value) -> String {
+    format!(
+        "{} [citation_anchor_id: {}]",
+        value["citation"].as_str().unwrap_or_default(),
+        value["citation_anchor_id"].as_str().unwrap_or("none")
+    )
+}
+
+fn results_of(response: &Value) -> &[Value] {
+    response["results"]
+        .as_array()
+        .map(Vec::as_slice)
+        .unwrap_or_default()
+}
+
+fn reject_unknown_arguments(arguments: &Map<String, Value>, accepted: &[&str]) -> Result<()> {
+    if let Some(key) = arguments
+        .keys()
+        .find(|key| !accepted.contains(&key.as_str()))
+    {
+        bail!(
+            "unknown argument '{key}'; expected one of {}",
+            accepted.join(", ")
+        );
+    }
+    Ok(())
+}
+
+fn required_string(arguments: &Map<String, Value>, key: &str) -> Result<String> {
+    match arguments.get(key) {
+        Some(Value::String(value)) if !value.trim().is_empty() => Ok(value.trim().to_string()),
+        _ => bail!("'{key}' must be a non-empty string"),
+    }
+}
+
+fn required_u32(arguments: &Map<String, Value>, key: &str) -> Result<u32> {
+    optional_u32(arguments, key)?.with_context(|| format!("'{key}' is required"))
+}
+
+fn optional_u32(arguments: &Map<String, Value>, key: &str) -> Result<Option<u32>> {
+    match arguments.get(key) {
+        None | Some(Value::Null) => Ok(None),
+        Some(value) => value
+            .as_u64()
+            .and_then(|value| u32::try_from(value).ok())
+            .map(Some)
+            .with_context(|| format!("'{key}' must be a positive integer")),
+    }
+}
@@ -1,6 +1,13 @@
+pub mod define;
+pub mod diff_editions;
 pub mod embed;
+pub mod export;
 pub mod ingest;
 pub mod inventory;
+pub mod mcp;
 pub mod query;
+pub mod refs;
+pub mod serve;
+pub mod shell;
 pub mod status;
 pub mod validate;
@@ -0,0 +1,179 @@
+use anyhow::{bail, Result};
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::Serialize;
+
+use super::request_error::QueryRequestError;
+use super::run::QueryCandidate;
+
+#[derive(Debug, Clone, Serialize)]
+pub(super) struct AsilRecommendationHit {
+    pub(super) marker: String,
+    pub(super) method_text: String,
+    pub(super) rating: String,
+    pub(super) recommendation_level: String,
+}
+
+#[derive(Debug, Default)]
+pub(super) struct AsilExclusionSql<'a> {
+    pub(super) join: String,
+    pub(super) condition: String,
+    pub(super) asil: Option<&'a String>,
+}
+
+#[derive(Debug)]
+pub(super) struct AsilProfile {
+    pub(super) asil: String,
+    pub(super) exclude_not_recommended: bool,
+}
+
+impl AsilProfile {
+    pub(super) fn load(
+        connection: &Connection,
+        asil: &str,
+        exclude_not_recommended: bool,
+    ) -> Result<Self> {
+        let table_exists = connection
+            .query_row(
+                "
+                SELECT name
+                FROM sqlite_master
+                WHERE type = 'table' AND name = 'asil_recommendations'
+                LIMIT 1
+                ",
+                [],
+                |row| row.get::<_, String>(0),
+            )
+            .optional()?
+            .is_some();
+        if !table_exists {
+            bail!(QueryRequestError::new(
+                "--asil requires the asil_recommendations table; re-run ingest to populate it"
+            ));
+        }
+
+        Ok(Self {
+            asil: asil.to_string(),
+            exclude_not_recommended,
+        })
+    }
+
+    fn hits_for_candidate(
+        &self,
+        connection: &Connection,
+        candidate: &QueryCandidate,
+    ) -> Result<Vec<AsilRecommendationHit>> {
+        let Some(origin_node_id) = candidate.origin_node_id.as_deref() else {
+            return Ok(Vec::new());
+        };
+
+        let mut statement = connection.prepare_cached(&format!(
+            "
+            SELECT marker, method_text, rating, recommendation_level
+            FROM asil_recommendations
+            WHERE asil = ?1 AND {} IN (row_node_id, table_node_id)
+            ORDER BY table_node_id ASC, row_node_id ASC
+            ",
+            recommendation_node_sql("?2", "?3")
+        ))?;
+        let hits = statement
+            .query_map(
+                params![self.asil, origin_node_id, candidate.leaf_node_type],
+                |row| {
+                    Ok(AsilRecommendationHit {
+                        marker: row.get(0)?,
+                        method_text: row.get(1)?,
+                        rating: row.get(2)?,
+                        recommendation_level: row.get(3)?,
+                    })
+                },
+            )?
+            .collect::<rusqlite::Result<Vec<AsilRecommendationHit>>>()?;
+        Ok(hits)
+    }
+}
+
+/// Builds the join and condition that drop rows not recommended at the profile's ASIL, with the
+/// level bound at `param`; empty unless `--exclude-not-recommended` is set.
+pub(super) fn asil_exclusion_sql<'a>(
+    profile: Option<&'a AsilProfile>,
+    origin_node_column: &str,
+    leaf_node_type_column: &str,
+    param: usize,
+) -> AsilExclusionSql<'a> {
+    let Some(profile) = profile.filter(|profile| profile.exclude_not_recommended) else {
+        return AsilExclusionSql::default();
+    };
+
+    let join = format!(
+        "
+        LEFT JOIN (
+          SELECT
+            node_id,
+            MIN(recommendation_level IN ('no_recommendation', 'not_recommended')) AS not_recommended
+          FROM (
+            SELECT row_node_id AS node_id, recommendation_level
+            FROM asil_recommendations
+            WHERE asil = ?{param}
+            UNION ALL
+            SELECT table_node_id, recommendation_level
+            FROM asil_recommendations
+            WHERE asil = ?{param}
+          )
+          GROUP BY node_id
+        ) asil_verdict ON asil_verdict.node_id = {}",
+        recommendation_node_sql(origin_node_column, leaf_node_type_column)
+    );
+    AsilExclusionSql {
+        join,
+        condition: "\n          AND COALESCE(asil_verdict.not_recommended, 0) = 0".to_string(),
+        asil: Some(&profile.asil),
+    }
+}
+
+fn recommendation_node_sql(origin_node: &str, leaf_node_type: &str) -> String {
+    format!(
+        "CASE WHEN {leaf_node_type} = 'table_cell' \
+         THEN (SELECT parent_node_id FROM nodes WHERE node_id = {origin_node}) \
+         ELSE {origin_node} END"
+    )
+}
+
+pub(super) fn annotate_asil_recommendations(
+    connection: &Connection,
+    candidates: &mut [QueryCandidate],
+    profile: Option<&AsilProfile>,
+) -> Result<()> {
+    let Some(profile) = profile else {
+        return Ok(());
+    };
+
+    for candidate in candidates.iter_mut() {
+        let hits = profile.hits_for_candidate(connection, candidate)?;
+        candidate.asil_demoted = is_not_recommended_at_asil(&hits);
+        candidate.asil_recommendations = Some(hits);
+    }
+
+    Ok(())
+}
+
+pub(super) fn is_not_recommended_at_asil(hits: &[AsilRecommendationHit]) -> bool {
+    !hits.is_empty()
+        && hits.iter().all(|hit| {
+            matches!(
+                hit.recommendation_level.as_str(),
+                "no_recommendation" | "not_recommended"
+            )
+        })
+}
+
+pub(super) fn summarize_recommendation_level(hits: &[AsilRecommendationHit]) -> Option<String> {
+    let first = hits.first()?;
+    if hits
+        .iter()
+        .all(|hit| hit.recommendation_level == first.recommendation_level)
+    {
+        Some(first.recommendation_level.clone())
+    } else {
+        Some("mixed".to_string())
+    }
+}
@@ -11,16 +11,35 @@ pub(super) fn format_page_range(start: Option<i64>, end: Option<i64>) -> String
 }
 
 pub(super) fn render_citation(candidate: &QueryCandidate) -> String {
-    let reference = if candidate.reference.is_empty() {
+    render_anchor_citation(&CitationSource {
+        part: candidate.part,
+        year: candidate.year,
+        reference: &candidate.reference,
+        anchor_type: candidate.anchor_type.as_deref(),
+        anchor_label_norm: candidate.anchor_label_norm.as_deref(),
+        page_pdf_start: candidate.page_pdf_start,
+        page_pdf_end: candidate.page_pdf_end,
+    })
+}
+
+pub(crate) struct CitationSource<'a> {
+    pub(crate) part: u32,
+    pub(crate) year: u32,
+    pub(crate) reference: &'a str,
+    pub(crate) anchor_type: Option<&'a str>,
+    pub(crate) anchor_label_norm: Option<&'a str>,
+    pub(crate) page_pdf_start: Option<i64>,
+    pub(crate) page_pdf_end: Option<i64>,
+}
+
+pub(crate) fn render_anchor_citation(source: &CitationSource<'_>) -> String {
+    let reference = if source.reference.is_empty() {
         "(unreferenced chunk)".to_string()
     } else {
-        candidate.reference.clone()
+        source.reference.to_string()
     };
 
-    let reference_with_anchor = match (
-        candidate.anchor_type.as_deref(),
-        candidate.anchor_label_norm.as_deref(),
-    ) {
+    let reference_with_anchor = match (source.anchor_type, source.anchor_label_norm) {
         (Some("marker"), Some(label)) if !label.is_empty() => {
             let base = marker_base_reference(&reference);
             if label.starts_with("NOTE") {
@@ -38,10 +57,10 @@ pub(super) fn render_citation(candidate: &QueryCandidate) -> String {
 
     format!(
         "ISO 26262-{}:{}, {}, PDF pages {}",
-        candidate.part,
-        candidate.year,
+        source.part,
+        source.year,
         reference_with_anchor,
-        format_page_range(candidate.page_pdf_start, candidate.page_pdf_end)
+        format_page_range(source.page_pdf_start, source.page_pdf_end)
     )
 }
 
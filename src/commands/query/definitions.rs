@@ -0,0 +1,160 @@
+use anyhow::{bail, Context, Result};
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::Serialize;
+
+use super::citation::{render_anchor_citation, CitationSource};
+use super::request_error::QueryRequestError;
+
+const MAX_INLINE_DEFINITIONS: i64 = 8;
+
+const TERM_SELECT_COLUMNS: &str = "
+  t.term_id,
+  t.doc_id,
+  d.part,
+  d.year,
+  t.ref,
+  t.term,
+  t.abbreviation,
+  t.definition,
+  t.notes,
+  t.page_pdf_start,
+  t.page_pdf_end
+";
+
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct TermDefinition {
+    pub(crate) term_id: String,
+    pub(crate) doc_id: String,
+    pub(crate) reference: String,
+    pub(crate) term: String,
+    pub(crate) abbreviation: Option<String>,
+    pub(crate) definition: String,
+    pub(crate) notes: Vec<String>,
+    pub(crate) citation: String,
+    pub(crate) occurrence_count: Option<i64>,
+}
+
+pub(crate) fn ensure_terms_available(connection: &Connection) -> Result<()> {
+    let table_exists = connection
+        .query_row(
+            "
+            SELECT name
+            FROM sqlite_master
+            WHERE type = 'table' AND name = 'terms'
+            LIMIT 1
+            ",
+            [],
+            |row| row.get::<_, String>(0),
+        )
+        .optional()?
+        .is_some();
+    if !table_exists {
+        bail!(QueryRequestError::new(
+            "terms table is missing; re-run ingest with Part 1 to build the glossary"
+        ));
+    }
+    Ok(())
+}
+
+pub(crate) fn lookup_term_definitions(
+    connection: &Connection,
+    term: &str,
+    limit: usize,
+) -> Result<Vec<TermDefinition>> {
+    let term_norm = term
+        .split_whitespace()
+        .collect::<Vec<&str>>()
+        .join(" ")
+        .to_lowercase();
+    if term_norm.is_empty() {
+        bail!(QueryRequestError::new("term must not be empty"));
+    }
+    let limit = limit.max(1) as i64;
+
+    let mut exact_statement = connection.prepare_cached(&format!(
+        "
+        SELECT {TERM_SELECT_COLUMNS}, NULL
+        FROM terms t
+        JOIN docs d ON d.doc_id = t.doc_id
+        WHERE t.term_norm = ?1 OR lower(COALESCE(t.abbreviation, '')) = ?1
+        ORDER BY d.year DESC, t.ref ASC
+        LIMIT ?2
+        "
+    ))?;
+    let exact = collect_term_definitions(&mut exact_statement, params![term_norm, limit])?;
+    if !exact.is_empty() {
+        return Ok(exact);
+    }
+
+    let mut partial_statement = connection.prepare_cached(&format!(
+        "
+        SELECT {TERM_SELECT_COLUMNS}, NULL
+        FROM terms t
+        JOIN docs d ON d.doc_id = t.doc_id
+        WHERE t.term_norm LIKE '%' || ?1 || '%'
+        ORDER BY length(t.term_norm) ASC, d.year DESC, t.ref ASC
+        LIMIT ?2
+        "
+    ))?;
+    collect_term_definitions(&mut partial_statement, params![term_norm, limit])
+}
+
+pub(super) fn fetch_chunk_definitions(
+    connection: &Connection,
+    chunk_id: &str,
+) -> Result<Vec<TermDefinition>> {
+    let mut statement = connection.prepare_cached(&format!(
+        "
+        SELECT {TERM_SELECT_COLUMNS}, o.occurrence_count
+        FROM term_occurrences o
+        JOIN terms t ON t.term_id = o.term_id
+        JOIN docs d ON d.doc_id = t.doc_id
+        WHERE o.chunk_id = ?1
+        ORDER BY o.occurrence_count DESC, t.term_norm ASC
+        LIMIT ?2
+        "
+    ))?;
+
+    collect_term_definitions(&mut statement, params![chunk_id, MAX_INLINE_DEFINITIONS])
+}
+
+fn collect_term_definitions(
+    statement: &mut rusqlite::Statement<'_>,
+    params: impl rusqlite::Params,
+) -> Result<Vec<TermDefinition>> {
+    let mut rows = statement.query(params)?;
+    let mut out = Vec::new();
+
+    while let Some(row) = rows.next()? {
+        let term_id: String = row.get(0)?;
+        let part: u32 = row.get(2)?;
+        let year: u32 = row.get(3)?;
+        let reference: String = row.get(4)?;
+        let notes_json: String = row.get(8)?;
+        let notes = serde_json::from_str::<Vec<String>>(&notes_json)
+            .with_context(|| format!("invalid notes payload for {term_id}"))?;
+        let citation = render_anchor_citation(&CitationSource {
+            part,
+            year,
+            reference: &reference,
+            anchor_type: Some("clause"),
+            anchor_label_norm: Some(&reference),
+            page_pdf_start: row.get(9)?,
+            page_pdf_end: row.get(10)?,
+        });
+
+        out.push(TermDefinition {
+            term_id,
+            doc_id: row.get(1)?,
+            reference,
+            term: row.get(5)?,
+            abbreviation: row.get(6)?,
+            definition: row.get(7)?,
+            notes,
+            citation,
+            occurrence_count: row.get(11)?,
+        });
+    }
+
+    Ok(out)
+}
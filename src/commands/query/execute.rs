@@ -0,0 +1,304 @@
+use std::time::Instant;
+
+use anyhow::{bail, Result};
+use rusqlite::Connection;
+use tracing::{info, warn};
+
+use crate::cli::{FusionMode, QueryArgs, QueryGranularity, RetrievalMode};
+
+use super::asil::AsilProfile;
+use super::definitions::ensure_terms_available;
+use super::fusion::fuse_rrf_candidates;
+use super::intent::is_exact_intent_query;
+use super::node_retrieval::collect_lexical_node_candidates;
+use super::references::ensure_reference_graph_available;
+use super::request_error::QueryRequestError;
+use super::requirements::{ensure_requirement_columns_available, retain_requirement_modality};
+use super::result_hydration::to_results;
+use super::retrieval::collect_lexical_candidates;
+use super::run::{
+    enforce_timeout, sort_candidates, QueryCandidate, QueryResponse, QueryTimeoutBudget,
+    RetrievalMetadata,
+};
+use super::semantic_retrieval::{retrieve_semantic_candidates, SemanticRetrieval};
+use super::syntax::LexicalQuery;
+
+const MAX_QUERY_CANDIDATES: usize = 256;
+
+pub(crate) fn execute_query(connection: &Connection, args: &QueryArgs) -> Result<QueryResponse> {
+    let query_started = Instant::now();
+    let query_text = args.query.trim();
+    if query_text.is_empty() {
+        bail!(QueryRequestError::new("query must not be empty"));
+    }
+    let lexical_query = LexicalQuery::parse(query_text)?;
+
+    let chunk_type_filter = args
+        .chunk_type
+        .as_deref()
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .map(str::to_lowercase);
+    let node_type_filter = args
+        .node_type
+        .as_deref()
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .map(str::to_lowercase);
+
+    let limit = args.limit.max(1);
+    let pinpoint_max_units = args.pinpoint_max_units.clamp(1, 12);
+    let lexical_k = clamp_candidates(args.lexical_k.max(limit));
+    let semantic_k = clamp_candidates(args.semantic_k.max(limit));
+    let timeout_budget = QueryTimeoutBudget::new(args.timeout_ms);
+    let exact_intent = is_exact_intent_query(query_text);
+    let asil_profile = args
+        .asil
+        .map(|level| AsilProfile::load(connection, level.as_str(), args.exclude_not_recommended))
+        .transpose()?;
+    let modality_filter = args.modality.map(|modality| modality.as_str());
+    if modality_filter.is_some() {
+        ensure_requirement_columns_available(connection)?;
+    }
+
+    let requested_mode = args.retrieval_mode;
+    let exact_intent_forced_lexical = exact_intent
+        && matches!(
+            requested_mode,
+            RetrievalMode::Hybrid | RetrievalMode::Semantic
+        );
+    let mut effective_mode = if exact_intent_forced_lexical {
+        RetrievalMode::Lexical
+    } else {
+        requested_mode
+    };
+
+    let mut fallback_used = false;
+    let mut fallback_reason = None::<String>;
+    let semantic_model_id = args.semantic_model_id.as_ref().map(|value| {
+        let trimmed = value.trim();
+        if trimmed.is_empty() {
+            None
+        } else {
+            Some(trimmed.to_string())
+        }
+    });
+    let semantic_model_id = semantic_model_id.flatten();
+
+    let mut lexical_candidates = Vec::<QueryCandidate>::new();
+    if matches!(
+        effective_mode,
+        RetrievalMode::Lexical | RetrievalMode::Hybrid
+    ) {
+        lexical_candidates = collect_granular_lexical_candidates(
+            connection,
+            args,
+            &lexical_query,
+            chunk_type_filter.as_deref(),
+            node_type_filter.as_deref(),
+            modality_filter,
+            asil_profile.as_ref(),
+            lexical_k,
+        )?;
+        enforce_timeout(timeout_budget, "lexical retrieval")?;
+    }
+
+    let mut semantic = SemanticRetrieval::default();
+    if matches!(
+        effective_mode,
+        RetrievalMode::Semantic | RetrievalMode::Hybrid
+    ) {
+        semantic = retrieve_semantic_candidates(
+            connection,
+            args,
+            &lexical_query,
+            semantic_model_id.as_deref(),
+            chunk_type_filter.as_deref(),
+            node_type_filter.as_deref(),
+            asil_profile.as_ref(),
+            semantic_k,
+            timeout_budget,
+        )?;
+        if let Some(reason) = semantic.unavailable_reason.take() {
+            warn!(reason = %reason, "semantic retrieval unavailable; falling back to lexical");
+            fallback_used = true;
+            fallback_reason = Some(reason);
+            effective_mode = RetrievalMode::Lexical;
+            if lexical_candidates.is_empty() {
+                lexical_candidates = collect_granular_lexical_candidates(
+                    connection,
+                    args,
+                    &lexical_query,
+                    chunk_type_filter.as_deref(),
+                    node_type_filter.as_deref(),
+                    modality_filter,
+                    asil_profile.as_ref(),
+                    lexical_k,
+                )?;
+            }
+        }
+    }
+
+    let lexical_candidate_count = lexical_candidates.len();
+    let semantic_candidate_count = semantic.candidates.len();
+    let mut candidates = match effective_mode {
+        RetrievalMode::Lexical => lexical_candidates,
+        RetrievalMode::Semantic => semantic.candidates,
+        RetrievalMode::Hybrid => fuse_rrf_candidates(
+            &lexical_candidates,
+            &semantic.candidates,
+            args.rrf_k,
+            args.fusion,
+        )?,
+    };
+    retain_requirement_modality(connection, &mut candidates, modality_filter)?;
+    let fused_candidate_count = candidates.len();
+
+    sort_candidates(&mut candidates);
+    if candidates.len() > limit {
+        candidates.truncate(limit);
+    }
+    enforce_timeout(timeout_budget, "candidate ranking")?;
+
+    if args.with_references || args.with_referenced_by {
+        ensure_reference_graph_available(connection)?;
+    }
+    if args.with_definitions {
+        ensure_terms_available(connection)?;
+    }
+
+    let results = to_results(
+        connection,
+        query_text,
+        candidates,
+        args.with_ancestors,
+        args.with_descendants,
+        args.granularity == QueryGranularity::Node,
+        args.with_references,
+        args.with_referenced_by,
+        args.with_definitions,
+        args.with_pinpoint,
+        pinpoint_max_units,
+    )?;
+    enforce_timeout(timeout_budget, "result hydration")?;
+
+    let query_duration_ms = query_started.elapsed().as_secs_f64() * 1000.0;
+
+    let retrieval_metadata = RetrievalMetadata {
+        requested_mode: retrieval_mode_label(requested_mode).to_string(),
+        effective_mode: retrieval_mode_label(effective_mode).to_string(),
+        granularity: granularity_label(args.granularity).to_string(),
+        lexical_k,
+        semantic_k,
+        lexical_candidate_count,
+        semantic_candidate_count,
+        fused_candidate_count,
+        fusion: fusion_mode_label(args.fusion).to_string(),
+        rrf_k: args.rrf_k,
+        semantic_model_id,
+        semantic_index: semantic.index,
+        semantic_index_reason: semantic.index_reason,
+        semantic_ann_candidate_count: semantic.ann_candidate_count,
+        exact_intent,
+        exact_intent_forced_lexical,
+        fallback_used,
+        fallback_reason,
+        pinpoint_enabled: args.with_pinpoint,
+        pinpoint_max_units,
+        asil: asil_profile.as_ref().map(|profile| profile.asil.clone()),
+        asil_exclude_not_recommended: args.exclude_not_recommended,
+        timeout_ms: args.timeout_ms,
+        query_duration_ms,
+    };
+
+    info!(
+        query = %query_text,
+        requested_mode = %retrieval_metadata.requested_mode,
+        effective_mode = %retrieval_metadata.effective_mode,
+        part_filter = ?args.part,
+        edition_filter = ?args.edition,
+        chunk_type_filter = ?chunk_type_filter,
+        node_type_filter = ?node_type_filter,
+        asil = ?retrieval_metadata.asil,
+        lexical_candidate_count,
+        semantic_candidate_count,
+        fused_candidate_count,
+        query_duration_ms,
+        result_count = results.len(),
+        "query completed"
+    );
+
+    Ok(QueryResponse {
+        query: query_text.to_string(),
+        limit,
+        returned: results.len(),
+        part_filter: args.part,
+        edition_filter: args.edition,
+        chunk_type_filter,
+        node_type_filter,
+        modality_filter: modality_filter.map(ToOwned::to_owned),
+        retrieval: retrieval_metadata,
+        results,
+    })
+}
+
+fn retrieval_mode_label(value: RetrievalMode) -> &'static str {
+    match value {
+        RetrievalMode::Lexical => "lexical",
+        RetrievalMode::Semantic => "semantic",
+        RetrievalMode::Hybrid => "hybrid",
+    }
+}
+
+fn granularity_label(value: QueryGranularity) -> &'static str {
+    match value {
+        QueryGranularity::Chunk => "chunk",
+        QueryGranularity::Node => "node",
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn collect_granular_lexical_candidates(
+    connection: &Connection,
+    args: &QueryArgs,
+    lexical_query: &LexicalQuery,
+    chunk_type_filter: Option<&str>,
+    node_type_filter: Option<&str>,
+    modality_filter: Option<&str>,
+    asil_profile: Option<&AsilProfile>,
+    lexical_k: usize,
+) -> Result<Vec<QueryCandidate>> {
+    match args.granularity {
+        QueryGranularity::Chunk => collect_lexical_candidates(
+            connection,
+            lexical_query,
+            args.part,
+            args.edition,
+            chunk_type_filter,
+            node_type_filter,
+            modality_filter,
+            asil_profile,
+            lexical_k,
+        ),
+        QueryGranularity::Node => collect_lexical_node_candidates(
+            connection,
+            lexical_query,
+            args.part,
+            args.edition,
+            node_type_filter,
+            modality_filter,
+            asil_profile,
+            lexical_k,
+        ),
+    }
+}
+
+fn fusion_mode_label(value: FusionMode) -> &'static str {
+    match value {
+        FusionMode::Rrf => "rrf",
+    }
+}
+
+fn clamp_candidates(value: usize) -> usize {
+    value.clamp(1, MAX_QUERY_CANDIDATES)
+}
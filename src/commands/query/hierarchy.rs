@@ -1,22 +1,27 @@
 use anyhow::Result;
-use rusqlite::{params, Connection};
+use rusqlite::{params, Connection, OptionalExtension};
 
-use super::run::DescendantNode;
+use crate::semantic::resolve_parent_chunk_id;
+
+use super::citation::{render_anchor_citation, CitationSource};
+use super::run::{DescendantNode, ParentChunkContext, QueryCandidate};
 use super::text::condense_whitespace;
 
 pub(super) fn fetch_descendants(
     connection: &Connection,
     origin_node_id: &str,
 ) -> Result<Vec<DescendantNode>> {
-    let mut statement = connection.prepare(
+    let mut statement = connection.prepare_cached(
         "
         WITH RECURSIVE descendants(
-          node_id, parent_node_id, node_type, ref, heading,
-          order_index, page_pdf_start, page_pdf_end, text, depth
+          node_id, parent_node_id, doc_id, node_type, ref, heading,
+          order_index, page_pdf_start, page_pdf_end, text,
+          anchor_type, anchor_label_norm, citation_anchor_id, depth
         ) AS (
           SELECT
             n.node_id,
             n.parent_node_id,
+            n.doc_id,
             n.node_type,
             n.ref,
             n.heading,
@@ -24,6 +29,9 @@ pub(super) fn fetch_descendants(
             n.page_pdf_start,
             n.page_pdf_end,
             n.text,
+            n.anchor_type,
+            n.anchor_label_norm,
+            n.citation_anchor_id,
             1
           FROM nodes n
           WHERE n.parent_node_id = ?1
@@ -33,6 +41,7 @@ pub(super) fn fetch_descendants(
           SELECT
             n.node_id,
             n.parent_node_id,
+            n.doc_id,
             n.node_type,
             n.ref,
             n.heading,
@@ -40,23 +49,32 @@ pub(super) fn fetch_descendants(
             n.page_pdf_start,
             n.page_pdf_end,
             n.text,
+            n.anchor_type,
+            n.anchor_label_norm,
+            n.citation_anchor_id,
             d.depth + 1
           FROM nodes n
           JOIN descendants d ON n.parent_node_id = d.node_id
           WHERE d.depth < 8
         )
         SELECT
-          node_id,
-          parent_node_id,
-          node_type,
-          ref,
-          heading,
-          order_index,
-          page_pdf_start,
-          page_pdf_end,
-          substr(COALESCE(text, ''), 1, 180)
-        FROM descendants
-        ORDER BY depth, order_index, node_id
+          ds.node_id,
+          ds.parent_node_id,
+          ds.node_type,
+          ds.ref,
+          ds.heading,
+          ds.order_index,
+          ds.page_pdf_start,
+          ds.page_pdf_end,
+          substr(COALESCE(ds.text, ''), 1, 180),
+          d.part,
+          d.year,
+          ds.anchor_type,
+          ds.anchor_label_norm,
+          ds.citation_anchor_id
+        FROM descendants ds
+        JOIN docs d ON d.doc_id = ds.doc_id
+        ORDER BY ds.depth, ds.order_index, ds.node_id
         LIMIT 256
         ",
     )?;
@@ -65,18 +83,34 @@ pub(super) fn fetch_descendants(
     let mut descendants = Vec::new();
 
     while let Some(row) = rows.next()? {
+        let reference: Option<String> = row.get(3)?;
+        let page_pdf_start: Option<i64> = row.get(6)?;
+        let page_pdf_end: Option<i64> = row.get(7)?;
+        let anchor_type: Option<String> = row.get(11)?;
+        let anchor_label_norm: Option<String> = row.get(12)?;
+        let citation = render_anchor_citation(&CitationSource {
+            part: row.get::<_, u32>(9)?,
+            year: row.get::<_, u32>(10)?,
+            reference: reference.as_deref().unwrap_or(""),
+            anchor_type: anchor_type.as_deref(),
+            anchor_label_norm: anchor_label_norm.as_deref(),
+            page_pdf_start,
+            page_pdf_end,
+        });
         descendants.push(DescendantNode {
             node_id: row.get(0)?,
             parent_node_id: row.get(1)?,
             node_type: row.get(2)?,
-            reference: row.get(3)?,
+            reference,
             heading: row.get(4)?,
             order_index: row.get(5)?,
-            page_pdf_start: row.get(6)?,
-            page_pdf_end: row.get(7)?,
+            page_pdf_start,
+            page_pdf_end,
             text_preview: row
                 .get::<_, Option<String>>(8)?
                 .map(|value| condense_whitespace(&value)),
+            citation,
+            citation_anchor_id: row.get(13)?,
         });
     }
 
@@ -123,3 +157,58 @@ pub(super) fn resolve_parent_ref(
 
     Ok(parent_ref)
 }
+
+pub(super) fn fetch_parent_chunk(
+    connection: &Connection,
+    candidate: &QueryCandidate,
+) -> Result<Option<ParentChunkContext>> {
+    let Some(node_id) = candidate.chunk_id.strip_prefix("node::") else {
+        return Ok(None);
+    };
+    let Some(chunk_id) = resolve_parent_chunk_id(connection, node_id)? else {
+        return Ok(None);
+    };
+
+    let parent = connection
+        .query_row(
+            "
+            SELECT
+              c.type,
+              COALESCE(c.ref, ''),
+              COALESCE(c.heading, ''),
+              c.page_pdf_start,
+              c.page_pdf_end,
+              substr(COALESCE(c.text, ''), 1, 420)
+            FROM chunks c
+            WHERE c.chunk_id = ?1
+            ",
+            [&chunk_id],
+            |row| {
+                let reference: String = row.get(1)?;
+                let page_pdf_start: Option<i64> = row.get(3)?;
+                let page_pdf_end: Option<i64> = row.get(4)?;
+                let citation = render_anchor_citation(&CitationSource {
+                    part: candidate.part,
+                    year: candidate.year,
+                    reference: &reference,
+                    anchor_type: None,
+                    anchor_label_norm: None,
+                    page_pdf_start,
+                    page_pdf_end,
+                });
+                Ok(ParentChunkContext {
+                    chunk_id: chunk_id.clone(),
+                    chunk_type: row.get(0)?,
+                    reference,
+                    heading: row.get(2)?,
+                    page_pdf_start,
+                    page_pdf_end,
+                    snippet: condense_whitespace(&row.get::<_, String>(5)?),
+                    citation,
+                })
+            },
+        )
+        .optional()?;
+
+    Ok(parent)
+}
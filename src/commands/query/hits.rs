@@ -0,0 +1,46 @@
+use crate::index::{Citation, SearchHit, SearchResults};
+
+use super::run::{QueryResponse, QueryResult};
+
+impl QueryResponse {
+    pub(crate) fn into_search_results(self) -> SearchResults {
+        SearchResults {
+            query: self.query,
+            effective_mode: self.retrieval.effective_mode,
+            fallback_reason: self.retrieval.fallback_reason,
+            hits: self.results.into_iter().map(SearchHit::from).collect(),
+        }
+    }
+
+    pub(crate) fn result_citation(&self, rank: usize) -> Option<Citation> {
+        self.results
+            .iter()
+            .find(|result| result.rank == rank)
+            .map(|result| SearchHit::from(result.clone()).citation)
+    }
+}
+
+impl From<QueryResult> for SearchHit {
+    fn from(result: QueryResult) -> Self {
+        SearchHit {
+            rank: result.rank,
+            score: result.score,
+            match_kind: result.match_kind,
+            chunk_id: result.chunk_id,
+            origin_node_id: result.origin_node_id,
+            doc_id: result.doc_id,
+            chunk_type: result.chunk_type,
+            heading: result.heading,
+            snippet: result.snippet,
+            citation: Citation {
+                text: result.citation,
+                citation_anchor_id: result.citation_anchor_id,
+                part: result.part,
+                year: result.year,
+                reference: result.reference,
+                page_pdf_start: result.page_pdf_start,
+                page_pdf_end: result.page_pdf_end,
+            },
+        }
+    }
+}
@@ -5,7 +5,7 @@ pub(super) fn is_exact_intent_query(query_text: &str) -> bool {
     }
 
     let lowered = trimmed.to_ascii_lowercase();
-    if lowered.starts_with("table ") {
+    if lowered.starts_with("table ") || lowered.starts_with("figure ") {
         return lowered
             .split_whitespace()
             .nth(1)
@@ -0,0 +1,263 @@
+use std::time::Instant;
+
+use anyhow::{bail, Result};
+use rusqlite::{params, Connection, Params};
+
+use crate::cli::QueryArgs;
+
+use super::definitions::ensure_terms_available;
+use super::references::ensure_reference_graph_available;
+use super::request_error::QueryRequestError;
+use super::result_hydration::to_results;
+use super::run::{QueryCandidate, QueryResponse, RetrievalMetadata};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum LookupKind {
+    Chunk,
+    Node,
+    Citation,
+    Reference,
+}
+
+impl LookupKind {
+    fn label(self) -> &'static str {
+        match self {
+            LookupKind::Chunk => "chunk_id",
+            LookupKind::Node => "node_id",
+            LookupKind::Citation => "citation_anchor_id",
+            LookupKind::Reference => "reference",
+        }
+    }
+}
+
+const LOOKUP_CHUNK_SELECT: &str = "
+    SELECT
+      c.chunk_id,
+      c.doc_id,
+      d.part,
+      d.year,
+      c.type,
+      COALESCE(c.ref, ''),
+      COALESCE(c.heading, ''),
+      c.page_pdf_start,
+      c.page_pdf_end,
+      COALESCE(c.source_hash, ''),
+      substr(COALESCE(c.text, ''), 1, 420),
+      c.origin_node_id,
+      c.leaf_node_type,
+      c.ancestor_path,
+      c.anchor_type,
+      c.anchor_label_raw,
+      c.anchor_label_norm,
+      c.anchor_order,
+      c.citation_anchor_id
+    FROM chunks c
+    JOIN docs d ON d.doc_id = c.doc_id
+    ";
+
+const LOOKUP_NODE_SELECT: &str = "
+    SELECT
+      'node::' || n.node_id,
+      n.doc_id,
+      d.part,
+      d.year,
+      n.node_type,
+      COALESCE(n.ref, ''),
+      COALESCE(n.heading, ''),
+      n.page_pdf_start,
+      n.page_pdf_end,
+      COALESCE(n.source_hash, ''),
+      substr(COALESCE(n.text, ''), 1, 420),
+      n.node_id,
+      n.node_type,
+      n.ancestor_path,
+      n.anchor_type,
+      n.anchor_label_raw,
+      n.anchor_label_norm,
+      n.anchor_order,
+      n.citation_anchor_id
+    FROM nodes n
+    JOIN docs d ON d.doc_id = n.doc_id
+    ";
+
+pub(crate) fn execute_lookup(
+    connection: &Connection,
+    kind: LookupKind,
+    args: &QueryArgs,
+) -> Result<QueryResponse> {
+    let lookup_started = Instant::now();
+    let key = args.query.trim();
+    if key.is_empty() {
+        bail!(QueryRequestError::new(format!(
+            "lookup {} must not be empty",
+            kind.label()
+        )));
+    }
+
+    let (mut candidates, node_results) = match kind {
+        LookupKind::Chunk => (
+            lookup_candidates(
+                connection,
+                &format!("{LOOKUP_CHUNK_SELECT} WHERE c.chunk_id = ?1"),
+                [key],
+            )?,
+            false,
+        ),
+        LookupKind::Node => (
+            lookup_candidates(
+                connection,
+                &format!("{LOOKUP_NODE_SELECT} WHERE n.node_id = ?1"),
+                [key],
+            )?,
+            true,
+        ),
+        LookupKind::Citation => {
+            let chunks = lookup_candidates(
+                connection,
+                &format!(
+                    "{LOOKUP_CHUNK_SELECT} WHERE c.citation_anchor_id = ?1 ORDER BY c.chunk_seq, c.chunk_id"
+                ),
+                [key],
+            )?;
+            if chunks.is_empty() {
+                let nodes = lookup_candidates(
+                    connection,
+                    &format!(
+                        "{LOOKUP_NODE_SELECT} WHERE n.citation_anchor_id = ?1 ORDER BY n.order_index, n.node_id"
+                    ),
+                    [key],
+                )?;
+                (nodes, true)
+            } else {
+                (chunks, false)
+            }
+        }
+        LookupKind::Reference => (
+            lookup_candidates(
+                connection,
+                &format!(
+                    "{LOOKUP_CHUNK_SELECT}
+                    WHERE lower(c.ref) = lower(?1)
+                      AND (?2 IS NULL OR d.part = ?2)
+                      AND (?3 IS NULL OR d.year = ?3)
+                    ORDER BY d.year DESC, d.part, c.chunk_seq, c.chunk_id"
+                ),
+                params![key, args.part.map(i64::from), args.edition.map(i64::from)],
+            )?,
+            false,
+        ),
+    };
+
+    let filtered = kind == LookupKind::Reference;
+    let limit = args.limit.max(1);
+    let matched_count = candidates.len();
+    candidates.truncate(limit);
+    for (index, candidate) in candidates.iter_mut().enumerate() {
+        candidate.lexical_rank = Some(index + 1);
+    }
+
+    if args.with_references || args.with_referenced_by {
+        ensure_reference_graph_available(connection)?;
+    }
+    if args.with_definitions {
+        ensure_terms_available(connection)?;
+    }
+
+    let results = to_results(
+        connection,
+        key,
+        candidates,
+        args.with_ancestors,
+        args.with_descendants,
+        node_results,
+        args.with_references,
+        args.with_referenced_by,
+        args.with_definitions,
+        false,
+        0,
+    )?;
+
+    Ok(QueryResponse {
+        query: key.to_string(),
+        limit,
+        returned: results.len(),
+        part_filter: args.part.filter(|_| filtered),
+        edition_filter: args.edition.filter(|_| filtered),
+        chunk_type_filter: None,
+        node_type_filter: None,
+        modality_filter: None,
+        retrieval: RetrievalMetadata {
+            requested_mode: "lookup".to_string(),
+            effective_mode: "lookup".to_string(),
+            granularity: if node_results { "node" } else { "chunk" }.to_string(),
+            lexical_k: 0,
+            semantic_k: 0,
+            lexical_candidate_count: matched_count,
+            semantic_candidate_count: 0,
+            fused_candidate_count: matched_count,
+            fusion: "none".to_string(),
+            rrf_k: 0,
+            semantic_model_id: None,
+            semantic_index: None,
+            semantic_index_reason: None,
+            semantic_ann_candidate_count: None,
+            exact_intent: true,
+            exact_intent_forced_lexical: false,
+            fallback_used: false,
+            fallback_reason: None,
+            pinpoint_enabled: false,
+            pinpoint_max_units: 0,
+            asil: None,
+            asil_exclude_not_recommended: false,
+            timeout_ms: args.timeout_ms,
+            query_duration_ms: lookup_started.elapsed().as_secs_f64() * 1000.0,
+        },
+        results,
+    })
+}
+
+fn lookup_candidates<P: Params>(
+    connection: &Connection,
+    sql: &str,
+    params: P,
+) -> Result<Vec<QueryCandidate>> {
+    let mut statement = connection.prepare_cached(sql)?;
+    let mut rows = statement.query(params)?;
+
+    let mut out = Vec::new();
+    while let Some(row) = rows.next()? {
+        out.push(QueryCandidate {
+            score: 1_000.0,
+            match_kind: "lookup".to_string(),
+            source_tags: vec!["lookup".to_string()],
+            lexical_rank: None,
+            semantic_rank: None,
+            lexical_score: None,
+            semantic_score: None,
+            rrf_score: None,
+            chunk_id: row.get(0)?,
+            doc_id: row.get(1)?,
+            part: row.get::<_, u32>(2)?,
+            year: row.get::<_, u32>(3)?,
+            chunk_type: row.get(4)?,
+            reference: row.get(5)?,
+            heading: row.get(6)?,
+            page_pdf_start: row.get(7)?,
+            page_pdf_end: row.get(8)?,
+            source_hash: row.get(9)?,
+            snippet: row.get(10)?,
+            origin_node_id: row.get(11)?,
+            leaf_node_type: row.get(12)?,
+            ancestor_path: row.get(13)?,
+            anchor_type: row.get(14)?,
+            anchor_label_raw: row.get(15)?,
+            anchor_label_norm: row.get(16)?,
+            anchor_order: row.get(17)?,
+            citation_anchor_id: row.get(18)?,
+            asil_recommendations: None,
+            asil_demoted: false,
+        });
+    }
+
+    Ok(out)
+}
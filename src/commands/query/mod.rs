@@ -1,14 +1,40 @@
+mod asil;
 mod citation;
+mod definitions;
+mod execute;
 mod fusion;
 mod hierarchy;
+mod hits;
 mod intent;
+mod lookup;
+mod node_retrieval;
 mod output;
+mod params;
 mod pinpoint;
+mod pinpoint_footnotes;
 mod ranking;
+mod references;
+mod regions;
+mod request_error;
+mod requirements;
 mod result_hydration;
 mod retrieval;
 mod run;
 mod semantic_retrieval;
+mod semantic_text;
+mod syntax;
+#[cfg(test)]
+mod tests;
 mod text;
 
-pub(crate) use run::run;
+pub(crate) use citation::{CitationSource, render_anchor_citation};
+pub(crate) use definitions::{
+    TermDefinition, ensure_terms_available, lookup_term_definitions,
+};
+pub(crate) use execute::execute_query;
+pub(crate) use lookup::{LookupKind, execute_lookup};
+pub(crate) use output::write_text_response_to;
+pub(crate) use params::{query_args_from_params, query_params_from_json};
+pub(crate) use request_error::QueryRequestError;
+pub(crate) use run::{QueryResponse, open_query_connection, run};
+pub(crate) use syntax::QuerySyntaxError;
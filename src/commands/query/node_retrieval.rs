@@ -0,0 +1,180 @@
+use anyhow::{bail, Result};
+use rusqlite::types::ToSql;
+use rusqlite::{params, Connection};
+
+use super::asil::{annotate_asil_recommendations, asil_exclusion_sql, AsilProfile};
+use super::request_error::QueryRequestError;
+use super::run::{sort_candidates, QueryCandidate};
+use super::syntax::LexicalQuery;
+
+#[allow(clippy::too_many_arguments)]
+pub(super) fn collect_lexical_node_candidates(
+    connection: &Connection,
+    lexical_query: &LexicalQuery,
+    part_filter: Option<u32>,
+    edition_filter: Option<u32>,
+    node_type_filter: Option<&str>,
+    modality_filter: Option<&str>,
+    asil_profile: Option<&AsilProfile>,
+    candidate_limit: usize,
+) -> Result<Vec<QueryCandidate>> {
+    let Some(node_text) = lexical_query.node_text() else {
+        bail!(QueryRequestError::new(
+            "node granularity matches plain text only; boolean operators, prefixes and field filters need --granularity chunk"
+        ));
+    };
+    let mut candidates = query_node_matches(
+        connection,
+        node_text,
+        part_filter,
+        edition_filter,
+        None,
+        node_type_filter,
+        modality_filter,
+        asil_profile,
+        candidate_limit,
+    )?;
+    annotate_asil_recommendations(connection, &mut candidates, asil_profile)?;
+    sort_candidates(&mut candidates);
+
+    for (index, candidate) in candidates.iter_mut().enumerate() {
+        candidate.lexical_rank = Some(index + 1);
+        candidate.lexical_score = Some(candidate.score);
+    }
+
+    Ok(candidates)
+}
+
+#[allow(clippy::too_many_arguments)]
+pub(super) fn query_node_matches(
+    connection: &Connection,
+    query_text: &str,
+    part_filter: Option<u32>,
+    edition_filter: Option<u32>,
+    chunk_type_filter: Option<&str>,
+    node_type_filter: Option<&str>,
+    modality_filter: Option<&str>,
+    asil_profile: Option<&AsilProfile>,
+    candidate_limit: usize,
+) -> Result<Vec<QueryCandidate>> {
+    let asil_exclusion = asil_exclusion_sql(asil_profile, "n.node_id", "n.node_type", 8);
+    let mut statement = connection.prepare_cached(&format!(
+        "
+        SELECT
+          n.node_id,
+          n.doc_id,
+          d.part,
+          d.year,
+          n.node_type,
+          COALESCE(n.ref, ''),
+          COALESCE(n.heading, ''),
+          n.page_pdf_start,
+          n.page_pdf_end,
+          COALESCE(n.source_hash, ''),
+          substr(COALESCE(n.text, ''), 1, 420),
+          n.ancestor_path,
+          n.anchor_type,
+          n.anchor_label_raw,
+          n.anchor_label_norm,
+          n.anchor_order,
+          n.citation_anchor_id
+        FROM nodes n
+        JOIN docs d ON d.doc_id = n.doc_id{asil_join}
+        WHERE
+          (?2 IS NULL OR d.part = ?2)
+          AND (?3 IS NULL OR lower(n.node_type) = lower(?3))
+          AND (?4 IS NULL OR lower(n.node_type) = lower(?4))
+          AND (?6 IS NULL OR n.modality = ?6)
+          AND (?7 IS NULL OR d.year = ?7)
+          AND (
+            lower(n.ref) = lower(?1)
+            OR lower(n.heading) = lower(?1)
+            OR lower(n.ref) LIKE '%' || lower(?1) || '%'
+            OR lower(n.heading) LIKE '%' || lower(?1) || '%'
+            OR lower(n.text) LIKE '%' || lower(?1) || '%'
+          ){asil_condition}
+        LIMIT ?5
+        ",
+        asil_join = asil_exclusion.join,
+        asil_condition = asil_exclusion.condition,
+    ))?;
+
+    let part = part_filter.map(i64::from);
+    let edition = edition_filter.map(i64::from);
+    let limit = candidate_limit as i64;
+    let mut bound = params![
+        query_text,
+        part,
+        chunk_type_filter,
+        node_type_filter,
+        limit,
+        modality_filter,
+        edition
+    ]
+    .to_vec();
+    bound.extend(asil_exclusion.asil.map(|asil| asil as &dyn ToSql));
+    let mut rows = statement.query(bound.as_slice())?;
+
+    let query_lower = query_text.to_lowercase();
+    let mut out = Vec::new();
+
+    while let Some(row) = rows.next()? {
+        let node_id: String = row.get(0)?;
+        let reference: String = row.get(5)?;
+        let heading: String = row.get(6)?;
+        let node_type: String = row.get(4)?;
+        let snippet: String = row.get(10)?;
+
+        let ref_lower = reference.to_lowercase();
+        let heading_lower = heading.to_lowercase();
+        let snippet_lower = snippet.to_lowercase();
+
+        let (score, match_kind) = if ref_lower == query_lower {
+            (850.0, "node_exact_ref")
+        } else if heading_lower == query_lower {
+            (760.0, "node_exact_heading")
+        } else if ref_lower.contains(&query_lower) {
+            (650.0, "node_ref_contains")
+        } else if heading_lower.contains(&query_lower) {
+            (620.0, "node_heading_contains")
+        } else if snippet_lower.contains(&query_lower) {
+            (580.0, "node_text_contains")
+        } else {
+            (550.0, "node_match")
+        };
+
+        out.push(QueryCandidate {
+            score,
+            match_kind: match_kind.to_string(),
+            source_tags: vec!["lexical_node".to_string()],
+            lexical_rank: None,
+            semantic_rank: None,
+            lexical_score: Some(score),
+            semantic_score: None,
+            rrf_score: None,
+            chunk_id: format!("node::{node_id}"),
+            doc_id: row.get(1)?,
+            part: row.get::<_, u32>(2)?,
+            year: row.get::<_, u32>(3)?,
+            chunk_type: node_type.clone(),
+            reference,
+            heading,
+            page_pdf_start: row.get(7)?,
+            page_pdf_end: row.get(8)?,
+            source_hash: row.get(9)?,
+            snippet,
+            origin_node_id: Some(node_id),
+            leaf_node_type: Some(node_type),
+            ancestor_path: row.get(11)?,
+            anchor_type: row.get(12)?,
+            anchor_label_raw: row.get(13)?,
+            anchor_label_norm: row.get(14)?,
+            anchor_order: row.get(15)?,
+            citation_anchor_id: row.get(16)?,
+            asil_recommendations: None,
+            asil_demoted: false,
+        });
+    }
+
+    Ok(out)
+}
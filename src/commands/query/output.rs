@@ -1,45 +1,32 @@
+use std::collections::HashMap;
 use std::io::{self, Write};
 
 use anyhow::{Context, Result};
 
 use super::citation::format_page_range;
-use super::run::{QueryResponse, QueryResult, RetrievalMetadata};
-
-#[allow(clippy::too_many_arguments)]
-pub(super) fn write_json_response(
-    query_text: &str,
-    limit: usize,
-    part_filter: Option<u32>,
-    chunk_type_filter: Option<String>,
-    node_type_filter: Option<String>,
-    retrieval: RetrievalMetadata,
-    results: Vec<QueryResult>,
-) -> Result<()> {
-    let response = QueryResponse {
-        query: query_text.to_string(),
-        limit,
-        returned: results.len(),
-        part_filter,
-        chunk_type_filter,
-        node_type_filter,
-        retrieval,
-        results,
-    };
+use super::run::{DescendantNode, QueryResponse};
 
+pub(super) fn write_json_response(response: &QueryResponse) -> Result<()> {
     let mut output = io::BufWriter::new(io::stdout().lock());
-    serde_json::to_writer_pretty(&mut output, &response)
+    serde_json::to_writer_pretty(&mut output, response)
         .context("failed to serialize query json output")?;
     writeln!(output)?;
     output.flush()?;
     Ok(())
 }
 
-pub(super) fn write_text_response(
-    query_text: &str,
-    retrieval: &RetrievalMetadata,
-    results: &[QueryResult],
-) -> Result<()> {
+pub(super) fn write_text_response(response: &QueryResponse) -> Result<()> {
     let mut output = io::BufWriter::new(io::stdout().lock());
+    write_text_response_to(&mut output, response)
+}
+
+pub(crate) fn write_text_response_to<W: Write>(
+    output: &mut W,
+    response: &QueryResponse,
+) -> Result<()> {
+    let query_text = &response.query;
+    let retrieval = &response.retrieval;
+    let results = &response.results;
 
     writeln!(output, "Query: {query_text}")?;
     writeln!(
@@ -63,6 +50,19 @@ pub(super) fn write_text_response(
         retrieval.pinpoint_enabled,
         retrieval.pinpoint_max_units,
     )?;
+    if let Some(semantic_index) = &retrieval.semantic_index {
+        match &retrieval.semantic_index_reason {
+            Some(reason) => writeln!(output, "Semantic index: {semantic_index} ({reason})")?,
+            None => writeln!(output, "Semantic index: {semantic_index}")?,
+        }
+    }
+    if let Some(asil) = &retrieval.asil {
+        writeln!(
+            output,
+            "ASIL: {} exclude_not_recommended={}",
+            asil, retrieval.asil_exclude_not_recommended
+        )?;
+    }
     writeln!(output, "Results: {}", results.len())?;
 
     for result in results {
@@ -106,6 +106,13 @@ pub(super) fn write_text_response(
         if let Some(parent_ref) = &result.parent_ref {
             writeln!(output, "\tparent_ref: {parent_ref}")?;
         }
+        if let Some(parent_chunk) = &result.parent_chunk {
+            writeln!(
+                output,
+                "\tparent_chunk: {} ({})",
+                parent_chunk.chunk_id, parent_chunk.citation
+            )?;
+        }
         if let Some(anchor_type) = &result.anchor_type {
             writeln!(output, "\tanchor_type: {anchor_type}")?;
         }
@@ -121,8 +128,66 @@ pub(super) fn write_text_response(
         if let Some(citation_anchor_id) = &result.citation_anchor_id {
             writeln!(output, "\tcitation_anchor_id: {citation_anchor_id}")?;
         }
+        if let Some(level) = &result.asil_recommendation_level {
+            writeln!(output, "\tasil_recommendation: {level}")?;
+        }
+        if result.asil_demoted == Some(true) {
+            writeln!(output, "\tasil_demoted: true")?;
+        }
+        if let Some(requirement) = &result.requirement {
+            writeln!(
+                output,
+                "\trequirement: modality={} actor={}",
+                requirement.modality.as_deref().unwrap_or("-"),
+                requirement.actor.as_deref().unwrap_or("-")
+            )?;
+            for condition in &requirement.conditions {
+                writeln!(output, "\trequirement_condition: {condition}")?;
+            }
+            for reference in &requirement.references {
+                writeln!(
+                    output,
+                    "\trequirement_reference: {} {}",
+                    reference.kind, reference.target
+                )?;
+            }
+        }
         writeln!(output, "\tcitation: {}", result.citation)?;
         writeln!(output, "\tsnippet: {}", result.snippet)?;
+        if let Some(descendants) = &result.descendants {
+            write_descendant_tree(output, result.origin_node_id.as_deref(), descendants)?;
+        }
+        if let Some(references) = &result.references {
+            for link in references {
+                writeln!(
+                    output,
+                    "\treferences: {} -> {} ({})",
+                    link.raw_text,
+                    link.node_id.as_deref().unwrap_or("-"),
+                    link.resolution_status
+                )?;
+            }
+        }
+        if let Some(referenced_by) = &result.referenced_by {
+            for link in referenced_by {
+                writeln!(
+                    output,
+                    "\treferenced_by: {} {} ({})",
+                    link.doc_id.as_deref().unwrap_or("-"),
+                    link.reference.as_deref().unwrap_or("-"),
+                    link.raw_text
+                )?;
+            }
+        }
+        if let Some(definitions) = &result.definitions {
+            for definition in definitions {
+                writeln!(
+                    output,
+                    "\tdefinition: {} ({}): {}",
+                    definition.term, definition.reference, definition.definition
+                )?;
+            }
+        }
         if let Some(fallback_used) = result.pinpoint_fallback_used {
             writeln!(output, "\tpinpoint_fallback_used: {fallback_used}")?;
         }
@@ -147,3 +212,44 @@ pub(super) fn write_text_response(
     output.flush()?;
     Ok(())
 }
+
+fn write_descendant_tree<W: Write>(
+    output: &mut W,
+    origin_node_id: Option<&str>,
+    descendants: &[DescendantNode],
+) -> Result<()> {
+    let mut children = HashMap::<Option<&str>, Vec<&DescendantNode>>::new();
+    for node in descendants {
+        children
+            .entry(node.parent_node_id.as_deref())
+            .or_default()
+            .push(node);
+    }
+    for siblings in children.values_mut() {
+        siblings.sort_by_key(|node| node.order_index);
+    }
+
+    let mut stack: Vec<(&DescendantNode, usize)> = children
+        .get(&origin_node_id)
+        .map(|roots| roots.iter().rev().map(|node| (*node, 0usize)).collect())
+        .unwrap_or_default();
+    while let Some((node, depth)) = stack.pop() {
+        let label = node
+            .heading
+            .as_deref()
+            .or(node.text_preview.as_deref())
+            .unwrap_or("");
+        writeln!(
+            output,
+            "\tdescendant: {}{} {} ({})",
+            "  ".repeat(depth),
+            node.node_type,
+            label,
+            node.citation
+        )?;
+        if let Some(nested) = children.get(&Some(node.node_id.as_str())) {
+            stack.extend(nested.iter().rev().map(|child| (*child, depth + 1)));
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,66 @@
+use anyhow::{bail, Result};
+use clap::{Args, ColorChoice, Command, FromArgMatches};
+use serde_json::{Map, Value};
+
+use crate::cli::QueryArgs;
+
+const CALLER_MANAGED_QUERY_FLAGS: [&str; 3] = ["cache-root", "db-path", "json"];
+
+pub(crate) fn query_params_from_json(object: Map<String, Value>) -> Result<Vec<(String, String)>> {
+    let mut params = Vec::with_capacity(object.len());
+    for (key, value) in object {
+        let value = match value {
+            Value::String(value) => value,
+            Value::Bool(value) => value.to_string(),
+            Value::Number(value) => value.to_string(),
+            Value::Null => continue,
+            other => bail!("parameter '{key}' must be a string, number or boolean, got {other}"),
+        };
+        params.push((key, value));
+    }
+    Ok(params)
+}
+
+pub(crate) fn query_args_from_params(
+    lookup_id: Option<&str>,
+    params: &[(String, String)],
+) -> Result<QueryArgs> {
+    let command = QueryArgs::augment_args(
+        Command::new("query")
+            .no_binary_name(true)
+            .color(ColorChoice::Never),
+    );
+    let mut argv = Vec::<String>::new();
+    if let Some(lookup_id) = lookup_id {
+        argv.push("--query".to_string());
+        argv.push(lookup_id.to_string());
+    }
+
+    for (key, value) in params {
+        let long = key.trim().replace('_', "-");
+        if CALLER_MANAGED_QUERY_FLAGS.contains(&long.as_str())
+            || (lookup_id.is_some() && long == "query")
+        {
+            bail!("parameter '{key}' is not accepted by this endpoint");
+        }
+        let Some(arg) = command
+            .get_arguments()
+            .find(|arg| arg.get_long() == Some(long.as_str()))
+        else {
+            bail!("unknown query parameter '{key}'");
+        };
+
+        if arg.get_action().takes_values() {
+            argv.push(format!("--{long}"));
+            argv.push(value.clone());
+        } else if matches!(
+            value.trim().to_ascii_lowercase().as_str(),
+            "" | "1" | "true" | "yes"
+        ) {
+            argv.push(format!("--{long}"));
+        }
+    }
+
+    let matches = command.try_get_matches_from(argv)?;
+    Ok(QueryArgs::from_arg_matches(&matches)?)
+}
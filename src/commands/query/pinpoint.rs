@@ -3,6 +3,7 @@ use std::collections::HashSet;
 use anyhow::Result;
 use rusqlite::{Connection, OptionalExtension};
 
+use super::pinpoint_footnotes::table_node_units;
 use super::run::{PinpointUnit, QueryCandidate};
 use super::text::condense_whitespace;
 
@@ -40,17 +41,17 @@ const PINPOINT_QUERY_STOPWORDS: &[&str] = &[
 ];
 
 #[derive(Debug, Clone)]
-struct PinpointUnitDraft {
-    unit_id: String,
-    unit_type: String,
-    text: String,
-    char_start: Option<usize>,
-    char_end: Option<usize>,
-    row_idx: Option<i64>,
-    col_idx: Option<i64>,
-    row_key: Option<String>,
-    origin_node_id: Option<String>,
-    citation_anchor_id: Option<String>,
+pub(super) struct PinpointUnitDraft {
+    pub(super) unit_id: String,
+    pub(super) unit_type: String,
+    pub(super) text: String,
+    pub(super) char_start: Option<usize>,
+    pub(super) char_end: Option<usize>,
+    pub(super) row_idx: Option<i64>,
+    pub(super) col_idx: Option<i64>,
+    pub(super) row_key: Option<String>,
+    pub(super) origin_node_id: Option<String>,
+    pub(super) citation_anchor_id: Option<String>,
 }
 
 pub(super) fn compute_pinpoint_units_for_candidate(
@@ -142,6 +143,7 @@ pub(super) fn compute_pinpoint_units_for_candidate(
                 origin_node_id: unit.origin_node_id,
                 citation_anchor_id: unit.citation_anchor_id,
                 citation_anchor_compatible: compatible,
+                highlights: None,
             }
         })
         .collect::<Vec<PinpointUnit>>();
@@ -304,54 +306,6 @@ fn sentence_window_units(
     units
 }
 
-fn table_node_units(
-    connection: &Connection,
-    chunk_id: &str,
-    table_node_id: &str,
-) -> Result<Vec<PinpointUnitDraft>> {
-    let mut statement = connection.prepare(
-        "
-        SELECT
-          node_id,
-          node_type,
-          COALESCE(text, ''),
-          row_idx,
-          col_idx,
-          citation_anchor_id
-        FROM nodes
-        WHERE table_node_id = ?1
-          AND node_type IN ('table_row', 'table_cell')
-        ORDER BY row_idx ASC, col_idx ASC, node_id ASC
-        LIMIT 512
-        ",
-    )?;
-
-    let mut rows = statement.query([table_node_id])?;
-    let mut units = Vec::<PinpointUnitDraft>::new();
-    while let Some(row) = rows.next()? {
-        let text = condense_whitespace(&row.get::<_, String>(2)?);
-        if text.is_empty() {
-            continue;
-        }
-
-        let row_idx = row.get::<_, Option<i64>>(3)?;
-        let col_idx = row.get::<_, Option<i64>>(4)?;
-        units.push(PinpointUnitDraft {
-            unit_id: row.get::<_, String>(0)?,
-            unit_type: row.get::<_, String>(1)?,
-            text,
-            char_start: None,
-            char_end: None,
-            row_idx,
-            col_idx,
-            row_key: row_idx.map(|value| format!("{chunk_id}:{value}")),
-            origin_node_id: row.get::<_, Option<String>>(0)?,
-            citation_anchor_id: row.get::<_, Option<String>>(5)?,
-        });
-    }
-
-    Ok(units)
-}
 fn table_markdown_row_units(
     chunk_id: &str,
     table_md: &str,
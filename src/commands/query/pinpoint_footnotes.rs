@@ -0,0 +1,108 @@
+use std::collections::HashSet;
+
+use anyhow::Result;
+use rusqlite::{Connection, OptionalExtension};
+
+use super::pinpoint::PinpointUnitDraft;
+use super::text::condense_whitespace;
+
+pub(super) fn table_node_units(
+    connection: &Connection,
+    chunk_id: &str,
+    table_node_id: &str,
+) -> Result<Vec<PinpointUnitDraft>> {
+    let mut statement = connection.prepare_cached(
+        "
+        SELECT
+          node_id,
+          node_type,
+          COALESCE(text, ''),
+          row_idx,
+          col_idx,
+          citation_anchor_id
+        FROM nodes
+        WHERE table_node_id = ?1
+          AND node_type IN ('table_row', 'table_cell', 'table_footnote')
+        ORDER BY row_idx IS NULL ASC, row_idx ASC, col_idx ASC, node_id ASC
+        LIMIT 512
+        ",
+    )?;
+
+    let footnotes = table_footnotes_by_cell(connection, table_node_id)?;
+    let mut rows = statement.query([table_node_id])?;
+    let mut units = Vec::<PinpointUnitDraft>::new();
+    while let Some(row) = rows.next()? {
+        let mut text = condense_whitespace(&row.get::<_, String>(2)?);
+        if text.is_empty() {
+            continue;
+        }
+
+        let row_idx = row.get::<_, Option<i64>>(3)?;
+        let col_idx = row.get::<_, Option<i64>>(4)?;
+        let mut seen_markers = HashSet::<&str>::new();
+        for (footnote_row, footnote_col, marker, footnote_text) in &footnotes {
+            let applies = Some(*footnote_row) == row_idx
+                && (col_idx.is_none() || Some(*footnote_col) == col_idx);
+            if applies && seen_markers.insert(marker.as_str()) {
+                text.push_str(&format!(" (footnote {marker}: {footnote_text})"));
+            }
+        }
+        units.push(PinpointUnitDraft {
+            unit_id: row.get::<_, String>(0)?,
+            unit_type: row.get::<_, String>(1)?,
+            text,
+            char_start: None,
+            char_end: None,
+            row_idx,
+            col_idx,
+            row_key: row_idx.map(|value| format!("{chunk_id}:{value}")),
+            origin_node_id: row.get::<_, Option<String>>(0)?,
+            citation_anchor_id: row.get::<_, Option<String>>(5)?,
+        });
+    }
+
+    Ok(units)
+}
+
+fn table_footnotes_by_cell(
+    connection: &Connection,
+    table_node_id: &str,
+) -> Result<Vec<(i64, i64, String, String)>> {
+    let links_table_exists = connection
+        .query_row(
+            "SELECT 1 FROM sqlite_master WHERE type = 'table' AND name = 'table_footnote_links'",
+            [],
+            |row| row.get::<_, i64>(0),
+        )
+        .optional()?
+        .is_some();
+    if !links_table_exists {
+        return Ok(Vec::new());
+    }
+
+    let mut statement = connection.prepare_cached(
+        "
+        SELECT c.row_idx, c.col_idx, f.anchor_label_raw, COALESCE(f.text, '')
+        FROM table_footnote_links l
+        JOIN nodes c ON c.node_id = l.cell_node_id
+        JOIN nodes f ON f.node_id = l.footnote_node_id
+        WHERE l.table_node_id = ?1
+        ORDER BY c.row_idx ASC, c.col_idx ASC, f.anchor_order ASC
+        ",
+    )?;
+
+    let rows = statement.query_map([table_node_id], |row| {
+        Ok((
+            row.get::<_, i64>(0)?,
+            row.get::<_, i64>(1)?,
+            row.get::<_, String>(2)?,
+            condense_whitespace(&row.get::<_, String>(3)?),
+        ))
+    })?;
+
+    let mut footnotes = Vec::new();
+    for row in rows {
+        footnotes.push(row?);
+    }
+    Ok(footnotes)
+}
@@ -0,0 +1,136 @@
+use anyhow::{bail, Result};
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::Serialize;
+
+use super::request_error::QueryRequestError;
+use super::run::QueryCandidate;
+
+const MAX_REFERENCE_LINKS: i64 = 128;
+
+#[derive(Debug, Clone, Serialize)]
+pub(super) struct NodeReferenceLink {
+    pub(super) reference_id: String,
+    pub(super) node_id: Option<String>,
+    pub(super) doc_id: Option<String>,
+    pub(super) reference: Option<String>,
+    pub(super) heading: Option<String>,
+    pub(super) raw_text: String,
+    pub(super) reference_kind: String,
+    pub(super) resolution_status: String,
+}
+
+pub(super) fn ensure_reference_graph_available(connection: &Connection) -> Result<()> {
+    let table_exists = connection
+        .query_row(
+            "
+            SELECT name
+            FROM sqlite_master
+            WHERE type = 'table' AND name = 'node_references'
+            LIMIT 1
+            ",
+            [],
+            |row| row.get::<_, String>(0),
+        )
+        .optional()?
+        .is_some();
+    if !table_exists {
+        bail!(QueryRequestError::new(
+            "node_references table is missing; re-run ingest to build the cross-reference graph"
+        ));
+    }
+    Ok(())
+}
+
+pub(super) fn fetch_references(
+    connection: &Connection,
+    candidate: &QueryCandidate,
+) -> Result<Vec<NodeReferenceLink>> {
+    let Some(origin_node_id) = candidate.origin_node_id.as_deref() else {
+        return Ok(Vec::new());
+    };
+
+    let mut statement = connection.prepare_cached(
+        "
+        SELECT
+          r.reference_id,
+          r.target_node_id,
+          r.target_doc_id,
+          COALESCE(n.ref, r.target_ref),
+          n.heading,
+          r.raw_text,
+          r.reference_kind,
+          r.resolution_status
+        FROM node_references r
+        LEFT JOIN nodes n ON n.node_id = r.target_node_id
+        WHERE r.source_node_id = ?1
+        ORDER BY r.reference_id ASC
+        LIMIT ?2
+        ",
+    )?;
+
+    collect_reference_links(&mut statement, params![origin_node_id, MAX_REFERENCE_LINKS])
+}
+
+pub(super) fn fetch_referenced_by(
+    connection: &Connection,
+    candidate: &QueryCandidate,
+) -> Result<Vec<NodeReferenceLink>> {
+    let mut statement = connection.prepare_cached(
+        "
+        SELECT
+          r.reference_id,
+          r.source_node_id,
+          r.doc_id,
+          n.ref,
+          n.heading,
+          r.raw_text,
+          r.reference_kind,
+          r.resolution_status
+        FROM node_references r
+        LEFT JOIN nodes n ON n.node_id = r.source_node_id
+        WHERE
+          (?1 IS NOT NULL AND r.target_node_id = ?1)
+          OR (
+            ?3 <> ''
+            AND r.resolution_status = 'resolved'
+            AND r.target_doc_id = ?2
+            AND lower(r.target_ref) = lower(?3)
+          )
+        ORDER BY r.doc_id ASC, r.reference_id ASC
+        LIMIT ?4
+        ",
+    )?;
+
+    collect_reference_links(
+        &mut statement,
+        params![
+            candidate.origin_node_id,
+            candidate.doc_id,
+            candidate.reference,
+            MAX_REFERENCE_LINKS
+        ],
+    )
+}
+
+fn collect_reference_links(
+    statement: &mut rusqlite::Statement<'_>,
+    params: impl rusqlite::Params,
+) -> Result<Vec<NodeReferenceLink>> {
+    let mut rows = statement.query(params)?;
+    let mut out = Vec::new();
+
+    while let Some(row) = rows.next()? {
+        out.push(NodeReferenceLink {
+            reference_id: row.get(0)?,
+            node_id: row.get(1)?,
+            doc_id: row.get(2)?,
+            reference: row.get(3)?,
+            heading: row.get(4)?,
+            raw_text: row.get(5)?,
+            reference_kind: row.get(6)?,
+            resolution_status: row.get(7)?,
+        });
+    }
+
+    Ok(out)
+}
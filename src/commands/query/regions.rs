@@ -0,0 +1,63 @@
+use anyhow::Result;
+use rusqlite::Connection;
+use serde::Serialize;
+
+#[derive(Debug, Clone, Serialize)]
+pub(super) struct HighlightRegion {
+    pub(super) page_pdf: i64,
+    pub(super) x_min: f64,
+    pub(super) y_min: f64,
+    pub(super) x_max: f64,
+    pub(super) y_max: f64,
+    pub(super) page_width: f64,
+    pub(super) page_height: f64,
+}
+
+pub(super) fn node_regions_available(connection: &Connection) -> Result<bool> {
+    let table_count = connection.query_row(
+        "SELECT COUNT(*) FROM sqlite_master WHERE type = 'table' AND name = 'node_regions'",
+        [],
+        |row| row.get::<_, i64>(0),
+    )?;
+    Ok(table_count > 0)
+}
+
+/// Returns `None` when the document was ingested without layout geometry.
+pub(super) fn fetch_node_regions(
+    connection: &Connection,
+    doc_id: &str,
+    node_id: &str,
+) -> Result<Option<Vec<HighlightRegion>>> {
+    let has_layout = connection
+        .prepare_cached("SELECT EXISTS(SELECT 1 FROM node_regions WHERE doc_id = ?1)")?
+        .query_row([doc_id], |row| row.get::<_, bool>(0))?;
+    if !has_layout {
+        return Ok(None);
+    }
+
+    let mut statement = connection.prepare_cached(
+        "
+        SELECT page_pdf, x_min, y_min, x_max, y_max, page_width, page_height
+        FROM node_regions
+        WHERE node_id = ?1
+        ORDER BY region_seq ASC
+        ",
+    )?;
+    let rows = statement.query_map([node_id], |row| {
+        Ok(HighlightRegion {
+            page_pdf: row.get(0)?,
+            x_min: row.get(1)?,
+            y_min: row.get(2)?,
+            x_max: row.get(3)?,
+            y_max: row.get(4)?,
+            page_width: row.get(5)?,
+            page_height: row.get(6)?,
+        })
+    })?;
+
+    let mut regions = Vec::new();
+    for row in rows {
+        regions.push(row?);
+    }
+    Ok(Some(regions))
+}
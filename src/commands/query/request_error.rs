@@ -0,0 +1,23 @@
+use std::fmt;
+
+/// A query the index cannot answer as asked, as opposed to a failure while answering it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct QueryRequestError {
+    message: String,
+}
+
+impl QueryRequestError {
+    pub(super) fn new(message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for QueryRequestError {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str(&self.message)
+    }
+}
+
+impl std::error::Error for QueryRequestError {}
@@ -0,0 +1,127 @@
+use anyhow::{bail, Context, Result};
+use rusqlite::{Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+
+use super::request_error::QueryRequestError;
+use super::run::QueryCandidate;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(super) struct RequirementReferenceLink {
+    pub(super) kind: String,
+    pub(super) target: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub(super) struct RequirementClassification {
+    pub(super) modality: Option<String>,
+    pub(super) actor: Option<String>,
+    pub(super) conditions: Vec<String>,
+    pub(super) references: Vec<RequirementReferenceLink>,
+}
+
+pub(super) fn requirement_columns_available(connection: &Connection) -> Result<bool> {
+    let column_count = connection.query_row(
+        "SELECT COUNT(*) FROM pragma_table_info('nodes') WHERE name = 'modality'",
+        [],
+        |row| row.get::<_, i64>(0),
+    )?;
+    Ok(column_count > 0)
+}
+
+pub(super) fn ensure_requirement_columns_available(connection: &Connection) -> Result<()> {
+    if !requirement_columns_available(connection)? {
+        bail!(QueryRequestError::new(
+            "--modality requires requirement atom classification; re-run ingest to populate it"
+        ));
+    }
+    Ok(())
+}
+
+/// Keeps candidates whose origin node or any descendant carries `modality`.
+pub(super) fn retain_requirement_modality(
+    connection: &Connection,
+    candidates: &mut Vec<QueryCandidate>,
+    modality: Option<&str>,
+) -> Result<()> {
+    let Some(modality) = modality else {
+        return Ok(());
+    };
+
+    let mut statement = connection.prepare_cached(
+        "
+        WITH RECURSIVE subtree(node_id) AS (
+          SELECT ?1
+          UNION ALL
+          SELECT n.node_id
+          FROM nodes n
+          JOIN subtree s ON n.parent_node_id = s.node_id
+        )
+        SELECT 1
+        FROM subtree s
+        JOIN nodes n ON n.node_id = s.node_id
+        WHERE n.modality = ?2
+        LIMIT 1
+        ",
+    )?;
+    let mut retained = Vec::with_capacity(candidates.len());
+    for candidate in candidates.drain(..) {
+        let Some(origin_node_id) = candidate.origin_node_id.as_deref() else {
+            continue;
+        };
+        let matches = statement
+            .query_row([origin_node_id, modality], |row| row.get::<_, i64>(0))
+            .optional()?
+            .is_some();
+        if matches {
+            retained.push(candidate);
+        }
+    }
+    *candidates = retained;
+
+    Ok(())
+}
+
+pub(super) fn fetch_requirement_classification(
+    connection: &Connection,
+    node_id: &str,
+) -> Result<Option<RequirementClassification>> {
+    let row = connection
+        .query_row(
+            "
+            SELECT modality, requirement_actor, requirement_conditions, requirement_references
+            FROM nodes
+            WHERE node_id = ?1 AND node_type = 'requirement_atom'
+            ",
+            [node_id],
+            |row| {
+                Ok((
+                    row.get::<_, Option<String>>(0)?,
+                    row.get::<_, Option<String>>(1)?,
+                    row.get::<_, Option<String>>(2)?,
+                    row.get::<_, Option<String>>(3)?,
+                ))
+            },
+        )
+        .optional()?;
+    let Some((modality, actor, conditions_json, references_json)) = row else {
+        return Ok(None);
+    };
+
+    let conditions = match conditions_json {
+        Some(value) => serde_json::from_str::<Vec<String>>(&value)
+            .with_context(|| format!("invalid requirement conditions for {node_id}"))?,
+        None => Vec::new(),
+    };
+    let references = match references_json {
+        Some(value) => serde_json::from_str::<Vec<RequirementReferenceLink>>(&value)
+            .with_context(|| format!("invalid requirement references for {node_id}"))?,
+        None => Vec::new(),
+    };
+
+    Ok(Some(RequirementClassification {
+        modality,
+        actor,
+        conditions,
+        references,
+    }))
+}
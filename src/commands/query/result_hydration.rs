@@ -1,22 +1,34 @@
 use anyhow::Result;
 use rusqlite::Connection;
 
+use super::asil::summarize_recommendation_level;
 use super::citation::render_citation;
-use super::hierarchy::{fetch_descendants, resolve_parent_ref};
+use super::definitions::fetch_chunk_definitions;
+use super::hierarchy::{fetch_descendants, fetch_parent_chunk, resolve_parent_ref};
 use super::pinpoint::compute_pinpoint_units_for_candidate;
+use super::references::{fetch_referenced_by, fetch_references};
+use super::regions::{fetch_node_regions, node_regions_available};
+use super::requirements::{fetch_requirement_classification, requirement_columns_available};
 use super::run::{QueryCandidate, QueryRankTrace, QueryResult};
 use super::text::condense_whitespace;
 
+#[allow(clippy::too_many_arguments)]
 pub(super) fn to_results(
     connection: &Connection,
     query_text: &str,
     candidates: Vec<QueryCandidate>,
     with_ancestors: bool,
     with_descendants: bool,
+    with_parent_chunk: bool,
+    with_references: bool,
+    with_referenced_by: bool,
+    with_definitions: bool,
     with_pinpoint: bool,
     pinpoint_max_units: usize,
 ) -> Result<Vec<QueryResult>> {
     let mut out = Vec::with_capacity(candidates.len());
+    let requirement_columns = requirement_columns_available(connection)?;
+    let node_regions = node_regions_available(connection)?;
 
     for (index, candidate) in candidates.into_iter().enumerate() {
         let citation = render_citation(&candidate);
@@ -40,6 +52,37 @@ pub(super) fn to_results(
             None
         };
 
+        let parent_chunk = if with_parent_chunk {
+            fetch_parent_chunk(connection, &candidate)?
+        } else {
+            None
+        };
+
+        let references = if with_references {
+            Some(fetch_references(connection, &candidate)?)
+        } else {
+            None
+        };
+        let referenced_by = if with_referenced_by {
+            Some(fetch_referenced_by(connection, &candidate)?)
+        } else {
+            None
+        };
+        let requirement = match candidate.origin_node_id.as_deref() {
+            Some(origin_node_id)
+                if requirement_columns
+                    && candidate.leaf_node_type.as_deref() == Some("requirement_atom") =>
+            {
+                fetch_requirement_classification(connection, origin_node_id)?
+            }
+            _ => None,
+        };
+        let definitions = if with_definitions {
+            Some(fetch_chunk_definitions(connection, &candidate.chunk_id)?)
+        } else {
+            None
+        };
+
         let (pinpoint_units, pinpoint_fallback_used) = if with_pinpoint {
             let computation = compute_pinpoint_units_for_candidate(
                 connection,
@@ -47,11 +90,36 @@ pub(super) fn to_results(
                 query_text,
                 pinpoint_max_units,
             )?;
-            (Some(computation.units), Some(computation.fallback_used))
+            let mut units = computation.units;
+            if node_regions {
+                for unit in &mut units {
+                    if let Some(origin_node_id) = unit.origin_node_id.as_deref() {
+                        unit.highlights =
+                            fetch_node_regions(connection, &candidate.doc_id, origin_node_id)?;
+                    }
+                }
+            }
+            (Some(units), Some(computation.fallback_used))
         } else {
             (None, None)
         };
 
+        let highlights = match candidate.origin_node_id.as_deref() {
+            Some(origin_node_id) if node_regions => {
+                fetch_node_regions(connection, &candidate.doc_id, origin_node_id)?
+            }
+            _ => None,
+        };
+
+        let asil_recommendation_level = candidate
+            .asil_recommendations
+            .as_deref()
+            .and_then(summarize_recommendation_level);
+        let asil_demoted = candidate
+            .asil_recommendations
+            .as_ref()
+            .map(|_| candidate.asil_demoted);
+
         out.push(QueryResult {
             rank: index + 1,
             score: candidate.score,
@@ -85,10 +153,19 @@ pub(super) fn to_results(
             anchor_label_norm: candidate.anchor_label_norm,
             anchor_order: candidate.anchor_order,
             citation_anchor_id: candidate.citation_anchor_id,
+            asil_recommendation_level,
+            asil_demoted,
+            asil_recommendations: candidate.asil_recommendations,
             ancestor_nodes,
             descendants,
+            parent_chunk,
+            references,
+            referenced_by,
+            requirement,
+            definitions,
             pinpoint_fallback_used,
             pinpoint_units,
+            highlights,
         });
     }
 
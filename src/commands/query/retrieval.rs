@@ -1,51 +1,71 @@
 use std::collections::HashMap;
 
 use anyhow::Result;
+use rusqlite::types::ToSql;
 use rusqlite::{params, Connection};
 
+use super::asil::{annotate_asil_recommendations, asil_exclusion_sql, AsilProfile};
+use super::node_retrieval::query_node_matches;
 use super::ranking::upsert_candidate;
 use super::run::{sort_candidates, QueryCandidate};
-use super::text::to_fts_query;
+use super::syntax::LexicalQuery;
 
+#[allow(clippy::too_many_arguments)]
 pub(super) fn collect_lexical_candidates(
     connection: &Connection,
-    query_text: &str,
+    lexical_query: &LexicalQuery,
     part_filter: Option<u32>,
+    edition_filter: Option<u32>,
     chunk_type_filter: Option<&str>,
     node_type_filter: Option<&str>,
+    modality_filter: Option<&str>,
+    asil_profile: Option<&AsilProfile>,
     candidate_limit: usize,
 ) -> Result<Vec<QueryCandidate>> {
     let mut dedup = HashMap::<String, QueryCandidate>::new();
 
-    for candidate in query_exact_matches(
-        connection,
-        query_text,
-        part_filter,
-        chunk_type_filter,
-        node_type_filter,
-        candidate_limit,
-    )? {
-        upsert_candidate(&mut dedup, candidate);
+    if let Some(plain_text) = lexical_query.plain_text.as_deref() {
+        for candidate in query_exact_matches(
+            connection,
+            plain_text,
+            lexical_query,
+            part_filter,
+            edition_filter,
+            chunk_type_filter,
+            node_type_filter,
+            asil_profile,
+            candidate_limit,
+        )? {
+            upsert_candidate(&mut dedup, candidate);
+        }
     }
 
     for candidate in query_fts_matches(
         connection,
-        query_text,
+        lexical_query,
         part_filter,
+        edition_filter,
         chunk_type_filter,
         node_type_filter,
+        asil_profile,
         candidate_limit,
     )? {
         upsert_candidate(&mut dedup, candidate);
     }
 
-    if node_type_filter.is_some() {
+    let node_text = lexical_query
+        .node_text()
+        .filter(|_| node_type_filter.is_some() || modality_filter.is_some());
+    if let Some(node_text) = node_text {
         for candidate in query_node_matches(
             connection,
-            query_text,
+            node_text,
             part_filter,
+            edition_filter,
             chunk_type_filter,
             node_type_filter,
+            modality_filter,
+            asil_profile,
             candidate_limit,
         )? {
             upsert_candidate(&mut dedup, candidate);
@@ -53,6 +73,7 @@ pub(super) fn collect_lexical_candidates(
     }
 
     let mut candidates = dedup.into_values().collect::<Vec<QueryCandidate>>();
+    annotate_asil_recommendations(connection, &mut candidates, asil_profile)?;
     sort_candidates(&mut candidates);
     if candidates.len() > candidate_limit {
         candidates.truncate(candidate_limit);
@@ -66,15 +87,26 @@ pub(super) fn collect_lexical_candidates(
     Ok(candidates)
 }
 
+#[allow(clippy::too_many_arguments)]
 fn query_exact_matches(
     connection: &Connection,
     query_text: &str,
+    lexical_query: &LexicalQuery,
     part_filter: Option<u32>,
+    edition_filter: Option<u32>,
     chunk_type_filter: Option<&str>,
     node_type_filter: Option<&str>,
+    asil_profile: Option<&AsilProfile>,
     candidate_limit: usize,
 ) -> Result<Vec<QueryCandidate>> {
-    let mut statement = connection.prepare(
+    let (filter_sql, filter_values) = lexical_query.filter_sql(7);
+    let asil_exclusion = asil_exclusion_sql(
+        asil_profile,
+        "c.origin_node_id",
+        "c.leaf_node_type",
+        7 + filter_values.len(),
+    );
+    let mut statement = connection.prepare_cached(&format!(
         "
         SELECT
           c.chunk_id,
@@ -97,9 +129,10 @@ fn query_exact_matches(
           c.anchor_order,
           c.citation_anchor_id
         FROM chunks c
-        JOIN docs d ON d.doc_id = c.doc_id
+        JOIN docs d ON d.doc_id = c.doc_id{asil_join}
         WHERE
           (?2 IS NULL OR d.part = ?2)
+          AND (?6 IS NULL OR d.year = ?6)
           AND (?3 IS NULL OR c.type = ?3)
           AND (?4 IS NULL OR lower(COALESCE(c.leaf_node_type, c.type)) = lower(?4))
           AND (
@@ -107,18 +140,28 @@ fn query_exact_matches(
             OR lower(c.heading) = lower(?1)
             OR lower(c.ref) LIKE '%' || lower(?1) || '%'
             OR lower(c.heading) LIKE '%' || lower(?1) || '%'
-          )
+          ){filter_sql}{asil_condition}
         LIMIT ?5
         ",
-    )?;
+        asil_join = asil_exclusion.join,
+        asil_condition = asil_exclusion.condition,
+    ))?;
 
-    let mut rows = statement.query(params![
+    let part = part_filter.map(i64::from);
+    let edition = edition_filter.map(i64::from);
+    let limit = candidate_limit as i64;
+    let mut bound = params![
         query_text,
-        part_filter.map(i64::from),
+        part,
         chunk_type_filter,
         node_type_filter,
-        candidate_limit as i64,
-    ])?;
+        limit,
+        edition
+    ]
+    .to_vec();
+    bound.extend(filter_values.iter().map(|value| value as &dyn ToSql));
+    bound.extend(asil_exclusion.asil.map(|asil| asil as &dyn ToSql));
+    let mut rows = statement.query(bound.as_slice())?;
 
     let mut out = Vec::new();
     let query_lower = query_text.to_lowercase();
@@ -168,23 +211,51 @@ fn query_exact_matches(
             anchor_label_norm: row.get(16)?,
             anchor_order: row.get(17)?,
             citation_anchor_id: row.get(18)?,
+            asil_recommendations: None,
+            asil_demoted: false,
         });
     }
 
     Ok(out)
 }
 
+#[allow(clippy::too_many_arguments)]
 fn query_fts_matches(
     connection: &Connection,
-    query_text: &str,
+    lexical_query: &LexicalQuery,
     part_filter: Option<u32>,
+    edition_filter: Option<u32>,
     chunk_type_filter: Option<&str>,
     node_type_filter: Option<&str>,
+    asil_profile: Option<&AsilProfile>,
     candidate_limit: usize,
 ) -> Result<Vec<QueryCandidate>> {
-    let fts_query = to_fts_query(query_text);
+    // Filter-only queries such as `part:6 ref:7.4.*` list chunks in document order.
+    let (snippet, source, match_clause, order, match_kind) = match lexical_query.fts_match {
+        Some(_) => (
+            "snippet(chunks_fts, 4, '[', ']', ' ... ', 18)",
+            "chunks_fts\n        JOIN chunks c ON c.rowid = chunks_fts.rowid",
+            "chunks_fts MATCH ?1\n          AND ",
+            "bm25(chunks_fts) ASC",
+            "fts",
+        ),
+        None => (
+            "substr(COALESCE(c.text, ''), 1, 420)",
+            "chunks c",
+            "",
+            "c.doc_id ASC, c.chunk_seq ASC",
+            "filter",
+        ),
+    };
+    let (filter_sql, filter_values) = lexical_query.filter_sql(7);
+    let asil_exclusion = asil_exclusion_sql(
+        asil_profile,
+        "c.origin_node_id",
+        "c.leaf_node_type",
+        7 + filter_values.len(),
+    );
 
-    let mut statement = connection.prepare(
+    let mut statement = connection.prepare_cached(&format!(
         "
         SELECT
           c.chunk_id,
@@ -197,7 +268,7 @@ fn query_fts_matches(
           c.page_pdf_start,
           c.page_pdf_end,
           COALESCE(c.source_hash, ''),
-          snippet(chunks_fts, 4, '[', ']', ' ... ', 18),
+          {snippet},
           c.origin_node_id,
           c.leaf_node_type,
           c.ancestor_path,
@@ -206,26 +277,35 @@ fn query_fts_matches(
           c.anchor_label_norm,
           c.anchor_order,
           c.citation_anchor_id
-        FROM chunks_fts
-        JOIN chunks c ON c.rowid = chunks_fts.rowid
-        JOIN docs d ON d.doc_id = c.doc_id
+        FROM {source}
+        JOIN docs d ON d.doc_id = c.doc_id{asil_join}
         WHERE
-          chunks_fts MATCH ?1
-          AND (?2 IS NULL OR d.part = ?2)
+          {match_clause}(?2 IS NULL OR d.part = ?2)
+          AND (?6 IS NULL OR d.year = ?6)
           AND (?3 IS NULL OR c.type = ?3)
-          AND (?4 IS NULL OR lower(COALESCE(c.leaf_node_type, c.type)) = lower(?4))
-        ORDER BY bm25(chunks_fts) ASC
+          AND (?4 IS NULL OR lower(COALESCE(c.leaf_node_type, c.type)) = lower(?4)){filter_sql}{asil_condition}
+        ORDER BY {order}
         LIMIT ?5
         ",
-    )?;
+        asil_join = asil_exclusion.join,
+        asil_condition = asil_exclusion.condition,
+    ))?;
 
-    let mut rows = statement.query(params![
-        fts_query,
-        part_filter.map(i64::from),
+    let part = part_filter.map(i64::from);
+    let edition = edition_filter.map(i64::from);
+    let limit = candidate_limit as i64;
+    let mut bound = params![
+        lexical_query.fts_match,
+        part,
         chunk_type_filter,
         node_type_filter,
-        candidate_limit as i64,
-    ])?;
+        limit,
+        edition
+    ]
+    .to_vec();
+    bound.extend(filter_values.iter().map(|value| value as &dyn ToSql));
+    bound.extend(asil_exclusion.asil.map(|asil| asil as &dyn ToSql));
+    let mut rows = statement.query(bound.as_slice())?;
 
     let mut out = Vec::new();
     let mut index = 0usize;
@@ -234,7 +314,7 @@ fn query_fts_matches(
         let score = 500.0 - (index as f64);
         out.push(QueryCandidate {
             score,
-            match_kind: "fts".to_string(),
+            match_kind: match_kind.to_string(),
             source_tags: vec!["lexical_fts".to_string()],
             lexical_rank: None,
             semantic_rank: None,
@@ -260,124 +340,11 @@ fn query_fts_matches(
             anchor_label_norm: row.get(16)?,
             anchor_order: row.get(17)?,
             citation_anchor_id: row.get(18)?,
+            asil_recommendations: None,
+            asil_demoted: false,
         });
         index += 1;
     }
 
     Ok(out)
 }
-
-fn query_node_matches(
-    connection: &Connection,
-    query_text: &str,
-    part_filter: Option<u32>,
-    chunk_type_filter: Option<&str>,
-    node_type_filter: Option<&str>,
-    candidate_limit: usize,
-) -> Result<Vec<QueryCandidate>> {
-    let mut statement = connection.prepare(
-        "
-        SELECT
-          n.node_id,
-          n.doc_id,
-          d.part,
-          d.year,
-          n.node_type,
-          COALESCE(n.ref, ''),
-          COALESCE(n.heading, ''),
-          n.page_pdf_start,
-          n.page_pdf_end,
-          COALESCE(n.source_hash, ''),
-          substr(COALESCE(n.text, ''), 1, 420),
-          n.ancestor_path,
-          n.anchor_type,
-          n.anchor_label_raw,
-          n.anchor_label_norm,
-          n.anchor_order,
-          n.citation_anchor_id
-        FROM nodes n
-        JOIN docs d ON d.doc_id = n.doc_id
-        WHERE
-          (?2 IS NULL OR d.part = ?2)
-          AND (?3 IS NULL OR lower(n.node_type) = lower(?3))
-          AND (?4 IS NULL OR lower(n.node_type) = lower(?4))
-          AND (
-            lower(n.ref) = lower(?1)
-            OR lower(n.heading) = lower(?1)
-            OR lower(n.ref) LIKE '%' || lower(?1) || '%'
-            OR lower(n.heading) LIKE '%' || lower(?1) || '%'
-            OR lower(n.text) LIKE '%' || lower(?1) || '%'
-          )
-        LIMIT ?5
-        ",
-    )?;
-
-    let mut rows = statement.query(params![
-        query_text,
-        part_filter.map(i64::from),
-        chunk_type_filter,
-        node_type_filter,
-        candidate_limit as i64,
-    ])?;
-
-    let query_lower = query_text.to_lowercase();
-    let mut out = Vec::new();
-
-    while let Some(row) = rows.next()? {
-        let node_id: String = row.get(0)?;
-        let reference: String = row.get(5)?;
-        let heading: String = row.get(6)?;
-        let node_type: String = row.get(4)?;
-        let snippet: String = row.get(10)?;
-
-        let ref_lower = reference.to_lowercase();
-        let heading_lower = heading.to_lowercase();
-        let snippet_lower = snippet.to_lowercase();
-
-        let (score, match_kind) = if ref_lower == query_lower {
-            (850.0, "node_exact_ref")
-        } else if heading_lower == query_lower {
-            (760.0, "node_exact_heading")
-        } else if ref_lower.contains(&query_lower) {
-            (650.0, "node_ref_contains")
-        } else if heading_lower.contains(&query_lower) {
-            (620.0, "node_heading_contains")
-        } else if snippet_lower.contains(&query_lower) {
-            (580.0, "node_text_contains")
-        } else {
-            (550.0, "node_match")
-        };
-
-        out.push(QueryCandidate {
-            score,
-            match_kind: match_kind.to_string(),
-            source_tags: vec!["lexical_node".to_string()],
-            lexical_rank: None,
-            semantic_rank: None,
-            lexical_score: Some(score),
-            semantic_score: None,
-            rrf_score: None,
-            chunk_id: format!("node::{node_id}"),
-            doc_id: row.get(1)?,
-            part: row.get::<_, u32>(2)?,
-            year: row.get::<_, u32>(3)?,
-            chunk_type: node_type.clone(),
-            reference,
-            heading,
-            page_pdf_start: row.get(7)?,
-            page_pdf_end: row.get(8)?,
-            source_hash: row.get(9)?,
-            snippet,
-            origin_node_id: Some(node_id),
-            leaf_node_type: Some(node_type),
-            ancestor_path: row.get(11)?,
-            anchor_type: row.get(12)?,
-            anchor_label_raw: row.get(13)?,
-            anchor_label_norm: row.get(14)?,
-            anchor_order: row.get(15)?,
-            citation_anchor_id: row.get(16)?,
-        });
-    }
-
-    Ok(out)
-}
@@ -1,28 +1,128 @@
-use anyhow::Result;
+use anyhow::{bail, Result};
+use rusqlite::types::ToSql;
 use rusqlite::{params, Connection, OptionalExtension};
 
-use crate::semantic::{cosine_similarity, decode_embedding_blob, embed_text_local};
+use crate::cli::{QueryArgs, QueryGranularity};
+use crate::semantic::ann::{ann_min_candidates, load_ann_index, AnnIndexStatus, IvfIndex};
+use crate::semantic::{
+    cosine_similarity, decode_embedding_blob, load_embedding_backend, load_model_storage_encoding,
+    load_registered_model_config, resolve_model_config, EmbeddingBackend, EmbeddingEncoding,
+};
 
+use super::asil::{annotate_asil_recommendations, asil_exclusion_sql, AsilProfile};
+use super::request_error::QueryRequestError;
 use super::run::{enforce_timeout, sort_candidates, QueryCandidate, QueryTimeoutBudget};
+use super::semantic_text::{
+    lexical_signal_bonus, query_signal_tokens, semantic_embedding_query_text,
+};
+use super::syntax::LexicalQuery;
 
 pub(super) struct SemanticIndexStatus {
     pub(super) available: bool,
     pub(super) reason: Option<String>,
 }
 
+#[derive(Default)]
+pub(super) struct SemanticRetrieval {
+    pub(super) candidates: Vec<QueryCandidate>,
+    pub(super) index: Option<String>,
+    pub(super) index_reason: Option<String>,
+    pub(super) ann_candidate_count: Option<usize>,
+    pub(super) unavailable_reason: Option<String>,
+}
+
+#[allow(clippy::too_many_arguments)]
+pub(super) fn retrieve_semantic_candidates(
+    connection: &Connection,
+    args: &QueryArgs,
+    lexical_query: &LexicalQuery,
+    semantic_model_id: Option<&str>,
+    chunk_type_filter: Option<&str>,
+    node_type_filter: Option<&str>,
+    asil_profile: Option<&AsilProfile>,
+    semantic_k: usize,
+    timeout_budget: Option<QueryTimeoutBudget>,
+) -> Result<SemanticRetrieval> {
+    let Some(model_id) = semantic_model_id else {
+        bail!(QueryRequestError::new(format!(
+            "semantic retrieval requires --semantic-model-id (recommended: {})",
+            crate::semantic::DEFAULT_MODEL_ID
+        )));
+    };
+
+    let semantic_status = semantic_index_status(connection, model_id, args.granularity)?;
+    if !semantic_status.available {
+        let reason = semantic_status
+            .reason
+            .unwrap_or_else(|| "semantic index is unavailable".to_string());
+        if !args.allow_lexical_fallback {
+            bail!(QueryRequestError::new(format!(
+                "{}; run `cargo run -- embed --cache-root .cache/iso26262 --model-id {}` or pass --allow-lexical-fallback",
+                reason, model_id
+            )));
+        }
+        return Ok(SemanticRetrieval {
+            unavailable_reason: Some(reason),
+            ..SemanticRetrieval::default()
+        });
+    }
+
+    let model = match load_registered_model_config(connection, model_id)? {
+        Some(model) => model,
+        None => resolve_model_config(model_id, None)?,
+    };
+    let mut embedder = load_embedding_backend(&model)?;
+    let ann_status = match args.granularity {
+        QueryGranularity::Chunk => load_ann_index(connection, model_id)?,
+        QueryGranularity::Node => AnnIndexStatus {
+            index: None,
+            reason: Some("ANN index covers chunk embeddings only".to_string()),
+        },
+    };
+    let scan = collect_semantic_candidates(
+        connection,
+        lexical_query,
+        args.part,
+        args.edition,
+        chunk_type_filter,
+        node_type_filter,
+        asil_profile,
+        model_id,
+        args.granularity,
+        embedder.as_mut(),
+        ann_status.index.as_deref(),
+        semantic_k,
+        timeout_budget,
+    )?;
+    enforce_timeout(timeout_budget, "semantic retrieval")?;
+
+    Ok(SemanticRetrieval {
+        candidates: scan.candidates,
+        index: Some(scan.scan_path.to_string()),
+        index_reason: scan.fallback_reason.or(ann_status.reason),
+        ann_candidate_count: scan.ann_candidate_count,
+        unavailable_reason: None,
+    })
+}
+
 pub(super) fn semantic_index_status(
     connection: &Connection,
     model_id: &str,
+    granularity: QueryGranularity,
 ) -> Result<SemanticIndexStatus> {
+    let embeddings_table = match granularity {
+        QueryGranularity::Chunk => "chunk_embeddings",
+        QueryGranularity::Node => "node_embeddings",
+    };
     let embeddings_table_exists = connection
         .query_row(
             "
             SELECT name
             FROM sqlite_master
-            WHERE type = 'table' AND name = 'chunk_embeddings'
+            WHERE type = 'table' AND name = ?1
             LIMIT 1
             ",
-            [],
+            [embeddings_table],
             |row| row.get::<_, String>(0),
         )
         .optional()?
@@ -31,9 +131,9 @@ pub(super) fn semantic_index_status(
     if !embeddings_table_exists {
         return Ok(SemanticIndexStatus {
             available: false,
-            reason: Some(
-                "chunk_embeddings table is missing; run ingest on schema 0.4.0+".to_string(),
-            ),
+            reason: Some(format!(
+                "{embeddings_table} table is missing; run ingest and embed on the current schema"
+            )),
         });
     }
 
@@ -53,7 +153,7 @@ pub(super) fn semantic_index_status(
     }
 
     let embedding_count: i64 = connection.query_row(
-        "SELECT COUNT(*) FROM chunk_embeddings WHERE model_id = ?1",
+        &format!("SELECT COUNT(*) FROM {embeddings_table} WHERE model_id = ?1"),
         [model_id],
         |row| row.get(0),
     )?;
@@ -61,7 +161,9 @@ pub(super) fn semantic_index_status(
     if embedding_count <= 0 {
         return Ok(SemanticIndexStatus {
             available: false,
-            reason: Some(format!("semantic index is empty for model '{model_id}'")),
+            reason: Some(format!(
+                "semantic index is empty for model '{model_id}' in {embeddings_table}"
+            )),
         });
     }
 
@@ -71,62 +173,254 @@ pub(super) fn semantic_index_status(
     })
 }
 
+const CHUNK_SEMANTIC_SQL: &str = "
+    SELECT
+      c.chunk_id,
+      c.doc_id,
+      d.part,
+      d.year,
+      c.type,
+      COALESCE(c.ref, ''),
+      COALESCE(c.heading, ''),
+      c.page_pdf_start,
+      c.page_pdf_end,
+      COALESCE(c.source_hash, ''),
+      substr(COALESCE(c.text, ''), 1, 420),
+      c.origin_node_id,
+      c.leaf_node_type,
+      c.ancestor_path,
+      c.anchor_type,
+      c.anchor_label_raw,
+      c.anchor_label_norm,
+      c.anchor_order,
+      c.citation_anchor_id,
+      ce.embedding,
+      ce.embedding_dim
+    FROM chunk_embeddings ce
+    JOIN chunks c ON c.chunk_id = ce.chunk_id
+    JOIN docs d ON d.doc_id = c.doc_id
+    ";
+
+const CHUNK_SEMANTIC_WHERE_SQL: &str = "
+    WHERE
+      ce.model_id = ?1
+      AND (?2 IS NULL OR d.part = ?2)
+      AND (?5 IS NULL OR d.year = ?5)
+      AND (?3 IS NULL OR c.type = ?3)
+      AND (?4 IS NULL OR lower(COALESCE(c.leaf_node_type, c.type)) = lower(?4))
+      AND (?6 IS NULL OR ce.chunk_id IN (SELECT value FROM json_each(?6)))
+    ";
+
+const NODE_SEMANTIC_SQL: &str = "
+    SELECT
+      'node::' || n.node_id,
+      n.doc_id,
+      d.part,
+      d.year,
+      n.node_type,
+      COALESCE(n.ref, ''),
+      COALESCE(n.heading, ''),
+      n.page_pdf_start,
+      n.page_pdf_end,
+      COALESCE(n.source_hash, ''),
+      substr(COALESCE(n.text, ''), 1, 420),
+      n.node_id,
+      n.node_type,
+      n.ancestor_path,
+      n.anchor_type,
+      n.anchor_label_raw,
+      n.anchor_label_norm,
+      n.anchor_order,
+      n.citation_anchor_id,
+      ne.embedding,
+      ne.embedding_dim
+    FROM node_embeddings ne
+    JOIN nodes n ON n.node_id = ne.node_id
+    JOIN chunks c ON c.chunk_id = ne.chunk_id
+    JOIN docs d ON d.doc_id = n.doc_id
+    ";
+
+const NODE_SEMANTIC_WHERE_SQL: &str = "
+    WHERE
+      ne.model_id = ?1
+      AND (?2 IS NULL OR d.part = ?2)
+      AND (?5 IS NULL OR d.year = ?5)
+      AND (?3 IS NULL OR c.type = ?3)
+      AND (?4 IS NULL OR lower(n.node_type) = lower(?4))
+      AND (?6 IS NULL OR ne.node_id IN (SELECT value FROM json_each(?6)))
+    ";
+
+pub(super) const SEMANTIC_SCAN_IVF: &str = "ivf";
+pub(super) const SEMANTIC_SCAN_EXACT: &str = "exact_scan";
+
+pub(super) struct SemanticCandidateScan {
+    pub(super) candidates: Vec<QueryCandidate>,
+    pub(super) scan_path: &'static str,
+    pub(super) ann_candidate_count: Option<usize>,
+    pub(super) fallback_reason: Option<String>,
+}
+
 #[allow(clippy::too_many_arguments)]
-pub(super) fn collect_semantic_candidates(
+fn collect_semantic_candidates(
     connection: &Connection,
-    query_text: &str,
+    lexical_query: &LexicalQuery,
     part_filter: Option<u32>,
+    edition_filter: Option<u32>,
     chunk_type_filter: Option<&str>,
     node_type_filter: Option<&str>,
+    asil_profile: Option<&AsilProfile>,
     model_id: &str,
-    embedding_dim: usize,
+    granularity: QueryGranularity,
+    embedder: &mut dyn EmbeddingBackend,
+    ann_index: Option<&IvfIndex>,
     candidate_limit: usize,
     timeout_budget: Option<QueryTimeoutBudget>,
-) -> Result<Vec<QueryCandidate>> {
-    let semantic_query_text = semantic_embedding_query_text(query_text);
-    let query_embedding = embed_text_local(&semantic_query_text, embedding_dim);
+) -> Result<SemanticCandidateScan> {
+    let semantic_query_text = semantic_embedding_query_text(&lexical_query.semantic_text()?);
+    let embedding_dim = embedder.dimensions();
+    let query_embedding = embedder.embed(&semantic_query_text)?;
     let query_tokens = query_signal_tokens(&semantic_query_text);
-    let mut statement = connection.prepare(
-        "
-        SELECT
-          c.chunk_id,
-          c.doc_id,
-          d.part,
-          d.year,
-          c.type,
-          COALESCE(c.ref, ''),
-          COALESCE(c.heading, ''),
-          c.page_pdf_start,
-          c.page_pdf_end,
-          COALESCE(c.source_hash, ''),
-          substr(COALESCE(c.text, ''), 1, 420),
-          c.origin_node_id,
-          c.leaf_node_type,
-          c.ancestor_path,
-          c.anchor_type,
-          c.anchor_label_raw,
-          c.anchor_label_norm,
-          c.anchor_order,
-          c.citation_anchor_id,
-          ce.embedding,
-          ce.embedding_dim
-        FROM chunk_embeddings ce
-        JOIN chunks c ON c.chunk_id = ce.chunk_id
-        JOIN docs d ON d.doc_id = c.doc_id
-        WHERE
-          ce.model_id = ?1
-          AND (?2 IS NULL OR d.part = ?2)
-          AND (?3 IS NULL OR c.type = ?3)
-          AND (?4 IS NULL OR lower(COALESCE(c.leaf_node_type, c.type)) = lower(?4))
-        ",
-    )?;
-
-    let mut rows = statement.query(params![
+    let filters = SemanticRowFilters {
         model_id,
-        part_filter.map(i64::from),
+        granularity,
+        storage_encoding: load_model_storage_encoding(connection, model_id)?.unwrap_or_default(),
+        part_filter,
+        edition_filter,
         chunk_type_filter,
         node_type_filter,
-    ])?;
+        asil_profile,
+        lexical_query,
+    };
+
+    let mut scan = SemanticCandidateScan {
+        candidates: Vec::new(),
+        scan_path: SEMANTIC_SCAN_EXACT,
+        ann_candidate_count: None,
+        fallback_reason: None,
+    };
+    let mut scanned = None::<Vec<QueryCandidate>>;
+    match ann_index {
+        Some(index) if index.dimensions != embedding_dim => {
+            scan.fallback_reason = Some(format!(
+                "ANN index dimension {} does not match query embedding dimension {embedding_dim}",
+                index.dimensions
+            ));
+        }
+        Some(index) => {
+            let pool = index.candidate_pool(&query_embedding, ann_min_candidates(candidate_limit));
+            let pool_json = serde_json::to_string(&pool)?;
+            let candidates = score_semantic_rows(
+                connection,
+                &filters,
+                Some(&pool_json),
+                &query_embedding,
+                &query_tokens,
+                embedding_dim,
+                timeout_budget,
+            )?;
+            scan.ann_candidate_count = Some(pool.len());
+            if candidates.len() >= candidate_limit || pool.len() >= index.vector_count {
+                scan.scan_path = SEMANTIC_SCAN_IVF;
+                scanned = Some(candidates);
+            } else {
+                scan.fallback_reason = Some(format!(
+                    "ANN probe returned {} of {candidate_limit} filtered candidates",
+                    candidates.len()
+                ));
+            }
+        }
+        None => {}
+    }
+
+    let mut out = match scanned {
+        Some(candidates) => candidates,
+        None => score_semantic_rows(
+            connection,
+            &filters,
+            None,
+            &query_embedding,
+            &query_tokens,
+            embedding_dim,
+            timeout_budget,
+        )?,
+    };
+
+    annotate_asil_recommendations(connection, &mut out, asil_profile)?;
+    sort_candidates(&mut out);
+    if out.len() > candidate_limit {
+        out.truncate(candidate_limit);
+    }
+    for (index, candidate) in out.iter_mut().enumerate() {
+        candidate.semantic_rank = Some(index + 1);
+        candidate.semantic_score = Some(candidate.score);
+    }
+    enforce_timeout(timeout_budget, "semantic candidate ranking")?;
+
+    scan.candidates = out;
+    Ok(scan)
+}
+
+struct SemanticRowFilters<'a> {
+    model_id: &'a str,
+    granularity: QueryGranularity,
+    storage_encoding: EmbeddingEncoding,
+    part_filter: Option<u32>,
+    edition_filter: Option<u32>,
+    chunk_type_filter: Option<&'a str>,
+    node_type_filter: Option<&'a str>,
+    asil_profile: Option<&'a AsilProfile>,
+    lexical_query: &'a LexicalQuery,
+}
+
+fn score_semantic_rows(
+    connection: &Connection,
+    filters: &SemanticRowFilters<'_>,
+    chunk_id_pool_json: Option<&str>,
+    query_embedding: &[f32],
+    query_tokens: &[String],
+    embedding_dim: usize,
+    timeout_budget: Option<QueryTimeoutBudget>,
+) -> Result<Vec<QueryCandidate>> {
+    let (select_sql, where_sql, origin_node, leaf_node_type) = match filters.granularity {
+        QueryGranularity::Chunk => (
+            CHUNK_SEMANTIC_SQL,
+            CHUNK_SEMANTIC_WHERE_SQL,
+            "c.origin_node_id",
+            "c.leaf_node_type",
+        ),
+        QueryGranularity::Node => (
+            NODE_SEMANTIC_SQL,
+            NODE_SEMANTIC_WHERE_SQL,
+            "n.node_id",
+            "n.node_type",
+        ),
+    };
+    let (filter_sql, filter_values) = filters.lexical_query.filter_sql(7);
+    let asil_exclusion = asil_exclusion_sql(
+        filters.asil_profile,
+        origin_node,
+        leaf_node_type,
+        7 + filter_values.len(),
+    );
+    let mut statement = connection.prepare_cached(&format!(
+        "{select_sql}{}{where_sql}{filter_sql}{}",
+        asil_exclusion.join, asil_exclusion.condition
+    ))?;
+
+    let part = filters.part_filter.map(i64::from);
+    let edition = filters.edition_filter.map(i64::from);
+    let mut bound = params![
+        filters.model_id,
+        part,
+        filters.chunk_type_filter,
+        filters.node_type_filter,
+        edition,
+        chunk_id_pool_json,
+    ]
+    .to_vec();
+    bound.extend(filter_values.iter().map(|value| value as &dyn ToSql));
+    bound.extend(asil_exclusion.asil.map(|asil| asil as &dyn ToSql));
+    let mut rows = statement.query(bound.as_slice())?;
 
     let mut out = Vec::<QueryCandidate>::new();
     let mut scanned_rows = 0usize;
@@ -142,16 +436,17 @@ pub(super) fn collect_semantic_candidates(
         }
 
         let embedding_blob = row.get::<_, Vec<u8>>(19)?;
-        let Some(candidate_embedding) = decode_embedding_blob(&embedding_blob, embedding_dim)
+        let Some(candidate_embedding) =
+            decode_embedding_blob(&embedding_blob, embedding_dim, filters.storage_encoding)
         else {
             continue;
         };
 
-        let semantic_score = cosine_similarity(&query_embedding, &candidate_embedding);
+        let semantic_score = cosine_similarity(query_embedding, &candidate_embedding);
         let reference = row.get::<_, String>(5)?;
         let heading = row.get::<_, String>(6)?;
         let snippet = row.get::<_, String>(10)?;
-        let lexical_bonus = lexical_signal_bonus(&query_tokens, &reference, &heading, &snippet);
+        let lexical_bonus = lexical_signal_bonus(query_tokens, &reference, &heading, &snippet);
         let score = semantic_score * 0.45 + lexical_bonus * 0.55;
         out.push(QueryCandidate {
             score,
@@ -181,106 +476,10 @@ pub(super) fn collect_semantic_candidates(
             anchor_label_norm: row.get(16)?,
             anchor_order: row.get(17)?,
             citation_anchor_id: row.get(18)?,
+            asil_recommendations: None,
+            asil_demoted: false,
         });
     }
 
-    sort_candidates(&mut out);
-    if out.len() > candidate_limit {
-        out.truncate(candidate_limit);
-    }
-    for (index, candidate) in out.iter_mut().enumerate() {
-        candidate.semantic_rank = Some(index + 1);
-        candidate.semantic_score = Some(candidate.score);
-    }
-    enforce_timeout(timeout_budget, "semantic candidate ranking")?;
-
     Ok(out)
 }
-
-fn query_signal_tokens(query_text: &str) -> Vec<String> {
-    const STOPWORDS: &[&str] = &[
-        "a",
-        "an",
-        "and",
-        "around",
-        "concept",
-        "concerning",
-        "for",
-        "guidance",
-        "in",
-        "of",
-        "on",
-        "related",
-        "requirement",
-        "requirements",
-        "the",
-        "to",
-        "with",
-    ];
-
-    let mut tokens = query_text
-        .to_ascii_lowercase()
-        .split(|ch: char| !ch.is_ascii_alphanumeric())
-        .filter(|token| token.len() >= 3)
-        .filter(|token| STOPWORDS.iter().all(|stopword| stopword != token))
-        .map(str::to_string)
-        .collect::<Vec<String>>();
-    tokens.sort();
-    tokens.dedup();
-    tokens
-}
-
-fn lexical_signal_bonus(
-    query_tokens: &[String],
-    reference: &str,
-    heading: &str,
-    text: &str,
-) -> f64 {
-    if query_tokens.is_empty() {
-        return 0.0;
-    }
-
-    let haystack = format!(
-        "{} {} {}",
-        reference.to_ascii_lowercase(),
-        heading.to_ascii_lowercase(),
-        text.to_ascii_lowercase()
-    );
-    let overlap = query_tokens
-        .iter()
-        .filter(|token| haystack.contains(token.as_str()))
-        .count();
-    overlap as f64 / query_tokens.len() as f64
-}
-
-fn semantic_embedding_query_text(query_text: &str) -> String {
-    const NOISE_PREFIXES: &[&str] = &[
-        "concept guidance for ",
-        "requirements concerning ",
-        "requirements regarding ",
-        "requirements for ",
-        "guidance for ",
-    ];
-
-    let normalized = query_text
-        .split_whitespace()
-        .collect::<Vec<&str>>()
-        .join(" ")
-        .trim()
-        .to_string();
-    if normalized.is_empty() {
-        return normalized;
-    }
-
-    let lowered = normalized.to_ascii_lowercase();
-    for prefix in NOISE_PREFIXES {
-        if lowered.starts_with(prefix) {
-            let stripped = normalized[prefix.len()..].trim();
-            if !stripped.is_empty() {
-                return stripped.to_string();
-            }
-        }
-    }
-
-    normalized
-}
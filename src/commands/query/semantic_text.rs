@@ -0,0 +1,87 @@
+pub(super) fn query_signal_tokens(query_text: &str) -> Vec<String> {
+    const STOPWORDS: &[&str] = &[
+        "a",
+        "an",
+        "and",
+        "around",
+        "concept",
+        "concerning",
+        "for",
+        "guidance",
+        "in",
+        "of",
+        "on",
+        "related",
+        "requirement",
+        "requirements",
+        "the",
+        "to",
+        "with",
+    ];
+
+    let mut tokens = query_text
+        .to_ascii_lowercase()
+        .split(|ch: char| !ch.is_ascii_alphanumeric())
+        .filter(|token| token.len() >= 3)
+        .filter(|token| STOPWORDS.iter().all(|stopword| stopword != token))
+        .map(str::to_string)
+        .collect::<Vec<String>>();
+    tokens.sort();
+    tokens.dedup();
+    tokens
+}
+
+pub(super) fn lexical_signal_bonus(
+    query_tokens: &[String],
+    reference: &str,
+    heading: &str,
+    text: &str,
+) -> f64 {
+    if query_tokens.is_empty() {
+        return 0.0;
+    }
+
+    let haystack = format!(
+        "{} {} {}",
+        reference.to_ascii_lowercase(),
+        heading.to_ascii_lowercase(),
+        text.to_ascii_lowercase()
+    );
+    let overlap = query_tokens
+        .iter()
+        .filter(|token| haystack.contains(token.as_str()))
+        .count();
+    overlap as f64 / query_tokens.len() as f64
+}
+
+pub(super) fn semantic_embedding_query_text(query_text: &str) -> String {
+    const NOISE_PREFIXES: &[&str] = &[
+        "concept guidance for ",
+        "requirements concerning ",
+        "requirements regarding ",
+        "requirements for ",
+        "guidance for ",
+    ];
+
+    let normalized = query_text
+        .split_whitespace()
+        .collect::<Vec<&str>>()
+        .join(" ")
+        .trim()
+        .to_string();
+    if normalized.is_empty() {
+        return normalized;
+    }
+
+    let lowered = normalized.to_ascii_lowercase();
+    for prefix in NOISE_PREFIXES {
+        if lowered.starts_with(prefix) {
+            let stripped = normalized[prefix.len()..].trim();
+            if !stripped.is_empty() {
+                return stripped.to_string();
+            }
+        }
+    }
+
+    normalized
+}
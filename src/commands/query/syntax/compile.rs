@@ -0,0 +1,234 @@
+use rusqlite::types::Value;
+
+use super::{
+    Expr, Field, FilterCondition, FilterField, LexicalQuery, QuerySyntaxError, SqlFilter, Term,
+};
+
+pub(super) fn compile(
+    query: &str,
+    expr: Expr,
+    uses_operators: bool,
+) -> Result<LexicalQuery, QuerySyntaxError> {
+    let mut items = Vec::new();
+    flatten_and(expr, &mut items);
+
+    let mut positives = Vec::new();
+    let mut negatives = Vec::new();
+    let mut filters = Vec::new();
+    let mut plain_terms = Vec::new();
+    let mut semantic_terms = Vec::new();
+    for item in items {
+        match item {
+            Expr::Term(term) => match term.filter_field() {
+                Some(field) => filters.push(sql_filter(query, field, &term, false)?),
+                None => {
+                    plain_terms.push(term.text.clone());
+                    semantic_terms.push(term.text.clone());
+                    positives.push(fts_term(&term));
+                }
+            },
+            Expr::Not(operand, ..) => {
+                let filter_field = match &*operand {
+                    Expr::Term(term) => term.filter_field(),
+                    _ => None,
+                };
+                match (filter_field, *operand) {
+                    (Some(field), Expr::Term(term)) => {
+                        filters.push(sql_filter(query, field, &term, true)?);
+                    }
+                    (_, operand) => negatives.push(compile_fts(query, &operand)?),
+                }
+            }
+            other => {
+                collect_semantic_terms(&other, &mut semantic_terms);
+                positives.push(compile_fts(query, &other)?);
+            }
+        }
+    }
+
+    // Top-level exclusions are filters rather than FTS NOT so semantic candidates honour them.
+    if !negatives.is_empty() {
+        filters.push(SqlFilter {
+            condition: FilterCondition::ExcludedText(negatives.join(" OR ")),
+            negated: false,
+        });
+    }
+    let plain_text = (!uses_operators && !plain_terms.is_empty()).then(|| plain_terms.join(" "));
+    let fts_match = (!positives.is_empty()).then(|| positives.join(" AND "));
+
+    Ok(LexicalQuery {
+        query: query.to_string(),
+        fts_match,
+        filters,
+        plain_text,
+        semantic_terms,
+    })
+}
+
+impl FilterCondition {
+    pub(super) fn clause(&self, param: usize) -> (String, Value) {
+        match self {
+            Self::Part(part) => (format!("d.part = ?{param}"), Value::from(*part)),
+            Self::Edition(year) => (format!("d.year = ?{param}"), Value::from(*year)),
+            Self::ChunkType(chunk_type) => (
+                format!("c.type = ?{param}"),
+                Value::from(chunk_type.clone()),
+            ),
+            Self::Ref(reference) => (
+                format!("lower(c.ref) = ?{param}"),
+                Value::from(reference.clone()),
+            ),
+            Self::RefLike(pattern) => (
+                format!("lower(c.ref) LIKE ?{param} ESCAPE '\\'"),
+                Value::from(pattern.clone()),
+            ),
+            Self::ExcludedText(expression) => (
+                format!(
+                    "c.rowid NOT IN (SELECT rowid FROM chunks_fts WHERE chunks_fts MATCH ?{param})"
+                ),
+                Value::from(expression.clone()),
+            ),
+        }
+    }
+}
+
+fn collect_semantic_terms(expr: &Expr, terms: &mut Vec<String>) {
+    match expr {
+        Expr::Term(term) if term.filter_field().is_none() => terms.push(term.text.clone()),
+        Expr::Term(_) | Expr::Not(..) => {}
+        Expr::And(items) | Expr::Or(items) => {
+            for item in items {
+                collect_semantic_terms(item, terms);
+            }
+        }
+    }
+}
+
+fn flatten_and(expr: Expr, items: &mut Vec<Expr>) {
+    match expr {
+        Expr::And(children) => {
+            for child in children {
+                flatten_and(child, items);
+            }
+        }
+        other => items.push(other),
+    }
+}
+
+fn conjunction(positives: Vec<String>, negatives: &[String]) -> String {
+    let mut expression = if positives.len() > 1 && !negatives.is_empty() {
+        format!("({})", positives.join(" AND "))
+    } else {
+        positives.join(" AND ")
+    };
+    for negative in negatives {
+        expression.push_str(" NOT ");
+        expression.push_str(negative);
+    }
+    expression
+}
+
+fn compile_fts(query: &str, expr: &Expr) -> Result<String, QuerySyntaxError> {
+    match expr {
+        Expr::Term(term) => {
+            if let Some(field) = term.filter_field() {
+                return Err(QuerySyntaxError::new(
+                    query,
+                    term.start,
+                    term.end,
+                    format!(
+                        "'{}:' filters results and can only be combined with AND at the top level",
+                        field.name()
+                    ),
+                ));
+            }
+            Ok(fts_term(term))
+        }
+        Expr::Or(branches) => {
+            let branches = branches
+                .iter()
+                .map(|branch| compile_fts(query, branch))
+                .collect::<Result<Vec<String>, QuerySyntaxError>>()?;
+            Ok(format!("({})", branches.join(" OR ")))
+        }
+        Expr::And(items) => {
+            let mut positives = Vec::new();
+            let mut negatives = Vec::new();
+            for item in items {
+                match item {
+                    Expr::Not(operand, _, _) => negatives.push(compile_fts(query, operand)?),
+                    other => positives.push(compile_fts(query, other)?),
+                }
+            }
+            if positives.is_empty() {
+                return Err(misplaced_not(query, &items[0]));
+            }
+            Ok(format!("({})", conjunction(positives, &negatives)))
+        }
+        Expr::Not(_, _, _) => Err(misplaced_not(query, expr)),
+    }
+}
+
+fn misplaced_not(query: &str, expr: &Expr) -> QuerySyntaxError {
+    let (start, end) = match expr {
+        Expr::Not(_, start, end) => (*start, *end),
+        _ => (0, query.chars().count()),
+    };
+    QuerySyntaxError::new(
+        query,
+        start,
+        end,
+        "NOT inside parentheses or OR needs a term to exclude from, e.g. (safety NOT annex)",
+    )
+}
+
+fn fts_term(term: &Term) -> String {
+    let mut quoted = format!("\"{}\"", term.text.replace('"', "\"\""));
+    if term.prefix {
+        quoted.push('*');
+    }
+    match term.field {
+        Some(Field::Column(column)) => format!("{} : {quoted}", column.name()),
+        _ => quoted,
+    }
+}
+
+fn sql_filter(
+    query: &str,
+    field: FilterField,
+    term: &Term,
+    negated: bool,
+) -> Result<SqlFilter, QuerySyntaxError> {
+    let value_error =
+        |message: String| QuerySyntaxError::new(query, term.value_start, term.end, message);
+    if term.prefix && field != FilterField::Ref {
+        return Err(value_error(format!(
+            "'{}:' does not support '*'; only ref: accepts a prefix",
+            field.name()
+        )));
+    }
+
+    let text = term.text.trim();
+    let number = || {
+        text.parse::<u32>().map_err(|_| {
+            value_error(format!(
+                "'{}:' expects a number, found '{text}'",
+                field.name()
+            ))
+        })
+    };
+    let condition = match field {
+        FilterField::Part => FilterCondition::Part(number()?),
+        FilterField::Edition => FilterCondition::Edition(number()?),
+        FilterField::Type => FilterCondition::ChunkType(text.to_lowercase()),
+        FilterField::Ref if term.prefix => FilterCondition::RefLike(format!(
+            "{}%",
+            text.to_lowercase()
+                .replace('\\', "\\\\")
+                .replace('%', "\\%")
+                .replace('_', "\\_")
+        )),
+        FilterField::Ref => FilterCondition::Ref(text.to_lowercase()),
+    };
+    Ok(SqlFilter { condition, negated })
+}
@@ -0,0 +1,196 @@
+mod compile;
+mod parse;
+
+use std::fmt;
+
+use rusqlite::types::Value;
+
+/// Lexical query compiled from the `--query` language.
+#[derive(Debug, Clone, PartialEq)]
+pub(super) struct LexicalQuery {
+    query: String,
+    /// FTS5 MATCH expression; `None` when the query only filters and excludes.
+    pub(super) fts_match: Option<String>,
+    /// Field filters and top-level NOT exclusions.
+    pub(super) filters: Vec<SqlFilter>,
+    /// Terms as typed for plain queries; drives exact ref/heading matching.
+    pub(super) plain_text: Option<String>,
+    /// Non-excluded terms in query order, for semantic retrieval.
+    semantic_terms: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(super) struct SqlFilter {
+    condition: FilterCondition,
+    negated: bool,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum FilterCondition {
+    Part(u32),
+    Edition(u32),
+    ChunkType(String),
+    Ref(String),
+    RefLike(String),
+    /// Chunks matching this FTS expression are dropped.
+    ExcludedText(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct QuerySyntaxError {
+    query: String,
+    start: usize,
+    end: usize,
+    message: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Field {
+    Column(FtsColumn),
+    Filter(FilterField),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FtsColumn {
+    Heading,
+    Text,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FilterField {
+    Ref,
+    Part,
+    Edition,
+    Type,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Term {
+    field: Option<Field>,
+    text: String,
+    prefix: bool,
+    start: usize,
+    value_start: usize,
+    end: usize,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Expr {
+    Term(Term),
+    And(Vec<Expr>),
+    Or(Vec<Expr>),
+    Not(Box<Expr>, usize, usize),
+}
+
+impl LexicalQuery {
+    pub(super) fn parse(query_text: &str) -> Result<Self, QuerySyntaxError> {
+        let (expr, uses_operators) = parse::parse(query_text)?;
+        compile::compile(query_text, expr, uses_operators)
+    }
+
+    /// Text for node LIKE matching.
+    pub(super) fn node_text(&self) -> Option<&str> {
+        self.plain_text
+            .as_deref()
+            .filter(|_| self.filters.is_empty())
+    }
+
+    /// Text handed to the embedder.
+    pub(super) fn semantic_text(&self) -> Result<String, QuerySyntaxError> {
+        if self.semantic_terms.is_empty() {
+            return Err(QuerySyntaxError::new(
+                &self.query,
+                0,
+                self.query.chars().count(),
+                "semantic retrieval needs at least one search term besides filters and NOT",
+            ));
+        }
+        Ok(self.semantic_terms.join(" "))
+    }
+
+    /// WHERE-clause SQL over `chunks c` and `docs d`, numbered from `first_param`.
+    pub(super) fn filter_sql(&self, first_param: usize) -> (String, Vec<Value>) {
+        let mut sql = String::new();
+        let mut values = Vec::with_capacity(self.filters.len());
+        for (offset, filter) in self.filters.iter().enumerate() {
+            let (clause, value) = filter.condition.clause(first_param + offset);
+            if filter.negated {
+                sql.push_str(&format!("\n          AND NOT COALESCE({clause}, 0)"));
+            } else {
+                sql.push_str(&format!("\n          AND {clause}"));
+            }
+            values.push(value);
+        }
+        (sql, values)
+    }
+}
+
+impl QuerySyntaxError {
+    fn new(query: &str, start: usize, end: usize, message: impl Into<String>) -> Self {
+        Self {
+            query: query.to_string(),
+            start,
+            end,
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for QuerySyntaxError {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            formatter,
+            "invalid query at column {}: {}\n  {}\n  {}{}",
+            self.start + 1,
+            self.message,
+            self.query,
+            " ".repeat(self.start),
+            "^".repeat(self.end.saturating_sub(self.start).max(1))
+        )
+    }
+}
+
+impl std::error::Error for QuerySyntaxError {}
+
+impl Field {
+    fn from_name(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "heading" => Some(Self::Column(FtsColumn::Heading)),
+            "text" => Some(Self::Column(FtsColumn::Text)),
+            "ref" => Some(Self::Filter(FilterField::Ref)),
+            "part" => Some(Self::Filter(FilterField::Part)),
+            "edition" | "year" => Some(Self::Filter(FilterField::Edition)),
+            "type" => Some(Self::Filter(FilterField::Type)),
+            _ => None,
+        }
+    }
+}
+
+impl FtsColumn {
+    fn name(self) -> &'static str {
+        match self {
+            Self::Heading => "heading",
+            Self::Text => "text",
+        }
+    }
+}
+
+impl FilterField {
+    fn name(self) -> &'static str {
+        match self {
+            Self::Ref => "ref",
+            Self::Part => "part",
+            Self::Edition => "edition",
+            Self::Type => "type",
+        }
+    }
+}
+
+impl Term {
+    fn filter_field(&self) -> Option<FilterField> {
+        match self.field {
+            Some(Field::Filter(field)) => Some(field),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,325 @@
+use super::{Expr, Field, QuerySyntaxError, Term};
+
+const OPERATOR_HINT: &str = "operators are AND, OR, NOT (upper case) and parentheses";
+
+#[derive(Debug, Clone, PartialEq)]
+enum TokenKind {
+    LeftParen,
+    RightParen,
+    And,
+    Or,
+    Not,
+    Term(Term),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Token {
+    kind: TokenKind,
+    start: usize,
+    end: usize,
+}
+
+/// Parses `query`, also reporting whether it uses operators beyond plain terms.
+pub(super) fn parse(query: &str) -> Result<(Expr, bool), QuerySyntaxError> {
+    let chars = query.chars().collect::<Vec<char>>();
+    let tokens = tokenize(query, &chars)?;
+    if tokens.is_empty() {
+        return Err(QuerySyntaxError::new(query, 0, 0, "query is empty"));
+    }
+    let mut parser = Parser {
+        query,
+        tokens: &tokens,
+        position: 0,
+    };
+    let expr = parser.parse_query()?;
+    let uses_operators = tokens.iter().any(|token| match &token.kind {
+        TokenKind::Term(term) => match term.field {
+            Some(Field::Filter(_)) => false,
+            Some(Field::Column(_)) => true,
+            None => term.prefix,
+        },
+        _ => true,
+    });
+    Ok((expr, uses_operators))
+}
+
+fn tokenize(query: &str, chars: &[char]) -> Result<Vec<Token>, QuerySyntaxError> {
+    let mut tokens = Vec::new();
+    let mut index = 0usize;
+
+    while index < chars.len() {
+        let start = index;
+        let kind = match chars[index] {
+            character if character.is_whitespace() => {
+                index += 1;
+                continue;
+            }
+            '(' => {
+                index += 1;
+                TokenKind::LeftParen
+            }
+            ')' => {
+                index += 1;
+                TokenKind::RightParen
+            }
+            '"' => {
+                let (text, prefix, end) = read_phrase(query, chars, index)?;
+                index = end;
+                TokenKind::Term(Term {
+                    field: None,
+                    text,
+                    prefix,
+                    start,
+                    value_start: start,
+                    end,
+                })
+            }
+            _ => {
+                // Parentheses opened inside a word stay part of it, so `8.4.5(a)` is one term.
+                let mut depth = 0usize;
+                while index < chars.len() {
+                    let character = chars[index];
+                    if character.is_whitespace() || character == '"' {
+                        break;
+                    }
+                    if character == '(' {
+                        depth += 1;
+                    } else if character == ')' {
+                        if depth == 0 {
+                            break;
+                        }
+                        depth -= 1;
+                    }
+                    index += 1;
+                }
+                let word = chars[start..index].iter().collect::<String>();
+                match word.as_str() {
+                    "AND" => TokenKind::And,
+                    "OR" => TokenKind::Or,
+                    "NOT" => TokenKind::Not,
+                    _ => {
+                        let term = read_word_term(query, chars, &word, start, &mut index)?;
+                        TokenKind::Term(term)
+                    }
+                }
+            }
+        };
+        tokens.push(Token {
+            kind,
+            start,
+            end: index,
+        });
+    }
+
+    Ok(tokens)
+}
+
+fn read_phrase(
+    query: &str,
+    chars: &[char],
+    open: usize,
+) -> Result<(String, bool, usize), QuerySyntaxError> {
+    let Some(close) = (open + 1..chars.len()).find(|index| chars[*index] == '"') else {
+        return Err(QuerySyntaxError::new(
+            query,
+            open,
+            chars.len(),
+            "unterminated phrase; add a closing '\"'",
+        ));
+    };
+    let text = chars[open + 1..close].iter().collect::<String>();
+    if text.trim().is_empty() {
+        return Err(QuerySyntaxError::new(
+            query,
+            open,
+            close + 1,
+            "empty phrase",
+        ));
+    }
+    let prefix = chars.get(close + 1) == Some(&'*');
+    let end = if prefix { close + 2 } else { close + 1 };
+    Ok((text, prefix, end))
+}
+
+fn read_word_term(
+    query: &str,
+    chars: &[char],
+    word: &str,
+    start: usize,
+    index: &mut usize,
+) -> Result<Term, QuerySyntaxError> {
+    let field = word
+        .split_once(':')
+        .and_then(|(name, value)| Field::from_name(name).map(|field| (field, name, value)));
+    let Some((field, name, value)) = field else {
+        let (text, prefix) = split_prefix(query, word, start, *index)?;
+        return Ok(Term {
+            field: None,
+            text,
+            prefix,
+            start,
+            value_start: start,
+            end: *index,
+        });
+    };
+
+    let value_start = start + name.chars().count() + 1;
+    if !value.is_empty() {
+        let (text, prefix) = split_prefix(query, value, value_start, *index)?;
+        return Ok(Term {
+            field: Some(field),
+            text,
+            prefix,
+            start,
+            value_start,
+            end: *index,
+        });
+    }
+    if chars.get(*index) == Some(&'"') {
+        let (text, prefix, end) = read_phrase(query, chars, *index)?;
+        *index = end;
+        return Ok(Term {
+            field: Some(field),
+            text,
+            prefix,
+            start,
+            value_start,
+            end,
+        });
+    }
+    Err(QuerySyntaxError::new(
+        query,
+        start,
+        *index,
+        format!("expected a value after '{name}:'"),
+    ))
+}
+
+fn split_prefix(
+    query: &str,
+    word: &str,
+    start: usize,
+    end: usize,
+) -> Result<(String, bool), QuerySyntaxError> {
+    let text = word.trim_end_matches('*');
+    if text.is_empty() {
+        return Err(QuerySyntaxError::new(
+            query,
+            start,
+            end,
+            "'*' must follow a term, e.g. interfer*",
+        ));
+    }
+    Ok((text.to_string(), text.len() != word.len()))
+}
+
+struct Parser<'a> {
+    query: &'a str,
+    tokens: &'a [Token],
+    position: usize,
+}
+
+impl Parser<'_> {
+    fn parse_query(&mut self) -> Result<Expr, QuerySyntaxError> {
+        let expr = self.parse_or()?;
+        if let Some(token) = self.tokens.get(self.position) {
+            // parse_or only stops early at a ')' it did not open.
+            return Err(self.error(token, "unexpected ')' without a matching '('"));
+        }
+        Ok(expr)
+    }
+
+    fn parse_or(&mut self) -> Result<Expr, QuerySyntaxError> {
+        let mut branches = vec![self.parse_and()?];
+        while self.peek() == Some(&TokenKind::Or) {
+            self.position += 1;
+            branches.push(self.parse_and()?);
+        }
+        Ok(match branches.len() {
+            1 => branches.remove(0),
+            _ => Expr::Or(branches),
+        })
+    }
+
+    fn parse_and(&mut self) -> Result<Expr, QuerySyntaxError> {
+        let mut items = vec![self.parse_unary()?];
+        loop {
+            match self.peek() {
+                Some(TokenKind::And) => {
+                    self.position += 1;
+                    items.push(self.parse_unary()?);
+                }
+                Some(TokenKind::Not | TokenKind::LeftParen | TokenKind::Term(_)) => {
+                    items.push(self.parse_unary()?);
+                }
+                _ => break,
+            }
+        }
+        Ok(match items.len() {
+            1 => items.remove(0),
+            _ => Expr::And(items),
+        })
+    }
+
+    fn parse_unary(&mut self) -> Result<Expr, QuerySyntaxError> {
+        if self.peek() == Some(&TokenKind::Not) {
+            let token = &self.tokens[self.position];
+            self.position += 1;
+            let operand = self.parse_unary()?;
+            return Ok(Expr::Not(Box::new(operand), token.start, token.end));
+        }
+        self.parse_primary()
+    }
+
+    fn parse_primary(&mut self) -> Result<Expr, QuerySyntaxError> {
+        let Some(token) = self.tokens.get(self.position) else {
+            let last = &self.tokens[self.position - 1];
+            return Err(self.error(
+                last,
+                format!("expected a term after '{}'", self.token_text(last)),
+            ));
+        };
+        self.position += 1;
+
+        match &token.kind {
+            TokenKind::Term(term) => Ok(Expr::Term(term.clone())),
+            TokenKind::LeftParen => {
+                if self.peek() == Some(&TokenKind::RightParen) {
+                    return Err(self.error(token, "empty parentheses"));
+                }
+                let expr = self.parse_or()?;
+                if self.peek() != Some(&TokenKind::RightParen) {
+                    return Err(self.error(token, "unclosed '('; add a matching ')'"));
+                }
+                self.position += 1;
+                Ok(expr)
+            }
+            TokenKind::RightParen => {
+                Err(self.error(token, "unexpected ')' without a matching '('"))
+            }
+            TokenKind::And | TokenKind::Or | TokenKind::Not => Err(self.error(
+                token,
+                format!(
+                    "expected a term before '{}'; {OPERATOR_HINT}",
+                    self.token_text(token)
+                ),
+            )),
+        }
+    }
+
+    fn peek(&self) -> Option<&TokenKind> {
+        self.tokens.get(self.position).map(|token| &token.kind)
+    }
+
+    fn token_text(&self, token: &Token) -> String {
+        self.query
+            .chars()
+            .skip(token.start)
+            .take(token.end - token.start)
+            .collect()
+    }
+
+    fn error(&self, token: &Token, message: impl Into<String>) -> QuerySyntaxError {
+        QuerySyntaxError::new(self.query, token.start, token.end, message)
+    }
+}
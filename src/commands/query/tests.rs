@@ -0,0 +1,490 @@
+use rusqlite::{params, Connection};
+
+use crate::semantic::{
+    encode_embedding_blob, load_embedding_backend, resolve_model_config, EmbeddingEncoding,
+};
+
+use super::asil::{is_not_recommended_at_asil, AsilRecommendationHit};
+use super::execute::execute_query;
+use super::params::query_args_from_params;
+use super::run::QueryResponse;
+use super::syntax::LexicalQuery;
+
+fn fixture_connection() -> Connection {
+    crate::commands::ingest::open_fixture_database(
+        None,
+        "
+        INSERT INTO docs(doc_id, filename, sha256, part, year, title) VALUES
+          ('ISO26262-6-2018', 'part6.pdf', 'sha6', 6, 2018, 'Part 6'),
+          ('ISO26262-9-2018', 'part9.pdf', 'sha9', 9, 2018, 'Part 9');
+        INSERT INTO chunks(
+          chunk_id, doc_id, type, ref, heading, chunk_seq, page_pdf_start, page_pdf_end,
+          text, source_hash
+        ) VALUES
+          ('ISO26262-6-2018:7.4.8', 'ISO26262-6-2018', 'clause', '7.4.8',
+           '7.4.8 Software partitioning', 0, 19, 19,
+           'Partitioning shall provide freedom from interference between software components.',
+           'hash-6-748'),
+          ('ISO26262-6-2018:7.4.9', 'ISO26262-6-2018', 'clause', '7.4.9',
+           '7.4.9 Safety analysis', 1, 19, 19,
+           'Dependent failures analysis shall examine interference between elements.',
+           'hash-6-749'),
+          ('ISO26262-6-2018:annex-d', 'ISO26262-6-2018', 'annex', 'Annex D',
+           'Annex D Freedom from interference between software elements', 2, 60, 60,
+           'Examples of faults relevant to freedom from interference.',
+           'hash-6-d'),
+          ('ISO26262-9-2018:7.4.2', 'ISO26262-9-2018', 'clause', '7.4.2',
+           '7.4.2 Analysis of dependent failures', 0, 12, 12,
+           'Freedom from interference is shown by the analysis of dependent failures.',
+           'hash-9-742');
+        ",
+    )
+}
+
+fn embed_fixture_chunks(connection: &Connection, model_id: &str) {
+    crate::commands::ingest::ensure_embedding_schema(connection)
+        .expect("embedding schema should initialize");
+    let model = resolve_model_config(model_id, None).expect("hash model should resolve");
+    let mut embedder = load_embedding_backend(&model).expect("hash backend should load");
+    connection
+        .execute(
+            "
+            INSERT INTO embedding_models(
+              model_id, backend, model_name, dimensions, normalize, created_at, config_json
+            ) VALUES (?1, ?2, ?3, ?4, 1, '2026-01-01T00:00:00Z', ?5)
+            ",
+            params![
+                model.model_id,
+                model.backend,
+                model.model_name,
+                model.dimensions as i64,
+                serde_json::to_string(&model).expect("model config should serialize"),
+            ],
+        )
+        .expect("model should register");
+
+    let chunks = connection
+        .prepare("SELECT chunk_id, heading || ' ' || text FROM chunks")
+        .and_then(|mut statement| {
+            statement
+                .query_map([], |row| Ok((row.get::<_, String>(0)?, row.get(1)?)))?
+                .collect::<rusqlite::Result<Vec<(String, String)>>>()
+        })
+        .expect("chunks should load");
+    for (chunk_id, text) in chunks {
+        let embedding = embedder
+            .embed(&text)
+            .expect("hash embedding should succeed");
+        connection
+            .execute(
+                "
+                INSERT INTO chunk_embeddings(
+                  chunk_id, model_id, embedding, embedding_dim, text_hash, generated_at
+                ) VALUES (?1, ?2, ?3, ?4, 'hash', '2026-01-01T00:00:00Z')
+                ",
+                params![
+                    chunk_id,
+                    model_id,
+                    encode_embedding_blob(&embedding, EmbeddingEncoding::F32),
+                    embedding.len() as i64,
+                ],
+            )
+            .expect("embedding should insert");
+    }
+}
+
+fn run_query(connection: &Connection, query: &str, extra: &[(&str, &str)]) -> QueryResponse {
+    let mut params = vec![("query".to_string(), query.to_string())];
+    params.extend(
+        extra
+            .iter()
+            .map(|(key, value)| (key.to_string(), value.to_string())),
+    );
+    let args = query_args_from_params(None, &params).expect("query args should parse");
+    execute_query(connection, &args).unwrap_or_else(|error| panic!("'{query}' failed: {error:#}"))
+}
+
+fn chunk_ids(response: &QueryResponse) -> Vec<&str> {
+    response
+        .results
+        .iter()
+        .map(|result| result.chunk_id.as_str())
+        .collect()
+}
+
+fn syntax_error(query: &str) -> String {
+    LexicalQuery::parse(query)
+        .expect_err("query should be rejected")
+        .to_string()
+}
+
+#[test]
+fn lexical_query_compiles_to_fts_expression_and_filters() {
+    let plain = LexicalQuery::parse("software architectural design").expect("plain query");
+    assert_eq!(
+        plain.fts_match.as_deref(),
+        Some(r#""software" AND "architectural" AND "design""#)
+    );
+    assert_eq!(
+        plain.plain_text.as_deref(),
+        Some("software architectural design")
+    );
+    assert!(plain.filters.is_empty());
+
+    let reference = LexicalQuery::parse("8.4.5(a)").expect("reference query");
+    assert_eq!(reference.fts_match.as_deref(), Some(r#""8.4.5(a)""#));
+
+    let scoped = LexicalQuery::parse(
+        r#""freedom from interference" AND part:6 NOT heading:annex ref:7.4.*"#,
+    )
+    .expect("scoped query");
+    assert_eq!(
+        scoped.fts_match.as_deref(),
+        Some(r#""freedom from interference""#)
+    );
+    assert_eq!(scoped.plain_text, None);
+    assert_eq!(
+        scoped.semantic_text().as_deref(),
+        Ok("freedom from interference")
+    );
+    let (sql, values) = scoped.filter_sql(7);
+    assert_eq!(
+        sql,
+        "\n          AND d.part = ?7\n          AND lower(c.ref) LIKE ?8 ESCAPE '\\'\n          AND c.rowid NOT IN (SELECT rowid FROM chunks_fts WHERE chunks_fts MATCH ?9)"
+    );
+    assert_eq!(values.len(), 3);
+
+    let grouped = LexicalQuery::parse(r#"(interfer* OR "dependent failures") safety NOT annex"#)
+        .expect("grouped query");
+    assert_eq!(
+        grouped.fts_match.as_deref(),
+        Some(r#"("interfer"* OR "dependent failures") AND "safety""#)
+    );
+    assert_eq!(
+        grouped.semantic_text().as_deref(),
+        Ok("interfer dependent failures safety")
+    );
+
+    let nested = LexicalQuery::parse(r#"analysis (failures NOT "cascading" OR text:coupling*)"#)
+        .expect("nested query");
+    assert_eq!(
+        nested.fts_match.as_deref(),
+        Some(r#""analysis" AND (("failures" NOT "cascading") OR text : "coupling"*)"#)
+    );
+
+    let filters_only = LexicalQuery::parse("part:9 NOT type:annex").expect("filter query");
+    assert_eq!(filters_only.fts_match, None);
+    assert_eq!(
+        filters_only.filter_sql(7).0,
+        "\n          AND d.part = ?7\n          AND NOT COALESCE(c.type = ?8, 0)"
+    );
+}
+
+#[test]
+fn lexical_query_errors_point_at_the_offending_token() {
+    assert_eq!(
+        syntax_error("safety AND"),
+        "invalid query at column 8: expected a term after 'AND'\n  safety AND\n         ^^^"
+    );
+    assert_eq!(
+        syntax_error("part:six safety"),
+        "invalid query at column 6: 'part:' expects a number, found 'six'\n  part:six safety\n       ^^^"
+    );
+    assert!(syntax_error("(safety OR part:6)").contains(
+        "column 12: 'part:' filters results and can only be combined with AND at the top level"
+    ));
+    assert!(syntax_error("safety)").contains("column 7: unexpected ')'"));
+    assert!(syntax_error("(safety analysis").contains("column 1: unclosed '('"));
+    assert!(syntax_error(r#"safety "freedom from"#).contains("column 8: unterminated phrase"));
+    assert!(syntax_error("OR safety").contains("column 1: expected a term before 'OR'"));
+    assert!(syntax_error("safety OR NOT annex").contains("column 11: NOT inside parentheses"));
+    assert!(syntax_error("heading: safety").contains("expected a value after 'heading:'"));
+    assert!(syntax_error("type:clause*").contains("'type:' does not support '*'"));
+}
+
+#[test]
+fn query_applies_boolean_phrase_and_field_syntax() {
+    let connection = fixture_connection();
+
+    let scoped = run_query(
+        &connection,
+        r#""freedom from interference" AND part:6 NOT heading:annex ref:7.4.*"#,
+        &[],
+    );
+    assert_eq!(chunk_ids(&scoped), ["ISO26262-6-2018:7.4.8"]);
+
+    let either = run_query(&connection, "partitioning OR dependent", &[("part", "6")]);
+    let mut ids = chunk_ids(&either);
+    ids.sort_unstable();
+    assert_eq!(ids, ["ISO26262-6-2018:7.4.8", "ISO26262-6-2018:7.4.9"]);
+
+    let prefix = run_query(&connection, "heading:partition*", &[]);
+    assert_eq!(chunk_ids(&prefix), ["ISO26262-6-2018:7.4.8"]);
+
+    let filtered = run_query(&connection, "part:9 ref:7.4.*", &[]);
+    assert_eq!(chunk_ids(&filtered), ["ISO26262-9-2018:7.4.2"]);
+    assert_eq!(filtered.results[0].match_kind, "filter");
+
+    let excluded = run_query(&connection, "NOT interference part:6", &[]);
+    assert!(excluded.results.is_empty());
+
+    let exact = run_query(&connection, "7.4.2", &[]);
+    assert_eq!(exact.results[0].match_kind, "exact_ref");
+
+    let mut params = vec![
+        ("query".to_string(), "safety AND".to_string()),
+        ("retrieval_mode".to_string(), "lexical".to_string()),
+    ];
+    let args = query_args_from_params(None, &params).expect("query args should parse");
+    let error = execute_query(&connection, &args).expect_err("syntax errors should surface");
+    assert!(error.is::<super::QuerySyntaxError>());
+
+    params[0].1 = "part:6 safety".to_string();
+    params.push(("granularity".to_string(), "node".to_string()));
+    let args = query_args_from_params(None, &params).expect("query args should parse");
+    let error = execute_query(&connection, &args).expect_err("node granularity is plain text");
+    assert!(format!("{error:#}").contains("--granularity chunk"));
+}
+
+#[test]
+fn hybrid_query_applies_field_filters_and_exclusions_to_semantic_candidates() {
+    let connection = fixture_connection();
+    embed_fixture_chunks(&connection, "hash-test");
+    let semantic = [
+        ("retrieval_mode", "hybrid"),
+        ("semantic_model_id", "hash-test"),
+        ("limit", "10"),
+    ];
+
+    let unfiltered = run_query(&connection, "freedom from interference", &semantic);
+    assert!(unfiltered.results.iter().any(|result| result.part == 9));
+
+    let scoped = run_query(
+        &connection,
+        r#""freedom from interference" AND part:6 NOT heading:annex"#,
+        &semantic,
+    );
+    assert!(!scoped.results.is_empty());
+    assert!(scoped.results.iter().all(|result| result.part == 6));
+    assert!(scoped
+        .results
+        .iter()
+        .all(|result| result.chunk_type != "annex"));
+    assert!(chunk_ids(&scoped).contains(&"ISO26262-6-2018:7.4.8"));
+
+    let mut params = vec![("query".to_string(), "part:6 ref:7.4.*".to_string())];
+    params.extend(
+        semantic
+            .iter()
+            .map(|(key, value)| (key.to_string(), value.to_string())),
+    );
+    let args = query_args_from_params(None, &params).expect("query args should parse");
+    let error = execute_query(&connection, &args).expect_err("semantic retrieval needs terms");
+    assert!(error.is::<super::QuerySyntaxError>());
+}
+
+#[test]
+fn modality_filter_keeps_chunks_whose_requirement_atoms_match() {
+    let connection = fixture_connection();
+    connection
+        .execute_batch(
+            "
+            INSERT INTO nodes(
+              node_id, parent_node_id, doc_id, node_type, ref, heading, order_index,
+              page_pdf_start, page_pdf_end, text, modality
+            ) VALUES
+              ('ISO26262-6-2018:clause:7.4.8', NULL, 'ISO26262-6-2018', 'clause', '7.4.8',
+               '7.4.8 Software partitioning', 1, 19, 19, NULL, NULL),
+              ('ISO26262-6-2018:req:7.4.8:1', 'ISO26262-6-2018:clause:7.4.8', 'ISO26262-6-2018',
+               'requirement_atom', '7.4.8', NULL, 2, 19, 19,
+               'Partitioning shall provide freedom from interference', 'shall'),
+              ('ISO26262-6-2018:clause:7.4.9', NULL, 'ISO26262-6-2018', 'clause', '7.4.9',
+               '7.4.9 Safety analysis', 3, 19, 19, NULL, NULL),
+              ('ISO26262-6-2018:req:7.4.9:1', 'ISO26262-6-2018:clause:7.4.9', 'ISO26262-6-2018',
+               'requirement_atom', '7.4.9', NULL, 4, 19, 19,
+               'Dependent failures analysis should examine interference', 'should');
+            UPDATE chunks SET origin_node_id = 'ISO26262-6-2018:clause:' || ref
+            WHERE chunk_id IN ('ISO26262-6-2018:7.4.8', 'ISO26262-6-2018:7.4.9');
+            ",
+        )
+        .expect("requirement nodes should insert");
+    embed_fixture_chunks(&connection, "hash-test");
+
+    for mode in ["lexical", "hybrid"] {
+        let response = run_query(
+            &connection,
+            "interference",
+            &[
+                ("modality", "shall"),
+                ("retrieval_mode", mode),
+                ("semantic_model_id", "hash-test"),
+            ],
+        );
+        let ids = chunk_ids(&response);
+        assert!(ids.contains(&"ISO26262-6-2018:7.4.8"), "{mode}: {ids:?}");
+        assert!(!ids.contains(&"ISO26262-6-2018:7.4.9"), "{mode}: {ids:?}");
+        assert!(!ids.contains(&"ISO26262-9-2018:7.4.2"), "{mode}: {ids:?}");
+    }
+}
+
+#[test]
+fn asil_profile_annotates_ratings_demotes_and_excludes_not_recommended_rows() {
+    let connection = fixture_connection();
+    connection
+        .execute_batch(
+            "
+            INSERT INTO nodes(node_id, parent_node_id, doc_id, node_type, ref, order_index)
+            VALUES
+              ('table-3', NULL, 'ISO26262-6-2018', 'table', 'Table 3', 1),
+              ('table-3:row:1a', 'table-3', 'ISO26262-6-2018', 'table_row', 'Table 3', 2),
+              ('table-3:row:1b', 'table-3', 'ISO26262-6-2018', 'table_row', 'Table 3', 3),
+              ('table-3:row:1c', 'table-3', 'ISO26262-6-2018', 'table_row', 'Table 3', 4);
+            INSERT INTO chunks(
+              chunk_id, doc_id, type, ref, heading, chunk_seq, page_pdf_start, page_pdf_end,
+              text, source_hash, origin_node_id
+            ) VALUES
+              ('ISO26262-6-2018:table-3:1a', 'ISO26262-6-2018', 'table', 'Table 3',
+               'Table 3 Notations for software design', 3, 20, 20,
+               'Natural language notation notation notation', 'hash-1a', 'table-3:row:1a'),
+              ('ISO26262-6-2018:table-3:1b', 'ISO26262-6-2018', 'table', 'Table 3',
+               'Table 3 Notations for software design', 4, 21, 21,
+               'Informal notation', 'hash-1b', 'table-3:row:1b'),
+              ('ISO26262-6-2018:table-3:1c', 'ISO26262-6-2018', 'table', 'Table 3',
+               'Table 3 Notations for software design', 5, 22, 22,
+               'Semi-formal notation', 'hash-1c', 'table-3:row:1c');
+            INSERT INTO asil_recommendations(
+              doc_id, table_node_id, table_ref, row_node_id, marker, method_text, asil, rating,
+              recommendation_level
+            ) VALUES
+              ('ISO26262-6-2018', 'table-3', 'Table 3', 'table-3:row:1a', '1a',
+               'Natural language', 'C', 'o', 'no_recommendation'),
+              ('ISO26262-6-2018', 'table-3', 'Table 3', 'table-3:row:1b', '1b',
+               'Informal notation', 'C', '+', 'recommended'),
+              ('ISO26262-6-2018', 'table-3', 'Table 3', 'table-3:row:1c', '1c',
+               'Semi-formal notation', 'C', '++', 'highly_recommended');
+            INSERT INTO chunks_fts(chunks_fts) VALUES('rebuild');
+            ",
+        )
+        .expect("table rows should insert");
+
+    let unprofiled = run_query(&connection, "notation", &[("type", "table")]);
+    assert_eq!(chunk_ids(&unprofiled)[0], "ISO26262-6-2018:table-3:1a");
+
+    let profiled = run_query(&connection, "notation", &[("type", "table"), ("asil", "c")]);
+    let ratings = profiled
+        .results
+        .iter()
+        .map(|result| {
+            let hits = result
+                .asil_recommendations
+                .as_ref()
+                .expect("profiled results carry recommendations");
+            (
+                result.chunk_id.as_str(),
+                hits[0].rating.as_str(),
+                result.asil_demoted,
+            )
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(ratings.len(), 3);
+    assert_eq!(ratings[2], ("ISO26262-6-2018:table-3:1a", "o", Some(true)));
+    assert!(ratings[..2].contains(&("ISO26262-6-2018:table-3:1b", "+", Some(false))));
+    assert!(ratings[..2].contains(&("ISO26262-6-2018:table-3:1c", "++", Some(false))));
+
+    let excluded = run_query(
+        &connection,
+        "notation",
+        &[
+            ("type", "table"),
+            ("asil", "c"),
+            ("exclude_not_recommended", "true"),
+        ],
+    );
+    let mut ids = chunk_ids(&excluded);
+    ids.sort_unstable();
+    assert_eq!(
+        ids,
+        ["ISO26262-6-2018:table-3:1b", "ISO26262-6-2018:table-3:1c"]
+    );
+    let limited = run_query(
+        &connection,
+        "notation",
+        &[
+            ("type", "table"),
+            ("asil", "c"),
+            ("exclude_not_recommended", "true"),
+            ("limit", "2"),
+            ("lexical_k", "2"),
+        ],
+    );
+    assert_eq!(limited.returned, 2);
+
+    assert!(query_args_from_params(
+        None,
+        &[
+            ("query".to_string(), "notation".to_string()),
+            ("exclude_not_recommended".to_string(), "true".to_string()),
+        ],
+    )
+    .is_err());
+
+    let hit = |level: &str| AsilRecommendationHit {
+        marker: "1a".to_string(),
+        method_text: "Natural language".to_string(),
+        rating: String::new(),
+        recommendation_level: level.to_string(),
+    };
+    assert!(!is_not_recommended_at_asil(&[]));
+    assert!(is_not_recommended_at_asil(&[
+        hit("no_recommendation"),
+        hit("not_recommended")
+    ]));
+    assert!(!is_not_recommended_at_asil(&[
+        hit("not_recommended"),
+        hit("recommended")
+    ]));
+}
+
+#[test]
+fn highlights_distinguish_documents_without_layout_from_unmatched_nodes() {
+    let connection = fixture_connection();
+    connection
+        .execute_batch(
+            "
+            INSERT INTO nodes(node_id, parent_node_id, doc_id, node_type, ref, order_index)
+            VALUES
+              ('clause:7.4.8', NULL, 'ISO26262-6-2018', 'clause', '7.4.8', 1),
+              ('clause:7.4.9', NULL, 'ISO26262-6-2018', 'clause', '7.4.9', 2);
+            UPDATE chunks SET origin_node_id = 'clause:' || ref
+            WHERE chunk_id IN ('ISO26262-6-2018:7.4.8', 'ISO26262-6-2018:7.4.9');
+            ",
+        )
+        .expect("clause nodes should insert");
+    let highlights = |connection: &Connection| {
+        run_query(connection, "7.4.8", &[("limit", "1")]).results[0]
+            .highlights
+            .as_ref()
+            .map(Vec::len)
+    };
+
+    assert_eq!(highlights(&connection), None);
+
+    let insert_region = |node_id: &str| {
+        connection
+            .execute(
+                "
+                INSERT INTO node_regions(
+                  node_id, region_seq, doc_id, page_pdf, x_min, y_min, x_max, y_max,
+                  page_width, page_height
+                ) VALUES (?1, 1, 'ISO26262-6-2018', 19, 72.0, 100.0, 300.0, 120.0, 595.0, 842.0)
+                ",
+                [node_id],
+            )
+            .expect("region should insert");
+    };
+    insert_region("clause:7.4.9");
+    assert_eq!(highlights(&connection), Some(0));
+
+    insert_region("clause:7.4.8");
+    assert_eq!(highlights(&connection), Some(1));
+}
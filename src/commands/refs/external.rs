@@ -0,0 +1,294 @@
+use std::io::{self, Write};
+
+use anyhow::{Context, Result, bail};
+use rusqlite::{Connection, OpenFlags, OptionalExtension, params};
+use serde::Serialize;
+use tracing::info;
+
+use crate::cli::RefsExternalArgs;
+use crate::commands::ingest::standard_family_root;
+use crate::commands::query::{CitationSource, render_anchor_citation};
+
+#[derive(Debug, Clone, Serialize)]
+pub(super) struct ExternalReferenceReport {
+    pub(super) standard: String,
+    pub(super) part_filter: Vec<u32>,
+    pub(super) reference_count: usize,
+    pub(super) citation_count: usize,
+    pub(super) references: Vec<ExternalReferenceEntry>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub(super) struct ExternalReferenceEntry {
+    pub(super) external_reference_id: String,
+    pub(super) doc_id: String,
+    pub(super) part: u32,
+    pub(super) year: u32,
+    pub(super) source_kind: String,
+    pub(super) list_label: Option<String>,
+    pub(super) standard_id: String,
+    pub(super) standard_year: Option<i64>,
+    pub(super) title: Option<String>,
+    pub(super) raw_text: String,
+    pub(super) citation: String,
+    pub(super) cited_by: Vec<ExternalReferenceCitation>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub(super) struct ExternalReferenceCitation {
+    pub(super) node_id: String,
+    pub(super) reference: String,
+    pub(super) heading: Option<String>,
+    pub(super) citation: String,
+}
+
+pub(super) fn run_external(args: RefsExternalArgs) -> Result<()> {
+    let standard = normalize_standard_query(&args.standard);
+    if standard.is_empty() {
+        bail!("standard must not be empty");
+    }
+
+    let db_path = args
+        .db_path
+        .clone()
+        .unwrap_or_else(|| args.cache_root.join("iso26262_index.sqlite"));
+    let connection = Connection::open_with_flags(
+        &db_path,
+        OpenFlags::SQLITE_OPEN_READ_ONLY | OpenFlags::SQLITE_OPEN_NO_MUTEX,
+    )
+    .with_context(|| format!("failed to open database read-only: {}", db_path.display()))?;
+
+    ensure_external_references_available(&connection)?;
+    let report = build_external_reference_report(&connection, &standard, &args.parts)?;
+    info!(
+        standard = %report.standard,
+        reference_count = report.reference_count,
+        citation_count = report.citation_count,
+        "external reference lookup completed"
+    );
+
+    let mut output = io::BufWriter::new(io::stdout().lock());
+    if args.json {
+        serde_json::to_writer_pretty(&mut output, &report)
+            .context("failed to serialize refs external json output")?;
+        writeln!(output)?;
+    } else if report.references.is_empty() {
+        writeln!(
+            output,
+            "No external reference found for: {}",
+            report.standard
+        )?;
+    } else {
+        for entry in &report.references {
+            let edition = entry
+                .standard_year
+                .map(|year| format!(":{year}"))
+                .unwrap_or_default();
+            match &entry.title {
+                Some(title) => writeln!(
+                    output,
+                    "{}{edition} ({}) {title}",
+                    entry.standard_id, entry.source_kind
+                )?,
+                None => writeln!(
+                    output,
+                    "{}{edition} ({})",
+                    entry.standard_id, entry.source_kind
+                )?,
+            }
+            writeln!(output, "\tlisted in: {}", entry.citation)?;
+            if entry.cited_by.is_empty() {
+                writeln!(output, "\tcited by: none")?;
+            }
+            for cited in &entry.cited_by {
+                match &cited.heading {
+                    Some(heading) => writeln!(
+                        output,
+                        "\tcited by: {} {heading} ({})",
+                        cited.reference, cited.citation
+                    )?,
+                    None => writeln!(
+                        output,
+                        "\tcited by: {} ({})",
+                        cited.reference, cited.citation
+                    )?,
+                }
+            }
+        }
+    }
+    output.flush()?;
+
+    Ok(())
+}
+
+pub(super) fn normalize_standard_query(value: &str) -> String {
+    let condensed = value.split_whitespace().collect::<Vec<&str>>().join(" ");
+    let without_edition = match condensed.rsplit_once(':') {
+        Some((base, edition))
+            if edition.trim().len() == 4
+                && edition.trim().chars().all(|ch| ch.is_ascii_digit()) =>
+        {
+            base
+        }
+        _ => condensed.as_str(),
+    };
+    without_edition.trim().to_ascii_uppercase()
+}
+
+fn ensure_external_references_available(connection: &Connection) -> Result<()> {
+    let table_exists = connection
+        .query_row(
+            "
+            SELECT name
+            FROM sqlite_master
+            WHERE type = 'table' AND name = 'external_references'
+            LIMIT 1
+            ",
+            [],
+            |row| row.get::<_, String>(0),
+        )
+        .optional()?
+        .is_some();
+    if !table_exists {
+        bail!("external_references table is missing; re-run ingest to extract external references");
+    }
+    Ok(())
+}
+
+pub(super) fn build_external_reference_report(
+    connection: &Connection,
+    standard: &str,
+    parts: &[u32],
+) -> Result<ExternalReferenceReport> {
+    let mut part_filter = parts.to_vec();
+    part_filter.sort_unstable();
+    part_filter.dedup();
+
+    let mut statement = connection.prepare(
+        "
+        SELECT
+          e.external_reference_id,
+          e.doc_id,
+          d.part,
+          d.year,
+          e.source_kind,
+          e.list_label,
+          e.standard_id,
+          e.year,
+          e.title,
+          e.raw_text,
+          e.page_pdf_start,
+          e.page_pdf_end
+        FROM external_references e
+        JOIN docs d ON d.doc_id = e.doc_id
+        WHERE e.standard_id = ?1
+           OR e.standard_id LIKE ?2 ESCAPE '\\'
+           OR e.standard_id = ?3
+        ORDER BY d.part ASC, d.year DESC, e.rowid ASC
+        ",
+    )?;
+    let part_pattern = format!(
+        "{}-%",
+        standard
+            .replace('\\', "\\\\")
+            .replace('%', "\\%")
+            .replace('_', "\\_")
+    );
+    let family_root = standard_family_root(standard).unwrap_or(standard);
+    let mut rows = statement.query(params![standard, part_pattern, family_root])?;
+    let mut references = Vec::<ExternalReferenceEntry>::new();
+
+    while let Some(row) = rows.next()? {
+        let part: u32 = row.get(2)?;
+        if !part_filter.is_empty() && !part_filter.contains(&part) {
+            continue;
+        }
+
+        let external_reference_id: String = row.get(0)?;
+        let year: u32 = row.get(3)?;
+        let source_kind: String = row.get(4)?;
+        let section = if source_kind == "normative" {
+            "Clause 2"
+        } else {
+            "Bibliography"
+        };
+        let citation = render_anchor_citation(&CitationSource {
+            part,
+            year,
+            reference: section,
+            anchor_type: None,
+            anchor_label_norm: None,
+            page_pdf_start: row.get(10)?,
+            page_pdf_end: row.get(11)?,
+        });
+        let cited_by =
+            load_external_reference_citations(connection, &external_reference_id, part, year)?;
+
+        references.push(ExternalReferenceEntry {
+            external_reference_id,
+            doc_id: row.get(1)?,
+            part,
+            year,
+            source_kind,
+            list_label: row.get(5)?,
+            standard_id: row.get(6)?,
+            standard_year: row.get(7)?,
+            title: row.get(8)?,
+            raw_text: row.get(9)?,
+            citation,
+            cited_by,
+        });
+    }
+
+    let citation_count = references.iter().map(|entry| entry.cited_by.len()).sum();
+
+    Ok(ExternalReferenceReport {
+        standard: standard.to_string(),
+        part_filter,
+        reference_count: references.len(),
+        citation_count,
+        references,
+    })
+}
+
+fn load_external_reference_citations(
+    connection: &Connection,
+    external_reference_id: &str,
+    part: u32,
+    year: u32,
+) -> Result<Vec<ExternalReferenceCitation>> {
+    let mut statement = connection.prepare_cached(
+        "
+        SELECT c.node_id, c.ref, n.heading, n.page_pdf_start, n.page_pdf_end
+        FROM external_reference_citations c
+        JOIN nodes n ON n.node_id = c.node_id
+        WHERE c.external_reference_id = ?1
+        ORDER BY n.order_index ASC
+        ",
+    )?;
+    let rows = statement.query_map(params![external_reference_id], |row| {
+        let reference: String = row.get(1)?;
+        let citation = render_anchor_citation(&CitationSource {
+            part,
+            year,
+            reference: &reference,
+            anchor_type: None,
+            anchor_label_norm: None,
+            page_pdf_start: row.get(3)?,
+            page_pdf_end: row.get(4)?,
+        });
+
+        Ok(ExternalReferenceCitation {
+            node_id: row.get(0)?,
+            reference,
+            heading: row.get(2)?,
+            citation,
+        })
+    })?;
+
+    let mut citations = Vec::new();
+    for row in rows {
+        citations.push(row?);
+    }
+    Ok(citations)
+}
@@ -0,0 +1,11 @@
+use anyhow::Result;
+
+use crate::cli::{RefsArgs, RefsCommands};
+
+use super::external::run_external;
+
+pub fn run(args: RefsArgs) -> Result<()> {
+    match args.command {
+        RefsCommands::External(args) => run_external(args),
+    }
+}
@@ -0,0 +1,84 @@
+use rusqlite::Connection;
+
+use crate::commands::ingest::ensure_schema;
+
+use super::external::{build_external_reference_report, normalize_standard_query};
+
+#[test]
+fn build_external_reference_report_lists_clauses_citing_a_standard() {
+    let connection = Connection::open_in_memory().expect("in-memory DB should open");
+    ensure_schema(&connection).expect("schema should be created");
+    connection
+        .execute_batch(
+            "
+            INSERT INTO docs(doc_id, filename, sha256, part, year)
+            VALUES ('ISO26262-6-2018', 'ISO26262-6-2018.pdf', 'hash-6', 6, 2018);
+            INSERT INTO nodes(
+              node_id, doc_id, node_type, ref, heading, order_index, page_pdf_start, page_pdf_end
+            ) VALUES
+              ('n-811', 'ISO26262-6-2018', 'clause', '8.1', '8.1 Objectives', 2, 24, 24),
+              (
+                'n-742', 'ISO26262-6-2018', 'clause', '7.4.2',
+                '7.4.2 Software architectural design', 1, 20, 21
+              );
+            INSERT INTO external_references(
+              external_reference_id, doc_id, source_kind, list_label, standard_id, year, title,
+              raw_text, page_pdf_start, page_pdf_end
+            ) VALUES
+              ('ISO26262-6-2018:extref:bibliography:001', 'ISO26262-6-2018', 'bibliography', '[3]',
+               'IEC 61508-3', 2010, 'Functional safety — Part 3: Software requirements',
+               'IEC 61508-3:2010, Functional safety — Part 3: Software requirements', 60, 60),
+              ('ISO26262-6-2018:extref:bibliography:002', 'ISO26262-6-2018', 'bibliography', '[4]',
+               'IEC 615080', NULL, NULL, 'IEC 615080', 60, 60);
+            INSERT INTO external_reference_citations VALUES
+              ('ISO26262-6-2018:extref:bibliography:001', 'n-811', 'ISO26262-6-2018', '8.1'),
+              ('ISO26262-6-2018:extref:bibliography:001', 'n-742', 'ISO26262-6-2018', '7.4.2');
+            ",
+        )
+        .expect("seed rows should insert");
+
+    let report = build_external_reference_report(&connection, "IEC 61508", &[])
+        .expect("report should build");
+
+    assert_eq!(report.reference_count, 1);
+    assert_eq!(report.citation_count, 2);
+    let entry = &report.references[0];
+    assert_eq!(entry.standard_id, "IEC 61508-3");
+    assert_eq!(entry.standard_year, Some(2010));
+    assert_eq!(
+        entry.citation,
+        "ISO 26262-6:2018, Bibliography, PDF pages 60"
+    );
+    let cited = entry
+        .cited_by
+        .iter()
+        .map(|citation| (citation.reference.as_str(), citation.citation.as_str()))
+        .collect::<Vec<(&str, &str)>>();
+    assert_eq!(
+        cited,
+        vec![
+            ("7.4.2", "ISO 26262-6:2018, 7.4.2, PDF pages 20-21"),
+            ("8.1", "ISO 26262-6:2018, 8.1, PDF pages 24"),
+        ]
+    );
+
+    let filtered = build_external_reference_report(&connection, "IEC 61508", &[8])
+        .expect("filtered report should build");
+    assert_eq!(filtered.reference_count, 0);
+
+    let wildcard = build_external_reference_report(&connection, "IEC 6150_", &[])
+        .expect("wildcard report should build");
+    assert_eq!(wildcard.reference_count, 0);
+
+    let family = build_external_reference_report(&connection, "IEC 615080-1", &[])
+        .expect("family report should build");
+    assert_eq!(family.reference_count, 1);
+    assert_eq!(family.references[0].standard_id, "IEC 615080");
+}
+
+#[test]
+fn normalize_standard_query_drops_edition_and_condenses_whitespace() {
+    assert_eq!(normalize_standard_query("  iec   61508:2010 "), "IEC 61508");
+    assert_eq!(normalize_standard_query("ISO/SAE 21434"), "ISO/SAE 21434");
+    assert_eq!(normalize_standard_query("ISO 26262-1"), "ISO 26262-1");
+}
@@ -0,0 +1,6 @@
+mod http;
+mod run;
+#[cfg(test)]
+mod tests;
+
+pub(crate) use run::run;
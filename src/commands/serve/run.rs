@@ -0,0 +1,276 @@
+use std::io::{BufReader, ErrorKind};
+use std::net::{TcpListener, TcpStream};
+use std::path::Path;
+use std::sync::{Condvar, Mutex, PoisonError};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use anyhow::{Context, Result, bail};
+use rusqlite::Connection;
+use serde_json::{Value, json};
+use tracing::{info, warn};
+
+use crate::cli::ServeArgs;
+use crate::commands::query::{
+    LookupKind, QueryRequestError, QueryResponse, QuerySyntaxError, execute_lookup, execute_query,
+    open_query_connection, query_args_from_params, query_params_from_json,
+};
+
+use super::http::{HttpRequest, read_request, write_response};
+
+const STATEMENT_CACHE_CAPACITY: usize = 128;
+const IDLE_CONNECTION_TIMEOUT: Duration = Duration::from_secs(5);
+
+enum RouteError {
+    BadRequest(anyhow::Error),
+    NotFound(String),
+    MethodNotAllowed(String),
+    Failed(anyhow::Error),
+}
+
+struct ConnectionPool {
+    idle: Mutex<Vec<Connection>>,
+    released: Condvar,
+}
+
+struct PooledConnection<'pool> {
+    pool: &'pool ConnectionPool,
+    connection: Option<Connection>,
+}
+
+pub fn run(args: ServeArgs) -> Result<()> {
+    let db_path = args
+        .db_path
+        .clone()
+        .unwrap_or_else(|| args.cache_root.join("iso26262_index.sqlite"));
+    let workers = args
+        .workers
+        .filter(|workers| *workers > 0)
+        .or_else(|| {
+            thread::available_parallelism()
+                .ok()
+                .map(|value| value.get())
+        })
+        .unwrap_or(1);
+
+    let listener = TcpListener::bind(&args.bind)
+        .with_context(|| format!("failed to bind query server to {}", args.bind))?;
+    info!(
+        address = %listener.local_addr()?,
+        db_path = %db_path.display(),
+        workers,
+        "query server listening"
+    );
+    serve(&listener, &db_path, workers)
+}
+
+pub(super) fn serve(listener: &TcpListener, db_path: &Path, workers: usize) -> Result<()> {
+    let pool = ConnectionPool::open(db_path, workers.max(1))?;
+
+    thread::scope(|scope| {
+        for stream in listener.incoming() {
+            match stream {
+                Ok(stream) => {
+                    let pool = &pool;
+                    scope.spawn(move || {
+                        if let Err(error) = serve_connection(pool, stream) {
+                            warn!(error = %error, "query server connection failed");
+                        }
+                    });
+                }
+                Err(error) => warn!(error = %error, "query server accept failed"),
+            }
+        }
+    });
+
+    Ok(())
+}
+
+impl ConnectionPool {
+    fn open(db_path: &Path, size: usize) -> Result<Self> {
+        let mut idle = Vec::<Connection>::with_capacity(size);
+        for _ in 0..size {
+            let connection = open_query_connection(db_path)?;
+            connection.set_prepared_statement_cache_capacity(STATEMENT_CACHE_CAPACITY);
+            idle.push(connection);
+        }
+        Ok(Self {
+            idle: Mutex::new(idle),
+            released: Condvar::new(),
+        })
+    }
+
+    fn checkout(&self) -> PooledConnection<'_> {
+        let mut idle = self.idle.lock().unwrap_or_else(PoisonError::into_inner);
+        loop {
+            if let Some(connection) = idle.pop() {
+                return PooledConnection {
+                    pool: self,
+                    connection: Some(connection),
+                };
+            }
+            idle = self
+                .released
+                .wait(idle)
+                .unwrap_or_else(PoisonError::into_inner);
+        }
+    }
+}
+
+impl std::ops::Deref for PooledConnection<'_> {
+    type Target = Connection;
+
+    fn deref(&self) -> &Connection {
+        self.connection
+            .as_ref()
+            .expect("pooled connection is present until drop")
+    }
+}
+
+impl Drop for PooledConnection<'_> {
+    fn drop(&mut self) {
+        if let Some(connection) = self.connection.take() {
+            self.pool
+                .idle
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .push(connection);
+            self.pool.released.notify_one();
+        }
+    }
+}
+
+fn serve_connection(pool: &ConnectionPool, stream: TcpStream) -> Result<()> {
+    stream.set_read_timeout(Some(IDLE_CONNECTION_TIMEOUT))?;
+    stream.set_nodelay(true)?;
+    let mut writer = stream.try_clone()?;
+    let mut reader = BufReader::new(stream);
+
+    loop {
+        let request = match read_request(&mut reader) {
+            Ok(Some(request)) => request,
+            Ok(None) => return Ok(()),
+            Err(error) if is_idle_timeout(&error) => return Ok(()),
+            Err(error) => {
+                let body = error_body(&error.to_string());
+                return write_response(&mut writer, 400, &body, false);
+            }
+        };
+
+        let started = Instant::now();
+        let (status, body) = handle_request(&pool.checkout(), &request);
+        write_response(&mut writer, status, &body, request.keep_alive)?;
+        info!(
+            method = %request.method,
+            path = %request.path,
+            status,
+            duration_ms = started.elapsed().as_secs_f64() * 1000.0,
+            "request served"
+        );
+        if !request.keep_alive {
+            return Ok(());
+        }
+    }
+}
+
+pub(super) fn handle_request(connection: &Connection, request: &HttpRequest) -> (u16, Vec<u8>) {
+    let outcome = route(connection, request).and_then(|(status, response)| {
+        serde_json::to_vec(&response)
+            .map(|body| (status, body))
+            .map_err(|error| RouteError::Failed(error.into()))
+    });
+
+    match outcome {
+        Ok(response) => response,
+        Err(RouteError::BadRequest(error)) => (400, error_body(&format!("{error:#}"))),
+        Err(RouteError::NotFound(message)) => (404, error_body(&message)),
+        Err(RouteError::MethodNotAllowed(message)) => (405, error_body(&message)),
+        Err(RouteError::Failed(error)) => (500, error_body(&format!("{error:#}"))),
+    }
+}
+
+fn route(
+    connection: &Connection,
+    request: &HttpRequest,
+) -> std::result::Result<(u16, QueryResponse), RouteError> {
+    let segments = request
+        .path
+        .trim_matches('/')
+        .splitn(2, '/')
+        .collect::<Vec<&str>>();
+    let lookup_kind = match segments.as_slice() {
+        ["query"] => None,
+        ["chunk", _] => Some(LookupKind::Chunk),
+        ["node", _] => Some(LookupKind::Node),
+        ["citation", _] => Some(LookupKind::Citation),
+        _ => {
+            return Err(RouteError::NotFound(format!(
+                "no route for {}",
+                request.path
+            )));
+        }
+    };
+
+    let allowed = match lookup_kind {
+        None => matches!(request.method.as_str(), "GET" | "POST"),
+        Some(_) => request.method == "GET",
+    };
+    if !allowed {
+        return Err(RouteError::MethodNotAllowed(format!(
+            "{} is not supported for {}",
+            request.method, request.path
+        )));
+    }
+
+    let params = request_params(request).map_err(RouteError::BadRequest)?;
+    let Some(kind) = lookup_kind else {
+        let args = query_args_from_params(None, &params).map_err(RouteError::BadRequest)?;
+        if args.query.trim().is_empty() {
+            return Err(RouteError::BadRequest(anyhow::anyhow!(
+                "query parameter 'query' must not be empty"
+            )));
+        }
+        let response = execute_query(connection, &args).map_err(classify_query_error)?;
+        return Ok((200, response));
+    };
+
+    let args =
+        query_args_from_params(Some(segments[1]), &params).map_err(RouteError::BadRequest)?;
+    let response = execute_lookup(connection, kind, &args).map_err(classify_query_error)?;
+    let status = if response.is_empty() { 404 } else { 200 };
+    Ok((status, response))
+}
+
+fn classify_query_error(error: anyhow::Error) -> RouteError {
+    if error.is::<QuerySyntaxError>() || error.is::<QueryRequestError>() {
+        RouteError::BadRequest(error)
+    } else {
+        RouteError::Failed(error)
+    }
+}
+
+fn request_params(request: &HttpRequest) -> Result<Vec<(String, String)>> {
+    let mut params = request.params.clone();
+    if request.body.iter().all(u8::is_ascii_whitespace) {
+        return Ok(params);
+    }
+
+    let Value::Object(object) =
+        serde_json::from_slice::<Value>(&request.body).context("request body is not valid JSON")?
+    else {
+        bail!("request body must be a JSON object");
+    };
+    params.extend(query_params_from_json(object)?);
+    Ok(params)
+}
+
+fn is_idle_timeout(error: &anyhow::Error) -> bool {
+    error
+        .downcast_ref::<std::io::Error>()
+        .map(|error| matches!(error.kind(), ErrorKind::WouldBlock | ErrorKind::TimedOut))
+        .unwrap_or(false)
+}
+
+fn error_body(message: &str) -> Vec<u8> {
+    serde_json::to_vec(&json!({ "error": message })).unwrap_or_default()
+}
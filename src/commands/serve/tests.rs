@@ -0,0 +1,268 @@
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::path::PathBuf;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use serde_json::Value;
+
+use crate::commands::query::query_args_from_params;
+
+use super::http::{parse_query_string, percent_decode};
+use super::run::serve;
+
+fn fixture_db_path(name: &str) -> PathBuf {
+    let db_path = std::env::temp_dir().join(format!(
+        "iso26262_serve_{name}_{}.sqlite",
+        std::process::id()
+    ));
+    crate::commands::ingest::open_fixture_database(
+        Some(&db_path),
+        "
+        INSERT INTO docs(doc_id, filename, sha256, part, year, title)
+        VALUES ('ISO26262-6-2018', 'part6.pdf', 'sha', 6, 2018, 'Part 6');
+        INSERT INTO nodes(
+          node_id, parent_node_id, doc_id, node_type, ref, heading, order_index,
+          page_pdf_start, page_pdf_end, text, citation_anchor_id
+        ) VALUES
+          ('ISO26262-6-2018:clause:8.4.1', NULL, 'ISO26262-6-2018', 'clause', '8.4.1',
+           '8.4.1 Software unit verification', 1, 24, 24, NULL, 'anchor-8.4.1'),
+          ('ISO26262-6-2018:para:8.4.1:1', 'ISO26262-6-2018:clause:8.4.1', 'ISO26262-6-2018',
+           'paragraph', '8.4.1', NULL, 2, 24, 24,
+           'The software unit design shall be verified.', NULL);
+        INSERT INTO chunks(
+          chunk_id, doc_id, type, ref, heading, chunk_seq, page_pdf_start, page_pdf_end,
+          text, source_hash, origin_node_id, leaf_node_type, citation_anchor_id
+        ) VALUES (
+          'ISO26262-6-2018:8.4.1', 'ISO26262-6-2018', 'clause', '8.4.1',
+          '8.4.1 Software unit verification', 0, 24, 24,
+          'The software unit design shall be verified.', 'hash',
+          'ISO26262-6-2018:clause:8.4.1', 'clause', 'anchor-8.4.1'
+        );
+        ",
+    );
+    db_path
+}
+
+fn send(address: SocketAddr, request: &str) -> (u16, Value) {
+    let mut stream = TcpStream::connect(address).expect("server should accept connections");
+    stream
+        .write_all(request.as_bytes())
+        .expect("request should be written");
+    let mut raw = String::new();
+    stream
+        .read_to_string(&mut raw)
+        .expect("response should be readable");
+
+    let (head, body) = raw
+        .split_once("\r\n\r\n")
+        .expect("response should have a header block");
+    let status = head
+        .split_whitespace()
+        .nth(1)
+        .and_then(|value| value.parse::<u16>().ok())
+        .expect("status line should carry a code");
+    (
+        status,
+        serde_json::from_str(body).expect("body should be JSON"),
+    )
+}
+
+fn get(address: SocketAddr, target: &str) -> (u16, Value) {
+    send(
+        address,
+        &format!("GET {target} HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\r\n"),
+    )
+}
+
+#[test]
+fn query_server_answers_query_and_lookup_endpoints_on_ephemeral_port() {
+    let db_path = fixture_db_path("endpoints");
+    let listener = TcpListener::bind("127.0.0.1:0").expect("ephemeral port should bind");
+    let address = listener.local_addr().expect("local address should resolve");
+    let server_db_path = db_path.clone();
+    thread::spawn(move || serve(&listener, &server_db_path, 2));
+
+    let (status, response) = get(
+        address,
+        "/query?query=software+unit&limit=2&with_ancestors=true",
+    );
+    assert_eq!(status, 200);
+    assert_eq!(response["query"], "software unit");
+    assert_eq!(response["retrieval"]["effective_mode"], "lexical");
+    assert_eq!(response["results"][0]["chunk_id"], "ISO26262-6-2018:8.4.1");
+
+    let body = r#"{"query": "8.4.1", "limit": 1, "with_descendants": true}"#;
+    let (status, response) = send(
+        address,
+        &format!(
+            "POST /query HTTP/1.1\r\nHost: localhost\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+            body.len()
+        ),
+    );
+    assert_eq!(status, 200);
+    assert_eq!(response["returned"], 1);
+    assert_eq!(response["results"][0]["match_kind"], "exact_ref");
+    assert_eq!(
+        response["results"][0]["descendants"][0]["node_id"],
+        "ISO26262-6-2018:para:8.4.1:1"
+    );
+
+    let (status, response) = get(address, "/chunk/ISO26262-6-2018%3A8.4.1");
+    assert_eq!(status, 200);
+    assert_eq!(response["retrieval"]["requested_mode"], "lookup");
+    assert_eq!(
+        response["results"][0]["citation"],
+        "ISO 26262-6:2018, 8.4.1, PDF pages 24"
+    );
+
+    let (status, response) = get(address, "/node/ISO26262-6-2018:para:8.4.1:1");
+    assert_eq!(status, 200);
+    assert_eq!(
+        response["results"][0]["chunk_id"],
+        "node::ISO26262-6-2018:para:8.4.1:1"
+    );
+    assert_eq!(
+        response["results"][0]["parent_chunk"]["chunk_id"],
+        "ISO26262-6-2018:8.4.1"
+    );
+
+    let (status, response) = get(address, "/citation/anchor-8.4.1");
+    assert_eq!(status, 200);
+    assert_eq!(response["results"][0]["chunk_id"], "ISO26262-6-2018:8.4.1");
+
+    let (status, response) = get(address, "/node/missing");
+    assert_eq!(status, 404);
+    assert_eq!(response["returned"], 0);
+
+    let (status, response) = get(address, "/query?query=x&bogus=1");
+    assert_eq!(status, 400);
+    assert!(
+        response["error"]
+            .as_str()
+            .expect("error should be a string")
+            .contains("bogus")
+    );
+    let (status, response) = get(address, "/query?query=software+AND");
+    assert_eq!(status, 400);
+    assert!(
+        response["error"]
+            .as_str()
+            .expect("error should be a string")
+            .contains("expected a term after 'AND'")
+    );
+    let (status, response) = get(address, "/query?query=unit&retrieval_mode=semantic");
+    assert_eq!(status, 400);
+    assert!(
+        response["error"]
+            .as_str()
+            .expect("error should be a string")
+            .contains("--semantic-model-id")
+    );
+    let (status, response) = get(
+        address,
+        "/query?query=unit&retrieval_mode=semantic&semantic_model_id=missing-model",
+    );
+    assert_eq!(status, 400);
+    assert!(
+        response["error"]
+            .as_str()
+            .expect("error should be a string")
+            .contains("--allow-lexical-fallback")
+    );
+    assert_eq!(get(address, "/chunk/%20").0, 400);
+    assert_eq!(get(address, "/unknown").0, 404);
+
+    let _ = std::fs::remove_file(&db_path);
+}
+
+fn keep_alive_get(reader: &mut BufReader<TcpStream>, target: &str) -> u16 {
+    reader
+        .get_mut()
+        .write_all(format!("GET {target} HTTP/1.1\r\nHost: localhost\r\n\r\n").as_bytes())
+        .expect("request should be written");
+
+    let mut status_line = String::new();
+    reader
+        .read_line(&mut status_line)
+        .expect("status line should be readable");
+    let mut content_length = 0usize;
+    loop {
+        let mut line = String::new();
+        reader
+            .read_line(&mut line)
+            .expect("header should be readable");
+        if line.trim().is_empty() {
+            break;
+        }
+        if let Some((name, value)) = line.split_once(':')
+            && name.eq_ignore_ascii_case("content-length")
+        {
+            content_length = value.trim().parse().expect("length should be numeric");
+        }
+    }
+    let mut body = vec![0u8; content_length];
+    reader
+        .read_exact(&mut body)
+        .expect("body should be readable");
+
+    status_line
+        .split_whitespace()
+        .nth(1)
+        .and_then(|value| value.parse::<u16>().ok())
+        .expect("status line should carry a code")
+}
+
+#[test]
+fn idle_keep_alive_clients_do_not_block_other_clients() {
+    let db_path = fixture_db_path("keep_alive");
+    let listener = TcpListener::bind("127.0.0.1:0").expect("ephemeral port should bind");
+    let address = listener.local_addr().expect("local address should resolve");
+    let server_db_path = db_path.clone();
+    thread::spawn(move || serve(&listener, &server_db_path, 1));
+
+    let mut first = BufReader::new(TcpStream::connect(address).expect("first client connects"));
+    let mut second = BufReader::new(TcpStream::connect(address).expect("second client connects"));
+
+    let started = Instant::now();
+    assert_eq!(keep_alive_get(&mut first, "/query?query=software"), 200);
+    assert_eq!(keep_alive_get(&mut second, "/query?query=unit"), 200);
+    assert_eq!(keep_alive_get(&mut first, "/citation/anchor-8.4.1"), 200);
+    assert_eq!(keep_alive_get(&mut second, "/node/missing"), 404);
+    assert!(started.elapsed() < Duration::from_secs(2));
+
+    let _ = std::fs::remove_file(&db_path);
+}
+
+#[test]
+fn query_params_map_onto_query_cli_arguments() {
+    assert_eq!(
+        parse_query_string("query=ASIL%20D+methods&part=6&with_pinpoint"),
+        vec![
+            ("query".to_string(), "ASIL D methods".to_string()),
+            ("part".to_string(), "6".to_string()),
+            ("with_pinpoint".to_string(), String::new()),
+        ]
+    );
+    assert_eq!(percent_decode("100%"), "100%");
+    assert_eq!(percent_decode("/chunk/a+b%20c"), "/chunk/a+b c");
+    assert_eq!(
+        parse_query_string("query=a%2Bb+c"),
+        vec![("query".to_string(), "a+b c".to_string())]
+    );
+
+    let params = parse_query_string(
+        "query=unit&retrieval_mode=hybrid&type=table&with_pinpoint&with_ancestors=false",
+    );
+    let args = query_args_from_params(None, &params).expect("params should parse");
+    assert_eq!(args.query, "unit");
+    assert_eq!(args.retrieval_mode, crate::cli::RetrievalMode::Hybrid);
+    assert_eq!(args.chunk_type.as_deref(), Some("table"));
+    assert!(args.with_pinpoint);
+    assert!(!args.with_ancestors);
+    assert_eq!(args.limit, 10);
+
+    assert!(query_args_from_params(None, &parse_query_string("query=unit&json=true")).is_err());
+    assert!(query_args_from_params(Some("node-1"), &parse_query_string("query=unit")).is_err());
+    assert!(query_args_from_params(None, &parse_query_string("query=unit&limit=ten")).is_err());
+}
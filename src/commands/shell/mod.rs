@@ -0,0 +1,6 @@
+mod run;
+mod session;
+#[cfg(test)]
+mod tests;
+
+pub(crate) use run::run;
@@ -0,0 +1,59 @@
+use std::io::{self, BufRead, IsTerminal, Write};
+
+use anyhow::Result;
+use rusqlite::Connection;
+
+use crate::cli::ShellArgs;
+use crate::commands::query::open_query_connection;
+
+use super::session::{ShellControl, ShellSession};
+
+const STATEMENT_CACHE_CAPACITY: usize = 128;
+const PROMPT: &str = "iso26262> ";
+
+pub fn run(args: ShellArgs) -> Result<()> {
+    let db_path = args
+        .db_path
+        .clone()
+        .unwrap_or_else(|| args.cache_root.join("iso26262_index.sqlite"));
+    let history_path = args
+        .history_path
+        .clone()
+        .unwrap_or_else(|| args.cache_root.join("shell_history.txt"));
+
+    let connection = open_query_connection(&db_path)?;
+    connection.set_prepared_statement_cache_capacity(STATEMENT_CACHE_CAPACITY);
+
+    let stdout = io::stdout();
+    let mut session = ShellSession::new(Some(history_path), stdout.is_terminal())?;
+    let mut output = stdout.lock();
+    writeln!(
+        output,
+        "iso26262 shell on {} (:help for commands, :quit to leave)",
+        db_path.display()
+    )?;
+    repl(&connection, &mut session, io::stdin().lock(), &mut output)
+}
+
+pub(super) fn repl<R: BufRead, W: Write>(
+    connection: &Connection,
+    session: &mut ShellSession,
+    mut input: R,
+    output: &mut W,
+) -> Result<()> {
+    loop {
+        write!(output, "{PROMPT}")?;
+        output.flush()?;
+
+        let mut line = String::new();
+        if input.read_line(&mut line)? == 0 {
+            writeln!(output)?;
+            return Ok(());
+        }
+        match session.execute_line(connection, &line, output) {
+            Ok(ShellControl::Exit) => return Ok(()),
+            Ok(ShellControl::Continue) => {}
+            Err(error) => writeln!(output, "error: {error:#}")?,
+        }
+    }
+}
@@ -0,0 +1,337 @@
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::PathBuf;
+
+use anyhow::{Context, Result, bail};
+use clap::ValueEnum;
+use rusqlite::Connection;
+
+use crate::cli::QueryArgs;
+use crate::commands::query::{
+    LookupKind, QueryResponse, execute_lookup, execute_query, query_args_from_params,
+    write_text_response_to,
+};
+
+const SETTING_ALIASES: [(&str, &str); 3] = [
+    ("mode", "retrieval_mode"),
+    ("type", "type"),
+    ("pinpoint", "with_pinpoint"),
+];
+const OPEN_RESULT_LIMIT: &str = "5";
+const HISTORY_LOAD_LIMIT: usize = 500;
+
+const HELP: &str = "\
+<text>                 run a query with the current settings
+:set <key> <value>     persist a setting (mode, part, edition, type, limit, pinpoint, or any query flag)
+:unset <key>           drop a setting back to its default
+:settings              show the current settings
+:open [part:]<ref>     print a clause or table with its hierarchy, e.g. :open 6:7.4.3
+:cite <n>              print and copy the citation of result n
+:history               list previous queries; !<n> reruns one
+:quit                  leave the shell";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum ShellControl {
+    Continue,
+    Exit,
+}
+
+pub(super) struct ShellSession {
+    settings: Vec<(String, String)>,
+    history: Vec<String>,
+    history_path: Option<PathBuf>,
+    last_response: Option<QueryResponse>,
+    clipboard: bool,
+}
+
+impl ShellSession {
+    pub(super) fn new(history_path: Option<PathBuf>, clipboard: bool) -> Result<Self> {
+        let mut history = match &history_path {
+            Some(path) if path.exists() => fs::read_to_string(path)
+                .with_context(|| format!("failed to read {}", path.display()))?
+                .lines()
+                .filter(|line| !line.trim().is_empty())
+                .map(str::to_string)
+                .collect::<Vec<String>>(),
+            _ => Vec::new(),
+        };
+        let overflow = history.len().saturating_sub(HISTORY_LOAD_LIMIT);
+        history.drain(..overflow);
+
+        Ok(Self {
+            settings: Vec::new(),
+            history,
+            history_path,
+            last_response: None,
+            clipboard,
+        })
+    }
+
+    pub(super) fn execute_line<W: Write>(
+        &mut self,
+        connection: &Connection,
+        line: &str,
+        output: &mut W,
+    ) -> Result<ShellControl> {
+        let line = line.trim();
+        if line.is_empty() {
+            return Ok(ShellControl::Continue);
+        }
+        if let Some(index) = line.strip_prefix('!') {
+            let query = self.history_entry(index)?;
+            writeln!(output, "{query}")?;
+            self.run_query(connection, &query, output)?;
+            return Ok(ShellControl::Continue);
+        }
+        let Some(command) = line.strip_prefix(':') else {
+            self.run_query(connection, line, output)?;
+            return Ok(ShellControl::Continue);
+        };
+
+        let (name, rest) = command
+            .split_once(char::is_whitespace)
+            .map(|(name, rest)| (name, rest.trim()))
+            .unwrap_or((command, ""));
+        match name {
+            "q" | "quit" | "exit" => return Ok(ShellControl::Exit),
+            "h" | "help" => writeln!(output, "{HELP}")?,
+            "set" => self.set(rest, output)?,
+            "unset" => self.unset(rest, output)?,
+            "settings" => self.write_settings(output)?,
+            "history" => {
+                for (index, query) in self.history.iter().enumerate() {
+                    writeln!(output, "{:>4}  {query}", index + 1)?;
+                }
+            }
+            "open" => self.open(connection, rest, output)?,
+            "cite" => self.cite(rest, output)?,
+            other => bail!("unknown command ':{other}'; try :help"),
+        }
+        Ok(ShellControl::Continue)
+    }
+
+    fn run_query<W: Write>(
+        &mut self,
+        connection: &Connection,
+        query: &str,
+        output: &mut W,
+    ) -> Result<()> {
+        let args = self.query_args(query, &self.settings)?;
+        self.record_history(query)?;
+        let response = execute_query(connection, &args)?;
+        write_text_response_to(output, &response)?;
+        self.last_response = Some(response);
+        Ok(())
+    }
+
+    fn open<W: Write>(
+        &mut self,
+        connection: &Connection,
+        spec: &str,
+        output: &mut W,
+    ) -> Result<()> {
+        let (part, reference) = match spec.split_once(':') {
+            Some((part, reference)) => (
+                Some(
+                    part.trim()
+                        .parse::<u32>()
+                        .with_context(|| format!("invalid part '{part}' in :open {spec}"))?,
+                ),
+                reference.trim(),
+            ),
+            None => (None, spec.trim()),
+        };
+        if reference.is_empty() {
+            bail!("usage: :open [part:]<ref>, e.g. :open 6:7.4.3");
+        }
+
+        let mut params = self
+            .settings
+            .iter()
+            .filter(|(key, _)| matches!(key.as_str(), "part" | "edition"))
+            .filter(|(key, _)| part.is_none() || key != "part")
+            .cloned()
+            .collect::<Vec<(String, String)>>();
+        if let Some(part) = part {
+            params.push(("part".to_string(), part.to_string()));
+        }
+        params.push(("limit".to_string(), OPEN_RESULT_LIMIT.to_string()));
+        params.push(("with_ancestors".to_string(), "true".to_string()));
+        params.push(("with_descendants".to_string(), "true".to_string()));
+
+        let args = query_args_from_params(Some(reference), &params)?;
+        let response = execute_lookup(connection, LookupKind::Reference, &args)?;
+        if response.is_empty() {
+            match args.part {
+                Some(part) => bail!("no clause or table {reference} in ISO 26262-{part}"),
+                None => bail!("no clause or table {reference}"),
+            }
+        }
+        write_text_response_to(output, &response)?;
+        self.last_response = Some(response);
+        Ok(())
+    }
+
+    fn cite<W: Write>(&self, rank: &str, output: &mut W) -> Result<()> {
+        let rank = rank
+            .parse::<usize>()
+            .with_context(|| format!("usage: :cite <n>, got '{rank}'"))?;
+        let Some(response) = &self.last_response else {
+            bail!("no results yet; run a query first");
+        };
+        let Some(citation) = response.result_citation(rank) else {
+            bail!("no result {rank} in the last response");
+        };
+
+        writeln!(output, "{citation}")?;
+        if let Some(citation_anchor_id) = &citation.citation_anchor_id {
+            writeln!(output, "citation_anchor_id: {citation_anchor_id}")?;
+        }
+        if self.clipboard {
+            // OSC 52 asks the terminal to place the payload on the system clipboard.
+            write!(
+                output,
+                "\x1b]52;c;{}\x07",
+                base64_encode(citation.text.as_bytes())
+            )?;
+            writeln!(output, "(copied to clipboard)")?;
+        }
+        Ok(())
+    }
+
+    fn set<W: Write>(&mut self, rest: &str, output: &mut W) -> Result<()> {
+        let Some((key, value)) = rest.split_once(char::is_whitespace) else {
+            bail!("usage: :set <key> <value>");
+        };
+        let key = setting_key(key);
+        let value = match value.trim().to_ascii_lowercase().as_str() {
+            "on" => "true".to_string(),
+            "off" => "false".to_string(),
+            _ => value.trim().to_string(),
+        };
+        if key == "query" {
+            bail!("the query is whatever you type at the prompt");
+        }
+
+        let mut settings = self.settings.clone();
+        settings.retain(|(existing, _)| *existing != key);
+        settings.push((key, value));
+        self.query_args("settings check", &settings)?;
+        self.settings = settings;
+        self.write_settings(output)
+    }
+
+    fn unset<W: Write>(&mut self, key: &str, output: &mut W) -> Result<()> {
+        let key = setting_key(key);
+        let before = self.settings.len();
+        self.settings.retain(|(existing, _)| *existing != key);
+        if self.settings.len() == before {
+            bail!("'{key}' is not set");
+        }
+        self.write_settings(output)
+    }
+
+    fn write_settings<W: Write>(&self, output: &mut W) -> Result<()> {
+        let args = self.query_args("settings check", &self.settings)?;
+        writeln!(
+            output,
+            "mode={} part={} type={} limit={} pinpoint={}",
+            value_name(args.retrieval_mode),
+            args.part
+                .map(|part| part.to_string())
+                .unwrap_or_else(|| "-".to_string()),
+            args.chunk_type.as_deref().unwrap_or("-"),
+            args.limit,
+            args.with_pinpoint
+        )?;
+        let others = self
+            .settings
+            .iter()
+            .filter(|(key, _)| {
+                !matches!(
+                    key.as_str(),
+                    "retrieval_mode" | "part" | "type" | "limit" | "with_pinpoint"
+                )
+            })
+            .map(|(key, value)| format!("{key}={value}"))
+            .collect::<Vec<String>>();
+        if !others.is_empty() {
+            writeln!(output, "also: {}", others.join(" "))?;
+        }
+        Ok(())
+    }
+
+    fn query_args(&self, query: &str, settings: &[(String, String)]) -> Result<QueryArgs> {
+        let mut params = settings.to_vec();
+        params.push(("query".to_string(), query.to_string()));
+        query_args_from_params(None, &params)
+    }
+
+    fn history_entry(&self, index: &str) -> Result<String> {
+        index
+            .trim()
+            .parse::<usize>()
+            .ok()
+            .and_then(|index| index.checked_sub(1))
+            .and_then(|index| self.history.get(index))
+            .cloned()
+            .with_context(|| format!("no history entry '{index}'; see :history"))
+    }
+
+    fn record_history(&mut self, query: &str) -> Result<()> {
+        if self.history.last().map(String::as_str) == Some(query) {
+            return Ok(());
+        }
+        self.history.push(query.to_string());
+
+        if let Some(path) = &self.history_path {
+            let mut file = OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(path)
+                .with_context(|| format!("failed to open {}", path.display()))?;
+            writeln!(file, "{query}")?;
+        }
+        Ok(())
+    }
+
+    #[cfg(test)]
+    pub(super) fn history(&self) -> &[String] {
+        &self.history
+    }
+}
+
+fn setting_key(key: &str) -> String {
+    let key = key.trim().replace('-', "_");
+    SETTING_ALIASES
+        .iter()
+        .find(|(alias, _)| *alias == key)
+        .map(|(_, target)| target.to_string())
+        .unwrap_or(key)
+}
+
+fn value_name<T: ValueEnum>(value: T) -> String {
+    value
+        .to_possible_value()
+        .map(|value| value.get_name().to_string())
+        .unwrap_or_default()
+}
+
+fn base64_encode(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+    let mut out = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let value = chunk.iter().enumerate().fold(0u32, |acc, (index, byte)| {
+            acc | (u32::from(*byte) << (16 - 8 * index))
+        });
+        for index in 0..4 {
+            if index <= chunk.len() {
+                out.push(ALPHABET[(value >> (18 - 6 * index)) as usize & 0x3f] as char);
+            } else {
+                out.push('=');
+            }
+        }
+    }
+    out
+}
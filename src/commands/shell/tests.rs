@@ -0,0 +1,135 @@
+use rusqlite::Connection;
+
+use super::run::repl;
+use super::session::{ShellControl, ShellSession};
+
+fn fixture_connection() -> Connection {
+    crate::commands::ingest::open_fixture_database(
+        None,
+        "
+        INSERT INTO docs(doc_id, filename, sha256, part, year, title) VALUES
+          ('ISO26262-6-2018', 'part6.pdf', 'sha6', 6, 2018, 'Part 6'),
+          ('ISO26262-8-2018', 'part8.pdf', 'sha8', 8, 2018, 'Part 8');
+        INSERT INTO nodes(
+          node_id, parent_node_id, doc_id, node_type, ref, heading, order_index,
+          page_pdf_start, page_pdf_end, text, anchor_type, anchor_label_norm,
+          citation_anchor_id
+        ) VALUES
+          ('n6-7.4.3', NULL, 'ISO26262-6-2018', 'clause', '7.4.3',
+           '7.4.3 Architectural design principles', 1, 18, 18, NULL, NULL, NULL,
+           'anchor-6-7.4.3'),
+          ('n6-7.4.3-list', 'n6-7.4.3', 'ISO26262-6-2018', 'list', '7.4.3', NULL, 2, 18, 18,
+           NULL, NULL, NULL, NULL),
+          ('n6-7.4.3-a', 'n6-7.4.3-list', 'ISO26262-6-2018', 'list_item', '7.4.3', NULL, 3,
+           18, 18, 'hierarchical structure of software components', 'marker', 'a',
+           'anchor-6-7.4.3-a'),
+          ('n8-7.4.3', NULL, 'ISO26262-8-2018', 'clause', '7.4.3',
+           '7.4.3 Configuration management', 1, 30, 30, NULL, NULL, NULL, 'anchor-8-7.4.3');
+        INSERT INTO chunks(
+          chunk_id, doc_id, type, ref, heading, chunk_seq, page_pdf_start, page_pdf_end,
+          text, source_hash, origin_node_id, leaf_node_type, citation_anchor_id
+        ) VALUES
+          ('ISO26262-6-2018:7.4.3', 'ISO26262-6-2018', 'clause', '7.4.3',
+           '7.4.3 Architectural design principles', 0, 18, 18,
+           'The software architectural design shall exhibit a hierarchical structure.',
+           'hash6', 'n6-7.4.3', 'clause', 'anchor-6-7.4.3'),
+          ('ISO26262-8-2018:7.4.3', 'ISO26262-8-2018', 'clause', '7.4.3',
+           '7.4.3 Configuration management', 0, 30, 30,
+           'Configuration management shall cover software architectural design artefacts.',
+           'hash8', 'n8-7.4.3', 'clause', 'anchor-8-7.4.3');
+        ",
+    )
+}
+
+fn execute(session: &mut ShellSession, connection: &Connection, line: &str) -> String {
+    let mut output = Vec::<u8>::new();
+    let control = session
+        .execute_line(connection, line, &mut output)
+        .unwrap_or_else(|error| panic!("'{line}' should succeed: {error:#}"));
+    assert_eq!(control, ShellControl::Continue);
+    String::from_utf8(output).expect("shell output should be UTF-8")
+}
+
+#[test]
+fn shell_settings_persist_across_queries_and_feed_cite() {
+    let connection = fixture_connection();
+    let mut session = ShellSession::new(None, false).expect("session should start");
+
+    let settings = execute(&mut session, &connection, ":set part 6");
+    assert_eq!(
+        settings,
+        "mode=lexical part=6 type=- limit=10 pinpoint=false\n"
+    );
+    let settings = execute(&mut session, &connection, ":set pinpoint on");
+    assert!(settings.contains("pinpoint=true"));
+    execute(&mut session, &connection, ":set limit 1");
+
+    let output = execute(&mut session, &connection, "software architectural design");
+    assert!(output.contains("Results: 1"));
+    assert!(output.contains("ISO 26262-6:2018\tclause\t7.4.3"));
+    assert!(output.contains("pinpoint=true"));
+
+    let cited = execute(&mut session, &connection, ":cite 1");
+    assert_eq!(
+        cited,
+        "ISO 26262-6:2018, 7.4.3, PDF pages 18\ncitation_anchor_id: anchor-6-7.4.3\n"
+    );
+
+    execute(&mut session, &connection, ":unset part");
+    let output = execute(&mut session, &connection, "!1");
+    assert!(output.starts_with("software architectural design\n"));
+    assert_eq!(session.history(), ["software architectural design"]);
+
+    let mut output = Vec::<u8>::new();
+    let error = session
+        .execute_line(&connection, ":set limit many", &mut output)
+        .expect_err("invalid settings should be rejected");
+    assert!(format!("{error:#}").contains("many"));
+    assert!(
+        session
+            .execute_line(&connection, ":cite 9", &mut output)
+            .is_err()
+    );
+}
+
+#[test]
+fn shell_open_prints_hierarchy_for_part_qualified_reference() {
+    let connection = fixture_connection();
+    let mut session = ShellSession::new(None, true).expect("session should start");
+
+    let output = execute(&mut session, &connection, ":open 6:7.4.3");
+    assert!(output.contains("Results: 1"));
+    assert!(output.contains("\tdescendant: list  (ISO 26262-6:2018, 7.4.3, PDF pages 18)"));
+    assert!(output.contains(
+        "\tdescendant:   list_item hierarchical structure of software components (ISO 26262-6:2018, 7.4.3(a), PDF pages 18)"
+    ));
+
+    let cited = execute(&mut session, &connection, ":cite 1");
+    assert!(cited.contains("\x1b]52;c;SVNPIDI2MjYyLTY6MjAxOCwgNy40LjMsIFBERiBwYWdlcyAxOA==\x07"));
+
+    let both_parts = execute(&mut session, &connection, ":open 7.4.3");
+    assert!(both_parts.contains("Results: 2"));
+
+    let mut output = Vec::<u8>::new();
+    assert!(
+        session
+            .execute_line(&connection, ":open 9:7.4.3", &mut output)
+            .is_err()
+    );
+}
+
+#[test]
+fn shell_repl_reports_errors_and_stops_on_quit() {
+    let connection = fixture_connection();
+    let mut session = ShellSession::new(None, false).expect("session should start");
+    let input = ":bogus\n:set mode hybrid\n:quit\nnever run\n";
+
+    let mut output = Vec::<u8>::new();
+    repl(&connection, &mut session, input.as_bytes(), &mut output).expect("repl should finish");
+    let output = String::from_utf8(output).expect("shell output should be UTF-8");
+
+    assert!(output.contains("error: unknown command ':bogus'; try :help"));
+    assert!(output.contains("mode=hybrid part=- type=- limit=10 pinpoint=false"));
+    assert!(!output.contains("never run"));
+    assert!(session.history().is_empty());
+}
@@ -1,6 +1,6 @@
 use super::*;
 
-pub const DB_SCHEMA_VERSION: &str = "0.4.0";
+pub const DB_SCHEMA_VERSION: &str = "0.5.0";
 pub const TABLE_SPARSE_ROW_RATIO_MAX: f64 = 0.20;
 pub const TABLE_OVERLOADED_ROW_RATIO_MAX: f64 = 0.10;
 pub const TABLE_MARKER_SEQUENCE_COVERAGE_MIN: f64 = 0.90;
@@ -114,27 +114,6 @@ pub struct TargetCoverageReport {
     pub unexpected_target_ids: Vec<String>,
 }
 
-#[derive(Debug, Clone, Serialize)]
-pub struct FreshnessReport {
-    pub source_manifest_dir: String,
-    pub required_parts: Vec<u32>,
-    pub latest_manifest: Option<String>,
-    pub latest_run_id: Option<String>,
-    pub latest_started_at: Option<String>,
-    pub latest_run_parts: Vec<u32>,
-    pub latest_run_by_part: Vec<PartFreshness>,
-    pub full_target_cycle_run_id: Option<String>,
-    pub stale_parts: Vec<u32>,
-}
-
-#[derive(Debug, Clone, Serialize)]
-pub struct PartFreshness {
-    pub part: u32,
-    pub manifest: Option<String>,
-    pub run_id: Option<String>,
-    pub started_at: Option<String>,
-}
-
 #[derive(Debug, Serialize)]
 pub struct QualityReport {
     pub manifest_version: u32,
@@ -261,6 +240,8 @@ pub struct TableQualityCounters {
     pub table_rows_with_descriptions_count: usize,
     pub table_marker_expected_count: usize,
     pub table_marker_observed_count: usize,
+    pub table_continuations_merged_count: usize,
+    pub table_repeated_header_rows_dropped_count: usize,
 }
 
 #[derive(Debug, Serialize)]
@@ -346,6 +327,8 @@ pub struct IngestRunSnapshot {
     #[serde(default)]
     pub processed_parts: Vec<u32>,
     #[serde(default)]
+    pub processed_editions: Vec<PartEdition>,
+    #[serde(default)]
     pub counts: IngestRunCountsSnapshot,
     #[serde(default)]
     pub paths: IngestRunPathsSnapshot,
@@ -379,6 +362,8 @@ pub struct IngestRunCountsSnapshot {
     pub table_rows_with_descriptions_count: usize,
     pub table_marker_expected_count: usize,
     pub table_marker_observed_count: usize,
+    pub table_continuations_merged_count: usize,
+    pub table_repeated_header_rows_dropped_count: usize,
 }
 
 impl IngestRunCountsSnapshot {
@@ -391,6 +376,9 @@ impl IngestRunCountsSnapshot {
             table_rows_with_descriptions_count: self.table_rows_with_descriptions_count,
             table_marker_expected_count: self.table_marker_expected_count,
             table_marker_observed_count: self.table_marker_observed_count,
+            table_continuations_merged_count: self.table_continuations_merged_count,
+            table_repeated_header_rows_dropped_count: self
+                .table_repeated_header_rows_dropped_count,
         }
     }
 }
@@ -1,5 +1,34 @@
 use super::*;
 
+#[derive(Debug, Clone, Serialize)]
+pub struct FreshnessReport {
+    pub source_manifest_dir: String,
+    pub required_editions: Vec<PartEdition>,
+    pub latest_manifest: Option<String>,
+    pub latest_run_id: Option<String>,
+    pub latest_started_at: Option<String>,
+    pub latest_run_editions: Vec<PartEdition>,
+    pub latest_run_by_edition: Vec<EditionFreshness>,
+    pub full_target_cycle_run_id: Option<String>,
+    pub stale_editions: Vec<PartEdition>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct PartEdition {
+    pub part: u32,
+    #[serde(default)]
+    pub year: Option<u32>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct EditionFreshness {
+    pub part: u32,
+    pub year: Option<u32>,
+    pub manifest: Option<String>,
+    pub run_id: Option<String>,
+    pub started_at: Option<String>,
+}
+
 pub fn load_page_provenance_entries(
     manifest_dir: &Path,
     snapshot: Option<&NamedIngestRunSnapshot>,
@@ -140,36 +169,38 @@ pub fn build_freshness_report(
     manifest_dir: &Path,
     target_sections: &Option<TargetSectionsManifest>,
 ) -> Result<FreshnessReport> {
-    let required_parts = target_sections
+    let required_editions = target_sections
         .as_ref()
-        .map(required_target_parts)
+        .map(required_target_editions)
         .unwrap_or_default();
 
     let snapshots = load_ingest_snapshots(manifest_dir)?;
     let latest = snapshots.last();
 
-    let latest_run_parts = latest
-        .map(|snapshot| resolve_processed_parts(&snapshot.snapshot, &required_parts))
+    let latest_run_editions = latest
+        .map(|snapshot| resolve_processed_editions(&snapshot.snapshot, &required_editions))
         .unwrap_or_default();
 
-    let stale_parts = required_parts
+    let stale_editions = required_editions
         .iter()
         .copied()
-        .filter(|part| !latest_run_parts.contains(part))
-        .collect::<Vec<u32>>();
-
-    let mut latest_run_by_part = Vec::<PartFreshness>::new();
-    for part in &required_parts {
-        let mut entry = PartFreshness {
-            part: *part,
+        .filter(|edition| !edition_processed(&latest_run_editions, edition))
+        .collect::<Vec<PartEdition>>();
+
+    let mut latest_run_by_edition = Vec::<EditionFreshness>::new();
+    for edition in &required_editions {
+        let mut entry = EditionFreshness {
+            part: edition.part,
+            year: edition.year,
             manifest: None,
             run_id: None,
             started_at: None,
         };
 
         for snapshot in snapshots.iter().rev() {
-            let processed_parts = resolve_processed_parts(&snapshot.snapshot, &required_parts);
-            if processed_parts.contains(part) {
+            let processed_editions =
+                resolve_processed_editions(&snapshot.snapshot, &required_editions);
+            if edition_processed(&processed_editions, edition) {
                 entry.manifest = Some(snapshot.manifest_name.clone());
                 entry.run_id = snapshot.snapshot.run_id.clone();
                 entry.started_at = snapshot.snapshot.started_at.clone();
@@ -177,15 +208,15 @@ pub fn build_freshness_report(
             }
         }
 
-        latest_run_by_part.push(entry);
+        latest_run_by_edition.push(entry);
     }
 
     let full_target_cycle_run_id = snapshots.iter().rev().find_map(|snapshot| {
-        let processed_parts = resolve_processed_parts(&snapshot.snapshot, &required_parts);
-        let all_parts_present = required_parts
+        let processed_editions = resolve_processed_editions(&snapshot.snapshot, &required_editions);
+        let all_editions_present = required_editions
             .iter()
-            .all(|required| processed_parts.contains(required));
-        if all_parts_present {
+            .all(|required| edition_processed(&processed_editions, required));
+        if all_editions_present {
             snapshot.snapshot.run_id.clone()
         } else {
             None
@@ -194,26 +225,29 @@ pub fn build_freshness_report(
 
     Ok(FreshnessReport {
         source_manifest_dir: manifest_dir.display().to_string(),
-        required_parts,
+        required_editions,
         latest_manifest: latest.map(|snapshot| snapshot.manifest_name.clone()),
         latest_run_id: latest.and_then(|snapshot| snapshot.snapshot.run_id.clone()),
         latest_started_at: latest.and_then(|snapshot| snapshot.snapshot.started_at.clone()),
-        latest_run_parts,
-        latest_run_by_part,
+        latest_run_editions,
+        latest_run_by_edition,
         full_target_cycle_run_id,
-        stale_parts,
+        stale_editions,
     })
 }
 
-pub fn required_target_parts(manifest: &TargetSectionsManifest) -> Vec<u32> {
-    let mut parts = manifest
+pub fn required_target_editions(manifest: &TargetSectionsManifest) -> Vec<PartEdition> {
+    let mut editions = manifest
         .targets
         .iter()
-        .map(|target| target.part)
-        .collect::<Vec<u32>>();
-    parts.sort_unstable();
-    parts.dedup();
-    parts
+        .map(|target| PartEdition {
+            part: target.part,
+            year: target.year,
+        })
+        .collect::<Vec<PartEdition>>();
+    editions.sort_unstable();
+    editions.dedup();
+    editions
 }
 
 pub fn load_ingest_snapshots(manifest_dir: &Path) -> Result<Vec<NamedIngestRunSnapshot>> {
@@ -242,20 +276,40 @@ pub fn load_ingest_snapshots(manifest_dir: &Path) -> Result<Vec<NamedIngestRunSn
     Ok(snapshots)
 }
 
-pub fn resolve_processed_parts(snapshot: &IngestRunSnapshot, required_parts: &[u32]) -> Vec<u32> {
-    let mut processed_parts = if !snapshot.processed_parts.is_empty() {
-        snapshot.processed_parts.clone()
+pub fn resolve_processed_editions(
+    snapshot: &IngestRunSnapshot,
+    required_editions: &[PartEdition],
+) -> Vec<PartEdition> {
+    let mut processed_editions = if !snapshot.processed_editions.is_empty() {
+        snapshot.processed_editions.clone()
     } else {
-        parse_target_parts_from_command(snapshot.command.as_deref().unwrap_or(""))
+        let processed_parts = if !snapshot.processed_parts.is_empty() {
+            snapshot.processed_parts.clone()
+        } else {
+            parse_target_parts_from_command(snapshot.command.as_deref().unwrap_or(""))
+        };
+        processed_parts
+            .into_iter()
+            .map(|part| PartEdition { part, year: None })
+            .collect()
     };
 
-    if processed_parts.is_empty() {
-        processed_parts = required_parts.to_vec();
+    if processed_editions.is_empty() {
+        processed_editions = required_editions.to_vec();
     }
 
-    processed_parts.sort_unstable();
-    processed_parts.dedup();
-    processed_parts
+    processed_editions.sort_unstable();
+    processed_editions.dedup();
+    processed_editions
+}
+
+// Targets and ingest manifests written before editions were tracked carry no
+// year; those match any edition of the same part.
+pub fn edition_processed(processed_editions: &[PartEdition], required: &PartEdition) -> bool {
+    processed_editions.iter().any(|edition| {
+        edition.part == required.part
+            && (edition.year.is_none() || required.year.is_none() || edition.year == required.year)
+    })
 }
 
 pub fn parse_target_parts_from_command(command: &str) -> Vec<u32> {
@@ -9,7 +9,12 @@ use serde::{Deserialize, Serialize};
 use tracing::info;
 
 use crate::cli::ValidateArgs;
-use crate::semantic::{chunk_payload_for_embedding, embedding_text_hash, DEFAULT_MODEL_ID};
+use crate::semantic::ann::{ann_min_candidates, exact_top_k, load_ann_index, load_model_vectors};
+use crate::semantic::{
+    chunk_payload_for_embedding, embedding_text_hash, load_embedding_backend,
+    load_model_storage_encoding, load_registered_model_config, quantize_embedding,
+    resolve_model_config, EmbeddingBackend, EmbeddingEncoding, DEFAULT_BACKEND, DEFAULT_MODEL_ID,
+};
 use crate::util::{now_utc_string, write_json_pretty};
 
 mod core_types;
@@ -441,9 +441,9 @@ pub fn build_quality_checks(
     checks.push(QualityCheck {
         check_id: "Q-022".to_string(),
         name: "Target-part freshness completeness".to_string(),
-        result: if freshness.required_parts.is_empty() {
+        result: if freshness.required_editions.is_empty() {
             "pending"
-        } else if freshness.stale_parts.is_empty() {
+        } else if freshness.stale_editions.is_empty() {
             "pass"
         } else {
             "failed"
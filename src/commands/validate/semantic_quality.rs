@@ -1,5 +1,7 @@
 use super::*;
 
+#[path = "semantic_quality_ann.rs"]
+mod semantic_quality_ann;
 #[path = "semantic_quality_checks.rs"]
 mod semantic_quality_checks;
 #[path = "semantic_quality_gates.rs"]
@@ -18,13 +20,17 @@ mod semantic_quality_manifest;
 mod semantic_quality_pinpoint;
 #[path = "semantic_quality_baseline.rs"]
 mod semantic_quality_baseline;
+#[path = "semantic_quality_quantization.rs"]
+mod semantic_quality_quantization;
 
+pub use self::semantic_quality_ann::*;
 pub use self::semantic_quality_baseline::*;
 pub use self::semantic_quality_checks::*;
 pub use self::semantic_quality_eval::*;
 pub use self::semantic_quality_exact::*;
 pub use self::semantic_quality_manifest::*;
 pub use self::semantic_quality_pinpoint::*;
+pub use self::semantic_quality_quantization::*;
 pub use self::semantic_quality_retrieval::*;
 pub use self::semantic_quality_gates::*;
 pub use self::semantic_quality_stats::*;
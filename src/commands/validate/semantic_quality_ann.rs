@@ -0,0 +1,140 @@
+use super::*;
+
+pub const WP3_ANN_RECALL_STAGE_A_MIN: f64 = 0.90;
+pub const WP3_ANN_RECALL_STAGE_B_MIN: f64 = 0.95;
+
+#[derive(Debug, Default)]
+pub struct AnnRecallComputation {
+    pub queries: usize,
+    pub recall_at_k: Option<f64>,
+    pub unavailable_reason: Option<String>,
+}
+
+pub fn append_ann_recall_assessment(
+    connection: &Connection,
+    stage: Wp2GateStage,
+    semantic_embeddings: &SemanticEmbeddingReport,
+    eval_manifest: &SemanticEvalManifest,
+    assessment: &mut SemanticQualityAssessment,
+) -> Result<()> {
+    let summary = &mut assessment.summary;
+    let active_model_id = if semantic_embeddings.active_model_id.trim().is_empty() {
+        DEFAULT_MODEL_ID.to_string()
+    } else {
+        semantic_embeddings.active_model_id.trim().to_string()
+    };
+
+    let recall = compute_ann_recall_at_k(
+        connection,
+        &active_model_id,
+        semantic_embeddings.embedding_dim,
+        eval_manifest,
+        SEMANTIC_TOP_K,
+    )?;
+    summary.ann_recall_queries = recall.queries;
+    summary.ann_recall_at_10 = recall.recall_at_k;
+    if let Some(reason) = recall.unavailable_reason {
+        summary
+            .warnings
+            .push(format!("{reason}; Q-049 ANN recall is pending"));
+    }
+
+    let q049_stage_a_warn = summary
+        .ann_recall_at_10
+        .map(|value| value < WP3_ANN_RECALL_STAGE_A_MIN)
+        .unwrap_or(false);
+    let q049_stage_b_fail = summary
+        .ann_recall_at_10
+        .map(|value| value < WP3_ANN_RECALL_STAGE_B_MIN)
+        .unwrap_or(false);
+    assessment.checks.push(stage_metric_check(
+        "Q-049",
+        "ANN recall@10 against brute-force semantic scan",
+        stage,
+        summary.ann_recall_at_10,
+        false,
+        q049_stage_b_fail,
+    ));
+    if (stage == Wp2GateStage::A && q049_stage_a_warn)
+        || (stage == Wp2GateStage::B && q049_stage_b_fail)
+    {
+        assessment.recommendations.push(
+            "Q-049: ANN recall@10 is below target; re-run embed to rebuild the IVF index or raise the probed list count.".to_string(),
+        );
+    }
+
+    Ok(())
+}
+
+pub fn compute_ann_recall_at_k(
+    connection: &Connection,
+    model_id: &str,
+    embedding_dim: Option<usize>,
+    eval_manifest: &SemanticEvalManifest,
+    k: usize,
+) -> Result<AnnRecallComputation> {
+    let status = load_ann_index(connection, model_id)?;
+    let Some(index) = status.index else {
+        return Ok(AnnRecallComputation {
+            unavailable_reason: status.reason,
+            ..AnnRecallComputation::default()
+        });
+    };
+
+    let mut embedder = load_semantic_eval_embedder(connection, model_id, embedding_dim)?;
+    if embedder.dimensions() != index.dimensions {
+        return Ok(AnnRecallComputation {
+            unavailable_reason: Some(format!(
+                "ANN index dimension {} does not match eval embedding dimension {}",
+                index.dimensions,
+                embedder.dimensions()
+            )),
+            ..AnnRecallComputation::default()
+        });
+    }
+
+    let vectors = load_model_vectors(connection, model_id, index.dimensions)?;
+    let by_chunk_id = vectors
+        .iter()
+        .map(|(chunk_id, vector)| (chunk_id.as_str(), vector.as_slice()))
+        .collect::<HashMap<&str, &[f32]>>();
+
+    let mut recalls = Vec::<f64>::new();
+    for query in &eval_manifest.queries {
+        let query_embedding = embedder.embed(&semantic_embedding_query_text(&query.query_text))?;
+        let exact = exact_top_k(
+            &query_embedding,
+            vectors
+                .iter()
+                .map(|(chunk_id, vector)| (chunk_id.as_str(), vector.as_slice())),
+            k,
+        );
+        if exact.is_empty() {
+            continue;
+        }
+
+        let pool = index.candidate_pool(
+            &query_embedding,
+            ann_min_candidates(SEMANTIC_RETRIEVAL_LIMIT),
+        );
+        let approximate = exact_top_k(
+            &query_embedding,
+            pool.iter()
+                .filter_map(|chunk_id| by_chunk_id.get_key_value(chunk_id))
+                .map(|(chunk_id, vector)| (*chunk_id, *vector)),
+            k,
+        );
+        let approximate = approximate.into_iter().collect::<HashSet<&str>>();
+        let found = exact
+            .iter()
+            .filter(|chunk_id| approximate.contains(**chunk_id))
+            .count();
+        recalls.push(found as f64 / exact.len() as f64);
+    }
+
+    Ok(AnnRecallComputation {
+        queries: recalls.len(),
+        recall_at_k: mean(&recalls),
+        unavailable_reason: None,
+    })
+}
@@ -37,16 +37,6 @@ pub struct SemanticQualityAssessment {
     pub recommendations: Vec<String>,
 }
 
-#[derive(Debug, Clone)]
-pub struct SemanticRetrievedHit {
-    pub chunk_id: String,
-    pub reference: String,
-    pub page_pdf_start: Option<i64>,
-    pub page_pdf_end: Option<i64>,
-    pub citation_anchor_id: Option<String>,
-    pub score: f64,
-}
-
 #[derive(Debug)]
 pub struct SemanticEvalComputation {
     pub summary: SemanticQualitySummaryReport,
@@ -120,6 +110,19 @@ pub fn build_semantic_quality_assessment(
         &mut assessment.checks,
         &mut assessment.recommendations,
     )?;
+    append_ann_recall_assessment(
+        connection,
+        stage,
+        semantic_embeddings,
+        &eval_manifest,
+        &mut assessment,
+    )?;
+    append_quantization_drift_assessment(
+        connection,
+        semantic_embeddings,
+        &eval_manifest,
+        &mut assessment.summary,
+    )?;
     Ok(assessment)
 }
 
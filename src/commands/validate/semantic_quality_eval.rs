@@ -14,9 +14,11 @@ pub fn compute_semantic_eval_quality(
     } else {
         semantic_embeddings.active_model_id.trim().to_string()
     };
-    let embedding_dim = semantic_embeddings
-        .embedding_dim
-        .unwrap_or(crate::semantic::DEFAULT_EMBEDDING_DIM);
+    let mut embedder = load_semantic_eval_embedder(
+        connection,
+        &active_model_id,
+        semantic_embeddings.embedding_dim,
+    )?;
 
     let mut query_results = Vec::<SemanticQualityQueryResult>::new();
     let mut lexical_ndcgs = Vec::<f64>::new();
@@ -43,7 +45,7 @@ pub fn compute_semantic_eval_quality(
             connection,
             query,
             &active_model_id,
-            embedding_dim,
+            embedder.as_mut(),
             SEMANTIC_RETRIEVAL_LIMIT,
         )?;
 
@@ -134,7 +136,7 @@ pub fn compute_semantic_eval_quality(
         connection,
         refs,
         &active_model_id,
-        embedding_dim,
+        embedder.as_mut(),
         SEMANTIC_RETRIEVAL_LIMIT,
     )?;
     if !exact_probe_evals.is_empty() {
@@ -276,6 +278,7 @@ pub fn compute_semantic_eval_quality(
         baseline_created: false,
         baseline_missing: false,
         warnings: Vec::new(),
+        ..SemanticQualitySummaryReport::default()
     };
 
     if summary.non_exact_queries == 0 {
@@ -365,7 +368,7 @@ pub fn evaluate_semantic_query(
     connection: &Connection,
     query: &SemanticEvalQuery,
     model_id: &str,
-    embedding_dim: usize,
+    embedder: &mut dyn EmbeddingBackend,
     retrieval_limit: usize,
 ) -> Result<QueryEvalRecord> {
     let exact_intent_priority = query.must_hit_top1
@@ -389,8 +392,9 @@ pub fn evaluate_semantic_query(
         query.part_filter,
         query.chunk_type_filter.as_deref(),
         model_id,
-        embedding_dim,
+        embedder,
         retrieval_limit,
+        None,
     )?;
     let semantic_latency_ms = semantic_started.elapsed().as_secs_f64() * 1000.0;
 
@@ -401,7 +405,7 @@ pub fn evaluate_semantic_query(
         query.part_filter,
         query.chunk_type_filter.as_deref(),
         model_id,
-        embedding_dim,
+        embedder,
         retrieval_limit,
         exact_intent_priority,
     )?;
@@ -413,7 +417,7 @@ pub fn evaluate_semantic_query(
         query.part_filter,
         query.chunk_type_filter.as_deref(),
         model_id,
-        embedding_dim,
+        embedder,
         retrieval_limit,
         exact_intent_priority,
     )?;
@@ -10,14 +10,14 @@ pub fn evaluate_exact_intent_probes(
     connection: &Connection,
     refs: &[GoldReference],
     model_id: &str,
-    embedding_dim: usize,
+    embedder: &mut dyn EmbeddingBackend,
     retrieval_limit: usize,
 ) -> Result<Vec<ExactIntentProbeEvaluation>> {
     let probes = build_exact_intent_probe_queries(connection, refs)?;
     let mut out = Vec::<ExactIntentProbeEvaluation>::with_capacity(probes.len());
     for probe in probes {
         let eval =
-            evaluate_semantic_query(connection, &probe, model_id, embedding_dim, retrieval_limit)?;
+            evaluate_semantic_query(connection, &probe, model_id, embedder, retrieval_limit)?;
         out.push(ExactIntentProbeEvaluation { query: probe, eval });
     }
     Ok(out)
@@ -11,9 +11,11 @@ pub fn compute_pinpoint_quality(
     } else {
         semantic_embeddings.active_model_id.trim().to_string()
     };
-    let embedding_dim = semantic_embeddings
-        .embedding_dim
-        .unwrap_or(crate::semantic::DEFAULT_EMBEDDING_DIM);
+    let mut embedder = load_semantic_eval_embedder(
+        connection,
+        &active_model_id,
+        semantic_embeddings.embedding_dim,
+    )?;
 
     let mut relevance_hits = Vec::<f64>::new();
     let mut table_hits = Vec::<f64>::new();
@@ -35,7 +37,7 @@ pub fn compute_pinpoint_quality(
             query.part_filter,
             query.chunk_type_filter.as_deref(),
             &active_model_id,
-            embedding_dim,
+            embedder.as_mut(),
             SEMANTIC_RETRIEVAL_LIMIT,
             exact_intent_priority,
         )?;
@@ -48,7 +50,7 @@ pub fn compute_pinpoint_quality(
             query.part_filter,
             query.chunk_type_filter.as_deref(),
             &active_model_id,
-            embedding_dim,
+            embedder.as_mut(),
             SEMANTIC_RETRIEVAL_LIMIT,
             exact_intent_priority,
         )?;
@@ -0,0 +1,115 @@
+use super::*;
+
+pub fn append_quantization_drift_assessment(
+    connection: &Connection,
+    semantic_embeddings: &SemanticEmbeddingReport,
+    eval_manifest: &SemanticEvalManifest,
+    summary: &mut SemanticQualitySummaryReport,
+) -> Result<()> {
+    let active_model_id = if semantic_embeddings.active_model_id.trim().is_empty() {
+        DEFAULT_MODEL_ID.to_string()
+    } else {
+        semantic_embeddings.active_model_id.trim().to_string()
+    };
+
+    let stored_encoding =
+        load_model_storage_encoding(connection, &active_model_id)?.unwrap_or_default();
+    summary.storage_encoding = Some(stored_encoding.as_str().to_string());
+    if stored_encoding != EmbeddingEncoding::F32 {
+        summary.warnings.push(format!(
+            "active model stores {} embeddings; quantization drift needs an f32 reference index",
+            stored_encoding.as_str()
+        ));
+        return Ok(());
+    }
+    let Some(reference_ndcg) = summary.semantic_ndcg_at_10 else {
+        summary
+            .warnings
+            .push("semantic nDCG@10 is unavailable; quantization drift is pending".to_string());
+        return Ok(());
+    };
+
+    let mut embedder = load_semantic_eval_embedder(
+        connection,
+        &active_model_id,
+        semantic_embeddings.embedding_dim,
+    )?;
+    let embedding_dim = embedder.dimensions();
+    summary
+        .quantized_index_drift
+        .push(QuantizedIndexDriftReport {
+            encoding: EmbeddingEncoding::F32.as_str().to_string(),
+            bytes_per_vector: EmbeddingEncoding::F32.blob_len(embedding_dim),
+            queries: summary.non_exact_queries,
+            semantic_ndcg_at_10: Some(reference_ndcg),
+            ndcg_at_10_drift_vs_f32: Some(0.0),
+        });
+
+    for encoding in EmbeddingEncoding::ALL
+        .into_iter()
+        .filter(|encoding| *encoding != EmbeddingEncoding::F32)
+    {
+        let mut ndcgs = Vec::<f64>::new();
+        for query in &eval_manifest.queries {
+            if query.intent.eq_ignore_ascii_case("exact_ref") || query.must_hit_top1 {
+                continue;
+            }
+
+            let hits = semantic_eval_semantic_hits(
+                connection,
+                &query.query_text,
+                query.part_filter,
+                query.chunk_type_filter.as_deref(),
+                &active_model_id,
+                embedder.as_mut(),
+                SEMANTIC_RETRIEVAL_LIMIT,
+                Some(encoding),
+            )?;
+            let expected = query
+                .expected_chunk_ids
+                .iter()
+                .map(|value| value.trim().to_string())
+                .filter(|value| !value.is_empty())
+                .collect::<HashSet<String>>();
+            let judged = query
+                .judged_chunk_ids
+                .iter()
+                .map(|value| value.trim().to_string())
+                .filter(|value| !value.is_empty())
+                .collect::<HashSet<String>>();
+            let hit_ids = hits
+                .iter()
+                .map(|hit| hit.chunk_id.clone())
+                .collect::<Vec<String>>();
+            if let Some(value) = ndcg_at_k(&hit_ids, &expected, &judged, SEMANTIC_TOP_K) {
+                ndcgs.push(value);
+            }
+        }
+
+        let semantic_ndcg_at_10 = mean(&ndcgs);
+        summary
+            .quantized_index_drift
+            .push(QuantizedIndexDriftReport {
+                encoding: encoding.as_str().to_string(),
+                bytes_per_vector: encoding.blob_len(embedding_dim),
+                queries: ndcgs.len(),
+                semantic_ndcg_at_10,
+                ndcg_at_10_drift_vs_f32: semantic_ndcg_at_10.map(|value| value - reference_ndcg),
+            });
+    }
+
+    Ok(())
+}
+
+pub fn decode_eval_embedding(
+    blob: &[u8],
+    embedding_dim: usize,
+    storage_encoding: EmbeddingEncoding,
+    requantize: Option<EmbeddingEncoding>,
+) -> Option<Vec<f32>> {
+    let embedding = crate::semantic::decode_embedding_blob(blob, embedding_dim, storage_encoding)?;
+    match requantize {
+        Some(encoding) => quantize_embedding(&embedding, encoding),
+        None => Some(embedding),
+    }
+}
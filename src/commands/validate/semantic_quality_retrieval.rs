@@ -1,12 +1,39 @@
 use super::*;
 
+#[derive(Debug, Clone)]
+pub struct SemanticRetrievedHit {
+    pub chunk_id: String,
+    pub reference: String,
+    pub page_pdf_start: Option<i64>,
+    pub page_pdf_end: Option<i64>,
+    pub citation_anchor_id: Option<String>,
+    pub score: f64,
+}
+
+pub fn load_semantic_eval_embedder(
+    connection: &Connection,
+    model_id: &str,
+    embedding_dim: Option<usize>,
+) -> Result<Box<dyn EmbeddingBackend>> {
+    let mut model = match load_registered_model_config(connection, model_id)? {
+        Some(model) => model,
+        None => resolve_model_config(model_id, None)?,
+    };
+    if model.backend == DEFAULT_BACKEND
+        && let Some(embedding_dim) = embedding_dim
+    {
+        model.dimensions = embedding_dim;
+    }
+    load_embedding_backend(&model)
+}
+
 pub fn semantic_eval_hybrid_hits(
     connection: &Connection,
     query_text: &str,
     part_filter: Option<u32>,
     chunk_type_filter: Option<&str>,
     model_id: &str,
-    embedding_dim: usize,
+    embedder: &mut dyn EmbeddingBackend,
     limit: usize,
     exact_intent_priority: bool,
 ) -> Result<Vec<SemanticRetrievedHit>> {
@@ -23,8 +50,9 @@ pub fn semantic_eval_hybrid_hits(
         part_filter,
         chunk_type_filter,
         model_id,
-        embedding_dim,
+        embedder,
         limit,
+        None,
     )?;
 
     if lexical_hits.is_empty() {
@@ -153,14 +181,16 @@ pub fn semantic_eval_lexical_hits(
     Ok(hits)
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn semantic_eval_semantic_hits(
     connection: &Connection,
     query_text: &str,
     part_filter: Option<u32>,
     chunk_type_filter: Option<&str>,
     model_id: &str,
-    embedding_dim: usize,
+    embedder: &mut dyn EmbeddingBackend,
     limit: usize,
+    requantize: Option<EmbeddingEncoding>,
 ) -> Result<Vec<SemanticRetrievedHit>> {
     let table_exists = connection
         .query_row(
@@ -180,8 +210,10 @@ pub fn semantic_eval_semantic_hits(
     }
 
     let semantic_query_text = semantic_embedding_query_text(query_text);
-    let query_embedding = crate::semantic::embed_text_local(&semantic_query_text, embedding_dim);
+    let embedding_dim = embedder.dimensions();
+    let query_embedding = embedder.embed(&semantic_query_text)?;
     let query_tokens = query_signal_tokens(&semantic_query_text);
+    let storage_encoding = load_model_storage_encoding(connection, model_id)?.unwrap_or_default();
     let mut statement = connection.prepare(
         "
         SELECT
@@ -217,7 +249,9 @@ pub fn semantic_eval_semantic_hits(
         }
 
         let blob = row.get::<_, Vec<u8>>(7)?;
-        let Some(embedding) = crate::semantic::decode_embedding_blob(&blob, embedding_dim) else {
+        let Some(embedding) =
+            decode_eval_embedding(&blob, embedding_dim, storage_encoding, requantize)
+        else {
             continue;
         };
         let semantic_score = crate::semantic::cosine_similarity(&query_embedding, &embedding);
@@ -435,68 +469,3 @@ pub fn lexical_signal_bonus(query_tokens: &[String], reference: &str, heading: &
         .count();
     overlap as f64 / query_tokens.len() as f64
 }
-
-pub fn reciprocal_rank_at_k(results: &[String], expected: &HashSet<String>, k: usize) -> Option<f64> {
-    if expected.is_empty() || k == 0 {
-        return None;
-    }
-
-    for (index, chunk_id) in results.iter().take(k).enumerate() {
-        if expected.contains(chunk_id) {
-            return Some(1.0 / (index as f64 + 1.0));
-        }
-    }
-    Some(0.0)
-}
-
-pub fn recall_at_k(results: &[String], expected: &HashSet<String>, k: usize) -> Option<f64> {
-    if expected.is_empty() || k == 0 {
-        return None;
-    }
-
-    let hit_count = results
-        .iter()
-        .take(k)
-        .filter(|chunk_id| expected.contains(*chunk_id))
-        .count();
-    Some(hit_count as f64 / expected.len() as f64)
-}
-
-pub fn judged_at_k(results: &[String], judged: &HashSet<String>, k: usize) -> Option<f64> {
-    if judged.is_empty() || k == 0 {
-        return None;
-    }
-
-    let limit = results.len().min(k);
-    if limit == 0 {
-        return Some(0.0);
-    }
-
-    let judged_in_top_k = results
-        .iter()
-        .take(limit)
-        .filter(|chunk_id| judged.contains(*chunk_id))
-        .count();
-    Some(judged_in_top_k as f64 / k as f64)
-}
-pub fn percentile(values: &[f64], quantile: f64) -> Option<f64> {
-    if values.is_empty() {
-        return None;
-    }
-
-    let mut sorted = values.to_vec();
-    sorted.sort_by(|left, right| left.total_cmp(right));
-
-    let q = quantile.clamp(0.0, 1.0);
-    let rank = ((sorted.len() as f64) * q).ceil() as usize;
-    let index = rank.saturating_sub(1).min(sorted.len().saturating_sub(1));
-    sorted.get(index).copied()
-}
-
-pub fn mean(values: &[f64]) -> Option<f64> {
-    if values.is_empty() {
-        return None;
-    }
-
-    Some(values.iter().sum::<f64>() / values.len() as f64)
-}
@@ -1,3 +1,5 @@
+use super::*;
+
 pub fn is_first_hit_intent(intent: &str) -> bool {
     let normalized = intent.trim().to_ascii_lowercase();
     matches!(
@@ -73,3 +75,73 @@ pub fn bootstrap_confidence_interval_95(
         .copied();
     Some((low, high))
 }
+
+pub fn reciprocal_rank_at_k(
+    results: &[String],
+    expected: &HashSet<String>,
+    k: usize,
+) -> Option<f64> {
+    if expected.is_empty() || k == 0 {
+        return None;
+    }
+
+    for (index, chunk_id) in results.iter().take(k).enumerate() {
+        if expected.contains(chunk_id) {
+            return Some(1.0 / (index as f64 + 1.0));
+        }
+    }
+    Some(0.0)
+}
+
+pub fn recall_at_k(results: &[String], expected: &HashSet<String>, k: usize) -> Option<f64> {
+    if expected.is_empty() || k == 0 {
+        return None;
+    }
+
+    let hit_count = results
+        .iter()
+        .take(k)
+        .filter(|chunk_id| expected.contains(*chunk_id))
+        .count();
+    Some(hit_count as f64 / expected.len() as f64)
+}
+
+pub fn judged_at_k(results: &[String], judged: &HashSet<String>, k: usize) -> Option<f64> {
+    if judged.is_empty() || k == 0 {
+        return None;
+    }
+
+    let limit = results.len().min(k);
+    if limit == 0 {
+        return Some(0.0);
+    }
+
+    let judged_in_top_k = results
+        .iter()
+        .take(limit)
+        .filter(|chunk_id| judged.contains(*chunk_id))
+        .count();
+    Some(judged_in_top_k as f64 / k as f64)
+}
+
+pub fn percentile(values: &[f64], quantile: f64) -> Option<f64> {
+    if values.is_empty() {
+        return None;
+    }
+
+    let mut sorted = values.to_vec();
+    sorted.sort_by(|left, right| left.total_cmp(right));
+
+    let q = quantile.clamp(0.0, 1.0);
+    let rank = ((sorted.len() as f64) * q).ceil() as usize;
+    let index = rank.saturating_sub(1).min(sorted.len().saturating_sub(1));
+    sorted.get(index).copied()
+}
+
+pub fn mean(values: &[f64]) -> Option<f64> {
+    if values.is_empty() {
+        return None;
+    }
+
+    Some(values.iter().sum::<f64>() / values.len() as f64)
+}
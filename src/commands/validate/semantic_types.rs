@@ -104,6 +104,10 @@ pub struct SemanticQualitySummaryReport {
     pub pinpoint_fallback_ratio: Option<f64>,
     pub pinpoint_determinism_top1: Option<f64>,
     pub pinpoint_latency_overhead_p95_ms: Option<f64>,
+    pub ann_recall_queries: usize,
+    pub ann_recall_at_10: Option<f64>,
+    pub storage_encoding: Option<String>,
+    pub quantized_index_drift: Vec<QuantizedIndexDriftReport>,
     pub baseline_path: String,
     pub baseline_mode: String,
     pub baseline_run_id: Option<String>,
@@ -113,6 +117,15 @@ pub struct SemanticQualitySummaryReport {
     pub warnings: Vec<String>,
 }
 
+#[derive(Debug, Clone, Serialize)]
+pub struct QuantizedIndexDriftReport {
+    pub encoding: String,
+    pub bytes_per_vector: usize,
+    pub queries: usize,
+    pub semantic_ndcg_at_10: Option<f64>,
+    pub ndcg_at_10_drift_vs_f32: Option<f64>,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum SemanticBaselineMode {
     Verify,
@@ -348,4 +361,6 @@ pub struct TargetSectionsManifest {
 pub struct TargetSectionReference {
     pub id: String,
     pub part: u32,
+    #[serde(default)]
+    pub year: Option<u32>,
 }
@@ -4,12 +4,13 @@ use super::*;
 #[cfg(test)]
 mod tests {
     use super::{
-        bootstrap_confidence_interval_95, ensure_citation_baseline_metadata_only,
-        fill_missing_judged_chunk_ids, parse_citation_baseline_mode, parse_citation_baseline_path,
-        parse_semantic_baseline_mode, parse_semantic_baseline_path,
-        parse_target_parts_from_command, resolve_processed_parts, sign_test_two_sided_p_value,
-        CitationBaselineMode, GoldReference, IngestRunSnapshot, SemanticBaselineMode,
-        SemanticEvalManifest, SemanticEvalQuery,
+        bootstrap_confidence_interval_95, build_freshness_report,
+        ensure_citation_baseline_metadata_only, fill_missing_judged_chunk_ids,
+        parse_citation_baseline_mode, parse_citation_baseline_path, parse_semantic_baseline_mode,
+        parse_semantic_baseline_path, parse_target_parts_from_command, resolve_processed_editions,
+        sign_test_two_sided_p_value, CitationBaselineMode, GoldReference, IngestRunSnapshot,
+        PartEdition, SemanticBaselineMode, SemanticEvalManifest, SemanticEvalQuery,
+        TargetSectionsManifest,
     };
     use rusqlite::Connection;
 
@@ -43,10 +44,68 @@ mod tests {
     }
 
     #[test]
-    fn resolve_processed_parts_falls_back_to_required_parts_when_missing() {
+    fn resolve_processed_editions_falls_back_to_required_editions_when_missing() {
+        let required = [2, 6, 8, 9].map(|part| PartEdition {
+            part,
+            year: Some(2018),
+        });
         let snapshot = IngestRunSnapshot::default();
-        let parts = resolve_processed_parts(&snapshot, &[2, 6, 8, 9]);
-        assert_eq!(parts, vec![2, 6, 8, 9]);
+        let editions = resolve_processed_editions(&snapshot, &required);
+        assert_eq!(editions, required.to_vec());
+    }
+
+    #[test]
+    fn freshness_report_keys_target_parts_by_edition() {
+        let manifest_dir = std::env::temp_dir().join(format!(
+            "iso26262_validate_freshness_{}",
+            std::process::id()
+        ));
+        std::fs::create_dir_all(&manifest_dir).expect("manifest dir should be created");
+        std::fs::write(
+            manifest_dir.join("ingest_run_20260101T000000Z.json"),
+            r#"{"run_id": "run-both", "processed_parts": [6],
+                "processed_editions": [{"part": 6, "year": 2011}, {"part": 6, "year": 2018}]}"#,
+        )
+        .expect("first snapshot should be written");
+        std::fs::write(
+            manifest_dir.join("ingest_run_20260102T000000Z.json"),
+            r#"{"run_id": "run-2011", "processed_parts": [6],
+                "processed_editions": [{"part": 6, "year": 2011}]}"#,
+        )
+        .expect("second snapshot should be written");
+        let target_sections: TargetSectionsManifest = serde_json::from_str(
+            r#"{"targets": [
+                {"id": "T-2011", "part": 6, "year": 2011},
+                {"id": "T-2018", "part": 6, "year": 2018}
+            ]}"#,
+        )
+        .expect("target sections should deserialize");
+
+        let report = build_freshness_report(&manifest_dir, &Some(target_sections))
+            .expect("freshness report should build");
+        std::fs::remove_dir_all(&manifest_dir).expect("manifest dir should be removed");
+
+        assert_eq!(report.required_editions.len(), 2);
+        assert_eq!(
+            report.stale_editions,
+            vec![PartEdition {
+                part: 6,
+                year: Some(2018),
+            }]
+        );
+        let latest_runs = report
+            .latest_run_by_edition
+            .iter()
+            .map(|entry| (entry.year, entry.run_id.as_deref()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            latest_runs,
+            vec![
+                (Some(2011), Some("run-2011")),
+                (Some(2018), Some("run-both")),
+            ]
+        );
+        assert_eq!(report.full_target_cycle_run_id.as_deref(), Some("run-both"));
     }
 
     #[test]
@@ -0,0 +1,282 @@
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+use rusqlite::{Connection, OptionalExtension, Row};
+use serde::Serialize;
+
+use crate::cli::{QueryGranularity, RetrievalMode};
+use crate::commands::query::{
+    CitationSource, LookupKind, execute_lookup, execute_query, open_query_connection,
+    query_args_from_params, render_anchor_citation,
+};
+
+const NODE_SELECT: &str = "
+    SELECT
+      n.node_id,
+      n.parent_node_id,
+      n.doc_id,
+      d.part,
+      d.year,
+      n.node_type,
+      n.ref,
+      n.heading,
+      n.order_index,
+      n.page_pdf_start,
+      n.page_pdf_end,
+      n.text,
+      n.anchor_type,
+      n.anchor_label_norm,
+      n.citation_anchor_id
+    FROM nodes n
+    JOIN docs d ON d.doc_id = n.doc_id
+    ";
+
+/// Read-only handle on an ingested index database.
+pub struct Index {
+    connection: Connection,
+    db_path: PathBuf,
+}
+
+#[derive(Debug, Clone)]
+pub struct SearchRequest {
+    pub query: String,
+    pub limit: usize,
+    pub retrieval_mode: RetrievalMode,
+    pub granularity: QueryGranularity,
+    pub part: Option<u32>,
+    pub edition: Option<u32>,
+    pub chunk_type: Option<String>,
+    pub node_type: Option<String>,
+    pub semantic_model_id: Option<String>,
+    pub allow_lexical_fallback: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SearchResults {
+    pub query: String,
+    pub effective_mode: String,
+    pub fallback_reason: Option<String>,
+    pub hits: Vec<SearchHit>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SearchHit {
+    pub rank: usize,
+    pub score: f64,
+    pub match_kind: String,
+    /// Chunk id, or `node::{node_id}` for node-granularity hits.
+    pub chunk_id: String,
+    pub origin_node_id: Option<String>,
+    pub doc_id: String,
+    pub chunk_type: String,
+    pub heading: String,
+    pub snippet: String,
+    pub citation: Citation,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Node {
+    pub node_id: String,
+    pub parent_node_id: Option<String>,
+    pub doc_id: String,
+    pub node_type: String,
+    pub heading: Option<String>,
+    pub order_index: i64,
+    pub text: Option<String>,
+    pub citation: Citation,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Citation {
+    /// Rendered citation, e.g. `ISO 26262-6:2018, 8.4.5(a), PDF pages 24`.
+    pub text: String,
+    pub citation_anchor_id: Option<String>,
+    pub part: u32,
+    pub year: u32,
+    pub reference: String,
+    pub page_pdf_start: Option<i64>,
+    pub page_pdf_end: Option<i64>,
+}
+
+impl fmt::Display for Citation {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str(&self.text)
+    }
+}
+
+impl SearchRequest {
+    pub fn new(query: impl Into<String>) -> Self {
+        Self {
+            query: query.into(),
+            limit: 10,
+            retrieval_mode: RetrievalMode::Lexical,
+            granularity: QueryGranularity::Chunk,
+            part: None,
+            edition: None,
+            chunk_type: None,
+            node_type: None,
+            semantic_model_id: None,
+            allow_lexical_fallback: false,
+        }
+    }
+
+    pub fn limit(mut self, limit: usize) -> Self {
+        self.limit = limit;
+        self
+    }
+
+    pub fn retrieval_mode(mut self, retrieval_mode: RetrievalMode) -> Self {
+        self.retrieval_mode = retrieval_mode;
+        self
+    }
+
+    pub fn granularity(mut self, granularity: QueryGranularity) -> Self {
+        self.granularity = granularity;
+        self
+    }
+
+    pub fn part(mut self, part: u32) -> Self {
+        self.part = Some(part);
+        self
+    }
+
+    pub fn edition(mut self, edition: u32) -> Self {
+        self.edition = Some(edition);
+        self
+    }
+
+    pub fn chunk_type(mut self, chunk_type: impl Into<String>) -> Self {
+        self.chunk_type = Some(chunk_type.into());
+        self
+    }
+
+    pub fn node_type(mut self, node_type: impl Into<String>) -> Self {
+        self.node_type = Some(node_type.into());
+        self
+    }
+
+    pub fn semantic_model_id(mut self, model_id: impl Into<String>) -> Self {
+        self.semantic_model_id = Some(model_id.into());
+        self
+    }
+
+    pub fn allow_lexical_fallback(mut self, allow: bool) -> Self {
+        self.allow_lexical_fallback = allow;
+        self
+    }
+}
+
+impl Index {
+    pub fn open(db_path: impl AsRef<Path>) -> Result<Self> {
+        let db_path = db_path.as_ref().to_path_buf();
+        let connection = open_query_connection(&db_path)?;
+        Ok(Self {
+            connection,
+            db_path,
+        })
+    }
+
+    /// Opens `iso26262_index.sqlite` under a cache root, as the CLI does by default.
+    pub fn open_cache_root(cache_root: impl AsRef<Path>) -> Result<Self> {
+        Self::open(cache_root.as_ref().join("iso26262_index.sqlite"))
+    }
+
+    pub fn db_path(&self) -> &Path {
+        &self.db_path
+    }
+
+    pub fn search(&self, request: &SearchRequest) -> Result<SearchResults> {
+        let mut args =
+            query_args_from_params(None, &[("query".to_string(), request.query.clone())])?;
+        args.limit = request.limit;
+        args.retrieval_mode = request.retrieval_mode;
+        args.granularity = request.granularity;
+        args.part = request.part;
+        args.edition = request.edition;
+        args.chunk_type = request.chunk_type.clone();
+        args.node_type = request.node_type.clone();
+        args.semantic_model_id = request.semantic_model_id.clone();
+        args.allow_lexical_fallback = request.allow_lexical_fallback;
+
+        Ok(execute_query(&self.connection, &args)?.into_search_results())
+    }
+
+    pub fn chunk(&self, chunk_id: &str) -> Result<Option<SearchHit>> {
+        self.lookup(LookupKind::Chunk, chunk_id)
+            .map(|hits| hits.into_iter().next())
+    }
+
+    pub fn node(&self, node_id: &str) -> Result<Option<Node>> {
+        let node = self
+            .connection
+            .query_row(
+                &format!("{NODE_SELECT} WHERE n.node_id = ?1"),
+                [node_id],
+                node_from_row,
+            )
+            .optional()?;
+        Ok(node)
+    }
+
+    pub fn children(&self, node_id: &str) -> Result<Vec<Node>> {
+        let mut statement = self.connection.prepare_cached(&format!(
+            "{NODE_SELECT} WHERE n.parent_node_id = ?1 ORDER BY n.order_index, n.node_id"
+        ))?;
+        let children = statement
+            .query_map([node_id], node_from_row)?
+            .collect::<rusqlite::Result<Vec<Node>>>()?;
+        Ok(children)
+    }
+
+    /// Resolves a `citation_anchor_id`, preferring the chunk that carries it over its node.
+    pub fn resolve_citation(&self, citation_anchor_id: &str) -> Result<Option<Citation>> {
+        let hits = self.lookup(LookupKind::Citation, citation_anchor_id)?;
+        Ok(hits.into_iter().next().map(|hit| hit.citation))
+    }
+
+    fn lookup(&self, kind: LookupKind, key: &str) -> Result<Vec<SearchHit>> {
+        let args = query_args_from_params(Some(key), &[])?;
+        Ok(execute_lookup(&self.connection, kind, &args)?
+            .into_search_results()
+            .hits)
+    }
+}
+
+fn node_from_row(row: &Row<'_>) -> rusqlite::Result<Node> {
+    let reference = row.get::<_, Option<String>>(6)?.unwrap_or_default();
+    let anchor_type: Option<String> = row.get(12)?;
+    let anchor_label_norm: Option<String> = row.get(13)?;
+    let part: u32 = row.get(3)?;
+    let year: u32 = row.get(4)?;
+    let page_pdf_start: Option<i64> = row.get(9)?;
+    let page_pdf_end: Option<i64> = row.get(10)?;
+
+    let text = render_anchor_citation(&CitationSource {
+        part,
+        year,
+        reference: &reference,
+        anchor_type: anchor_type.as_deref(),
+        anchor_label_norm: anchor_label_norm.as_deref(),
+        page_pdf_start,
+        page_pdf_end,
+    });
+    Ok(Node {
+        node_id: row.get(0)?,
+        parent_node_id: row.get(1)?,
+        doc_id: row.get(2)?,
+        node_type: row.get(5)?,
+        heading: row.get(7)?,
+        order_index: row.get::<_, Option<i64>>(8)?.unwrap_or_default(),
+        text: row.get(11)?,
+        citation: Citation {
+            text,
+            citation_anchor_id: row.get(14)?,
+            part,
+            year,
+            reference,
+            page_pdf_start,
+            page_pdf_end,
+        },
+    })
+}
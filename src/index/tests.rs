@@ -0,0 +1,111 @@
+use std::path::PathBuf;
+
+use crate::cli::QueryGranularity;
+
+use super::{Index, SearchRequest};
+
+fn fixture_db_path() -> PathBuf {
+    let db_path =
+        std::env::temp_dir().join(format!("iso26262_index_api_{}.sqlite", std::process::id()));
+    crate::commands::ingest::open_fixture_database(
+        Some(&db_path),
+        "
+        INSERT INTO docs(doc_id, filename, sha256, part, year, title)
+        VALUES ('ISO26262-6-2018', 'part6.pdf', 'sha', 6, 2018, 'Part 6');
+        INSERT INTO nodes(
+          node_id, parent_node_id, doc_id, node_type, ref, heading, order_index,
+          page_pdf_start, page_pdf_end, text, anchor_type, anchor_label_norm,
+          citation_anchor_id
+        ) VALUES
+          ('n-8.4.1', NULL, 'ISO26262-6-2018', 'clause', '8.4.1',
+           '8.4.1 Software unit verification', 1, 24, 24, NULL, NULL, NULL, 'anchor-8.4.1'),
+          ('n-8.4.1-b', 'n-8.4.1', 'ISO26262-6-2018', 'list_item', '8.4.1', NULL, 3, 24, 24,
+           'inspection of the software unit design', 'marker', 'b', 'anchor-8.4.1-b'),
+          ('n-8.4.1-a', 'n-8.4.1', 'ISO26262-6-2018', 'list_item', '8.4.1', NULL, 2, 24, 24,
+           'walk-through of the software unit design', 'marker', 'a', 'anchor-8.4.1-a');
+        INSERT INTO chunks(
+          chunk_id, doc_id, type, ref, heading, chunk_seq, page_pdf_start, page_pdf_end,
+          text, source_hash, origin_node_id, leaf_node_type, citation_anchor_id
+        ) VALUES (
+          'ISO26262-6-2018:8.4.1', 'ISO26262-6-2018', 'clause', '8.4.1',
+          '8.4.1 Software unit verification', 0, 24, 24,
+          'The software unit design shall be verified by walk-through or inspection.',
+          'hash', 'n-8.4.1', 'clause', 'anchor-8.4.1'
+        );
+        ",
+    );
+    db_path
+}
+
+#[test]
+fn index_returns_typed_hits_nodes_and_citations() {
+    let db_path = fixture_db_path();
+    let index = Index::open(&db_path).expect("index should open read-only");
+
+    let results = index
+        .search(&SearchRequest::new("software unit design").part(6).limit(3))
+        .expect("search should succeed");
+    assert_eq!(results.effective_mode, "lexical");
+    let hit = &results.hits[0];
+    assert_eq!(hit.chunk_id, "ISO26262-6-2018:8.4.1");
+    assert_eq!(hit.origin_node_id.as_deref(), Some("n-8.4.1"));
+    assert_eq!(
+        hit.citation.to_string(),
+        "ISO 26262-6:2018, 8.4.1, PDF pages 24"
+    );
+    assert_eq!(
+        hit.citation.citation_anchor_id.as_deref(),
+        Some("anchor-8.4.1")
+    );
+
+    let node_hits = index
+        .search(&SearchRequest::new("inspection").granularity(QueryGranularity::Node))
+        .expect("node search should succeed");
+    assert_eq!(node_hits.hits[0].chunk_id, "node::n-8.4.1-b");
+
+    let clause = index
+        .node("n-8.4.1")
+        .expect("node lookup should succeed")
+        .expect("clause node should exist");
+    assert_eq!(clause.citation.reference, "8.4.1");
+    let children = index
+        .children(&clause.node_id)
+        .expect("children lookup should succeed");
+    assert_eq!(
+        children
+            .iter()
+            .map(|node| node.citation.text.as_str())
+            .collect::<Vec<&str>>(),
+        vec![
+            "ISO 26262-6:2018, 8.4.1(a), PDF pages 24",
+            "ISO 26262-6:2018, 8.4.1(b), PDF pages 24",
+        ]
+    );
+    assert!(
+        index
+            .node("missing")
+            .expect("lookup should succeed")
+            .is_none()
+    );
+
+    let chunk = index
+        .chunk("ISO26262-6-2018:8.4.1")
+        .expect("chunk lookup should succeed")
+        .expect("chunk should exist");
+    assert_eq!(chunk.heading, "8.4.1 Software unit verification");
+
+    let citation = index
+        .resolve_citation("anchor-8.4.1-a")
+        .expect("citation lookup should succeed")
+        .expect("node anchor should resolve");
+    assert_eq!(citation.text, "ISO 26262-6:2018, 8.4.1(a), PDF pages 24");
+    assert!(
+        index
+            .resolve_citation("anchor-missing")
+            .expect("citation lookup should succeed")
+            .is_none()
+    );
+
+    drop(index);
+    let _ = std::fs::remove_file(&db_path);
+}
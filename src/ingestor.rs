@@ -0,0 +1,115 @@
+use std::path::PathBuf;
+
+use anyhow::Result;
+
+use crate::cli::{ExtractBackend, IngestArgs, OcrMode};
+use crate::commands::ingest::execute_ingest;
+use crate::model::IngestRunManifest;
+
+#[cfg(test)]
+mod tests;
+
+/// Builder for an ingest run; defaults match `iso26262 ingest`.
+#[derive(Debug, Clone)]
+pub struct Ingestor {
+    args: IngestArgs,
+}
+
+impl Ingestor {
+    pub fn new(cache_root: impl Into<PathBuf>) -> Self {
+        Self {
+            args: IngestArgs {
+                cache_root: cache_root.into(),
+                inventory_manifest_path: None,
+                ingest_manifest_path: None,
+                db_path: None,
+                refresh_inventory: false,
+                seed_page_chunks: false,
+                force: false,
+                jobs: None,
+                target_parts: Vec::new(),
+                target_years: Vec::new(),
+                max_pages_per_doc: None,
+                extract_backend: ExtractBackend::Text,
+                ocr_mode: OcrMode::Off,
+                ocr_lang: "eng".to_string(),
+                ocr_min_text_chars: 120,
+            },
+        }
+    }
+
+    pub fn db_path(mut self, db_path: impl Into<PathBuf>) -> Self {
+        self.args.db_path = Some(db_path.into());
+        self
+    }
+
+    pub fn inventory_manifest_path(mut self, path: impl Into<PathBuf>) -> Self {
+        self.args.inventory_manifest_path = Some(path.into());
+        self
+    }
+
+    pub fn ingest_manifest_path(mut self, path: impl Into<PathBuf>) -> Self {
+        self.args.ingest_manifest_path = Some(path.into());
+        self
+    }
+
+    pub fn refresh_inventory(mut self, refresh: bool) -> Self {
+        self.args.refresh_inventory = refresh;
+        self
+    }
+
+    pub fn seed_page_chunks(mut self, seed: bool) -> Self {
+        self.args.seed_page_chunks = seed;
+        self
+    }
+
+    pub fn force(mut self, force: bool) -> Self {
+        self.args.force = force;
+        self
+    }
+
+    pub fn jobs(mut self, jobs: usize) -> Self {
+        self.args.jobs = Some(jobs);
+        self
+    }
+
+    pub fn target_parts(mut self, parts: impl IntoIterator<Item = u32>) -> Self {
+        self.args.target_parts = parts.into_iter().collect();
+        self
+    }
+
+    pub fn target_years(mut self, years: impl IntoIterator<Item = u32>) -> Self {
+        self.args.target_years = years.into_iter().collect();
+        self
+    }
+
+    pub fn max_pages_per_doc(mut self, max_pages: usize) -> Self {
+        self.args.max_pages_per_doc = Some(max_pages);
+        self
+    }
+
+    pub fn extract_backend(mut self, backend: ExtractBackend) -> Self {
+        self.args.extract_backend = backend;
+        self
+    }
+
+    pub fn ocr_mode(mut self, mode: OcrMode) -> Self {
+        self.args.ocr_mode = mode;
+        self
+    }
+
+    pub fn ocr_lang(mut self, lang: impl Into<String>) -> Self {
+        self.args.ocr_lang = lang.into();
+        self
+    }
+
+    pub fn ocr_min_text_chars(mut self, min_chars: usize) -> Self {
+        self.args.ocr_min_text_chars = min_chars;
+        self
+    }
+
+    /// Runs the ingest and returns the manifest it also writes under `manifests/`.
+    pub fn run(&self) -> Result<IngestRunManifest> {
+        execute_ingest(&self.args)
+    }
+}
@@ -0,0 +1,21 @@
+use clap::{Args, Command, FromArgMatches};
+
+use crate::cli::IngestArgs;
+
+use super::Ingestor;
+
+#[test]
+fn ingestor_defaults_match_ingest_command_defaults() {
+    let matches = IngestArgs::augment_args(Command::new("ingest").no_binary_name(true))
+        .try_get_matches_from(["--cache-root", "/tmp/iso26262-cache"])
+        .expect("default ingest arguments should parse");
+    let cli_defaults = IngestArgs::from_arg_matches(&matches).expect("ingest args should build");
+
+    let ingestor = Ingestor::new("/tmp/iso26262-cache");
+    assert_eq!(format!("{:?}", ingestor.args), format!("{cli_defaults:?}"));
+
+    let configured = ingestor.target_parts([6, 8]).jobs(2).force(true);
+    assert_eq!(configured.args.target_parts, vec![6, 8]);
+    assert_eq!(configured.args.jobs, Some(2));
+    assert!(configured.args.force);
+}
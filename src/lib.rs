@@ -0,0 +1,40 @@
+//! Local ISO 26262 extraction index.
+//!
+//! [`Index`] opens an ingested SQLite index read-only and answers searches, node lookups and
+//! citation resolution with typed results. [`Ingestor`] builds or refreshes that index from the
+//! cached PDFs. The `iso26262` binary is a thin command-line wrapper over [`run`].
+
+pub mod cli;
+mod commands;
+mod index;
+mod ingestor;
+pub mod model;
+mod semantic;
+mod util;
+
+use anyhow::Result;
+
+pub use cli::{ExtractBackend, OcrMode, QueryGranularity, RetrievalMode};
+pub use index::{Citation, Index, Node, SearchHit, SearchRequest, SearchResults};
+pub use ingestor::Ingestor;
+pub use model::{IngestCounts, IngestRunManifest};
+
+pub fn run(command: cli::Commands) -> Result<()> {
+    use cli::Commands;
+
+    match command {
+        Commands::Inventory(args) => commands::inventory::run(args),
+        Commands::Ingest(args) => commands::ingest::run(args),
+        Commands::Embed(args) => commands::embed::run(args),
+        Commands::Query(args) => commands::query::run(args),
+        Commands::Status(args) => commands::status::run(args),
+        Commands::Validate(args) => commands::validate::run(args),
+        Commands::Export(args) => commands::export::run(args),
+        Commands::Define(args) => commands::define::run(args),
+        Commands::DiffEditions(args) => commands::diff_editions::run(args),
+        Commands::Refs(args) => commands::refs::run(args),
+        Commands::Serve(args) => commands::serve::run(args),
+        Commands::Mcp(args) => commands::mcp::run(args),
+        Commands::Shell(args) => commands::shell::run(args),
+    }
+}
@@ -1,20 +1,14 @@
-mod cli;
-mod commands;
-mod model;
-mod semantic;
-mod util;
-
-use anyhow::Result;
 use clap::Parser;
 use tracing::error;
 use tracing_subscriber::EnvFilter;
 
-use crate::cli::{Cli, Commands};
+use iso26262::cli::Cli;
 
 fn main() {
     init_tracing();
 
-    if let Err(err) = run() {
+    let cli = Cli::parse();
+    if let Err(err) = iso26262::run(cli.command) {
         error!(error = %err, "command failed");
         for cause in err.chain().skip(1) {
             error!(cause = %cause, "caused by");
@@ -23,19 +17,6 @@ fn main() {
     }
 }
 
-fn run() -> Result<()> {
-    let cli = Cli::parse();
-
-    match cli.command {
-        Commands::Inventory(args) => commands::inventory::run(args),
-        Commands::Ingest(args) => commands::ingest::run(args),
-        Commands::Embed(args) => commands::embed::run(args),
-        Commands::Query(args) => commands::query::run(args),
-        Commands::Status(args) => commands::status::run(args),
-        Commands::Validate(args) => commands::validate::run(args),
-    }
-}
-
 fn init_tracing() {
     let env_filter = EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("info"));
 
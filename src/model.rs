@@ -65,12 +65,14 @@ pub struct IngestPaths {
     pub inventory_manifest_path: String,
     pub db_path: String,
     pub page_provenance_path: String,
+    pub cross_reference_diagnostics_path: String,
 }
 
 #[derive(Debug, Clone, Serialize)]
 pub struct IngestCounts {
     pub pdf_count: usize,
     pub processed_pdf_count: usize,
+    pub skipped_unchanged_pdf_count: usize,
     pub text_layer_page_count: usize,
     pub ocr_fallback_page_count: usize,
     pub empty_page_count: usize,
@@ -84,6 +86,7 @@ pub struct IngestCounts {
     pub structured_chunks_inserted: usize,
     pub clause_chunks_inserted: usize,
     pub table_chunks_inserted: usize,
+    pub figure_chunks_inserted: usize,
     pub annex_chunks_inserted: usize,
     pub page_chunks_inserted: usize,
     pub clause_nodes_inserted: usize,
@@ -92,6 +95,9 @@ pub struct IngestCounts {
     pub table_nodes_inserted: usize,
     pub table_row_nodes_inserted: usize,
     pub table_cell_nodes_inserted: usize,
+    pub table_footnote_nodes_inserted: usize,
+    pub table_footnote_links_inserted: usize,
+    pub figure_nodes_inserted: usize,
     pub list_nodes_inserted: usize,
     pub list_item_nodes_inserted: usize,
     pub note_nodes_inserted: usize,
@@ -107,9 +113,30 @@ pub struct IngestCounts {
     pub table_rows_with_descriptions_count: usize,
     pub table_marker_expected_count: usize,
     pub table_marker_observed_count: usize,
+    pub table_continuations_merged_count: usize,
+    pub table_repeated_header_rows_dropped_count: usize,
+    pub asil_recommendations_inserted: usize,
+    pub asil_matrix_rows_incomplete_count: usize,
+    pub node_references_inserted: usize,
+    pub node_references_resolved: usize,
+    pub node_references_unresolved: usize,
+    pub term_nodes_inserted: usize,
+    pub term_occurrences_linked: usize,
+    pub work_products_inserted: usize,
+    pub work_product_sources_inserted: usize,
+    pub work_product_sources_unresolved: usize,
+    pub node_regions_inserted: usize,
+    pub external_references_inserted: usize,
+    pub external_reference_citations_inserted: usize,
     pub ocr_page_count: usize,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct ProcessedEdition {
+    pub part: u32,
+    pub year: u32,
+}
+
 #[derive(Debug, Clone, Serialize)]
 pub struct IngestRunManifest {
     pub manifest_version: u32,
@@ -126,6 +153,7 @@ pub struct IngestRunManifest {
     pub tool_versions: ToolVersions,
     pub paths: IngestPaths,
     pub processed_parts: Vec<u32>,
+    pub processed_editions: Vec<ProcessedEdition>,
     pub counts: IngestCounts,
     pub source_hashes: Vec<PdfEntry>,
     pub warnings: Vec<String>,
@@ -1,49 +1,241 @@
 use std::collections::hash_map::DefaultHasher;
 use std::hash::{Hash, Hasher};
+use std::path::Path;
 
+use anyhow::{Context, Result, bail};
+use rusqlite::{Connection, OptionalExtension};
 use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
 
+use crate::util::sha256_file;
+
+pub mod ann;
+mod encoding;
+#[cfg(feature = "onnx")]
+mod onnx;
+
+pub use encoding::{
+    EmbeddingEncoding, decode_embedding_blob, encode_embedding_blob, load_model_storage_encoding,
+    quantize_embedding,
+};
+#[cfg(feature = "onnx")]
+pub use onnx::OnnxEmbeddingBackend;
+
+#[cfg(test)]
+mod tests;
+
 pub const DEFAULT_MODEL_ID: &str = "miniLM-L6-v2-local-v1";
 pub const DEFAULT_MODEL_NAME: &str = "sentence-transformers/all-MiniLM-L6-v2";
 pub const DEFAULT_EMBEDDING_DIM: usize = 384;
 pub const DEFAULT_NORMALIZATION: &str = "l2";
 pub const DEFAULT_BACKEND: &str = "local-hash-v1";
-
-#[derive(Debug, Clone, Serialize, Deserialize)]
+pub const ONNX_BACKEND: &str = "onnx-cpu-v1";
+pub const ONNX_MODEL_FILES: [&str; 2] = ["model.onnx", "onnx/model.onnx"];
+pub const ONNX_TOKENIZER_FILE: &str = "tokenizer.json";
+pub const NODE_EMBEDDING_TYPES: [&str; 5] = [
+    "paragraph",
+    "list_item",
+    "note",
+    "note_item",
+    "requirement_atom",
+];
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct SemanticModelConfig {
     pub model_id: String,
     pub model_name: String,
     pub dimensions: usize,
     pub normalization: String,
     pub backend: String,
+    #[serde(default)]
+    pub model_path: Option<String>,
+    #[serde(default)]
+    pub model_sha256: Option<String>,
+    #[serde(default)]
+    pub tokenizer_path: Option<String>,
+    #[serde(default)]
+    pub tokenizer_sha256: Option<String>,
+}
+
+impl SemanticModelConfig {
+    pub fn same_embedding_space(&self, other: &SemanticModelConfig) -> bool {
+        self.backend == other.backend
+            && self.dimensions == other.dimensions
+            && self.model_sha256 == other.model_sha256
+            && self.tokenizer_sha256 == other.tokenizer_sha256
+    }
+}
+
+pub trait EmbeddingBackend {
+    fn dimensions(&self) -> usize;
+
+    fn embed(&mut self, payload: &str) -> Result<Vec<f32>>;
+}
+
+pub struct HashEmbeddingBackend {
+    dimensions: usize,
+}
+
+impl HashEmbeddingBackend {
+    pub fn new(dimensions: usize) -> Self {
+        Self { dimensions }
+    }
 }
 
-pub fn resolve_model_config(model_id: &str) -> SemanticModelConfig {
+impl EmbeddingBackend for HashEmbeddingBackend {
+    fn dimensions(&self) -> usize {
+        self.dimensions
+    }
+
+    fn embed(&mut self, payload: &str) -> Result<Vec<f32>> {
+        Ok(embed_text_local(payload, self.dimensions))
+    }
+}
+
+pub fn resolve_model_config(
+    model_id: &str,
+    model_dir: Option<&Path>,
+) -> Result<SemanticModelConfig> {
     let trimmed = model_id.trim();
     let resolved_id = if trimmed.is_empty() {
         DEFAULT_MODEL_ID
     } else {
         trimmed
     };
+    let model_name = if resolved_id == DEFAULT_MODEL_ID {
+        DEFAULT_MODEL_NAME
+    } else {
+        resolved_id
+    };
 
-    if resolved_id == DEFAULT_MODEL_ID {
-        return SemanticModelConfig {
-            model_id: DEFAULT_MODEL_ID.to_string(),
-            model_name: DEFAULT_MODEL_NAME.to_string(),
-            dimensions: DEFAULT_EMBEDDING_DIM,
-            normalization: DEFAULT_NORMALIZATION.to_string(),
-            backend: DEFAULT_BACKEND.to_string(),
-        };
-    }
-
-    SemanticModelConfig {
+    let mut config = SemanticModelConfig {
         model_id: resolved_id.to_string(),
-        model_name: resolved_id.to_string(),
+        model_name: model_name.to_string(),
         dimensions: DEFAULT_EMBEDDING_DIM,
         normalization: DEFAULT_NORMALIZATION.to_string(),
         backend: DEFAULT_BACKEND.to_string(),
+        model_path: None,
+        model_sha256: None,
+        tokenizer_path: None,
+        tokenizer_sha256: None,
+    };
+
+    if let Some(model_dir) = model_dir {
+        let model_path = ONNX_MODEL_FILES
+            .iter()
+            .map(|file| model_dir.join(file))
+            .find(|path| path.is_file())
+            .with_context(|| {
+                format!(
+                    "no ONNX model file ({}) found in {}",
+                    ONNX_MODEL_FILES.join(" or "),
+                    model_dir.display()
+                )
+            })?;
+        let tokenizer_path = model_dir.join(ONNX_TOKENIZER_FILE);
+        if !tokenizer_path.is_file() {
+            bail!(
+                "tokenizer file {} not found in {}",
+                ONNX_TOKENIZER_FILE,
+                model_dir.display()
+            );
+        }
+
+        config.backend = ONNX_BACKEND.to_string();
+        config.model_sha256 = Some(sha256_file(&model_path)?);
+        config.tokenizer_sha256 = Some(sha256_file(&tokenizer_path)?);
+        config.model_path = Some(model_path.display().to_string());
+        config.tokenizer_path = Some(tokenizer_path.display().to_string());
     }
+
+    Ok(config)
+}
+
+pub fn load_embedding_backend(model: &SemanticModelConfig) -> Result<Box<dyn EmbeddingBackend>> {
+    match model.backend.as_str() {
+        DEFAULT_BACKEND => Ok(Box::new(HashEmbeddingBackend::new(model.dimensions))),
+        ONNX_BACKEND => load_onnx_backend(model),
+        other => bail!(
+            "embedding backend '{}' for model '{}' is not supported",
+            other,
+            model.model_id
+        ),
+    }
+}
+
+#[cfg(feature = "onnx")]
+fn load_onnx_backend(model: &SemanticModelConfig) -> Result<Box<dyn EmbeddingBackend>> {
+    let (Some(model_path), Some(tokenizer_path)) = (&model.model_path, &model.tokenizer_path)
+    else {
+        bail!(
+            "model '{}' uses the {} backend but has no model or tokenizer path recorded",
+            model.model_id,
+            ONNX_BACKEND
+        );
+    };
+    let model_path = Path::new(model_path);
+    let tokenizer_path = Path::new(tokenizer_path);
+    for (path, expected) in [
+        (model_path, &model.model_sha256),
+        (tokenizer_path, &model.tokenizer_sha256),
+    ] {
+        if let Some(expected) = expected
+            && sha256_file(path)? != *expected
+        {
+            bail!(
+                "{} changed since model '{}' was embedded; re-run embed",
+                path.display(),
+                model.model_id
+            );
+        }
+    }
+
+    Ok(Box::new(OnnxEmbeddingBackend::load(
+        model_path,
+        tokenizer_path,
+    )?))
+}
+
+#[cfg(not(feature = "onnx"))]
+fn load_onnx_backend(model: &SemanticModelConfig) -> Result<Box<dyn EmbeddingBackend>> {
+    bail!(
+        "model '{}' requires the {} backend; rebuild with `--features onnx`",
+        model.model_id,
+        ONNX_BACKEND
+    )
+}
+
+pub fn load_registered_model_config(
+    connection: &Connection,
+    model_id: &str,
+) -> Result<Option<SemanticModelConfig>> {
+    let table_exists = connection
+        .query_row(
+            "SELECT 1 FROM sqlite_master WHERE type = 'table' AND name = 'embedding_models' LIMIT 1",
+            [],
+            |row| row.get::<_, i64>(0),
+        )
+        .optional()?
+        .is_some();
+    if !table_exists {
+        return Ok(None);
+    }
+
+    let config_json = connection
+        .query_row(
+            "SELECT config_json FROM embedding_models WHERE model_id = ?1 LIMIT 1",
+            [model_id],
+            |row| row.get::<_, Option<String>>(0),
+        )
+        .optional()?
+        .flatten();
+
+    let Some(config_json) = config_json else {
+        return Ok(None);
+    };
+    let config = serde_json::from_str::<SemanticModelConfig>(&config_json)
+        .with_context(|| format!("failed to parse embedding model config for '{model_id}'"))?;
+    Ok(Some(config))
 }
 
 pub fn normalize_whitespace(input: &str) -> String {
@@ -92,6 +284,53 @@ pub fn chunk_payload_for_embedding(
     }
 }
 
+pub fn node_payload_for_embedding(
+    node_type: &str,
+    reference: &str,
+    text: Option<&str>,
+) -> Option<String> {
+    let node_type_norm = node_type.trim().to_ascii_lowercase();
+    if !NODE_EMBEDDING_TYPES.contains(&node_type_norm.as_str()) {
+        return None;
+    }
+
+    let body_norm = text.map(normalize_whitespace).unwrap_or_default();
+    if body_norm.is_empty() {
+        return None;
+    }
+
+    let reference_norm = normalize_whitespace(reference);
+    if reference_norm.is_empty() {
+        Some(body_norm)
+    } else {
+        Some(format!("{reference_norm}\n\n{body_norm}"))
+    }
+}
+
+pub fn resolve_parent_chunk_id(connection: &Connection, node_id: &str) -> Result<Option<String>> {
+    let chunk_id = connection
+        .prepare_cached(
+            "
+            WITH RECURSIVE lineage(node_id, depth) AS (
+              SELECT ?1, 0
+              UNION ALL
+              SELECT n.parent_node_id, l.depth + 1
+              FROM lineage l
+              JOIN nodes n ON n.node_id = l.node_id
+              WHERE n.parent_node_id IS NOT NULL AND l.depth < 32
+            )
+            SELECT c.chunk_id
+            FROM lineage l
+            JOIN chunks c ON c.origin_node_id = l.node_id
+            ORDER BY l.depth ASC, c.chunk_seq ASC, c.chunk_id ASC
+            LIMIT 1
+            ",
+        )?
+        .query_row([node_id], |row| row.get::<_, String>(0))
+        .optional()?;
+    Ok(chunk_id)
+}
+
 pub fn embedding_text_hash(payload: &str) -> String {
     let mut hasher = Sha256::new();
     hasher.update(payload.as_bytes());
@@ -130,31 +369,6 @@ pub fn cosine_similarity(left: &[f32], right: &[f32]) -> f64 {
         .sum::<f64>()
 }
 
-pub fn encode_embedding_blob(values: &[f32]) -> Vec<u8> {
-    let mut out = Vec::<u8>::with_capacity(values.len() * 4);
-    for value in values {
-        out.extend_from_slice(&value.to_le_bytes());
-    }
-    out
-}
-
-pub fn decode_embedding_blob(blob: &[u8], expected_dim: usize) -> Option<Vec<f32>> {
-    if expected_dim == 0 || blob.len() != expected_dim.saturating_mul(4) {
-        return None;
-    }
-
-    let mut out = Vec::<f32>::with_capacity(expected_dim);
-    for chunk in blob.chunks_exact(4) {
-        out.push(f32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]));
-    }
-
-    if out.len() == expected_dim {
-        Some(out)
-    } else {
-        None
-    }
-}
-
 fn stable_hash(value: &str) -> u64 {
     let mut hasher = DefaultHasher::new();
     value.hash(&mut hasher);
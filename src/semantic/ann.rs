@@ -0,0 +1,386 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::SystemTime;
+
+use anyhow::{Context, Result};
+use rusqlite::{Connection, OptionalExtension, params};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::util::now_utc_string;
+
+use super::{cosine_similarity, decode_embedding_blob, load_model_storage_encoding};
+
+pub const ANN_INDEX_FORMAT: &str = "ivf-flat-v1";
+pub const ANN_KMEANS_ITERATIONS: usize = 8;
+pub const ANN_MAX_LISTS: usize = 4096;
+pub const ANN_MIN_PROBES: usize = 4;
+pub const ANN_CANDIDATE_MULTIPLIER: usize = 4;
+
+static ANN_INDEX_CACHE: Mutex<Vec<CachedAnnIndex>> = Mutex::new(Vec::new());
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct IvfIndex {
+    pub format: String,
+    pub model_id: String,
+    pub dimensions: usize,
+    pub vector_count: usize,
+    pub centroids: Vec<Vec<f32>>,
+    pub lists: Vec<Vec<String>>,
+}
+
+#[derive(Debug, Clone)]
+pub struct AnnIndexRecord {
+    pub index_path: String,
+    pub checksum: String,
+    pub vector_count: usize,
+    pub list_count: usize,
+}
+
+pub struct AnnIndexStatus {
+    pub index: Option<Arc<IvfIndex>>,
+    pub reason: Option<String>,
+}
+
+struct CachedAnnIndex {
+    model_id: String,
+    checksum: String,
+    file_stamp: (u64, Option<SystemTime>),
+    index: Arc<IvfIndex>,
+}
+
+impl IvfIndex {
+    pub fn candidate_pool(&self, query: &[f32], min_candidates: usize) -> Vec<&str> {
+        let mut ranked_lists = self
+            .centroids
+            .iter()
+            .enumerate()
+            .map(|(index, centroid)| (index, cosine_similarity(query, centroid)))
+            .collect::<Vec<(usize, f64)>>();
+        ranked_lists.sort_by(|left, right| right.1.total_cmp(&left.1).then(left.0.cmp(&right.0)));
+
+        let mut pool = Vec::<&str>::new();
+        for (probed, (list_index, _)) in ranked_lists.iter().enumerate() {
+            if probed >= ANN_MIN_PROBES && pool.len() >= min_candidates {
+                break;
+            }
+            pool.extend(self.lists[*list_index].iter().map(String::as_str));
+        }
+        pool
+    }
+}
+
+pub fn ann_min_candidates(candidate_limit: usize) -> usize {
+    candidate_limit.max(1) * ANN_CANDIDATE_MULTIPLIER
+}
+
+pub fn build_ivf_index(
+    model_id: &str,
+    dimensions: usize,
+    vectors: &[(String, Vec<f32>)],
+) -> IvfIndex {
+    let list_count = ((vectors.len() as f64).sqrt().round() as usize).clamp(1, ANN_MAX_LISTS);
+    let mut centroids = (0..list_count)
+        .filter_map(|index| vectors.get(index * vectors.len() / list_count))
+        .map(|(_, vector)| vector.clone())
+        .collect::<Vec<Vec<f32>>>();
+    let mut assignments = vec![0usize; vectors.len()];
+
+    for _ in 0..ANN_KMEANS_ITERATIONS {
+        for (assignment, (_, vector)) in assignments.iter_mut().zip(vectors) {
+            *assignment = nearest_centroid(&centroids, vector);
+        }
+
+        let mut sums = vec![vec![0_f32; dimensions]; centroids.len()];
+        let mut counts = vec![0usize; centroids.len()];
+        for (assignment, (_, vector)) in assignments.iter().zip(vectors) {
+            counts[*assignment] += 1;
+            for (sum, value) in sums[*assignment].iter_mut().zip(vector) {
+                *sum += value;
+            }
+        }
+        for ((centroid, mut sum), count) in centroids.iter_mut().zip(sums).zip(counts) {
+            if count == 0 {
+                continue;
+            }
+            super::normalize_vector(&mut sum);
+            *centroid = sum;
+        }
+    }
+
+    let mut lists = vec![Vec::<String>::new(); centroids.len()];
+    for (chunk_id, vector) in vectors {
+        lists[nearest_centroid(&centroids, vector)].push(chunk_id.clone());
+    }
+
+    IvfIndex {
+        format: ANN_INDEX_FORMAT.to_string(),
+        model_id: model_id.to_string(),
+        dimensions,
+        vector_count: vectors.len(),
+        centroids,
+        lists,
+    }
+}
+
+fn nearest_centroid(centroids: &[Vec<f32>], vector: &[f32]) -> usize {
+    let mut best = (0usize, f64::NEG_INFINITY);
+    for (index, centroid) in centroids.iter().enumerate() {
+        let score = cosine_similarity(vector, centroid);
+        if score > best.1 {
+            best = (index, score);
+        }
+    }
+    best.0
+}
+
+pub fn exact_top_k<'a>(
+    query: &[f32],
+    vectors: impl IntoIterator<Item = (&'a str, &'a [f32])>,
+    k: usize,
+) -> Vec<&'a str> {
+    let mut scored = vectors
+        .into_iter()
+        .map(|(chunk_id, vector)| (chunk_id, cosine_similarity(query, vector)))
+        .collect::<Vec<(&str, f64)>>();
+    scored.sort_by(|left, right| right.1.total_cmp(&left.1).then(left.0.cmp(right.0)));
+    scored.truncate(k);
+    scored.into_iter().map(|(chunk_id, _)| chunk_id).collect()
+}
+
+pub fn ann_index_path(db_path: &Path, model_id: &str) -> PathBuf {
+    let safe_model_id = model_id
+        .chars()
+        .map(|character| {
+            if character.is_ascii_alphanumeric() || character == '-' || character == '.' {
+                character
+            } else {
+                '_'
+            }
+        })
+        .collect::<String>();
+    let stem = db_path
+        .file_stem()
+        .and_then(|value| value.to_str())
+        .unwrap_or("iso26262_index");
+    db_path.with_file_name(format!("{stem}.{safe_model_id}.ivf.json"))
+}
+
+pub fn load_model_vectors(
+    connection: &Connection,
+    model_id: &str,
+    dimensions: usize,
+) -> Result<Vec<(String, Vec<f32>)>> {
+    let encoding = load_model_storage_encoding(connection, model_id)?.unwrap_or_default();
+    let mut statement = connection.prepare(
+        "
+        SELECT chunk_id, embedding, embedding_dim
+        FROM chunk_embeddings
+        WHERE model_id = ?1
+        ORDER BY chunk_id ASC
+        ",
+    )?;
+    let mut rows = statement.query([model_id])?;
+    let mut vectors = Vec::<(String, Vec<f32>)>::new();
+    while let Some(row) = rows.next()? {
+        if row.get::<_, i64>(2)? as usize != dimensions {
+            continue;
+        }
+        let blob = row.get::<_, Vec<u8>>(1)?;
+        if let Some(vector) = decode_embedding_blob(&blob, dimensions, encoding) {
+            vectors.push((row.get(0)?, vector));
+        }
+    }
+    Ok(vectors)
+}
+
+pub fn write_ann_index(
+    connection: &Connection,
+    index: &IvfIndex,
+    index_path: &Path,
+) -> Result<AnnIndexRecord> {
+    let payload = serde_json::to_vec(index).context("failed to serialize ANN index")?;
+    fs::write(index_path, &payload)
+        .with_context(|| format!("failed to write ANN index {}", index_path.display()))?;
+    let stored_path = database_directory(connection)
+        .and_then(|directory| {
+            let directory = fs::canonicalize(directory).ok()?;
+            let index_path = fs::canonicalize(index_path).ok()?;
+            Some(index_path.strip_prefix(directory).ok()?.to_path_buf())
+        })
+        .unwrap_or_else(|| index_path.to_path_buf());
+    let record = AnnIndexRecord {
+        index_path: index_path.display().to_string(),
+        checksum: format!("{:x}", Sha256::digest(&payload)),
+        vector_count: index.vector_count,
+        list_count: index.lists.len(),
+    };
+
+    connection.execute(
+        "
+        INSERT INTO ann_indexes(
+          model_id, index_format, index_path, checksum, vector_count, list_count,
+          embeddings_generated_at, built_at
+        )
+        VALUES(
+          ?1, ?2, ?3, ?4, ?5, ?6,
+          (SELECT MAX(generated_at) FROM chunk_embeddings WHERE model_id = ?1), ?7
+        )
+        ON CONFLICT(model_id) DO UPDATE SET
+          index_format=excluded.index_format,
+          index_path=excluded.index_path,
+          checksum=excluded.checksum,
+          vector_count=excluded.vector_count,
+          list_count=excluded.list_count,
+          embeddings_generated_at=excluded.embeddings_generated_at,
+          built_at=excluded.built_at
+        ",
+        params![
+            index.model_id,
+            index.format,
+            stored_path.display().to_string(),
+            record.checksum,
+            record.vector_count as i64,
+            record.list_count as i64,
+            now_utc_string(),
+        ],
+    )?;
+
+    Ok(record)
+}
+
+pub fn load_ann_index(connection: &Connection, model_id: &str) -> Result<AnnIndexStatus> {
+    let unavailable = |reason: String| {
+        Ok(AnnIndexStatus {
+            index: None,
+            reason: Some(reason),
+        })
+    };
+
+    let table_exists = connection
+        .query_row(
+            "SELECT 1 FROM sqlite_master WHERE type = 'table' AND name = 'ann_indexes' LIMIT 1",
+            [],
+            |row| row.get::<_, i64>(0),
+        )
+        .optional()?
+        .is_some();
+    if !table_exists {
+        return unavailable("ann_indexes table is missing; re-run embed".to_string());
+    }
+
+    let row = connection
+        .query_row(
+            "
+            SELECT
+              a.index_format,
+              a.index_path,
+              a.checksum,
+              a.vector_count,
+              a.embeddings_generated_at,
+              (SELECT COUNT(*) FROM chunk_embeddings WHERE model_id = a.model_id),
+              (SELECT MAX(generated_at) FROM chunk_embeddings WHERE model_id = a.model_id)
+            FROM ann_indexes a
+            WHERE a.model_id = ?1
+            ",
+            [model_id],
+            |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    row.get::<_, String>(1)?,
+                    row.get::<_, String>(2)?,
+                    row.get::<_, i64>(3)?,
+                    row.get::<_, Option<String>>(4)?,
+                    row.get::<_, i64>(5)?,
+                    row.get::<_, Option<String>>(6)?,
+                ))
+            },
+        )
+        .optional()?;
+    let Some((format, index_path, checksum, vector_count, built_from, current_count, current_at)) =
+        row
+    else {
+        return unavailable(format!("no ANN index is registered for model '{model_id}'"));
+    };
+
+    if format != ANN_INDEX_FORMAT {
+        return unavailable(format!("unsupported ANN index format '{format}'"));
+    }
+    if vector_count != current_count || built_from != current_at {
+        return unavailable(format!(
+            "ANN index for model '{model_id}' is stale; re-run embed"
+        ));
+    }
+    let index_path = match database_directory(connection) {
+        Some(directory) => directory.join(index_path),
+        None => PathBuf::from(index_path),
+    };
+    let Some(file_stamp) = fs::metadata(&index_path)
+        .ok()
+        .filter(|metadata| metadata.is_file())
+        .map(|metadata| (metadata.len(), metadata.modified().ok()))
+    else {
+        return unavailable(format!(
+            "ANN index file {} is missing",
+            index_path.display()
+        ));
+    };
+    if let Some(index) = cached_ann_index(model_id, &checksum, file_stamp) {
+        return Ok(AnnIndexStatus {
+            index: Some(index),
+            reason: None,
+        });
+    }
+
+    let payload = fs::read(&index_path)
+        .with_context(|| format!("failed to read ANN index {}", index_path.display()))?;
+    if format!("{:x}", Sha256::digest(&payload)) != checksum {
+        return unavailable(format!(
+            "ANN index file {} does not match its recorded checksum",
+            index_path.display()
+        ));
+    }
+    let index = serde_json::from_slice::<IvfIndex>(&payload)
+        .with_context(|| format!("failed to parse ANN index {}", index_path.display()))?;
+    let index = Arc::new(index);
+
+    let mut cache = ANN_INDEX_CACHE
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner);
+    cache.retain(|cached| cached.model_id != model_id);
+    cache.push(CachedAnnIndex {
+        model_id: model_id.to_string(),
+        checksum,
+        file_stamp,
+        index: Arc::clone(&index),
+    });
+    Ok(AnnIndexStatus {
+        index: Some(index),
+        reason: None,
+    })
+}
+
+fn cached_ann_index(
+    model_id: &str,
+    checksum: &str,
+    file_stamp: (u64, Option<SystemTime>),
+) -> Option<Arc<IvfIndex>> {
+    ANN_INDEX_CACHE
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .iter()
+        .find(|cached| {
+            cached.model_id == model_id
+                && cached.checksum == checksum
+                && cached.file_stamp == file_stamp
+        })
+        .map(|cached| Arc::clone(&cached.index))
+}
+
+fn database_directory(connection: &Connection) -> Option<&Path> {
+    connection
+        .path()
+        .filter(|path| !path.is_empty())
+        .and_then(|path| Path::new(path).parent())
+}
@@ -0,0 +1,214 @@
+use anyhow::{Result, bail};
+use rusqlite::{Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum EmbeddingEncoding {
+    #[default]
+    F32,
+    F16,
+    Int8,
+    Binary,
+}
+
+impl EmbeddingEncoding {
+    pub const ALL: [EmbeddingEncoding; 4] = [
+        EmbeddingEncoding::F32,
+        EmbeddingEncoding::F16,
+        EmbeddingEncoding::Int8,
+        EmbeddingEncoding::Binary,
+    ];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            EmbeddingEncoding::F32 => "f32",
+            EmbeddingEncoding::F16 => "f16",
+            EmbeddingEncoding::Int8 => "int8",
+            EmbeddingEncoding::Binary => "binary",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|encoding| encoding.as_str() == value.trim().to_ascii_lowercase())
+    }
+
+    /// Int8 blobs carry a leading little-endian f32 scale; binary blobs pack one sign bit per dimension.
+    pub fn blob_len(self, dimensions: usize) -> usize {
+        match self {
+            EmbeddingEncoding::F32 => dimensions.saturating_mul(4),
+            EmbeddingEncoding::F16 => dimensions.saturating_mul(2),
+            EmbeddingEncoding::Int8 => dimensions.saturating_add(4),
+            EmbeddingEncoding::Binary => dimensions.div_ceil(8),
+        }
+    }
+}
+
+pub fn encode_embedding_blob(values: &[f32], encoding: EmbeddingEncoding) -> Vec<u8> {
+    let mut out = Vec::<u8>::with_capacity(encoding.blob_len(values.len()));
+    match encoding {
+        EmbeddingEncoding::F32 => {
+            for value in values {
+                out.extend_from_slice(&value.to_le_bytes());
+            }
+        }
+        EmbeddingEncoding::F16 => {
+            for value in values {
+                out.extend_from_slice(&f32_to_f16_bits(*value).to_le_bytes());
+            }
+        }
+        EmbeddingEncoding::Int8 => {
+            let max_abs = values
+                .iter()
+                .map(|value| value.abs())
+                .fold(0.0_f32, f32::max);
+            let scale = if max_abs > 0.0 { max_abs / 127.0 } else { 0.0 };
+            out.extend_from_slice(&scale.to_le_bytes());
+            for value in values {
+                let quantized = if scale > 0.0 {
+                    (value / scale).round().clamp(-127.0, 127.0) as i8
+                } else {
+                    0
+                };
+                out.push(quantized as u8);
+            }
+        }
+        EmbeddingEncoding::Binary => {
+            out.resize(encoding.blob_len(values.len()), 0);
+            for (index, value) in values.iter().enumerate() {
+                if *value >= 0.0 {
+                    out[index / 8] |= 1 << (index % 8);
+                }
+            }
+        }
+    }
+    out
+}
+
+pub fn decode_embedding_blob(
+    blob: &[u8],
+    expected_dim: usize,
+    encoding: EmbeddingEncoding,
+) -> Option<Vec<f32>> {
+    if expected_dim == 0 || blob.len() != encoding.blob_len(expected_dim) {
+        return None;
+    }
+
+    let out = match encoding {
+        EmbeddingEncoding::F32 => blob
+            .chunks_exact(4)
+            .map(|chunk| f32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]))
+            .collect::<Vec<f32>>(),
+        EmbeddingEncoding::F16 => blob
+            .chunks_exact(2)
+            .map(|chunk| f16_bits_to_f32(u16::from_le_bytes([chunk[0], chunk[1]])))
+            .collect::<Vec<f32>>(),
+        EmbeddingEncoding::Int8 => {
+            let scale = f32::from_le_bytes([blob[0], blob[1], blob[2], blob[3]]);
+            blob[4..]
+                .iter()
+                .map(|value| f32::from(*value as i8) * scale)
+                .collect::<Vec<f32>>()
+        }
+        EmbeddingEncoding::Binary => {
+            let magnitude = 1.0 / (expected_dim as f32).sqrt();
+            (0..expected_dim)
+                .map(|index| {
+                    if blob[index / 8] & (1 << (index % 8)) != 0 {
+                        magnitude
+                    } else {
+                        -magnitude
+                    }
+                })
+                .collect::<Vec<f32>>()
+        }
+    };
+
+    if out.len() == expected_dim {
+        Some(out)
+    } else {
+        None
+    }
+}
+
+pub fn quantize_embedding(values: &[f32], encoding: EmbeddingEncoding) -> Option<Vec<f32>> {
+    decode_embedding_blob(
+        &encode_embedding_blob(values, encoding),
+        values.len(),
+        encoding,
+    )
+}
+
+pub fn load_model_storage_encoding(
+    connection: &Connection,
+    model_id: &str,
+) -> Result<Option<EmbeddingEncoding>> {
+    let column_exists = connection
+        .query_row(
+            "SELECT 1 FROM pragma_table_info('embedding_models') WHERE name = 'storage_encoding'",
+            [],
+            |row| row.get::<_, i64>(0),
+        )
+        .optional()?
+        .is_some();
+    if !column_exists {
+        return Ok(None);
+    }
+
+    let value = connection
+        .query_row(
+            "SELECT storage_encoding FROM embedding_models WHERE model_id = ?1 LIMIT 1",
+            [model_id],
+            |row| row.get::<_, String>(0),
+        )
+        .optional()?;
+    let Some(value) = value else {
+        return Ok(None);
+    };
+    match EmbeddingEncoding::parse(&value) {
+        Some(encoding) => Ok(Some(encoding)),
+        None => bail!("unsupported storage encoding '{value}' for model '{model_id}'"),
+    }
+}
+
+fn f32_to_f16_bits(value: f32) -> u16 {
+    let bits = value.to_bits();
+    let sign = ((bits >> 16) & 0x8000) as u16;
+    let exponent = ((bits >> 23) & 0xFF) as i32;
+    let mantissa = bits & 0x7F_FFFF;
+
+    if exponent == 0xFF {
+        return sign | 0x7C00 | if mantissa != 0 { 0x0200 } else { 0 };
+    }
+    let half_exponent = exponent - 127 + 15;
+    if half_exponent >= 0x1F {
+        return sign | 0x7C00;
+    }
+    if half_exponent <= 0 {
+        if half_exponent < -10 {
+            return sign;
+        }
+        let mantissa = mantissa | 0x80_0000;
+        let shift = (14 - half_exponent) as u32;
+        let rounded = (mantissa >> shift) + ((mantissa >> (shift - 1)) & 1);
+        return sign | rounded as u16;
+    }
+
+    let half = ((half_exponent as u32) << 10) | (mantissa >> 13);
+    let rounded = half + ((mantissa >> 12) & 1);
+    sign | rounded as u16
+}
+
+fn f16_bits_to_f32(bits: u16) -> f32 {
+    let sign = if bits & 0x8000 != 0 { -1.0 } else { 1.0 };
+    let exponent = i32::from((bits >> 10) & 0x1F);
+    let mantissa = f32::from(bits & 0x03FF);
+    match exponent {
+        0 => sign * mantissa * 2_f32.powi(-24),
+        0x1F if mantissa == 0.0 => sign * f32::INFINITY,
+        0x1F => f32::NAN,
+        _ => sign * (1.0 + mantissa / 1024.0) * 2_f32.powi(exponent - 15),
+    }
+}
@@ -0,0 +1,140 @@
+use std::path::Path;
+
+use anyhow::{Result, anyhow, bail};
+use ort::session::Session;
+use ort::session::builder::GraphOptimizationLevel;
+use ort::value::Tensor;
+use tokenizers::{Tokenizer, TruncationParams};
+
+use super::{EmbeddingBackend, normalize_vector};
+
+const ONNX_MAX_SEQUENCE_TOKENS: usize = 256;
+
+pub struct OnnxEmbeddingBackend {
+    session: Session,
+    tokenizer: Tokenizer,
+    dimensions: usize,
+    uses_token_type_ids: bool,
+}
+
+impl OnnxEmbeddingBackend {
+    pub fn load(model_path: &Path, tokenizer_path: &Path) -> Result<Self> {
+        let mut tokenizer = Tokenizer::from_file(tokenizer_path).map_err(|err| {
+            anyhow!(
+                "failed to load tokenizer {}: {err}",
+                tokenizer_path.display()
+            )
+        })?;
+        tokenizer
+            .with_truncation(Some(TruncationParams {
+                max_length: ONNX_MAX_SEQUENCE_TOKENS,
+                ..TruncationParams::default()
+            }))
+            .map_err(|err| anyhow!("failed to configure tokenizer truncation: {err}"))?;
+        tokenizer.with_padding(None);
+
+        let session = Session::builder()
+            .and_then(|builder| builder.with_optimization_level(GraphOptimizationLevel::Level3))
+            .and_then(|builder| builder.commit_from_file(model_path))
+            .map_err(|err| anyhow!("failed to load ONNX model {}: {err}", model_path.display()))?;
+        let uses_token_type_ids = session
+            .inputs
+            .iter()
+            .any(|input| input.name == "token_type_ids");
+
+        let mut backend = Self {
+            session,
+            tokenizer,
+            dimensions: 0,
+            uses_token_type_ids,
+        };
+        backend.dimensions = backend.embed("embedding dimension probe")?.len();
+        if backend.dimensions == 0 {
+            bail!(
+                "ONNX model {} produced an empty embedding",
+                model_path.display()
+            );
+        }
+
+        Ok(backend)
+    }
+}
+
+impl EmbeddingBackend for OnnxEmbeddingBackend {
+    fn dimensions(&self) -> usize {
+        self.dimensions
+    }
+
+    fn embed(&mut self, payload: &str) -> Result<Vec<f32>> {
+        let encoding = self
+            .tokenizer
+            .encode(payload, true)
+            .map_err(|err| anyhow!("failed to tokenize embedding payload: {err}"))?;
+        let to_i64 = |values: &[u32]| {
+            values
+                .iter()
+                .map(|value| i64::from(*value))
+                .collect::<Vec<i64>>()
+        };
+        let sequence_len = encoding.get_ids().len();
+        let attention_mask = to_i64(encoding.get_attention_mask());
+
+        let input_ids = Tensor::from_array(([1, sequence_len], to_i64(encoding.get_ids())))?;
+        let mask = Tensor::from_array(([1, sequence_len], attention_mask.clone()))?;
+        let outputs = if self.uses_token_type_ids {
+            let type_ids =
+                Tensor::from_array(([1, sequence_len], to_i64(encoding.get_type_ids())))?;
+            self.session.run(ort::inputs! {
+                "input_ids" => input_ids,
+                "attention_mask" => mask,
+                "token_type_ids" => type_ids,
+            })?
+        } else {
+            self.session.run(ort::inputs! {
+                "input_ids" => input_ids,
+                "attention_mask" => mask,
+            })?
+        };
+
+        let (shape, values) = outputs[0].try_extract_tensor::<f32>()?;
+        let mut vector = match shape.len() {
+            2 => values.to_vec(),
+            3 => mean_pool(
+                values,
+                shape[1] as usize,
+                shape[2] as usize,
+                &attention_mask,
+            ),
+            rank => bail!("unsupported ONNX embedding output rank {rank}"),
+        };
+        normalize_vector(&mut vector);
+        Ok(vector)
+    }
+}
+
+fn mean_pool(
+    hidden: &[f32],
+    sequence_len: usize,
+    width: usize,
+    attention_mask: &[i64],
+) -> Vec<f32> {
+    let mut pooled = vec![0_f32; width];
+    let mut weight = 0_f32;
+    for (token_index, mask) in attention_mask.iter().enumerate().take(sequence_len) {
+        if *mask == 0 {
+            continue;
+        }
+        let row = &hidden[token_index * width..(token_index + 1) * width];
+        for (sum, value) in pooled.iter_mut().zip(row) {
+            *sum += value;
+        }
+        weight += 1.0;
+    }
+
+    if weight > 0.0 {
+        for value in &mut pooled {
+            *value /= weight;
+        }
+    }
+    pooled
+}
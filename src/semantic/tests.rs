@@ -0,0 +1,333 @@
+use std::fs;
+
+use rusqlite::Connection;
+
+use super::*;
+
+#[test]
+fn resolve_model_config_records_onnx_backend_and_file_hashes() {
+    let model_dir =
+        std::env::temp_dir().join(format!("iso26262_semantic_model_{}", std::process::id()));
+    fs::create_dir_all(model_dir.join("onnx")).expect("model dir should be created");
+    fs::write(model_dir.join("onnx/model.onnx"), b"onnx-bytes").expect("model should write");
+    fs::write(model_dir.join(ONNX_TOKENIZER_FILE), b"{}").expect("tokenizer should write");
+
+    let hashed = resolve_model_config("", None).expect("hash config should resolve");
+    let onnx = resolve_model_config(DEFAULT_MODEL_ID, Some(&model_dir))
+        .expect("onnx config should resolve");
+    fs::remove_dir_all(&model_dir).expect("model dir should be removed");
+
+    assert_eq!(hashed.backend, DEFAULT_BACKEND);
+    assert_eq!(hashed.model_name, DEFAULT_MODEL_NAME);
+    assert_eq!(onnx.backend, ONNX_BACKEND);
+    assert_eq!(
+        onnx.model_sha256.as_deref(),
+        Some(embedding_text_hash("onnx-bytes").as_str())
+    );
+    assert_eq!(
+        onnx.tokenizer_sha256.as_deref(),
+        Some(embedding_text_hash("{}").as_str())
+    );
+    assert!(!hashed.same_embedding_space(&onnx));
+
+    let connection = Connection::open_in_memory().expect("in-memory DB should open");
+    assert!(
+        load_registered_model_config(&connection, DEFAULT_MODEL_ID)
+            .expect("missing table should not fail")
+            .is_none()
+    );
+    connection
+        .execute_batch(
+            "CREATE TABLE embedding_models (model_id TEXT PRIMARY KEY, config_json TEXT NOT NULL);",
+        )
+        .expect("table should be created");
+    connection
+        .execute(
+            "INSERT INTO embedding_models VALUES (?1, ?2)",
+            [
+                DEFAULT_MODEL_ID,
+                r#"{"model_id":"miniLM-L6-v2-local-v1","model_name":"sentence-transformers/all-MiniLM-L6-v2","dimensions":384,"normalization":"l2","backend":"local-hash-v1"}"#,
+            ],
+        )
+        .expect("legacy config row should insert");
+    let registered = load_registered_model_config(&connection, DEFAULT_MODEL_ID)
+        .expect("legacy config should parse")
+        .expect("legacy config should be registered");
+    assert_eq!(registered, hashed);
+}
+
+#[test]
+fn hash_embedding_backend_matches_local_hash_embeddings() {
+    let model = resolve_model_config(DEFAULT_MODEL_ID, None).expect("config should resolve");
+    let mut backend = load_embedding_backend(&model).expect("hash backend should load");
+
+    assert_eq!(backend.dimensions(), DEFAULT_EMBEDDING_DIM);
+    assert_eq!(
+        backend
+            .embed("software unit verification")
+            .expect("embedding should succeed"),
+        embed_text_local("software unit verification", DEFAULT_EMBEDDING_DIM)
+    );
+
+    let unknown = SemanticModelConfig {
+        backend: "remote-api".to_string(),
+        ..model
+    };
+    assert!(load_embedding_backend(&unknown).is_err());
+}
+
+#[test]
+fn ivf_index_recalls_exact_neighbours_and_detects_stale_files() {
+    const TOPICS: [&str; 8] = [
+        "hazard analysis",
+        "software unit verification",
+        "hardware metrics",
+        "safety goal",
+        "confirmation review",
+        "tool qualification",
+        "fault injection",
+        "production release",
+    ];
+    let vectors = (0..400)
+        .map(|index| {
+            let text = format!(
+                "{} {} clause {}",
+                TOPICS[index % TOPICS.len()],
+                TOPICS[(index / TOPICS.len()) % TOPICS.len()],
+                index
+            );
+            (
+                format!("chunk-{index:04}"),
+                embed_text_local(&text, DEFAULT_EMBEDDING_DIM),
+            )
+        })
+        .collect::<Vec<(String, Vec<f32>)>>();
+
+    let index = ann::build_ivf_index(DEFAULT_MODEL_ID, DEFAULT_EMBEDDING_DIM, &vectors);
+    assert_eq!(index.vector_count, vectors.len());
+    assert_eq!(index.lists.len(), 20);
+    assert_eq!(
+        index.lists.iter().map(Vec::len).sum::<usize>(),
+        vectors.len()
+    );
+
+    let mut found = 0usize;
+    for topic in TOPICS {
+        let query = embed_text_local(topic, DEFAULT_EMBEDDING_DIM);
+        let exact = ann::exact_top_k(
+            &query,
+            vectors
+                .iter()
+                .map(|(chunk_id, vector)| (chunk_id.as_str(), vector.as_slice())),
+            10,
+        );
+        let pool = index
+            .candidate_pool(&query, ann::ann_min_candidates(10))
+            .into_iter()
+            .collect::<std::collections::HashSet<&str>>();
+        found += exact
+            .iter()
+            .filter(|chunk_id| pool.contains(**chunk_id))
+            .count();
+    }
+    assert!(found as f64 / (TOPICS.len() * 10) as f64 >= 0.9);
+
+    let db_path = std::env::temp_dir().join(format!("iso26262_ann_{}.sqlite", std::process::id()));
+    let _ = fs::remove_file(&db_path);
+    let connection = Connection::open(&db_path).expect("fixture DB should open");
+    connection
+        .execute_batch("PRAGMA foreign_keys = OFF;")
+        .expect("foreign keys should be disabled");
+    crate::commands::ingest::ensure_embedding_schema(&connection).expect("schema should apply");
+    assert!(
+        ann::load_ann_index(&connection, DEFAULT_MODEL_ID)
+            .expect("missing index should not fail")
+            .index
+            .is_none()
+    );
+    for (chunk_id, vector) in &vectors {
+        connection
+            .execute(
+                "INSERT INTO chunk_embeddings VALUES (?1, ?2, ?3, ?4, 'hash', '2026-01-01T00:00:00Z')",
+                rusqlite::params![
+                    chunk_id,
+                    DEFAULT_MODEL_ID,
+                    encode_embedding_blob(vector, EmbeddingEncoding::F32),
+                    DEFAULT_EMBEDDING_DIM as i64
+                ],
+            )
+            .expect("embedding should insert");
+    }
+
+    let index_path = ann::ann_index_path(&db_path, DEFAULT_MODEL_ID);
+    let stored = ann::load_model_vectors(&connection, DEFAULT_MODEL_ID, DEFAULT_EMBEDDING_DIM)
+        .expect("vectors should load");
+    assert_eq!(stored, vectors);
+    let record =
+        ann::write_ann_index(&connection, &index, &index_path).expect("index should write");
+    assert_eq!(record.list_count, 20);
+    let stored_path = connection
+        .query_row("SELECT index_path FROM ann_indexes", [], |row| {
+            row.get::<_, String>(0)
+        })
+        .expect("index path should be recorded");
+    assert_eq!(
+        Some(std::ffi::OsStr::new(&stored_path)),
+        index_path.file_name()
+    );
+
+    let loaded = ann::load_ann_index(&connection, DEFAULT_MODEL_ID).expect("index should load");
+    assert_eq!(loaded.index.as_deref(), Some(&index));
+    assert!(loaded.reason.is_none());
+    let reloaded = ann::load_ann_index(&connection, DEFAULT_MODEL_ID).expect("index should load");
+    assert!(std::sync::Arc::ptr_eq(
+        loaded.index.as_ref().expect("index should be loaded"),
+        reloaded.index.as_ref().expect("index should be cached")
+    ));
+
+    fs::write(&index_path, b"{}").expect("index should be overwritten");
+    let tampered = ann::load_ann_index(&connection, DEFAULT_MODEL_ID).expect("status should load");
+    assert!(tampered.index.is_none());
+    assert!(
+        tampered
+            .reason
+            .expect("reason should be set")
+            .contains("checksum")
+    );
+
+    connection
+        .execute(
+            "DELETE FROM chunk_embeddings WHERE chunk_id = 'chunk-0000'",
+            [],
+        )
+        .expect("embedding should delete");
+    let stale = ann::load_ann_index(&connection, DEFAULT_MODEL_ID).expect("status should load");
+    fs::remove_file(&index_path).expect("index file should be removed");
+    drop(connection);
+    let _ = fs::remove_file(&db_path);
+    assert!(
+        stale
+            .reason
+            .expect("reason should be set")
+            .contains("stale")
+    );
+}
+
+#[test]
+fn node_payloads_cover_selected_types_and_resolve_parent_chunks() {
+    assert_eq!(
+        node_payload_for_embedding(
+            "requirement_atom",
+            "8.4.1",
+            Some("The  tool shall\nbe qualified.")
+        )
+        .as_deref(),
+        Some("8.4.1\n\nThe tool shall be qualified.")
+    );
+    assert_eq!(
+        node_payload_for_embedding("list_item", "", Some("a) unit tests")).as_deref(),
+        Some("a) unit tests")
+    );
+    assert!(node_payload_for_embedding("table_cell", "Table 1", Some("++")).is_none());
+    assert!(node_payload_for_embedding("paragraph", "8.4", Some("   ")).is_none());
+
+    let connection = Connection::open_in_memory().expect("in-memory DB should open");
+    connection
+        .execute_batch(
+            "
+            CREATE TABLE nodes (node_id TEXT PRIMARY KEY, parent_node_id TEXT);
+            CREATE TABLE chunks (chunk_id TEXT PRIMARY KEY, origin_node_id TEXT, chunk_seq INTEGER);
+            INSERT INTO nodes VALUES
+              ('doc', NULL),
+              ('clause-8.4', 'doc'),
+              ('list-1', 'clause-8.4'),
+              ('item-1', 'list-1'),
+              ('orphan', 'doc');
+            INSERT INTO chunks VALUES ('chunk-8.4', 'clause-8.4', 0);
+            ",
+        )
+        .expect("fixture should load");
+
+    assert_eq!(
+        resolve_parent_chunk_id(&connection, "item-1")
+            .expect("lookup should succeed")
+            .as_deref(),
+        Some("chunk-8.4")
+    );
+    assert_eq!(
+        resolve_parent_chunk_id(&connection, "clause-8.4")
+            .expect("lookup should succeed")
+            .as_deref(),
+        Some("chunk-8.4")
+    );
+    assert!(
+        resolve_parent_chunk_id(&connection, "orphan")
+            .expect("lookup should succeed")
+            .is_none()
+    );
+}
+
+#[test]
+fn quantized_encodings_round_trip_and_preserve_neighbour_ranking() {
+    let query = embed_text_local("software unit verification methods", DEFAULT_EMBEDDING_DIM);
+    let near = embed_text_local(
+        "methods for software unit verification",
+        DEFAULT_EMBEDDING_DIM,
+    );
+    let far = embed_text_local(
+        "hazard analysis of vehicle functions",
+        DEFAULT_EMBEDDING_DIM,
+    );
+
+    for encoding in EmbeddingEncoding::ALL {
+        let blob = encode_embedding_blob(&near, encoding);
+        assert_eq!(blob.len(), encoding.blob_len(DEFAULT_EMBEDDING_DIM));
+        assert!(decode_embedding_blob(&blob, DEFAULT_EMBEDDING_DIM + 1, encoding).is_none());
+
+        let decoded = decode_embedding_blob(&blob, DEFAULT_EMBEDDING_DIM, encoding)
+            .expect("blob should decode");
+        let far_decoded = quantize_embedding(&far, encoding).expect("vector should quantize");
+        assert!(cosine_similarity(&query, &decoded) > cosine_similarity(&query, &far_decoded));
+        if encoding != EmbeddingEncoding::Binary {
+            assert!(cosine_similarity(&near, &decoded) > 0.99);
+        }
+        assert_eq!(EmbeddingEncoding::parse(encoding.as_str()), Some(encoding));
+    }
+    assert_eq!(
+        decode_embedding_blob(
+            &encode_embedding_blob(&near, EmbeddingEncoding::F32),
+            DEFAULT_EMBEDDING_DIM,
+            EmbeddingEncoding::F32
+        ),
+        Some(near)
+    );
+
+    let connection = Connection::open_in_memory().expect("in-memory DB should open");
+    connection
+        .execute_batch(
+            "
+            CREATE TABLE embedding_models (model_id TEXT PRIMARY KEY, config_json TEXT);
+            INSERT INTO embedding_models VALUES ('legacy', '{}');
+            ",
+        )
+        .expect("fixture should load");
+    assert_eq!(
+        load_model_storage_encoding(&connection, "legacy").expect("lookup should succeed"),
+        None
+    );
+    crate::commands::ingest::ensure_embedding_schema(&connection).expect("schema should apply");
+    assert_eq!(
+        load_model_storage_encoding(&connection, "legacy").expect("lookup should succeed"),
+        Some(EmbeddingEncoding::F32)
+    );
+    connection
+        .execute(
+            "UPDATE embedding_models SET storage_encoding = 'int8' WHERE model_id = 'legacy'",
+            [],
+        )
+        .expect("encoding should update");
+    assert_eq!(
+        load_model_storage_encoding(&connection, "legacy").expect("lookup should succeed"),
+        Some(EmbeddingEncoding::Int8)
+    );
+}